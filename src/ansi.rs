@@ -0,0 +1,372 @@
+//! ANSI terminal escape sequences for [`Color`]s.
+//!
+//! [`Color::to_ansi_truecolor`] produces a 24-bit escape sequence. For
+//! terminals with a limited palette, [`Color::nearest_ansi256`] and
+//! [`Color::nearest_ansi16`] pick the perceptually closest palette entry by
+//! their distance in [`Oklab`].
+//!
+//! The escape sequences implement [`Display`](fmt::Display), so they can be
+//! written without allocating.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::ansi::RESET;
+//!
+//! let orange = Color::srgb_u8(255, 128, 0);
+//!
+//! assert_eq!(orange.to_ansi_truecolor().to_string(), "\x1b[38;2;255;128;0m");
+//! assert_eq!(orange.nearest_ansi256().to_string(), "\x1b[38;5;208m");
+//! assert_eq!(orange.nearest_ansi16().background().to_string(), "\x1b[101m");
+//!
+//! println!("{}warning{RESET}", orange.to_ansi_truecolor());
+//! ```
+//!
+//! [`Swatches`] previews a palette as a row of colored blocks, with hex codes
+//! or labels below:
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::ansi::Swatches;
+//!
+//! let palette = [Color::srgb_u8(255, 0, 0), Color::srgb_u8(0, 0, 255)];
+//! println!("{}", Swatches::new(&palette).with_hex());
+//! ```
+
+use crate::details::encodings::{EncodedSrgbU8, Oklab};
+use crate::details::linear_spaces::{CieXYZ, Srgb};
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+
+/// Resets all terminal attributes, including colors.
+pub const RESET: &str = "\x1b[0m";
+
+/// The xterm defaults of the 16 system colors.
+///
+/// Terminals let users configure these, so they are only an approximation.
+const ANSI16: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+/// The component levels of the 6×6×6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// A 24-bit color escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnsiTruecolor {
+    /// The sRGB components.
+    pub rgb: [u8; 3],
+    /// Whether this sets the background rather than the foreground color.
+    pub background: bool,
+}
+
+/// An escape sequence selecting an entry of the 256 color palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ansi256 {
+    /// The palette index.
+    pub index: u8,
+    /// Whether this sets the background rather than the foreground color.
+    pub background: bool,
+}
+
+/// An escape sequence selecting one of the 16 system colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ansi16 {
+    /// The palette index, `0..16`.
+    pub index: u8,
+    /// Whether this sets the background rather than the foreground color.
+    pub background: bool,
+}
+
+macro_rules! impl_background {
+    ($($ty:ident),+) => {$(
+        impl $ty {
+            /// Set the background instead of the foreground color.
+            #[inline(always)]
+            pub fn background(self) -> Self {
+                Self {
+                    background: true,
+                    ..self
+                }
+            }
+        }
+    )+};
+}
+
+impl_background!(AnsiTruecolor, Ansi256, Ansi16);
+
+impl fmt::Display for AnsiTruecolor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.rgb;
+        let layer = if self.background { 48 } else { 38 };
+        write!(f, "\x1b[{layer};2;{r};{g};{b}m")
+    }
+}
+
+impl fmt::Display for Ansi256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layer = if self.background { 48 } else { 38 };
+        write!(f, "\x1b[{layer};5;{}m", self.index)
+    }
+}
+
+impl fmt::Display for Ansi16 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = match (self.index < 8, self.background) {
+            (true, false) => 30,
+            (true, true) => 40,
+            (false, false) => 90 - 8,
+            (false, true) => 100 - 8,
+        };
+        write!(f, "\x1b[{}m", base + self.index)
+    }
+}
+
+/// The sRGB value of an entry of the 256 color palette.
+///
+/// Entries `0..16` are the xterm defaults of the system colors.
+pub fn ansi256_to_srgb(index: u8) -> Color<EncodedSrgbU8> {
+    Color::from_repr(match index {
+        0..16 => ANSI16[index as usize],
+        16..232 => {
+            let i = index - 16;
+            [i / 36, i / 6 % 6, i % 6].map(|level| CUBE_LEVELS[level as usize])
+        }
+        232.. => [8 + 10 * (index - 232); 3],
+    })
+}
+
+/// The index in `indices` whose palette entry is closest to `color`.
+fn nearest(color: Color<Oklab>, indices: impl Iterator<Item = u8>) -> u8 {
+    indices
+        .map(|index| (index, color.distance(ansi256_to_srgb(index).convert())))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+        .expect("palette is not empty")
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    EncodedSrgbU8: ConvertFrom<E>,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// A 24-bit foreground color escape sequence for this color.
+    ///
+    /// Components outside the sRGB gamut are clamped.
+    #[inline]
+    pub fn to_ansi_truecolor(self) -> AnsiTruecolor {
+        AnsiTruecolor {
+            rgb: self.convert::<EncodedSrgbU8>().repr,
+            background: false,
+        }
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Oklab: ConvertFrom<E>,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The perceptually closest entry of the 256 color palette, as a
+    /// foreground color escape sequence.
+    ///
+    /// Only the color cube and the gray ramp (`16..256`) are considered, as
+    /// the system colors differ between terminals.
+    pub fn nearest_ansi256(self) -> Ansi256 {
+        Ansi256 {
+            index: nearest(self.convert(), 16..=255),
+            background: false,
+        }
+    }
+
+    /// The perceptually closest of the 16 system colors, as a foreground
+    /// color escape sequence.
+    ///
+    /// Distances are measured against the xterm defaults.
+    pub fn nearest_ansi16(self) -> Ansi16 {
+        Ansi16 {
+            index: nearest(self.convert(), 0..16),
+            background: false,
+        }
+    }
+}
+
+/// What to write below the blocks of [`Swatches`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Labels<'a> {
+    None,
+    Hex,
+    Text(&'a [&'a str]),
+}
+
+/// A preview of colors as a row of 24-bit colored blocks, for inspecting
+/// palettes in terminal output.
+///
+/// Written with [`Display`](fmt::Display), one block per color followed by
+/// [`RESET`]. With [`Swatches::with_hex`] or [`Swatches::with_labels`], a
+/// second line has a label below each block, and blocks are widened to fit
+/// their label.
+pub struct Swatches<'a, E: ColorEncoding> {
+    colors: &'a [Color<E>],
+    labels: Labels<'a>,
+    block_width: usize,
+}
+
+impl<'a, E: ColorEncoding> Swatches<'a, E> {
+    /// Swatches of `colors`, two columns wide each and without labels.
+    #[inline]
+    pub fn new(colors: &'a [Color<E>]) -> Self {
+        Self {
+            colors,
+            labels: Labels::None,
+            block_width: 2,
+        }
+    }
+
+    /// Set the minimum width of each block in columns.
+    #[inline]
+    pub fn block_width(self, block_width: usize) -> Self {
+        Self {
+            block_width,
+            ..self
+        }
+    }
+
+    /// Label each block with its hex code, as with [`Color::to_hex`].
+    #[inline]
+    pub fn with_hex(self) -> Self {
+        Self {
+            labels: Labels::Hex,
+            ..self
+        }
+    }
+
+    /// Label each block with the label at the same index of `labels`.
+    ///
+    /// Blocks without a label are left blank.
+    #[inline]
+    pub fn with_labels(self, labels: &'a [&'a str]) -> Self {
+        Self {
+            labels: Labels::Text(labels),
+            ..self
+        }
+    }
+
+    /// The width of the label of block `index`, in columns.
+    fn label_width(&self, index: usize, color: Color<E>) -> usize
+    where
+        Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        match self.labels {
+            Labels::None => 0,
+            Labels::Hex if color.to_hex().0[3] == 255 => 7,
+            Labels::Hex => 9,
+            Labels::Text(labels) => labels.get(index).map_or(0, |label| label.chars().count()),
+        }
+    }
+
+    /// The width of block `index`, leaving a column after its label.
+    fn cell_width(&self, index: usize, color: Color<E>) -> usize
+    where
+        Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        match self.labels {
+            Labels::None => self.block_width,
+            _ => self.block_width.max(self.label_width(index, color) + 1),
+        }
+    }
+}
+
+impl<E: ColorEncoding> Clone for Swatches<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: ColorEncoding> Copy for Swatches<'_, E> {}
+
+impl<E> fmt::Debug for Swatches<'_, E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Swatches")
+            .field("colors", &self.colors)
+            .field("labels", &self.labels)
+            .field("block_width", &self.block_width)
+            .finish()
+    }
+}
+
+impl<E> PartialEq for Swatches<'_, E>
+where
+    E: ColorEncoding,
+    E::Repr: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.colors == other.colors
+            && self.labels == other.labels
+            && self.block_width == other.block_width
+    }
+}
+
+impl<E> fmt::Display for Swatches<'_, E>
+where
+    E: ColorEncoding,
+    EncodedSrgbU8: ConvertFrom<E>,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.colors
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, &color)| {
+                let width = self.cell_width(index, color);
+                write!(f, "{}{:width$}", color.to_ansi_truecolor().background(), "")
+            })?;
+        f.write_str(RESET)?;
+
+        match self.labels {
+            Labels::None => Ok(()),
+            labels => {
+                f.write_str("\n")?;
+                self.colors
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(index, &color)| {
+                        match labels {
+                            Labels::Hex => write!(f, "{}", color.to_hex())?,
+                            Labels::Text(labels) => {
+                                f.write_str(labels.get(index).copied().unwrap_or(""))?
+                            }
+                            Labels::None => {}
+                        }
+                        let padding =
+                            self.cell_width(index, color) - self.label_width(index, color);
+                        write!(f, "{:padding$}", "")
+                    })
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Conversions between integer component values of different bit depths.
+//!
+//! Values are rescaled so that the maximum value of one depth maps to the
+//! maximum value of the other, with rounding to nearest. Expanding 8 to 16
+//! bits multiplies by 257 and reducing rounds instead of truncating, unlike
+//! plain bit shifts, which darken expanded values and bias reduced ones
+//! downward.
+//!
+//! Values with fewer than 16 bits are stored in the low bits of a `u16`.
+//!
+//! The encodings [`EncodedSrgbU10`](crate::details::encodings::EncodedSrgbU10),
+//! [`EncodedSrgbU12`](crate::details::encodings::EncodedSrgbU12),
+//! [`EncodedSrgbU14`](crate::details::encodings::EncodedSrgbU14) and
+//! [`EncodedSrgbU16`](crate::details::encodings::EncodedSrgbU16) scale
+//! their components the same way, so converting between them and
+//! [`EncodedSrgbU8`](crate::details::encodings::EncodedSrgbU8) agrees with
+//! [`rescale`].
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::bit_depth;
+//!
+//! assert_eq!(bit_depth::rescale(255, 8, 16), 65535);
+//! assert_eq!(bit_depth::rescale(0x80, 8, 10), 514);
+//! assert_eq!(bit_depth::rescale(514, 10, 8), 0x80);
+//!
+//! // A plain shift would give 1.
+//! assert_eq!(bit_depth::u16_to_u8(0x01c0), 2);
+//! ```
+
+/// The largest value with `bits` bits.
+///
+/// # Panics
+///
+/// Panics if `bits` is not in `1..=16`.
+#[inline]
+pub const fn max_value(bits: u32) -> u16 {
+    assert!(bits >= 1 && bits <= 16, "bit depth must be in 1..=16");
+    u16::MAX >> (16 - bits)
+}
+
+/// Rescale `value` from `from_bits` to `to_bits` bits, rounding to nearest.
+///
+/// `value` is clamped to the largest value with `from_bits` bits.
+///
+/// # Panics
+///
+/// Panics if `from_bits` or `to_bits` is not in `1..=16`.
+#[inline]
+pub const fn rescale(value: u16, from_bits: u32, to_bits: u32) -> u16 {
+    let from_max = max_value(from_bits) as u32;
+    let to_max = max_value(to_bits) as u32;
+    let value = if value as u32 > from_max {
+        from_max
+    } else {
+        value as u32
+    };
+    ((value * to_max + from_max / 2) / from_max) as u16
+}
+
+/// Expand an 8-bit value to 16 bits.
+#[inline]
+pub const fn u8_to_u16(value: u8) -> u16 {
+    value as u16 * 257
+}
+
+/// Reduce a 16-bit value to 8 bits, rounding to nearest.
+#[inline]
+pub const fn u16_to_u8(value: u16) -> u8 {
+    rescale(value, 16, 8) as u8
+}
+
+/// `value` with `bits` bits as a float in `0.0..=1.0`.
+///
+/// `value` is clamped to the largest value with `bits` bits.
+///
+/// # Panics
+///
+/// Panics if `bits` is not in `1..=16`.
+#[inline]
+pub fn normalize(value: u16, bits: u32) -> f32 {
+    let max = max_value(bits);
+    value.min(max) as f32 / max as f32
+}
@@ -0,0 +1,318 @@
+//! Typed image buffers.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+//! use colstodian::buffer::ColorBuffer;
+//!
+//! let gradient = ColorBuffer::<LinearSrgb>::from_fn(4, 2, |x, _| {
+//!     let v = x as f32 / 3.0;
+//!     Color::linear_srgb(v, v, v)
+//! });
+//!
+//! let encoded = gradient.convert::<SrgbU8>();
+//! assert_eq!(encoded[(3, 1)], Color::srgb_u8(255, 255, 255));
+//! assert_eq!(encoded.as_bytes().len(), 4 * 2 * 3);
+//! ```
+
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::quantize::GrayEncoding;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::dither::{Dither, dither};
+use crate::{Color, ColorEncoding};
+
+#[cfg(feature = "bytemuck")]
+use crate::view::StridedView;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+/// An image of `Color<E>` pixels, stored row by row without padding.
+pub struct ColorBuffer<E: ColorEncoding> {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color<E>>,
+}
+
+impl<E: ColorEncoding> ColorBuffer<E> {
+    /// Create a buffer filled with [`Color::default`].
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        E::Repr: Default,
+    {
+        Self::filled(width, height, Color::default())
+    }
+
+    /// Create a buffer filled with `color`.
+    pub fn filled(width: usize, height: usize, color: Color<E>) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// Create a buffer with the color `f(x, y)` at each pixel.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(usize, usize) -> Color<E>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            pixels: (0..width * height)
+                .map(|i| f(i % width, i / width))
+                .collect(),
+        }
+    }
+
+    /// Create a buffer from `pixels` stored row by row, or `None` if there are
+    /// not exactly `width * height` pixels.
+    pub fn from_pixels(width: usize, height: usize, pixels: Vec<Color<E>>) -> Option<Self> {
+        (pixels.len() == width * height).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// The width in pixels.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height in pixels.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All pixels, row by row.
+    #[inline]
+    pub fn pixels(&self) -> &[Color<E>] {
+        &self.pixels
+    }
+
+    /// All pixels, row by row.
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [Color<E>] {
+        &mut self.pixels
+    }
+
+    /// Consume the buffer, returning its pixels row by row.
+    #[inline]
+    pub fn into_pixels(self) -> Vec<Color<E>> {
+        self.pixels
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&Color<E>> {
+        (x < self.width && y < self.height).then(|| &self.pixels[y * self.width + x])
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Color<E>> {
+        (x < self.width && y < self.height).then(|| &mut self.pixels[y * self.width + x])
+    }
+
+    /// The pixels of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    #[inline]
+    pub fn row(&self, y: usize) -> &[Color<E>] {
+        assert!(y < self.height, "row out of bounds");
+        &self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The pixels of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> &mut [Color<E>] {
+        assert!(y < self.height, "row out of bounds");
+        &mut self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    /// An iterator over the rows, top to bottom.
+    #[inline]
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Color<E>]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Apply `f` to every pixel, producing a buffer of the same size.
+    pub fn map<D: ColorEncoding>(&self, f: impl FnMut(Color<E>) -> Color<D>) -> ColorBuffer<D> {
+        ColorBuffer {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().copied().map(f).collect(),
+        }
+    }
+
+    /// Apply `f` to every pixel in parallel, producing a buffer of the same
+    /// size.
+    #[cfg(feature = "rayon")]
+    pub fn par_map<D: ColorEncoding>(
+        &self,
+        f: impl Fn(Color<E>) -> Color<D> + Send + Sync,
+    ) -> ColorBuffer<D>
+    where
+        E::Repr: Send + Sync,
+        D::Repr: Send,
+    {
+        ColorBuffer {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.par_iter().copied().map(f).collect(),
+        }
+    }
+
+    /// Convert every pixel to the encoding `D`.
+    ///
+    /// See [`Color::convert`].
+    pub fn convert<D>(&self) -> ColorBuffer<D>
+    where
+        D: ColorEncoding + ConvertFrom<E>,
+        D::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        self.map(Color::convert)
+    }
+
+    /// Convert every pixel to the encoding `D` in parallel.
+    ///
+    /// See [`Color::convert`].
+    #[cfg(feature = "rayon")]
+    pub fn par_convert<D>(&self) -> ColorBuffer<D>
+    where
+        D: ColorEncoding + ConvertFrom<E>,
+        D::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+        E::Repr: Send + Sync,
+        D::Repr: Send,
+    {
+        self.par_map(Color::convert)
+    }
+
+    /// Dither the buffer to the low-bit gray encoding `D`.
+    ///
+    /// See [`dither`].
+    pub fn dithered<D>(&self, method: Dither) -> ColorBuffer<D>
+    where
+        D: GrayEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let mut dithered = ColorBuffer::filled(self.width, self.height, Color::from_repr(0));
+        let mut scratch = vec![0.0; method.scratch_len(self.width)];
+        dither(
+            &self.pixels,
+            self.width,
+            method,
+            &mut scratch,
+            &mut dithered.pixels,
+        );
+        dithered
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<E> ColorBuffer<E>
+where
+    E: ColorEncoding,
+    E::Repr: bytemuck::Pod,
+{
+    /// Create a buffer by copying raw bytes laid out like the repr of `E`, row
+    /// by row, or `None` if `bytes` does not hold exactly `width * height`
+    /// pixels.
+    ///
+    /// `bytes` need not be aligned.
+    pub fn from_bytes(width: usize, height: usize, bytes: &[u8]) -> Option<Self> {
+        (bytes.len() == width * height * core::mem::size_of::<E::Repr>()).then_some(())?;
+        let mut buffer = Self::filled(width, height, bytemuck::Zeroable::zeroed());
+        buffer.as_bytes_mut().copy_from_slice(bytes);
+        Some(buffer)
+    }
+
+    /// Create a buffer by copying the pixels of `view`.
+    pub fn from_view(view: &StridedView<'_, E>) -> Self {
+        Self {
+            width: view.width(),
+            height: view.height(),
+            pixels: view.pixels().collect(),
+        }
+    }
+
+    /// The pixels as raw bytes, e.g. for upload to the GPU or writing to an
+    /// image file.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.pixels)
+    }
+
+    /// The pixels as mutable raw bytes.
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(&mut self.pixels)
+    }
+}
+
+impl<E: ColorEncoding> Index<(usize, usize)> for ColorBuffer<E> {
+    type Output = Color<E>;
+
+    /// The pixel at `(x, y)`.
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Color<E> {
+        self.get(x, y).expect("pixel out of bounds")
+    }
+}
+
+impl<E: ColorEncoding> IndexMut<(usize, usize)> for ColorBuffer<E> {
+    /// The pixel at `(x, y)`.
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Color<E> {
+        self.get_mut(x, y).expect("pixel out of bounds")
+    }
+}
+
+impl<E: ColorEncoding> Clone for ColorBuffer<E> {
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.clone(),
+        }
+    }
+}
+
+impl<E> PartialEq for ColorBuffer<E>
+where
+    E: ColorEncoding,
+    Color<E>: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.pixels == other.pixels
+    }
+}
+
+impl<E> fmt::Debug for ColorBuffer<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColorBuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("pixels", &self.pixels)
+            .finish()
+    }
+}
@@ -0,0 +1,197 @@
+//! Color correction matrices and fitting them to measurements.
+//!
+//! Camera profiling and scanner calibration photograph a chart of known
+//! colors, like the [ColorChecker](crate::colorchecker), and fit the 3x3
+//! matrix, optionally with an offset, that maps the measured colors closest
+//! to the reference colors in the least-squares sense. Both sides should be in
+//! the same linear working encoding.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::calibration::ColorMatrix;
+//! use glam::{Mat3, Vec3};
+//!
+//! // A camera that records red as slightly orange.
+//! let camera = ColorMatrix::new(Mat3::from_cols(
+//!     Vec3::new(0.9, 0.1, 0.0),
+//!     Vec3::Y,
+//!     Vec3::Z,
+//! ));
+//! let references = [
+//!     Color::linear_srgb(0.8, 0.1, 0.1),
+//!     Color::linear_srgb(0.1, 0.7, 0.2),
+//!     Color::linear_srgb(0.1, 0.2, 0.6),
+//!     Color::linear_srgb(0.5, 0.5, 0.5),
+//! ];
+//! let pairs = references.map(|reference| (camera.transform(reference), reference));
+//!
+//! let correction = ColorMatrix::fit(pairs).unwrap();
+//! let measured = camera.transform(Color::linear_srgb(1.0, 0.0, 0.0));
+//! assert!(correction.transform(measured).repr.abs_diff_eq(Vec3::X, 1e-5));
+//! ```
+
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding, WorkingEncoding};
+
+use glam::{DMat3, DMat4, DVec3, DVec4, Mat3, Vec3};
+
+/// A 3x3 matrix and offset applied to the components of colors in a working
+/// encoding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorMatrix {
+    /// The matrix, applied first.
+    pub matrix: Mat3,
+    /// The offset, added after the matrix.
+    pub offset: Vec3,
+}
+
+impl Default for ColorMatrix {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ColorMatrix {
+    /// The matrix that leaves colors unchanged.
+    pub const IDENTITY: Self = Self::new(Mat3::IDENTITY);
+
+    /// A matrix without offset.
+    #[inline]
+    pub const fn new(matrix: Mat3) -> Self {
+        Self::with_offset(matrix, Vec3::ZERO)
+    }
+
+    /// A matrix followed by an offset.
+    #[inline]
+    pub const fn with_offset(matrix: Mat3, offset: Vec3) -> Self {
+        Self { matrix, offset }
+    }
+
+    /// The matrix converting the linear components of the color space `Src`
+    /// to those of `Dst`.
+    pub fn conversion<Src, Dst>() -> Self
+    where
+        Src: LinearColorSpace,
+        Dst: LinearConvertFromRaw<Src>,
+    {
+        let column = |mut axis: Vec3| {
+            Dst::linear_part_raw(&mut axis);
+            axis
+        };
+        Self::new(Mat3::from_cols(
+            column(Vec3::X),
+            column(Vec3::Y),
+            column(Vec3::Z),
+        ))
+    }
+
+    /// The Bradford chromatic adaptation from the white point `from` to the
+    /// white point `to`, both given in CIE XYZ.
+    pub fn bradford(from: Vec3, to: Vec3) -> Self {
+        const CONE: Mat3 = Mat3::from_cols(
+            Vec3::new(0.8951, -0.7502, 0.0389),
+            Vec3::new(0.2664, 1.7135, -0.0685),
+            Vec3::new(-0.1614, 0.0367, 1.0296),
+        );
+        let scale = Mat3::from_diagonal(CONE * to / (CONE * from));
+        Self::new(CONE.inverse() * scale * CONE)
+    }
+
+    /// Apply the matrix and offset to the components of `color`.
+    #[inline]
+    pub fn transform<E>(self, color: Color<E>) -> Color<E>
+    where
+        E: ColorEncoding<Repr = Vec3> + WorkingEncoding,
+    {
+        Color::from_repr(self.matrix * color.repr + self.offset)
+    }
+
+    /// The matrix applying `self` and then `next`.
+    #[inline]
+    pub fn then(self, next: Self) -> Self {
+        Self {
+            matrix: next.matrix * self.matrix,
+            offset: next.matrix * self.offset + next.offset,
+        }
+    }
+
+    /// The matrix undoing `self`, or `None` if it is singular.
+    pub fn inverse(self) -> Option<Self> {
+        let determinant = self.matrix.determinant();
+        (determinant != 0.0 && determinant.is_finite()).then(|| {
+            let matrix = self.matrix.inverse();
+            Self::with_offset(matrix, -(matrix * self.offset))
+        })
+    }
+
+    /// The matrix without offset mapping the measured colors of `pairs`
+    /// closest to their reference colors, as `(measured, reference)`.
+    ///
+    /// Returns `None` if the measured colors don't span all three dimensions,
+    /// e.g. with fewer than three pairs or only grays.
+    pub fn fit<E>(pairs: impl IntoIterator<Item = (Color<E>, Color<E>)>) -> Option<Self>
+    where
+        E: ColorEncoding<Repr = Vec3> + WorkingEncoding,
+    {
+        let (measured, cross) =
+            pairs
+                .into_iter()
+                .fold((DMat3::ZERO, DMat3::ZERO), |(measured, cross), (m, r)| {
+                    let (m, r) = (m.repr.as_dvec3(), r.repr.as_dvec3());
+                    (measured + outer3(m, m), cross + outer3(r, m))
+                });
+        well_conditioned(
+            measured.determinant(),
+            measured.x_axis.x + measured.y_axis.y + measured.z_axis.z,
+            3,
+        )
+        .then(|| Self::new((cross * measured.inverse()).as_mat3()))
+    }
+
+    /// The matrix and offset mapping the measured colors of `pairs` closest
+    /// to their reference colors, as `(measured, reference)`.
+    ///
+    /// The offset accounts for black level or flare. Returns `None` if the
+    /// measured colors don't span all three dimensions and the offset, e.g.
+    /// with fewer than four pairs or colors on a plane.
+    pub fn fit_affine<E>(pairs: impl IntoIterator<Item = (Color<E>, Color<E>)>) -> Option<Self>
+    where
+        E: ColorEncoding<Repr = Vec3> + WorkingEncoding,
+    {
+        let (measured, cross) =
+            pairs
+                .into_iter()
+                .fold((DMat4::ZERO, DMat4::ZERO), |(measured, cross), (m, r)| {
+                    let (m, r) = (m.repr.as_dvec3().extend(1.0), r.repr.as_dvec3().extend(0.0));
+                    (measured + outer4(m, m), cross + outer4(r, m))
+                });
+        let trace = measured.x_axis.x + measured.y_axis.y + measured.z_axis.z + measured.w_axis.w;
+        well_conditioned(measured.determinant(), trace, 4).then(|| {
+            let solution = cross * measured.inverse();
+            Self::with_offset(
+                DMat3::from_mat4(solution).as_mat3(),
+                solution.w_axis.truncate().as_vec3(),
+            )
+        })
+    }
+}
+
+/// The outer product `a bᵀ`.
+fn outer3(a: DVec3, b: DVec3) -> DMat3 {
+    DMat3::from_cols(a * b.x, a * b.y, a * b.z)
+}
+
+/// The outer product `a bᵀ`.
+fn outer4(a: DVec4, b: DVec4) -> DMat4 {
+    DMat4::from_cols(a * b.x, a * b.y, a * b.z, a * b.w)
+}
+
+/// Whether a symmetric positive semi-definite matrix of size `n` with the
+/// given determinant and trace is far enough from singular to invert.
+fn well_conditioned(determinant: f64, trace: f64, n: usize) -> bool {
+    let scale: f64 = (0..n).map(|_| trace / n as f64).product();
+    determinant.is_finite() && scale > 0.0 && determinant / scale > 1e-12
+}
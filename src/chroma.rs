@@ -0,0 +1,307 @@
+//! Chroma subsampling of color buffers.
+//!
+//! Video is usually stored as YCbCr with the chroma (Cb and Cr) planes at a
+//! lower resolution than the luma (Y) plane. [`SubsampledBuffer`] splits a
+//! [`ColorBuffer`] into a full resolution luma plane and a subsampled chroma
+//! plane, and reconstructs full resolution colors from them.
+//!
+//! The first component of the encoding is treated as luma and the other two as
+//! chroma, so this is meant for YCbCr encodings like
+//! [`YCbCrBt709`](crate::details::encodings::YCbCrBt709).
+//!
+//! Where chroma samples sit relative to luma samples is given by a
+//! [`ChromaSiting`]. Subsampling and reconstructing with different sitings
+//! shifts the chroma against the luma, which shows as colored fringes along
+//! edges.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::buffer::ColorBuffer;
+//! use colstodian::chroma::{ChromaFilter, ChromaSiting, Subsampling, SubsampledBuffer};
+//! use colstodian::details::encodings::YCbCrBt709;
+//!
+//! let color = Color::srgb_u8(200, 40, 90);
+//! let frame = ColorBuffer::filled(4, 4, color).convert::<YCbCrBt709>();
+//! let subsampled = SubsampledBuffer::new(
+//!     &frame,
+//!     Subsampling::Yuv420,
+//!     ChromaSiting::Left,
+//!     ChromaFilter::Bilinear,
+//! );
+//! assert_eq!(subsampled.chroma().len(), 2 * 2);
+//!
+//! let reconstructed = subsampled.reconstruct(ChromaFilter::Bilinear);
+//! assert_eq!(reconstructed.convert::<SrgbU8>()[(3, 3)], color);
+//! ```
+
+use crate::buffer::ColorBuffer;
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use glam::{Vec2, Vec3, Vec3Swizzles};
+
+/// How far the chroma planes are subsampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsampling {
+    /// Full resolution chroma.
+    Yuv444,
+    /// Half horizontal chroma resolution.
+    Yuv422,
+    /// Half horizontal and vertical chroma resolution.
+    Yuv420,
+}
+
+impl Subsampling {
+    /// The number of luma samples per chroma sample, horizontally and
+    /// vertically.
+    #[inline]
+    pub fn factors(self) -> (usize, usize) {
+        match self {
+            Subsampling::Yuv444 => (1, 1),
+            Subsampling::Yuv422 => (2, 1),
+            Subsampling::Yuv420 => (2, 2),
+        }
+    }
+}
+
+/// Where chroma samples sit relative to the luma samples they cover.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaSiting {
+    /// Centered between luma samples in both directions, as in JPEG and
+    /// MPEG-1.
+    Center,
+    /// Co-sited with the left luma sample and centered vertically, as in
+    /// MPEG-2, H.264 and H.265 by default.
+    #[default]
+    Left,
+    /// Co-sited with the top-left luma sample, as in BT.2020 and 4:2:2 video.
+    TopLeft,
+}
+
+impl ChromaSiting {
+    /// The position of the first chroma sample in luma samples, horizontally
+    /// and vertically, for the given subsampling factors.
+    fn offsets(self, (fx, fy): (usize, usize)) -> (f32, f32) {
+        let center = |f: usize| (f as f32 - 1.0) / 2.0;
+        match self {
+            ChromaSiting::Center => (center(fx), center(fy)),
+            ChromaSiting::Left => (0.0, center(fy)),
+            ChromaSiting::TopLeft => (0.0, 0.0),
+        }
+    }
+}
+
+/// The filter used to subsample and reconstruct chroma.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaFilter {
+    /// Average the luma-resolution block covered by each chroma sample when
+    /// subsampling, and repeat the nearest chroma sample when reconstructing.
+    ///
+    /// Subsampling ignores the siting, which only matches
+    /// [`ChromaSiting::Center`].
+    Box,
+    /// A triangle filter centered on each chroma sample when subsampling, and
+    /// linear interpolation between chroma samples when reconstructing.
+    #[default]
+    Bilinear,
+}
+
+/// A luma plane at full resolution and a chroma plane at reduced resolution.
+pub struct SubsampledBuffer<E: ColorEncoding<Repr = Vec3>> {
+    width: usize,
+    height: usize,
+    subsampling: Subsampling,
+    siting: ChromaSiting,
+    luma: Vec<f32>,
+    chroma: Vec<Vec2>,
+    _encoding: PhantomData<E>,
+}
+
+impl<E: ColorEncoding<Repr = Vec3>> SubsampledBuffer<E> {
+    /// Split `buffer` into its luma plane and a chroma plane subsampled with
+    /// `filter` to samples sited at `siting`.
+    pub fn new(
+        buffer: &ColorBuffer<E>,
+        subsampling: Subsampling,
+        siting: ChromaSiting,
+        filter: ChromaFilter,
+    ) -> Self {
+        let (width, height) = (buffer.width(), buffer.height());
+        let (fx, fy) = subsampling.factors();
+        let (ox, oy) = siting.offsets((fx, fy));
+        let columns = downsample_taps(width, fx, ox, filter);
+        let rows = downsample_taps(height, fy, oy, filter);
+        let chroma_at = |x: usize, y: usize| buffer[(x, y)].repr.yz();
+        let chroma = rows
+            .iter()
+            .flat_map(|row| {
+                columns
+                    .iter()
+                    .map(move |column| filter_2d(row, column, &chroma_at))
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            subsampling,
+            siting,
+            luma: buffer.pixels().iter().map(|color| color.repr.x).collect(),
+            chroma,
+            _encoding: PhantomData,
+        }
+    }
+
+    /// The width of the luma plane.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the luma plane.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The width and height of the chroma plane.
+    #[inline]
+    pub fn chroma_size(&self) -> (usize, usize) {
+        let (fx, fy) = self.subsampling.factors();
+        (self.width.div_ceil(fx), self.height.div_ceil(fy))
+    }
+
+    /// The subsampling of the chroma plane.
+    #[inline]
+    pub fn subsampling(&self) -> Subsampling {
+        self.subsampling
+    }
+
+    /// The siting of the chroma samples.
+    #[inline]
+    pub fn siting(&self) -> ChromaSiting {
+        self.siting
+    }
+
+    /// The luma samples, row by row.
+    #[inline]
+    pub fn luma(&self) -> &[f32] {
+        &self.luma
+    }
+
+    /// The chroma samples, row by row, as pairs of the second and third
+    /// components.
+    #[inline]
+    pub fn chroma(&self) -> &[Vec2] {
+        &self.chroma
+    }
+
+    /// Reconstruct full resolution colors, upsampling chroma with `filter`.
+    pub fn reconstruct(&self, filter: ChromaFilter) -> ColorBuffer<E> {
+        let (fx, fy) = self.subsampling.factors();
+        let (ox, oy) = self.siting.offsets((fx, fy));
+        let (chroma_width, chroma_height) = self.chroma_size();
+        let columns = upsample_taps(self.width, chroma_width, fx, ox, filter);
+        let rows = upsample_taps(self.height, chroma_height, fy, oy, filter);
+        let chroma_at = |x: usize, y: usize| self.chroma[y * chroma_width + x];
+        ColorBuffer::from_fn(self.width, self.height, |x, y| {
+            let chroma = filter_2d(&rows[y], &columns[x], &chroma_at);
+            Color::from_repr(Vec3::new(self.luma[y * self.width + x], chroma.x, chroma.y))
+        })
+    }
+}
+
+impl<E: ColorEncoding<Repr = Vec3>> Clone for SubsampledBuffer<E> {
+    fn clone(&self) -> Self {
+        Self {
+            luma: self.luma.clone(),
+            chroma: self.chroma.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<E: ColorEncoding<Repr = Vec3>> fmt::Debug for SubsampledBuffer<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubsampledBuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("subsampling", &self.subsampling)
+            .field("siting", &self.siting)
+            .field("luma", &self.luma)
+            .field("chroma", &self.chroma)
+            .finish()
+    }
+}
+
+/// Sample indices and weights along one axis.
+type Taps = Vec<(usize, f32)>;
+
+/// The weighted sum of `sample` over the product of `rows` and `columns`.
+fn filter_2d(rows: &Taps, columns: &Taps, sample: &impl Fn(usize, usize) -> Vec2) -> Vec2 {
+    rows.iter()
+        .flat_map(|&(y, wy)| columns.iter().map(move |&(x, wx)| sample(x, y) * wx * wy))
+        .sum()
+}
+
+/// The taps of each chroma sample along an axis of `len` luma samples.
+fn downsample_taps(len: usize, factor: usize, offset: f32, filter: ChromaFilter) -> Vec<Taps> {
+    (0..len.div_ceil(factor))
+        .map(|i| {
+            let taps: Taps = match filter {
+                ChromaFilter::Box => (i * factor..((i + 1) * factor).min(len))
+                    .map(|x| (x, 1.0))
+                    .collect(),
+                ChromaFilter::Bilinear => {
+                    let center = (i * factor) as f32 + offset;
+                    let radius = factor as f32;
+                    let first = (center - radius).floor() as isize + 1;
+                    let last = (center + radius).ceil() as isize - 1;
+                    (first..=last)
+                        .map(|x| {
+                            let weight = 1.0 - (x as f32 - center).abs() / radius;
+                            (x.clamp(0, len as isize - 1) as usize, weight)
+                        })
+                        .collect()
+                }
+            };
+            let total: f32 = taps.iter().map(|(_, weight)| weight).sum();
+            taps.into_iter()
+                .map(|(x, weight)| (x, weight / total))
+                .collect()
+        })
+        .collect()
+}
+
+/// The chroma taps of each of `len` luma samples along an axis with
+/// `chroma_len` chroma samples.
+fn upsample_taps(
+    len: usize,
+    chroma_len: usize,
+    factor: usize,
+    offset: f32,
+    filter: ChromaFilter,
+) -> Vec<Taps> {
+    let last = chroma_len as isize - 1;
+    let index = |i: isize| i.clamp(0, last) as usize;
+    (0..len)
+        .map(|x| {
+            let position = (x as f32 - offset) / factor as f32;
+            match filter {
+                ChromaFilter::Box => vec![(index((position + 0.5).floor() as isize), 1.0)],
+                ChromaFilter::Bilinear => {
+                    let below = position.floor();
+                    let t = position - below;
+                    vec![
+                        (index(below as isize), 1.0 - t),
+                        (index(below as isize + 1), t),
+                    ]
+                }
+            }
+        })
+        .collect()
+}
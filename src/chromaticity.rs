@@ -0,0 +1,176 @@
+//! Chromaticity coordinates, correlated color temperature, Duv and whiteness.
+//!
+//! Besides CIE 1931 xy, chromaticities are given in the more perceptually
+//! uniform CIE 1976 u′v′ coordinates, in which display and lighting white
+//! points are specified and checked. The correlated color temperature (CCT) of
+//! a white is the temperature of the closest black body, and its Duv is its
+//! signed distance from the Planckian locus in CIE 1960 uv, positive above
+//! the locus (towards green) and negative below it (towards magenta).
+//!
+//! Paper, textiles and displays are checked for how white they are with the
+//! CIE whiteness and tint of [`whiteness`], relative to the white point of
+//! the illuminant they are measured under.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::chromaticity;
+//! use colstodian::details::encodings::CieXyz;
+//!
+//! let white = Color::srgb_u8(255, 255, 255).convert::<CieXyz>();
+//! let (u, v) = white.to_uv_prime();
+//! assert!((u - 0.1978).abs() < 1e-3 && (v - 0.4683).abs() < 1e-3);
+//!
+//! let (cct, duv) = chromaticity::cct_duv(u, v);
+//! assert!((cct - 6504.0).abs() < 10.0);
+//! assert!((duv - 0.0032).abs() < 1e-3);
+//! ```
+
+use crate::Color;
+use crate::details::encodings::CieXyz;
+
+use glam::Vec2;
+
+/// The CIE 1976 u′v′ coordinates of the CIE 1931 xy chromaticity `(x, y)`.
+#[inline]
+pub fn xy_to_uv_prime(x: f32, y: f32) -> (f32, f32) {
+    let denominator = -2.0 * x + 12.0 * y + 3.0;
+    (4.0 * x / denominator, 9.0 * y / denominator)
+}
+
+/// The CIE 1931 xy chromaticity of the CIE 1976 u′v′ coordinates `(u, v)`.
+#[inline]
+pub fn uv_prime_to_xy(u: f32, v: f32) -> (f32, f32) {
+    let denominator = 6.0 * u - 16.0 * v + 12.0;
+    (9.0 * u / denominator, 4.0 * v / denominator)
+}
+
+/// The CIE 1960 uv coordinates of a black body at `kelvin`, from Krystek's
+/// approximation of the Planckian locus.
+///
+/// The approximation holds from 1000 K to 15000 K.
+pub fn planckian_uv(kelvin: f32) -> (f32, f32) {
+    let t = kelvin;
+    let u = (0.860_117_8 + 1.541_182_5e-4 * t + 1.286_412e-7 * t * t)
+        / (1.0 + 8.424_202e-4 * t + 7.081_452e-7 * t * t);
+    let v = (0.317_398_7 + 4.228_062_5e-5 * t + 4.204_817e-8 * t * t)
+        / (1.0 - 2.897_418_2e-5 * t + 1.614_560_5e-7 * t * t);
+    (u, v)
+}
+
+/// The correlated color temperature in kelvin and the Duv of the CIE 1976
+/// u′v′ chromaticity `(u, v)`.
+///
+/// The temperature is searched from 1000 K to 15000 K, so chromaticities
+/// outside of that range get its nearest end.
+pub fn cct_duv(u: f32, v: f32) -> (f32, f32) {
+    // CIE 1960 uv has the u of u′v′ and two thirds of its v.
+    let uv = Vec2::new(u, v * 2.0 / 3.0);
+    let locus = |mired: f32| Vec2::from(planckian_uv(1e6 / mired));
+    let distance = |mired: f32| uv.distance_squared(locus(mired));
+
+    // Search in mireds, in which the locus is sampled about evenly, first
+    // coarsely and then by ternary search around the closest sample.
+    const MIN_MIRED: f32 = 1e6 / 15000.0;
+    const MAX_MIRED: f32 = 1e6 / 1000.0;
+    let coarse = (0..=1000)
+        .map(|i| MIN_MIRED + (MAX_MIRED - MIN_MIRED) * i as f32 / 1000.0)
+        .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+        .unwrap_or(MIN_MIRED);
+    let step = (MAX_MIRED - MIN_MIRED) / 1000.0;
+    let bracket = (
+        (coarse - step).max(MIN_MIRED),
+        (coarse + step).min(MAX_MIRED),
+    );
+    let (low, high) = (0..32).fold(bracket, |(low, high), _| {
+        let (a, b) = (low + (high - low) / 3.0, high - (high - low) / 3.0);
+        if distance(a) < distance(b) {
+            (low, b)
+        } else {
+            (a, high)
+        }
+    });
+    let mired = (low + high) / 2.0;
+
+    let nearest = locus(mired);
+    let duv = uv.distance(nearest).copysign(uv.y - nearest.y);
+    (1e6 / mired, duv)
+}
+
+/// The CIE whiteness and tint of a near-white color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Whiteness {
+    /// The whiteness `W`, `100.0` for the perfect reflecting diffuser and
+    /// higher for bluer whites.
+    pub whiteness: f32,
+    /// The tint `T`, positive for greenish and negative for reddish whites.
+    pub tint: f32,
+}
+
+impl Whiteness {
+    /// Whether the formulas apply to a color of the luminance `luminance`,
+    /// which needs `40 < W < 5 Y - 280` and `-4 < T < 2`, with `Y` in
+    /// percent.
+    #[inline]
+    pub fn in_range(self, luminance: f32) -> bool {
+        self.whiteness > 40.0
+            && self.whiteness < 500.0 * luminance - 280.0
+            && self.tint > -4.0
+            && self.tint < 2.0
+    }
+}
+
+/// The CIE whiteness and tint of the CIE 1931 xy chromaticity `(x, y)` with
+/// the luminance `luminance`, relative to the illuminant white point `white`.
+///
+/// These are the formulas of CIE 15 for the 2° observer. The results are
+/// only meaningful for near-white colors, as with [`Whiteness::in_range`].
+#[inline]
+pub fn whiteness(x: f32, y: f32, luminance: f32, white: (f32, f32)) -> Whiteness {
+    let (dx, dy) = (white.0 - x, white.1 - y);
+    Whiteness {
+        whiteness: 100.0 * luminance + 800.0 * dx + 1700.0 * dy,
+        tint: 1000.0 * dx - 650.0 * dy,
+    }
+}
+
+impl Color<CieXyz> {
+    /// Create a [`Color`] from CIE 1976 u′v′ coordinates and the luminance
+    /// `Y`.
+    ///
+    /// A `v` of zero has no defined color, and gives black.
+    #[inline]
+    pub fn from_uv_prime_luminance(u: f32, v: f32, luminance: f32) -> Self {
+        let (x, y) = uv_prime_to_xy(u, v);
+        Self::from_xy_luminance(x, y, luminance)
+    }
+
+    /// The CIE 1976 u′v′ coordinates.
+    ///
+    /// Black has no chromaticity and gets that of the D65 white point.
+    #[inline]
+    pub fn to_uv_prime(self) -> (f32, f32) {
+        let (x, y, _) = self.to_xy_luminance();
+        xy_to_uv_prime(x, y)
+    }
+
+    /// The correlated color temperature in kelvin and the Duv, as with
+    /// [`cct_duv`].
+    #[inline]
+    pub fn cct_duv(self) -> (f32, f32) {
+        let (u, v) = self.to_uv_prime();
+        cct_duv(u, v)
+    }
+
+    /// The CIE whiteness and tint relative to the illuminant white point
+    /// `white`, as with [`whiteness`].
+    ///
+    /// The color is taken as measured under that illuminant, so for colors
+    /// adapted to D65 `white` is that of D65, `(0.3127, 0.3290)`.
+    #[inline]
+    pub fn whiteness(self, white: (f32, f32)) -> Whiteness {
+        let (x, y, luminance) = self.to_xy_luminance();
+        whiteness(x, y, luminance, white)
+    }
+}
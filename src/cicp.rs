@@ -0,0 +1,253 @@
+//! Mapping between color encodings and CICP code points (ITU-T H.273).
+//!
+//! Coding-independent code points identify a color space by three small
+//! integers: the color primaries, the transfer characteristics and the matrix
+//! coefficients. They are used by video containers, AVIF, HEIF and the PNG
+//! `cICP` chunk.
+//!
+//! Encodings with a CICP equivalent implement [`CicpEncoding`]. In the other
+//! direction, [`Cicp::to_custom_color_space`] resolves the primaries and white
+//! point of a triplet and [`Cicp::is`] checks whether a triplet describes a
+//! given encoding.
+//!
+//! CICP does not describe alpha, so encodings that only differ in their alpha
+//! channel share a triplet. Premultiplied encodings have no CICP equivalent.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::cicp::{Cicp, CicpEncoding};
+//!
+//! assert_eq!(SrgbU8::CICP, Cicp::new(1, 13, 0, true));
+//!
+//! let parsed = Cicp::new(1, 13, 0, true);
+//! assert!(parsed.is::<SrgbU8>());
+//! ```
+
+use crate::ColorEncoding;
+use crate::custom::{CustomColorSpace, RgbPrimaries, WhitePoint};
+use crate::details::encodings::{
+    Bt2020, DisplayP3, EncodedBgrU8, EncodedBgraU8, EncodedSrgbF32, EncodedSrgbU8, EncodedSrgbaF32,
+    EncodedSrgbaU8, Srgb, Srgba,
+};
+use crate::white::D65_XY;
+
+use glam::Vec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Color primaries code points (H.273 table 2).
+pub mod primaries {
+    /// BT.709, sRGB.
+    pub const BT709: u8 = 1;
+    /// Unspecified.
+    pub const UNSPECIFIED: u8 = 2;
+    /// BT.470 System M.
+    pub const BT470M: u8 = 4;
+    /// BT.470 System B, G and BT.601 625 lines.
+    pub const BT470BG: u8 = 5;
+    /// BT.601 525 lines, SMPTE 170M.
+    pub const BT601: u8 = 6;
+    /// SMPTE 240M.
+    pub const SMPTE240: u8 = 7;
+    /// Generic film with illuminant C.
+    pub const FILM: u8 = 8;
+    /// BT.2020, BT.2100.
+    pub const BT2020: u8 = 9;
+    /// SMPTE ST 428-1, CIE XYZ.
+    pub const XYZ: u8 = 10;
+    /// SMPTE RP 431-2, DCI-P3.
+    pub const SMPTE431: u8 = 11;
+    /// SMPTE EG 432-1, Display P3.
+    pub const SMPTE432: u8 = 12;
+    /// EBU Tech. 3213-E.
+    pub const EBU3213: u8 = 22;
+}
+
+/// Transfer characteristics code points (H.273 table 3).
+pub mod transfer {
+    /// BT.709.
+    pub const BT709: u8 = 1;
+    /// Unspecified.
+    pub const UNSPECIFIED: u8 = 2;
+    /// Linear.
+    pub const LINEAR: u8 = 8;
+    /// IEC 61966-2-1, sRGB.
+    pub const SRGB: u8 = 13;
+    /// SMPTE ST 2084, perceptual quantizer.
+    pub const PQ: u8 = 16;
+    /// ARIB STD-B67, hybrid log-gamma.
+    pub const HLG: u8 = 18;
+}
+
+/// Matrix coefficients code points (H.273 table 4).
+pub mod matrix {
+    /// Identity, i.e. the components are RGB.
+    pub const IDENTITY: u8 = 0;
+    /// BT.709.
+    pub const BT709: u8 = 1;
+    /// Unspecified.
+    pub const UNSPECIFIED: u8 = 2;
+    /// BT.601.
+    pub const BT601: u8 = 6;
+    /// BT.2020 non-constant luminance.
+    pub const BT2020_NCL: u8 = 9;
+    /// ICtCp.
+    pub const ICTCP: u8 = 14;
+}
+
+/// A CICP triplet plus the video full range flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cicp {
+    /// The color primaries, see [`primaries`].
+    pub color_primaries: u8,
+    /// The transfer characteristics, see [`transfer`].
+    pub transfer_characteristics: u8,
+    /// The matrix coefficients, see [`matrix`].
+    pub matrix_coefficients: u8,
+    /// Whether components use the full range rather than the narrow video
+    /// range.
+    pub full_range: bool,
+}
+
+impl Cicp {
+    /// The [`EncodedSrgbU8`] triplet.
+    pub const SRGB: Self = Self::new(primaries::BT709, transfer::SRGB, matrix::IDENTITY, true);
+    /// The linear [`Srgb`] triplet.
+    pub const LINEAR_SRGB: Self =
+        Self::new(primaries::BT709, transfer::LINEAR, matrix::IDENTITY, true);
+    /// Display P3 with the sRGB transfer function.
+    pub const DISPLAY_P3: Self =
+        Self::new(primaries::SMPTE432, transfer::SRGB, matrix::IDENTITY, true);
+    /// The linear [`DisplayP3`] triplet.
+    pub const LINEAR_DISPLAY_P3: Self = Self::new(
+        primaries::SMPTE432,
+        transfer::LINEAR,
+        matrix::IDENTITY,
+        true,
+    );
+    /// The linear [`Bt2020`] triplet.
+    pub const LINEAR_BT2020: Self =
+        Self::new(primaries::BT2020, transfer::LINEAR, matrix::IDENTITY, true);
+    /// BT.2100 RGB with the PQ transfer function.
+    pub const BT2100_PQ: Self = Self::new(primaries::BT2020, transfer::PQ, matrix::IDENTITY, true);
+    /// BT.2100 RGB with the HLG transfer function.
+    pub const BT2100_HLG: Self =
+        Self::new(primaries::BT2020, transfer::HLG, matrix::IDENTITY, true);
+
+    /// Create a [`Cicp`] from its code points.
+    #[inline(always)]
+    pub const fn new(
+        color_primaries: u8,
+        transfer_characteristics: u8,
+        matrix_coefficients: u8,
+        full_range: bool,
+    ) -> Self {
+        Self {
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            full_range,
+        }
+    }
+
+    /// Whether this triplet describes the encoding `E`.
+    #[inline]
+    pub fn is<E: CicpEncoding>(self) -> bool {
+        self == E::CICP
+    }
+
+    /// The primaries and white point identified by
+    /// [`color_primaries`](Self::color_primaries).
+    ///
+    /// Returns `None` for unspecified, reserved and unknown code points.
+    pub fn to_custom_color_space(self) -> Option<CustomColorSpace> {
+        if self.color_primaries == primaries::XYZ {
+            Some(CustomColorSpace {
+                primaries: RgbPrimaries::CieXyz,
+                white_point: WhitePoint::from_xy(1.0 / 3.0, 1.0 / 3.0),
+            })
+        } else {
+            let ([r, g, b], [w_x, w_y]) = primaries_xy(self.color_primaries)?;
+            Some(CustomColorSpace::from_primaries_and_white_point(
+                r, g, b, w_x, w_y,
+            ))
+        }
+    }
+
+    /// The color primaries code point matching `space`, if any.
+    ///
+    /// Spaces are compared by their RGB to XYZ matrices, so a space built from
+    /// rounded chromaticities still matches.
+    pub fn color_primaries_of(space: &CustomColorSpace) -> Option<u8> {
+        const CODES: [u8; 11] = [
+            primaries::BT709,
+            primaries::BT470M,
+            primaries::BT470BG,
+            primaries::BT601,
+            primaries::SMPTE240,
+            primaries::FILM,
+            primaries::BT2020,
+            primaries::XYZ,
+            primaries::SMPTE431,
+            primaries::SMPTE432,
+            primaries::EBU3213,
+        ];
+
+        CODES.into_iter().find(|&code| {
+            let candidate = Self::new(code, transfer::UNSPECIFIED, matrix::IDENTITY, true)
+                .to_custom_color_space()
+                .expect("all listed code points are known");
+            [Vec3::X, Vec3::Y, Vec3::Z].into_iter().all(|axis| {
+                (space.to_xyz(axis) - candidate.to_xyz(axis))
+                    .abs()
+                    .max_element()
+                    < 1e-3
+            })
+        })
+    }
+}
+
+/// Chromaticities of the red, green and blue primaries plus the white point.
+pub(crate) fn primaries_xy(code: u8) -> Option<([[f32; 2]; 3], [f32; 2])> {
+    const C: [f32; 2] = [0.310, 0.316];
+
+    match code {
+        primaries::BT709 => Some(([[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]], D65_XY)),
+        primaries::BT470M => Some(([[0.67, 0.33], [0.21, 0.71], [0.14, 0.08]], C)),
+        primaries::BT470BG => Some(([[0.64, 0.33], [0.29, 0.60], [0.15, 0.06]], D65_XY)),
+        primaries::BT601 | primaries::SMPTE240 => {
+            Some(([[0.630, 0.340], [0.310, 0.595], [0.155, 0.070]], D65_XY))
+        }
+        primaries::FILM => Some(([[0.681, 0.319], [0.243, 0.692], [0.145, 0.049]], C)),
+        primaries::BT2020 => Some(([[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]], D65_XY)),
+        primaries::SMPTE431 => Some((
+            [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+            [0.314, 0.351],
+        )),
+        primaries::SMPTE432 => Some(([[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]], D65_XY)),
+        primaries::EBU3213 => Some(([[0.630, 0.340], [0.295, 0.605], [0.155, 0.077]], D65_XY)),
+        _ => None,
+    }
+}
+
+/// Implemented by color encodings that have an exact CICP equivalent.
+pub trait CicpEncoding: ColorEncoding {
+    /// The CICP triplet describing this encoding.
+    const CICP: Cicp;
+}
+
+macro_rules! impl_cicp {
+    ($cicp:expr => $($enc:ident),+) => {$(
+        impl CicpEncoding for $enc {
+            const CICP: Cicp = $cicp;
+        }
+    )+};
+}
+
+impl_cicp!(Cicp::SRGB => EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedBgrU8, EncodedBgraU8);
+impl_cicp!(Cicp::LINEAR_SRGB => Srgb, Srgba);
+impl_cicp!(Cicp::LINEAR_DISPLAY_P3 => DisplayP3);
+impl_cicp!(Cicp::LINEAR_BT2020 => Bt2020);
@@ -0,0 +1,366 @@
+//! CMYK separation, with device CMYK and with ICC output profiles.
+//!
+//! Printing mixes cyan, magenta, yellow and black ink, and most colors can be
+//! printed with different amounts of black. Black generation decides how much
+//! of the gray component of a color, the part that cyan, magenta and yellow
+//! have in common, is printed with black instead:
+//!
+//! - Gray-component replacement (GCR) uses black for all colors, which saves
+//!   ink and keeps grays neutral on press.
+//! - Under-color removal (UCR) only uses black for neutral colors and
+//!   shadows, which keeps saturated colors rich.
+//!
+//! Presses also limit the total ink coverage, the sum of the four inks, which
+//! [`Separation`] holds together with the black generation.
+//!
+//! Device CMYK, [`Color::to_device_cmyk`], derives the inks from sRGB with
+//! the idealized model of inks that absorb exactly one of red, green and blue.
+//! It is fine for previews and for devices without a profile, but real inks
+//! and papers differ widely. [`CmykProfile`] separates colors with the tables
+//! of the ICC output profile of a printing condition instead, and can replace
+//! the black generation of the profile with that of a [`Separation`] while
+//! keeping the printed color.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::cmyk::{BlackGeneration, Separation};
+//!
+//! let brown = Color::srgb_u8(102, 77, 51);
+//!
+//! // Without black generation, the gray component is printed with CMY.
+//! let none = Separation {
+//!     black_generation: BlackGeneration::NONE,
+//!     ..Separation::default()
+//! };
+//! let cmyk = brown.to_device_cmyk(&none);
+//! assert_eq!(cmyk.k, 0.0);
+//! assert!((cmyk.y - 0.8).abs() < 1e-3);
+//!
+//! // Maximum GCR prints it with black and two inks.
+//! let max = Separation {
+//!     black_generation: BlackGeneration::Gcr {
+//!         amount: 1.0,
+//!         start: 0.0,
+//!     },
+//!     ..Separation::default()
+//! };
+//! let cmyk = brown.to_device_cmyk(&max);
+//! assert!((cmyk.k - 0.6).abs() < 1e-3 && cmyk.c < 1e-3);
+//!
+//! // Both print the same color.
+//! let preview: Color<SrgbU8> = cmyk.to_device_color().convert();
+//! assert_eq!(preview, brown);
+//! ```
+
+use crate::details::encodings::{CieXyz, EncodedSrgbF32};
+use crate::details::linear_spaces::{self, CieXYZ};
+use crate::details::traits::LinearConvertFromRaw;
+use crate::icc::{IccProfile, RenderingIntent};
+use crate::transfer::srgb_oetf;
+use crate::{Color, ColorEncoding};
+
+use core::ops::ControlFlow;
+
+use glam::{Mat3, Vec3};
+
+/// Amounts of cyan, magenta, yellow and black ink, each in `0.0..=1.0`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Cmyk {
+    /// Cyan.
+    pub c: f32,
+    /// Magenta.
+    pub m: f32,
+    /// Yellow.
+    pub y: f32,
+    /// Black.
+    pub k: f32,
+}
+
+impl Cmyk {
+    /// Inks with the given amounts.
+    #[inline]
+    pub const fn new(c: f32, m: f32, y: f32, k: f32) -> Self {
+        Self { c, m, y, k }
+    }
+
+    /// The total ink coverage, the sum of the inks, up to `4.0`.
+    #[inline]
+    pub fn total_ink(&self) -> f32 {
+        self.c + self.m + self.y + self.k
+    }
+
+    /// The color of these inks in device CMYK, for a preview without a
+    /// profile.
+    #[inline]
+    pub fn to_device_color(self) -> Color<EncodedSrgbF32> {
+        let rgb = (Vec3::ONE - Vec3::new(self.c, self.m, self.y)) * (1.0 - self.k);
+        Color::from_repr(rgb.clamp(Vec3::ZERO, Vec3::ONE))
+    }
+
+    /// The cyan, magenta and yellow inks.
+    #[inline]
+    fn cmy(&self) -> Vec3 {
+        Vec3::new(self.c, self.m, self.y)
+    }
+
+    /// Inks with the cyan, magenta and yellow of `cmy`.
+    #[inline]
+    fn from_cmy(cmy: Vec3, k: f32) -> Self {
+        Self::new(cmy.x, cmy.y, cmy.z, k)
+    }
+
+    /// These inks, with cyan, magenta and yellow reduced alike to keep the
+    /// total coverage within `max_ink`.
+    fn limit_ink(self, max_ink: f32) -> Self {
+        let cmy = self.cmy().element_sum();
+        if self.total_ink() <= max_ink || cmy <= 0.0 {
+            self
+        } else {
+            let scale = (max_ink - self.k).max(0.0) / cmy;
+            Self::from_cmy(self.cmy() * scale, self.k)
+        }
+    }
+}
+
+/// How much black replaces the gray component of colors.
+///
+/// The gray component is the amount of cyan, magenta and yellow a color has
+/// in common in device CMYK, from `0.0` for saturated colors and white to
+/// `1.0` for black.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlackGeneration {
+    /// Gray-component replacement: black replaces the gray component of all
+    /// colors, ramping in linearly from a gray component of `start` to
+    /// `amount` of black at `1.0`.
+    Gcr {
+        /// The fraction of the gray component printed with black, in
+        /// `0.0..=1.0`.
+        amount: f32,
+        /// The gray component where black starts, in `0.0..1.0`.
+        start: f32,
+    },
+    /// Under-color removal: as [`BlackGeneration::Gcr`], but only for the
+    /// neutral part of colors, so saturated colors get no black.
+    Ucr {
+        /// The fraction of the gray component of neutral colors printed with
+        /// black, in `0.0..=1.0`.
+        amount: f32,
+        /// The gray component where black starts, in `0.0..1.0`.
+        start: f32,
+    },
+}
+
+impl Default for BlackGeneration {
+    /// A medium GCR.
+    #[inline]
+    fn default() -> Self {
+        BlackGeneration::Gcr {
+            amount: 0.5,
+            start: 0.2,
+        }
+    }
+}
+
+impl BlackGeneration {
+    /// No black generation.
+    pub const NONE: Self = BlackGeneration::Gcr {
+        amount: 0.0,
+        start: 0.0,
+    };
+
+    /// The black for the device CMY inks `cmy`.
+    fn black(self, cmy: Vec3) -> f32 {
+        let gray = cmy.min_element();
+        let (amount, start, weight) = match self {
+            BlackGeneration::Gcr { amount, start } => (amount, start, 1.0),
+            BlackGeneration::Ucr { amount, start } => {
+                (amount, start, 1.0 - (cmy.max_element() - gray))
+            }
+        };
+        if start >= 1.0 {
+            0.0
+        } else {
+            let ramp = ((gray - start) / (1.0 - start)).clamp(0.0, 1.0);
+            amount.clamp(0.0, 1.0) * ramp * weight
+        }
+    }
+}
+
+/// The black generation and ink limits of a separation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Separation {
+    /// How black replaces the gray component.
+    pub black_generation: BlackGeneration,
+    /// The most black ink, in `0.0..=1.0`.
+    pub max_black: f32,
+    /// The most total ink coverage, in `0.0..=4.0`, like `3.0` for 300%.
+    pub max_ink: f32,
+}
+
+impl Default for Separation {
+    /// A medium GCR with the 300% total ink limit of coated paper.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            black_generation: BlackGeneration::default(),
+            max_black: 1.0,
+            max_ink: 3.0,
+        }
+    }
+}
+
+impl Separation {
+    /// The black for the device CMY inks `cmy`.
+    #[inline]
+    fn black(&self, cmy: Vec3) -> f32 {
+        self.black_generation
+            .black(cmy)
+            .min(self.max_black.clamp(0.0, 1.0))
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The inks of this color in device CMYK, with the black generation and
+    /// ink limits of `separation`.
+    ///
+    /// Colors outside of the sRGB gamut are clipped.
+    pub fn to_device_cmyk(self, separation: &Separation) -> Cmyk {
+        let cmy = device_cmy(self);
+        let k = separation.black(cmy);
+        let cmy = if k < 1.0 {
+            ((cmy - k) / (1.0 - k)).clamp(Vec3::ZERO, Vec3::ONE)
+        } else {
+            Vec3::ZERO
+        };
+        Cmyk::from_cmy(cmy, k).limit_ink(separation.max_ink)
+    }
+}
+
+/// A CMYK output profile, separating colors for a printing condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CmykProfile {
+    profile: IccProfile,
+}
+
+impl CmykProfile {
+    /// The CMYK profile `profile`, or `None` if it isn't a CMYK profile with
+    /// `AToB` and `BToA` tables.
+    pub fn new(profile: IccProfile) -> Option<Self> {
+        (profile.color_space() == *b"CMYK" && profile.has_luts()).then_some(Self { profile })
+    }
+
+    /// Read the ICC profile `data`, as with [`IccProfile::parse`] and
+    /// [`CmykProfile::new`].
+    #[inline]
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        Self::new(IccProfile::parse(data)?)
+    }
+
+    /// The ICC profile.
+    #[inline]
+    pub fn profile(&self) -> &IccProfile {
+        &self.profile
+    }
+
+    /// The inks of `color`, from the `BToA` table of `intent`, with the black
+    /// generation of the profile.
+    pub fn separate<E>(&self, color: Color<E>, intent: RenderingIntent) -> Cmyk
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let pcs = self.profile.color_to_pcs(color);
+        let [c, m, y, k, ..] = self
+            .profile
+            .from_pcs(pcs, intent)
+            .expect("CMYK profiles have tables");
+        Cmyk::new(c, m, y, k)
+    }
+
+    /// The inks of `color` with the black generation and ink limits of
+    /// `separation`.
+    ///
+    /// The color printed by the `BToA` table of `intent` is kept: the black
+    /// is set by `separation`, and cyan, magenta and yellow are solved for
+    /// through the `AToB` table. Where the ink limit removes ink, the color
+    /// gets lighter.
+    pub fn separate_with<E>(
+        &self,
+        color: Color<E>,
+        intent: RenderingIntent,
+        separation: &Separation,
+    ) -> Cmyk
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let initial = self.separate(color, intent);
+        let target = self.to_pcs(initial, intent);
+        let k = separation.black(device_cmy(color));
+
+        // Gauss–Newton iterations on cyan, magenta and yellow, with a
+        // numerical Jacobian, as the tables can't be inverted directly.
+        const STEP: f32 = 1e-3;
+        let pcs_of = |cmy: Vec3| self.to_pcs(Cmyk::from_cmy(cmy, k), intent);
+        let iterate = |cmy: Vec3| {
+            let residual = pcs_of(cmy) - target;
+            if residual.length_squared() < 1e-8 {
+                ControlFlow::Break(cmy)
+            } else {
+                let jacobian = Mat3::from_cols_array_2d(&[0, 1, 2].map(|axis| {
+                    let mut step = Vec3::ZERO;
+                    step[axis] = if cmy[axis] > 1.0 - STEP { -STEP } else { STEP };
+                    ((pcs_of(cmy + step) - pcs_of(cmy)) / step[axis]).to_array()
+                }));
+                // Damped normal equations, which stay solvable where an ink has
+                // no effect.
+                let normal = jacobian.transpose() * jacobian;
+                let trace = normal.x_axis.x + normal.y_axis.y + normal.z_axis.z;
+                let damping = Mat3::from_diagonal(Vec3::splat(1e-6 + 1e-4 * trace));
+                let delta = (normal + damping).inverse() * (jacobian.transpose() * -residual);
+                let cmy = (cmy + delta).clamp(Vec3::ZERO, Vec3::ONE);
+                if delta.length_squared() < 1e-12 {
+                    ControlFlow::Break(cmy)
+                } else {
+                    ControlFlow::Continue(cmy)
+                }
+            }
+        };
+        let (ControlFlow::Break(cmy) | ControlFlow::Continue(cmy)) =
+            (0..16).try_fold(initial.cmy(), |cmy, _| iterate(cmy));
+        Cmyk::from_cmy(cmy, k).limit_ink(separation.max_ink)
+    }
+
+    /// The color the inks `cmyk` print, from the `AToB` table of `intent`.
+    #[inline]
+    pub fn to_color(&self, cmyk: Cmyk, intent: RenderingIntent) -> Color<CieXyz> {
+        self.profile.pcs_to_color(self.to_pcs(cmyk, intent))
+    }
+
+    /// The PCS value the inks `cmyk` print.
+    #[inline]
+    fn to_pcs(&self, cmyk: Cmyk, intent: RenderingIntent) -> Vec3 {
+        self.profile
+            .to_pcs(&[cmyk.c, cmyk.m, cmyk.y, cmyk.k], intent)
+            .expect("CMYK profiles have tables")
+    }
+}
+
+/// The cyan, magenta and yellow of `color` in device CMY, without black.
+fn device_cmy<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <linear_spaces::Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    Vec3::ONE - srgb_oetf(raw.clamp(Vec3::ZERO, Vec3::ONE))
+}
@@ -0,0 +1,378 @@
+//! Shader code generation for encodings and conversions.
+//!
+//! Generates WGSL or GLSL functions from the same transfer functions and
+//! matrices the CPU conversions use, so shaders can't drift out of sync with
+//! them. Functions take and return the three color components as a `vec3`;
+//! alpha, premultiplication and the channel order of encodings like
+//! [`EncodedBgrU8`](crate::details::encodings::EncodedBgrU8) are left to the
+//! texture formats, and integer encodings are expected as normalized floats.
+//!
+//! Only encodings with an RGB [`TransferFunction`], see
+//! [`ColorEncoding::TRANSFER`], are supported.
+//!
+//! [`compute_shader`] turns a whole [`Pipeline`] into a WGSL compute shader
+//! converting textures, as long as all of its steps are matrices.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+//! use colstodian::codegen::{self, ShaderLanguage};
+//! use colstodian::details::encodings::DisplayP3;
+//!
+//! let wgsl = codegen::conversion::<SrgbU8, DisplayP3>(ShaderLanguage::Wgsl).unwrap();
+//! assert!(wgsl.contains("fn encoded_srgb_u8_to_display_p3(c: vec3<f32>) -> vec3<f32>"));
+//!
+//! let glsl = codegen::decode::<SrgbU8>(ShaderLanguage::Glsl).unwrap();
+//! assert!(glsl.contains("vec3 encoded_srgb_u8_decode(vec3 c)"));
+//! ```
+
+use crate::ColorEncoding;
+use crate::calibration::ColorMatrix;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::pipeline::Pipeline;
+use crate::transfer::{
+    CINEON_BLACK, CINEON_DENSITY_PER_CODE, CINEON_GAMMA, CINEON_WHITE, TransferFunction,
+};
+
+use core::fmt::Write;
+
+use glam::{Mat3, Vec3};
+
+/// A shading language to generate code in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderLanguage {
+    /// The WebGPU Shading Language.
+    Wgsl,
+    /// GLSL 3.30 and later, or GLSL ES 3.00 and later.
+    Glsl,
+}
+
+impl ShaderLanguage {
+    fn vec3(self) -> &'static str {
+        match self {
+            ShaderLanguage::Wgsl => "vec3<f32>",
+            ShaderLanguage::Glsl => "vec3",
+        }
+    }
+
+    fn signature(self, name: &str) -> String {
+        match self {
+            ShaderLanguage::Wgsl => format!("fn {name}(c: vec3<f32>) -> vec3<f32>"),
+            ShaderLanguage::Glsl => format!("vec3 {name}(vec3 c)"),
+        }
+    }
+
+    fn local(self, name: &str, value: &str) -> String {
+        match self {
+            ShaderLanguage::Wgsl => format!("let {name} = {value};"),
+            ShaderLanguage::Glsl => format!("vec3 {name} = {value};"),
+        }
+    }
+
+    fn splat(self, value: f32) -> String {
+        format!("{}({value:?})", self.vec3())
+    }
+
+    /// `low` where `c <= threshold`, `high` elsewhere.
+    fn select_le(self, threshold: f32, low: &str, high: &str) -> String {
+        let threshold = self.splat(threshold);
+        match self {
+            ShaderLanguage::Wgsl => format!("select({high}, {low}, c <= {threshold})"),
+            ShaderLanguage::Glsl => format!("mix({high}, {low}, lessThanEqual(c, {threshold}))"),
+        }
+    }
+
+    fn matrix(self, matrix: Mat3) -> String {
+        let columns = matrix
+            .to_cols_array_2d()
+            .map(|[x, y, z]| format!("{}({x:?}, {y:?}, {z:?})", self.vec3()));
+        let [x, y, z] = &columns;
+        match self {
+            ShaderLanguage::Wgsl => format!("mat3x3<f32>({x}, {y}, {z})"),
+            ShaderLanguage::Glsl => format!("mat3({x}, {y}, {z})"),
+        }
+    }
+
+    /// A function binding `locals` and returning `result`.
+    fn function(self, name: &str, locals: &[String], result: &str) -> String {
+        let mut code = format!("{} {{\n", self.signature(name));
+        locals
+            .iter()
+            .for_each(|local| writeln!(code, "    {local}").unwrap());
+        writeln!(code, "    return {result};\n}}").unwrap();
+        code
+    }
+}
+
+/// A function named `name` decoding encoded values with the EOTF of
+/// `transfer`.
+pub fn transfer_decode(transfer: TransferFunction, language: ShaderLanguage, name: &str) -> String {
+    let pow = |exponent: f32| {
+        format!(
+            "pow(max(c, {}), {})",
+            language.splat(0.0),
+            language.splat(exponent)
+        )
+    };
+    let (locals, result) = match transfer {
+        TransferFunction::Linear => (vec![], "c".to_string()),
+        TransferFunction::Srgb => {
+            let high = format!(
+                "pow((max(c, {}) + 0.055) / 1.055, {})",
+                language.splat(0.0),
+                language.splat(2.4)
+            );
+            (vec![], language.select_le(0.04045, "c / 12.92", &high))
+        }
+        TransferFunction::AdobeRgb => (vec![], pow(563.0 / 256.0)),
+        TransferFunction::ProPhotoRgb => (
+            vec![],
+            language.select_le(16.0 / 512.0, "c / 16.0", &pow(1.8)),
+        ),
+        TransferFunction::Pq => {
+            let (m1, m2, c1, c2, c3) = pq_constants();
+            let locals = vec![
+                language.local(
+                    "e",
+                    &format!(
+                        "pow(clamp(c, {}, {}), {})",
+                        language.splat(0.0),
+                        language.splat(1.0),
+                        language.splat(1.0 / m2)
+                    ),
+                ),
+                language.local(
+                    "linear",
+                    &format!(
+                        "pow(max(e - {c1:?}, {}) / ({c2:?} - {c3:?} * e), {})",
+                        language.splat(0.0),
+                        language.splat(1.0 / m1)
+                    ),
+                ),
+            ];
+            (locals, "linear * 10000.0".to_string())
+        }
+        TransferFunction::Cineon => {
+            let offset = cineon_offset();
+            let scale = CINEON_DENSITY_PER_CODE / CINEON_GAMMA * core::f32::consts::LOG2_10;
+            let linear = format!(
+                "(exp2((c * 1023.0 - {CINEON_WHITE:?}) * {scale:?}) - {offset:?}) / {:?}",
+                1.0 - offset
+            );
+            (vec![], linear)
+        }
+    };
+    language.function(name, &locals, &result)
+}
+
+/// A function named `name` encoding linear values with the OETF of
+/// `transfer`, or the inverse EOTF for [`TransferFunction::Pq`].
+pub fn transfer_encode(transfer: TransferFunction, language: ShaderLanguage, name: &str) -> String {
+    let pow = |exponent: f32| {
+        format!(
+            "pow(max(c, {}), {})",
+            language.splat(0.0),
+            language.splat(exponent)
+        )
+    };
+    let (locals, result) = match transfer {
+        TransferFunction::Linear => (vec![], "c".to_string()),
+        TransferFunction::Srgb => {
+            let high = format!("1.055 * {} - 0.055", pow(1.0 / 2.4));
+            (vec![], language.select_le(0.003_130_8, "c * 12.92", &high))
+        }
+        TransferFunction::AdobeRgb => (vec![], pow(256.0 / 563.0)),
+        TransferFunction::ProPhotoRgb => (
+            vec![],
+            language.select_le(1.0 / 512.0, "c * 16.0", &pow(1.0 / 1.8)),
+        ),
+        TransferFunction::Pq => {
+            let (m1, m2, c1, c2, c3) = pq_constants();
+            let locals = vec![language.local(
+                "y",
+                &format!(
+                    "pow(clamp(c / 10000.0, {}, {}), {})",
+                    language.splat(0.0),
+                    language.splat(1.0),
+                    language.splat(m1)
+                ),
+            )];
+            let encoded = format!(
+                "pow(({c1:?} + {c2:?} * y) / (1.0 + {c3:?} * y), {})",
+                language.splat(m2)
+            );
+            (locals, encoded)
+        }
+        TransferFunction::Cineon => {
+            let offset = cineon_offset();
+            let scale = CINEON_GAMMA / CINEON_DENSITY_PER_CODE / core::f32::consts::LOG2_10;
+            let exposure = language.local(
+                "exposure",
+                &format!(
+                    "max(c * {:?} + {offset:?}, {})",
+                    1.0 - offset,
+                    language.splat(1e-10)
+                ),
+            );
+            let encoded = format!("({CINEON_WHITE:?} + log2(exposure) * {scale:?}) / 1023.0");
+            (vec![exposure], encoded)
+        }
+    };
+    language.function(name, &locals, &result)
+}
+
+/// The linear value the Cineon curve maps to zero before rescaling black.
+fn cineon_offset() -> f32 {
+    10f32.powf((CINEON_BLACK - CINEON_WHITE) * CINEON_DENSITY_PER_CODE / CINEON_GAMMA)
+}
+
+/// The PQ constants `m1`, `m2`, `c1`, `c2` and `c3`.
+fn pq_constants() -> (f32, f32, f32, f32, f32) {
+    (
+        2610.0 / 16384.0,
+        2523.0 / 4096.0 * 128.0,
+        3424.0 / 4096.0,
+        2413.0 / 4096.0 * 32.0,
+        2392.0 / 4096.0 * 32.0,
+    )
+}
+
+/// A function `{name}_decode` from the components of `E` to linear
+/// components in its linear color space, where `{name}` is the snake case
+/// [`ColorEncoding::NAME`], or `None` if `E` has no RGB transfer function.
+pub fn decode<E: ColorEncoding>(language: ShaderLanguage) -> Option<String> {
+    E::TRANSFER.map(|transfer| {
+        transfer_decode(
+            transfer,
+            language,
+            &format!("{}_decode", snake_case(E::NAME)),
+        )
+    })
+}
+
+/// A function `{name}_encode` from linear components in the linear color
+/// space of `E` to its components, where `{name}` is the snake case
+/// [`ColorEncoding::NAME`], or `None` if `E` has no RGB transfer function.
+pub fn encode<E: ColorEncoding>(language: ShaderLanguage) -> Option<String> {
+    E::TRANSFER.map(|transfer| {
+        transfer_encode(
+            transfer,
+            language,
+            &format!("{}_encode", snake_case(E::NAME)),
+        )
+    })
+}
+
+/// A function `{src}_to_{dst}` converting the components of `Src` to those
+/// of `Dst`, where `{src}` and `{dst}` are the snake case names of the
+/// encodings, along with the decode and encode functions it calls.
+///
+/// Returns `None` if either encoding has no RGB transfer function.
+pub fn conversion<Src, Dst>(language: ShaderLanguage) -> Option<String>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    Dst::LinearSpace: LinearConvertFromRaw<Src::LinearSpace>,
+{
+    let (src, dst) = (snake_case(Src::NAME), snake_case(Dst::NAME));
+    let matrix = ColorMatrix::conversion::<Src::LinearSpace, Dst::LinearSpace>().matrix;
+    let linear = if matrix == Mat3::IDENTITY {
+        format!("{src}_decode(c)")
+    } else {
+        format!("{} * {src}_decode(c)", language.matrix(matrix))
+    };
+    let function = language.function(
+        &format!("{src}_to_{dst}"),
+        &[],
+        &format!("{dst}_encode({linear})"),
+    );
+    Some(format!(
+        "{}\n{}\n{function}",
+        decode::<Src>(language)?,
+        encode::<Dst>(language)?
+    ))
+}
+
+/// A WGSL compute shader running `pipeline` on the texture bound to
+/// `@group(0) @binding(0)`, writing the results to the storage texture of the
+/// format `output_format`, like `rgba16float`, bound to `@binding(1)`.
+///
+/// The entry point is `main`, with a workgroup size of 8×8 pixels. Alpha is
+/// passed through.
+///
+/// Returns `None` if the pipeline has function steps, either encoding has no
+/// RGB transfer function, or either encoding is premultiplied.
+pub fn compute_shader<Src, Dst, L>(
+    pipeline: &Pipeline<Src, Dst, L>,
+    output_format: &str,
+) -> Option<String>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    L: LinearColorSpace + LinearConvertFromRaw<Src::LinearSpace>,
+    Dst::LinearSpace: LinearConvertFromRaw<L>,
+{
+    let language = ShaderLanguage::Wgsl;
+    let (src, dst) = (snake_case(Src::NAME), snake_case(Dst::NAME));
+    let matrices = pipeline
+        .matrices()
+        .filter(|_| !Src::IS_PREMULTIPLIED && !Dst::IS_PREMULTIPLIED)?;
+    let mut code = format!(
+        "{}\n{}\n",
+        decode::<Src>(language)?,
+        encode::<Dst>(language)?
+    );
+
+    writeln!(code, "@group(0) @binding(0) var input: texture_2d<f32>;").unwrap();
+    writeln!(
+        code,
+        "@group(0) @binding(1) var output: texture_storage_2d<{output_format}, write>;\n"
+    )
+    .unwrap();
+    code.push_str("@compute @workgroup_size(8, 8)\n");
+    code.push_str("fn main(@builtin(global_invocation_id) id: vec3<u32>) {\n");
+    code.push_str("    if (all(id.xy < textureDimensions(input))) {\n");
+    code.push_str("        let pixel = textureLoad(input, id.xy, 0);\n");
+    writeln!(code, "        var c = {src}_decode(pixel.rgb);").unwrap();
+    matrices
+        .into_iter()
+        .filter(|matrix| *matrix != ColorMatrix::IDENTITY)
+        .for_each(|matrix| {
+            let mut step = format!("{} * c", language.matrix(matrix.matrix));
+            if matrix.offset != Vec3::ZERO {
+                let [x, y, z] = matrix.offset.to_array();
+                write!(step, " + {}({x:?}, {y:?}, {z:?})", language.vec3()).unwrap();
+            }
+            writeln!(code, "        c = {step};").unwrap();
+        });
+    writeln!(
+        code,
+        "        textureStore(output, id.xy, vec4<f32>({dst}_encode(c), pixel.a));"
+    )
+    .unwrap();
+    code.push_str("    }\n}\n");
+    Some(code)
+}
+
+/// `name` in snake case, e.g. `encoded_srgb_u8` for `EncodedSrgbU8`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .fold(String::new(), |mut snake, (i, &c)| {
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let boundary = c.is_ascii_uppercase()
+                && previous.is_some_and(|previous| {
+                    !previous.is_ascii_uppercase()
+                        || next.is_some_and(|next| next.is_ascii_lowercase())
+                });
+            if boundary {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+            snake
+        })
+}
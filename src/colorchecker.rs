@@ -0,0 +1,222 @@
+//! The X-Rite ColorChecker Classic chart.
+//!
+//! Camera and display calibration usually starts by measuring the 24 patches
+//! of the chart and comparing them with their reference values. [`PATCHES`]
+//! holds those as CIELAB under D50, as published by X-Rite for charts made
+//! since November 2014, and as 8-bit sRGB. [`evaluate`] compares measured
+//! patches with the references by their CIEDE2000 color difference.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::colorchecker::{self, PATCHES};
+//!
+//! // A perfect capture, quantized to 8-bit sRGB.
+//! let measured = PATCHES.map(|patch| patch.srgb);
+//! let report = colorchecker::evaluate(&measured);
+//! assert!(report.average < 1.0);
+//!
+//! // Cyan is outside of the sRGB gamut, so it is the worst patch.
+//! assert_eq!(PATCHES[report.worst].name, "cyan");
+//! ```
+
+use crate::details::encodings::EncodedSrgbU8;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::white::D50;
+use crate::{Color, ColorEncoding};
+
+use glam::{Mat3, Vec2, Vec3, Vec3Swizzles};
+
+/// A patch of the ColorChecker chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Patch {
+    /// The name of the patch, in lowercase.
+    pub name: &'static str,
+    /// The reference CIELAB value under D50.
+    pub lab: Vec3,
+    /// The reference value as 8-bit sRGB, clipped to the sRGB gamut.
+    pub srgb: Color<EncodedSrgbU8>,
+}
+
+const fn patch(name: &'static str, [l, a, b]: [f32; 3], [r, g, bl]: [u8; 3]) -> Patch {
+    Patch {
+        name,
+        lab: Vec3::new(l, a, b),
+        srgb: Color::srgb_u8(r, g, bl),
+    }
+}
+
+/// The 24 patches of the chart, row by row from dark skin to black.
+pub const PATCHES: [Patch; 24] = [
+    patch("dark skin", [37.54, 14.37, 14.92], [116, 79, 65]),
+    patch("light skin", [64.66, 19.27, 17.50], [197, 144, 127]),
+    patch("blue sky", [49.32, -3.82, -22.54], [91, 120, 155]),
+    patch("foliage", [43.46, -12.74, 22.72], [91, 108, 64]),
+    patch("blue flower", [54.94, 9.61, -24.79], [131, 127, 175]),
+    patch("bluish green", [70.48, -32.26, -0.37], [95, 189, 172]),
+    patch("orange", [62.73, 35.83, 56.50], [224, 124, 48]),
+    patch("purplish blue", [39.43, 10.75, -45.17], [69, 90, 167]),
+    patch("moderate red", [50.57, 48.64, 16.67], [197, 80, 95]),
+    patch("purple", [30.10, 22.54, -20.87], [93, 58, 104]),
+    patch("yellow green", [71.77, -24.13, 58.19], [156, 187, 58]),
+    patch("orange yellow", [71.51, 18.24, 67.37], [227, 161, 39]),
+    patch("blue", [28.37, 15.42, -49.80], [40, 62, 145]),
+    patch("green", [54.38, -39.72, 32.27], [61, 147, 70]),
+    patch("red", [42.43, 51.05, 28.62], [178, 54, 57]),
+    patch("yellow", [81.80, 2.67, 80.41], [236, 199, 15]),
+    patch("magenta", [50.63, 51.28, -14.12], [191, 79, 146]),
+    patch("cyan", [49.57, -29.71, -28.32], [0, 133, 165]),
+    patch("white 9.5", [95.19, -1.03, 2.93], [241, 242, 235]),
+    patch("neutral 8", [81.29, -0.57, 0.44], [201, 202, 201]),
+    patch("neutral 6.5", [66.89, -0.75, -0.06], [161, 163, 163]),
+    patch("neutral 5", [50.76, -0.13, 0.14], [121, 121, 121]),
+    patch("neutral 3.5", [35.63, -0.46, -0.48], [83, 84, 85]),
+    patch("black 2", [20.64, 0.07, -0.46], [50, 50, 50]),
+];
+
+/// How far measured patches are from the reference values, in CIEDE2000.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaEReport {
+    /// The color difference of each patch, in the order of [`PATCHES`].
+    pub delta_e: [f32; 24],
+    /// The mean color difference over all patches.
+    pub average: f32,
+    /// The largest color difference.
+    pub max: f32,
+    /// The index of the patch with the largest color difference.
+    pub worst: usize,
+}
+
+/// Compare `measured` patches, in the order of [`PATCHES`], with the
+/// reference values.
+pub fn evaluate<E>(measured: &[Color<E>; 24]) -> DeltaEReport
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let delta_e: [f32; 24] =
+        core::array::from_fn(|i| delta_e_2000(lab_d50(measured[i]), PATCHES[i].lab));
+    let (worst, max) = delta_e
+        .iter()
+        .copied()
+        .enumerate()
+        .fold(
+            (0, 0.0),
+            |worst, (i, de)| if de > worst.1 { (i, de) } else { worst },
+        );
+    DeltaEReport {
+        delta_e,
+        average: delta_e.iter().sum::<f32>() / 24.0,
+        max,
+        worst,
+    }
+}
+
+/// Bradford chromatic adaptation from D65 to D50.
+const D65_TO_D50: Mat3 = Mat3::from_cols(
+    Vec3::new(1.047_811, 0.029_542_4, -0.009_234_5),
+    Vec3::new(0.022_886_6, 0.990_484_4, 0.015_043_6),
+    Vec3::new(-0.050_127, -0.017_049_1, 0.752_131_6),
+);
+
+/// The CIELAB value of `color` under D50, adapted from D65 with the Bradford
+/// transform.
+pub fn lab_d50<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut xyz, _) = E::src_transform_raw(color.repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut xyz);
+    xyz_to_lab(D65_TO_D50 * xyz, D50)
+}
+
+/// The CIE ratio below which CIELAB is linear in CIE XYZ.
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+/// The CIE slope of the linear part of CIELAB.
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+/// The CIELAB value of the CIE XYZ value `xyz` relative to the white point
+/// `white`.
+pub(crate) fn xyz_to_lab(xyz: Vec3, white: Vec3) -> Vec3 {
+    let t = xyz / white;
+    let f = Vec3::select(
+        t.cmpgt(Vec3::splat(LAB_EPSILON)),
+        t.powf(1.0 / 3.0),
+        (LAB_KAPPA * t + 16.0) / 116.0,
+    );
+    Vec3::new(116.0 * f.y - 16.0, 500.0 * (f.x - f.y), 200.0 * (f.y - f.z))
+}
+
+/// The CIE XYZ value of the CIELAB value `lab` relative to the white point
+/// `white`, the inverse of [`xyz_to_lab`].
+#[cfg(feature = "std")]
+pub(crate) fn lab_to_xyz(lab: Vec3, white: Vec3) -> Vec3 {
+    let fy = (lab.x + 16.0) / 116.0;
+    let f = Vec3::new(fy + lab.y / 500.0, fy, fy - lab.z / 200.0);
+    let cube = f * f * f;
+    let t = Vec3::select(
+        cube.cmpgt(Vec3::splat(LAB_EPSILON)),
+        cube,
+        (116.0 * f - 16.0) / LAB_KAPPA,
+    );
+    t * white
+}
+
+/// The CIEDE2000 color difference of two CIELAB values.
+pub fn delta_e_2000(lab1: Vec3, lab2: Vec3) -> f32 {
+    let pow7 = |x: f32| x * x * x * x * x * x * x;
+    let sqrt = |x: f32| Vec2::new(x, 0.0).powf(0.5).x;
+    let cos = |degrees: f32| Vec2::from_angle(degrees.to_radians()).x;
+    let hue = |a: f32, b: f32| {
+        let h = Vec2::new(a, b).to_angle().to_degrees();
+        if h < 0.0 { h + 360.0 } else { h }
+    };
+
+    let c_bar = (lab1.yz().length() + lab2.yz().length()) / 2.0;
+    let g = 0.5 * (1.0 - sqrt(pow7(c_bar) / (pow7(c_bar) + pow7(25.0))));
+    let (a1, a2) = ((1.0 + g) * lab1.y, (1.0 + g) * lab2.y);
+    let (c1, c2) = (
+        Vec2::new(a1, lab1.z).length(),
+        Vec2::new(a2, lab2.z).length(),
+    );
+    let (h1, h2) = (hue(a1, lab1.z), hue(a2, lab2.z));
+    let chromatic = c1 * c2 != 0.0;
+
+    let delta_l = lab2.x - lab1.x;
+    let delta_c = c2 - c1;
+    let delta_h = match h2 - h1 {
+        _ if !chromatic => 0.0,
+        d if d > 180.0 => d - 360.0,
+        d if d < -180.0 => d + 360.0,
+        d => d,
+    };
+    let delta_h = 2.0 * sqrt(c1 * c2) * Vec2::from_angle((delta_h / 2.0).to_radians()).y;
+
+    let l_bar = (lab1.x + lab2.x) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = match (h1 + h2, (h1 - h2).abs()) {
+        (sum, _) if !chromatic => sum,
+        (sum, difference) if difference <= 180.0 => sum / 2.0,
+        (sum, _) if sum < 360.0 => (sum + 360.0) / 2.0,
+        (sum, _) => (sum - 360.0) / 2.0,
+    };
+
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+    let delta_theta = 30.0
+        * Vec2::splat(-((h_bar - 275.0) / 25.0) * ((h_bar - 275.0) / 25.0))
+            .exp()
+            .x;
+    let r_c = 2.0 * sqrt(pow7(c_bar) / (pow7(c_bar) + pow7(25.0)));
+    let l_50 = (l_bar - 50.0) * (l_bar - 50.0);
+    let s_l = 1.0 + 0.015 * l_50 / sqrt(20.0 + l_50);
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -Vec2::from_angle((2.0 * delta_theta).to_radians()).y * r_c;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+    sqrt(l * l + c * c + h * h + r_t * c * h)
+}
@@ -0,0 +1,308 @@
+//! Text contrast metrics and palette adjustment to meet them.
+//!
+//! [`Color::contrast_ratio`] is the contrast ratio of WCAG 2.x, and
+//! [`Color::apca_contrast`] the lightness contrast Lc of APCA 0.0.98G, the
+//! candidate for WCAG 3. Both measure colors as displayed, i.e. clipped to the
+//! sRGB gamut.
+//!
+//! [`Color::adjust_contrast`] and [`adjust_palette`] change the [`Oklch`]
+//! lightness of colors as little as possible to meet a [`ContrastTarget`]
+//! against a background, keeping chroma and hue.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::contrast::{ContrastTarget, adjust_palette};
+//!
+//! let dark_mode = Color::srgb_u8(24, 24, 32);
+//! let palette = [Color::srgb_u8(40, 80, 200), Color::srgb_u8(250, 200, 80)];
+//!
+//! let fixed = adjust_palette(palette, dark_mode, ContrastTarget::WCAG_AA);
+//!
+//! // The blue is lightened, the yellow already has enough contrast.
+//! assert!(fixed[0].met && fixed[0].lightness_change > 0.0);
+//! assert_eq!(fixed[1].color, palette[1]);
+//! assert!(fixed[0].color.contrast_ratio(dark_mode) >= 4.5);
+//! ```
+
+use crate::details::encodings::Oklch;
+use crate::details::linear_spaces::{CieXYZ, Srgb};
+use crate::details::traits::LinearConvertFromRaw;
+use crate::transfer::srgb_oetf;
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+
+use glam::{Vec2, Vec3};
+
+/// The BT.709 luminance coefficients of linear sRGB used by WCAG.
+const WCAG_LUMINANCE: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+/// The luminance coefficients of APCA, applied to sRGB components decoded with
+/// a pure gamma of 2.4.
+const APCA_LUMINANCE: Vec3 = Vec3::new(0.2126729, 0.7151522, 0.0721750);
+
+/// The number of bisection steps when searching for a lightness.
+const SEARCH_STEPS: usize = 24;
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The relative luminance of WCAG 2.x, the luminance of this color clipped
+    /// to the sRGB gamut.
+    pub fn relative_luminance(self) -> f32 {
+        displayed_srgb(self).dot(WCAG_LUMINANCE)
+    }
+
+    /// The WCAG 2.x contrast ratio between this color and `other`, from `1.0`
+    /// (no contrast) to `21.0` (black on white).
+    ///
+    /// WCAG level AA asks for at least `4.5` for body text.
+    pub fn contrast_ratio<B>(self, other: Color<B>) -> f32
+    where
+        B: ColorEncoding,
+        Srgb: LinearConvertFromRaw<B::LinearSpace>,
+    {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// The APCA lightness contrast Lc of this color as text on `background`.
+    ///
+    /// Lc is positive for dark text on a light background and negative for
+    /// light text on a dark one. An absolute value of `75.0` is recommended
+    /// for body text, `60.0` for larger text.
+    pub fn apca_contrast<B>(self, background: Color<B>) -> f32
+    where
+        B: ColorEncoding,
+        Srgb: LinearConvertFromRaw<B::LinearSpace>,
+    {
+        let text = apca_luminance(displayed_srgb(self));
+        let background = apca_luminance(displayed_srgb(background));
+        if (background - text).abs() < 0.0005 {
+            0.0
+        } else if background > text {
+            let sapc = (powf(background, 0.56) - powf(text, 0.57)) * 1.14;
+            if sapc < 0.1 {
+                0.0
+            } else {
+                (sapc - 0.027) * 100.0
+            }
+        } else {
+            let sapc = (powf(background, 0.65) - powf(text, 0.62)) * 1.14;
+            if sapc > -0.1 {
+                0.0
+            } else {
+                (sapc + 0.027) * 100.0
+            }
+        }
+    }
+}
+
+/// A minimum contrast between a color and a background.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContrastTarget {
+    /// A WCAG 2.x contrast ratio, see [`Color::contrast_ratio`].
+    Wcag(f32),
+    /// An absolute APCA lightness contrast Lc, see [`Color::apca_contrast`].
+    Apca(f32),
+}
+
+impl ContrastTarget {
+    /// WCAG level AA for body text, a contrast ratio of `4.5`.
+    pub const WCAG_AA: Self = ContrastTarget::Wcag(4.5);
+    /// WCAG level AAA for body text, a contrast ratio of `7.0`.
+    pub const WCAG_AAA: Self = ContrastTarget::Wcag(7.0);
+    /// The APCA recommendation for body text, an Lc of `75.0`.
+    pub const APCA_BODY: Self = ContrastTarget::Apca(75.0);
+
+    /// The contrast of `color` on `background` in the metric of this target.
+    ///
+    /// APCA contrast is returned as an absolute value.
+    pub fn contrast<E, B>(self, color: Color<E>, background: Color<B>) -> f32
+    where
+        E: ColorEncoding,
+        B: ColorEncoding,
+        Srgb: LinearConvertFromRaw<E::LinearSpace> + LinearConvertFromRaw<B::LinearSpace>,
+    {
+        match self {
+            ContrastTarget::Wcag(_) => color.contrast_ratio(background),
+            ContrastTarget::Apca(_) => color.apca_contrast(background).abs(),
+        }
+    }
+
+    /// Whether `color` on `background` meets this target.
+    pub fn is_met<E, B>(self, color: Color<E>, background: Color<B>) -> bool
+    where
+        E: ColorEncoding,
+        B: ColorEncoding,
+        Srgb: LinearConvertFromRaw<E::LinearSpace> + LinearConvertFromRaw<B::LinearSpace>,
+    {
+        let target = match self {
+            ContrastTarget::Wcag(target) | ContrastTarget::Apca(target) => target,
+        };
+        self.contrast(color, background) >= target
+    }
+}
+
+/// The result of adjusting a color to meet a [`ContrastTarget`].
+pub struct ContrastAdjustment<E: ColorEncoding> {
+    /// The adjusted color, or the original color if it already met the
+    /// target.
+    pub color: Color<E>,
+    /// How much the [`Oklch`] lightness was changed.
+    pub lightness_change: f32,
+    /// The contrast of the adjusted color, as with
+    /// [`ContrastTarget::contrast`].
+    pub contrast: f32,
+    /// Whether the adjusted color meets the target. If no lightness does, the
+    /// color is the one with the most contrast.
+    pub met: bool,
+}
+
+impl<E: ColorEncoding> Clone for ContrastAdjustment<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: ColorEncoding> Copy for ContrastAdjustment<E> {}
+
+impl<E> fmt::Debug for ContrastAdjustment<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContrastAdjustment")
+            .field("color", &self.color)
+            .field("lightness_change", &self.lightness_change)
+            .field("contrast", &self.contrast)
+            .field("met", &self.met)
+            .finish()
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    /// Change the [`Oklch`] lightness of this color as little as possible so
+    /// that it meets `target` on `background`, keeping its chroma and hue.
+    ///
+    /// Both lightening and darkening are tried and the smaller change wins.
+    /// Lightnesses are found by bisection, assuming that contrast grows
+    /// steadily as the lightness moves away from the background.
+    pub fn adjust_contrast<B>(
+        self,
+        background: Color<B>,
+        target: ContrastTarget,
+    ) -> ContrastAdjustment<E>
+    where
+        B: ColorEncoding,
+        Srgb: LinearConvertFromRaw<B::LinearSpace>,
+    {
+        let adjustment = |color: Color<E>, lightness_change| ContrastAdjustment {
+            color,
+            lightness_change,
+            contrast: target.contrast(color, background),
+            met: target.is_met(color, background),
+        };
+        if target.is_met(self, background) {
+            adjustment(self, 0.0)
+        } else {
+            let (mut raw, alpha) = E::src_transform_raw(self.repr);
+            <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            let lch = Oklch::dst_transform_raw(raw, alpha);
+            let with_lightness = |l: f32| {
+                let mut raw = Oklch::src_transform_raw(Vec3::new(l, lch.y, lch.z)).0;
+                <E::LinearSpace as LinearConvertFromRaw<CieXYZ>>::linear_part_raw(&mut raw);
+                Color::<E>::from_repr(E::dst_transform_raw(raw, alpha))
+            };
+
+            // The smallest change towards `end` that meets the target, if any.
+            let search = |end: f32| {
+                target.is_met(with_lightness(end), background).then(|| {
+                    let (_, high) = (0..SEARCH_STEPS).fold((0.0f32, 1.0f32), |(low, high), _| {
+                        let middle = (low + high) * 0.5;
+                        if target.is_met(with_lightness(lch.x + (end - lch.x) * middle), background)
+                        {
+                            (low, middle)
+                        } else {
+                            (middle, high)
+                        }
+                    });
+                    (end - lch.x) * high
+                })
+            };
+
+            let change = match (search(1.0), search(0.0)) {
+                (Some(lighter), Some(darker)) if darker.abs() < lighter => Some(darker),
+                (Some(lighter), _) => Some(lighter),
+                (None, darker) => darker,
+            };
+            match change {
+                Some(change) => adjustment(with_lightness(lch.x + change), change),
+                None => {
+                    let (white, black) = (with_lightness(1.0), with_lightness(0.0));
+                    if target.contrast(white, background) >= target.contrast(black, background) {
+                        adjustment(white, 1.0 - lch.x)
+                    } else {
+                        adjustment(black, -lch.x)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Adjust each color of `palette` to meet `target` on `background`, as with
+/// [`Color::adjust_contrast`].
+pub fn adjust_palette<E, B, const N: usize>(
+    palette: [Color<E>; N],
+    background: Color<B>,
+    target: ContrastTarget,
+) -> [ContrastAdjustment<E>; N]
+where
+    E: ColorEncoding,
+    B: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace> + LinearConvertFromRaw<B::LinearSpace>,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    palette.map(|color| color.adjust_contrast(background, target))
+}
+
+/// The linear sRGB components of `color` as displayed, i.e. clipped.
+fn displayed_srgb<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    raw.clamp(Vec3::ZERO, Vec3::ONE)
+}
+
+/// The APCA screen luminance of linear sRGB components, with the soft clamp
+/// of near-black levels.
+fn apca_luminance(linear: Vec3) -> f32 {
+    let y = srgb_oetf(linear).powf(2.4).dot(APCA_LUMINANCE);
+    if y < 0.022 {
+        y + powf(0.022 - y, 1.414)
+    } else {
+        y
+    }
+}
+
+/// `x` to the power of `n`.
+#[inline(always)]
+fn powf(x: f32, n: f32) -> f32 {
+    Vec2::splat(x).powf(n).x
+}
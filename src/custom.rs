@@ -30,12 +30,17 @@
 //! ```
 
 use crate::details::encodings::{EncodedSrgbF32, EncodedSrgbU8, Srgb, Srgba};
-use crate::details::linear_spaces::Srgb as SrgbLinearSpace;
+use crate::details::linear_spaces::{CieXYZ, Srgb as SrgbLinearSpace};
 use crate::details::traits::{ConvertFrom, LinearColorSpace, LinearConvertFromRaw};
+use crate::pcs::ConnectionSpace;
 use crate::{Color, ColorEncoding};
 
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
 use glam::Vec3;
 use kolor::details::conversion::LinearColorConversion;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 // Re-export types that are part of our public API.
 pub use kolor::details::color::{RgbPrimaries, WhitePoint};
@@ -70,6 +75,7 @@ pub use kolor::details::color::{RgbPrimaries, WhitePoint};
 /// let color: Color<LinearSrgb> = dynamic.into();
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CustomColorSpace {
     /// The RGB primaries for this color space.
     pub primaries: RgbPrimaries,
@@ -120,6 +126,46 @@ impl CustomColorSpace {
         }
     }
 
+    /// The primaries and white point of the linear color space `L`.
+    pub fn of<L: LinearColorSpace>() -> Self {
+        Self {
+            primaries: L::PRIMARIES,
+            white_point: L::WHITE_POINT,
+        }
+    }
+
+    /// The CIE xy chromaticities of the red, green and blue primaries.
+    pub fn primaries_xy(&self) -> [[f32; 2]; 3] {
+        [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| xyz_to_xy(self.to_xyz(axis)))
+    }
+
+    /// The CIE xy chromaticity of the white point.
+    pub fn white_point_xy(&self) -> [f32; 2] {
+        xyz_to_xy(self.to_xyz(Vec3::ONE))
+    }
+
+    /// Whether every color of `other` with components in `0.0..=1.0` also has
+    /// components in `0.0..=1.0` in this space, i.e. whether this gamut
+    /// contains the gamut of `other`.
+    ///
+    /// Spaces with different white points are compared after chromatic
+    /// adaptation, so white always fits.
+    pub fn gamut_contains(&self, other: &CustomColorSpace) -> bool {
+        let conversion = LinearColorConversion::new(
+            kolor::ColorSpace::new(other.primaries, other.white_point, None),
+            kolor::ColorSpace::new(self.primaries, self.white_point, None),
+        );
+        (0..8u32)
+            .map(|corner| {
+                Vec3::new(
+                    (corner & 1) as f32,
+                    (corner >> 1 & 1) as f32,
+                    (corner >> 2) as f32,
+                )
+            })
+            .all(|corner| in_unit_cube(conversion.convert(corner)))
+    }
+
     /// Convert a color from this custom space to CIE XYZ.
     pub fn to_xyz(&self, color: Vec3) -> Vec3 {
         let conversion = LinearColorConversion::new(
@@ -158,6 +204,25 @@ impl CustomColorSpace {
     }
 }
 
+/// The tolerance of gamut checks, which absorbs rounding in conversions.
+///
+/// This is well below half a step of 8-bit quantization.
+pub(crate) const GAMUT_EPSILON: f32 = 1e-3;
+
+/// Whether all components of `value` are in `0.0..=1.0`, within
+/// [`GAMUT_EPSILON`].
+#[inline(always)]
+pub(crate) fn in_unit_cube(value: Vec3) -> bool {
+    value.cmpge(Vec3::splat(-GAMUT_EPSILON)).all()
+        && value.cmple(Vec3::splat(1.0 + GAMUT_EPSILON)).all()
+}
+
+#[inline(always)]
+fn xyz_to_xy(xyz: Vec3) -> [f32; 2] {
+    let sum = xyz.element_sum();
+    [xyz.x / sum, xyz.y / sum]
+}
+
 /// A color with runtime-defined color space.
 ///
 /// This type allows working with colors in arbitrary color spaces that are not
@@ -188,6 +253,7 @@ impl CustomColorSpace {
 /// let srgb: Color<SrgbU8> = dynamic.into();
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DynamicColor {
     /// The color values in the custom color space.
     pub value: Vec3,
@@ -204,6 +270,21 @@ impl DynamicColor {
         }
     }
 
+    /// Whether this color has components in `0.0..=1.0` in `space`, i.e.
+    /// whether it lies within the gamut of `space`.
+    pub fn fits_in(&self, space: &CustomColorSpace) -> bool {
+        in_unit_cube(self.value_in(space))
+    }
+
+    /// The values of this color in `space`.
+    fn value_in(&self, space: &CustomColorSpace) -> Vec3 {
+        let conversion = LinearColorConversion::new(
+            kolor::ColorSpace::new(self.space.primaries, self.space.white_point, None),
+            kolor::ColorSpace::new(space.primaries, space.white_point, None),
+        );
+        conversion.convert(self.value)
+    }
+
     /// Convert this dynamic color to linear sRGB values.
     fn to_linear_srgb(self) -> Vec3 {
         self.space.to_linear_srgb(self.value)
@@ -266,6 +347,186 @@ impl DynamicColor {
     }
 }
 
+impl DynamicColor {
+    /// This color in `space`, converted through `pcs` where `f` is applied.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use colstodian::Color;
+    /// use colstodian::custom::{CustomColorSpace, DynamicColor};
+    /// use colstodian::details::encodings::Oklab;
+    /// use colstodian::details::linear_spaces::DisplayP3;
+    /// use colstodian::pcs::ConnectionSpace;
+    /// use glam::{Vec2, Vec3};
+    ///
+    /// let red = DynamicColor::new(1.0, 0.0, 0.0, CustomColorSpace::of::<DisplayP3>());
+    ///
+    /// // Limit chroma in Oklab, keeping lightness and hue.
+    /// let limit = |lab: Vec3| {
+    ///     let chroma = Vec2::new(lab.y, lab.z).clamp_length_max(0.2);
+    ///     Vec3::new(lab.x, chroma.x, chroma.y)
+    /// };
+    /// let limited: Color<Oklab> = red.to_color_via(ConnectionSpace::Oklab, limit);
+    /// assert!(limited.chroma() < 0.2 + 1e-4);
+    /// ```
+    pub fn convert_via(
+        &self,
+        space: &CustomColorSpace,
+        pcs: ConnectionSpace,
+        f: impl FnOnce(Vec3) -> Vec3,
+    ) -> Self {
+        let xyz = CustomColorSpace::of::<CieXYZ>();
+        let value = pcs.decode(f(pcs.encode(self.value_in(&xyz))));
+        Self {
+            value: Self { value, space: xyz }.value_in(space),
+            space: *space,
+        }
+    }
+
+    /// This color in the encoding `E`, converted through `pcs` where `f` is
+    /// applied.
+    pub fn to_color_via<E: ColorEncoding>(
+        &self,
+        pcs: ConnectionSpace,
+        f: impl FnOnce(Vec3) -> Vec3,
+    ) -> Color<E> {
+        let converted = self.convert_via(&CustomColorSpace::of::<E::LinearSpace>(), pcs, f);
+        Color::from_repr(E::dst_transform_raw(converted.value, 1.0))
+    }
+}
+
+// Math on `DynamicColor`. The values are linear, so adding, subtracting and
+// scaling are meaningful as long as both operands share the same space.
+
+impl DynamicColor {
+    /// Linearly interpolate from `self`'s value to `other`'s value.
+    ///
+    /// `factor` ranges from `[0..=1.0]`. If `factor` is > `1.0`, results may
+    /// not be sensical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` are not in the same [`CustomColorSpace`].
+    #[inline]
+    pub fn lerp(self, other: Self, factor: f32) -> Self {
+        assert_same_space(&self, &other);
+        Self {
+            value: self.value.lerp(other.value, factor),
+            space: self.space,
+        }
+    }
+}
+
+#[inline(always)]
+#[track_caller]
+fn assert_same_space(a: &DynamicColor, b: &DynamicColor) {
+    assert!(
+        a.space == b.space,
+        "`DynamicColor` operands must share the same `CustomColorSpace`: {:?} != {:?}",
+        a.space,
+        b.space
+    );
+}
+
+/// # Panics
+///
+/// Panics if the operands are not in the same [`CustomColorSpace`].
+impl Add for DynamicColor {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_same_space(&self, &rhs);
+        Self {
+            value: self.value + rhs.value,
+            space: self.space,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the operands are not in the same [`CustomColorSpace`].
+impl AddAssign for DynamicColor {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        assert_same_space(self, &rhs);
+        self.value += rhs.value;
+    }
+}
+
+/// # Panics
+///
+/// Panics if the operands are not in the same [`CustomColorSpace`].
+impl Sub for DynamicColor {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_same_space(&self, &rhs);
+        Self {
+            value: self.value - rhs.value,
+            space: self.space,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the operands are not in the same [`CustomColorSpace`].
+impl SubAssign for DynamicColor {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        assert_same_space(self, &rhs);
+        self.value -= rhs.value;
+    }
+}
+
+impl Mul<f32> for DynamicColor {
+    type Output = Self;
+
+    #[inline]
+    fn mul(mut self, rhs: f32) -> Self::Output {
+        self.value *= rhs;
+        self
+    }
+}
+
+impl Mul<DynamicColor> for f32 {
+    type Output = DynamicColor;
+
+    #[inline]
+    fn mul(self, mut rhs: DynamicColor) -> Self::Output {
+        rhs.value *= self;
+        rhs
+    }
+}
+
+impl MulAssign<f32> for DynamicColor {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.value *= rhs;
+    }
+}
+
+impl Div<f32> for DynamicColor {
+    type Output = Self;
+
+    #[inline]
+    fn div(mut self, rhs: f32) -> Self::Output {
+        self.value /= rhs;
+        self
+    }
+}
+
+impl DivAssign<f32> for DynamicColor {
+    #[inline]
+    fn div_assign(&mut self, rhs: f32) {
+        self.value /= rhs;
+    }
+}
+
 // Implement From<DynamicColor> for various Color types.
 
 impl From<DynamicColor> for Color<Srgb> {
@@ -364,3 +625,198 @@ where
         (custom.x, custom.y, custom.z)
     }
 }
+
+// Measured transfer functions.
+
+/// A transfer function measured as a 1D lookup table per channel, as from the
+/// characterization of a display.
+///
+/// Entry `i` of a table with `n` entries is the linear value of the encoded
+/// value `i / (n - 1)`, relative to white, so the last entry is usually `1.0`.
+/// Values between entries are interpolated linearly.
+///
+/// With the `serde` feature, it is serialized as the array of its three
+/// tables, and tables that [`MeasuredTransfer::new`] rejects fail to
+/// deserialize.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
+pub struct MeasuredTransfer {
+    tables: [Vec<f32>; 3],
+}
+
+#[cfg(feature = "std")]
+impl MeasuredTransfer {
+    /// A transfer function from the tables of the red, green and blue
+    /// channels.
+    ///
+    /// Returns `None` if a table has fewer than two entries or decreases
+    /// anywhere, as it then can't be inverted.
+    pub fn new(red: Vec<f32>, green: Vec<f32>, blue: Vec<f32>) -> Option<Self> {
+        let tables = [red, green, blue];
+        tables
+            .iter()
+            .all(|table| table.len() >= 2 && table.windows(2).all(|pair| pair[0] <= pair[1]))
+            .then_some(Self { tables })
+    }
+
+    /// A transfer function with the same table for all channels.
+    pub fn neutral(table: Vec<f32>) -> Option<Self> {
+        Self::new(table.clone(), table.clone(), table)
+    }
+
+    /// The tables of the red, green and blue channels.
+    pub fn tables(&self) -> &[Vec<f32>; 3] {
+        &self.tables
+    }
+
+    /// Decode encoded values in `0.0..=1.0` into linear values.
+    ///
+    /// Encoded values outside that range are clamped.
+    pub fn decode(&self, encoded: Vec3) -> Vec3 {
+        let [r, g, b] = &self.tables;
+        Vec3::new(
+            lookup(r, encoded.x),
+            lookup(g, encoded.y),
+            lookup(b, encoded.z),
+        )
+    }
+
+    /// Encode linear values, the inverse of [`MeasuredTransfer::decode`].
+    ///
+    /// Linear values beyond the first and last entries encode to `0.0` and
+    /// `1.0`, and values on a flat stretch of a table to its start.
+    pub fn encode(&self, linear: Vec3) -> Vec3 {
+        let [r, g, b] = &self.tables;
+        Vec3::new(
+            inverse_lookup(r, linear.x),
+            inverse_lookup(g, linear.y),
+            inverse_lookup(b, linear.z),
+        )
+    }
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<'de> Deserialize<'de> for MeasuredTransfer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [red, green, blue] = <[Vec<f32>; 3]>::deserialize(deserializer)?;
+        Self::new(red, green, blue).ok_or_else(|| {
+            serde::de::Error::custom("tables need two or more entries and must not decrease")
+        })
+    }
+}
+
+/// Interpolate `table` at the encoded value `encoded`.
+#[cfg(feature = "std")]
+pub(crate) fn lookup(table: &[f32], encoded: f32) -> f32 {
+    let last = table.len() - 1;
+    let position = encoded.clamp(0.0, 1.0) * last as f32;
+    let index = (position as usize).min(last - 1);
+    let (low, high) = (table[index], table[index + 1]);
+    low + (high - low) * (position - index as f32)
+}
+
+/// The encoded value that `table` interpolates to `linear`.
+#[cfg(feature = "std")]
+pub(crate) fn inverse_lookup(table: &[f32], linear: f32) -> f32 {
+    let last = table.len() - 1;
+    let upper = table
+        .partition_point(|&value| value < linear)
+        .clamp(1, last);
+    let (low, high) = (table[upper - 1], table[upper]);
+    let t = if high > low {
+        ((linear - low) / (high - low)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((upper - 1) as f32 + t) / last as f32
+}
+
+/// A custom color space with a measured transfer function, like a
+/// characterized display whose response doesn't follow an analytic curve.
+///
+/// # Examples
+///
+/// ```
+/// use colstodian::Color;
+/// use colstodian::basic_encodings::LinearSrgb;
+/// use colstodian::custom::{CustomColorSpace, CustomEncoding, DynamicColor, MeasuredTransfer};
+/// use glam::Vec3;
+///
+/// // A display with sRGB primaries whose blue channel is a little dim.
+/// let neutral = vec![0.0, 0.04, 0.2, 0.5, 1.0];
+/// let blue = vec![0.0, 0.03, 0.18, 0.46, 0.95];
+/// let transfer = MeasuredTransfer::new(neutral.clone(), neutral, blue).unwrap();
+/// let display = CustomEncoding::new(CustomColorSpace::default(), transfer);
+///
+/// let linear: Color<LinearSrgb> = DynamicColor::from_encoded(Vec3::splat(0.5), &display).to_color();
+/// assert!(linear.repr.abs_diff_eq(Vec3::new(0.2, 0.2, 0.18), 1e-6));
+///
+/// // Encoding for the display compensates the dim channel where it can.
+/// let encoded = display.encode_color(Color::srgb(0.2, 0.2, 0.2));
+/// assert!(encoded.z > 0.5);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CustomEncoding {
+    /// The primaries and white point of the linear values.
+    pub space: CustomColorSpace,
+    /// The transfer function between encoded and linear values.
+    pub transfer: MeasuredTransfer,
+}
+
+#[cfg(feature = "std")]
+impl CustomEncoding {
+    /// Create a custom encoding.
+    pub fn new(space: CustomColorSpace, transfer: MeasuredTransfer) -> Self {
+        Self { space, transfer }
+    }
+
+    /// Decode encoded components into a [`DynamicColor`] in the space of this
+    /// encoding.
+    pub fn decode(&self, encoded: Vec3) -> DynamicColor {
+        DynamicColor {
+            value: self.transfer.decode(encoded),
+            space: self.space,
+        }
+    }
+
+    /// Encode `color`, converting it to the space of this encoding first.
+    pub fn encode(&self, color: &DynamicColor) -> Vec3 {
+        self.transfer.encode(color.value_in(&self.space))
+    }
+
+    /// Encode a [`Color`], converting it to the space of this encoding first.
+    pub fn encode_color<E>(&self, color: Color<E>) -> Vec3
+    where
+        E: ColorEncoding,
+        Srgb: ConvertFrom<E>,
+        SrgbLinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (r, g, b) = color.to_custom_rgb(&self.space);
+        self.transfer.encode(Vec3::new(r, g, b))
+    }
+
+    /// Convert `encoded` components of this encoding to the components of
+    /// `dst`, decoding, converting the linear values between the spaces and
+    /// encoding again.
+    pub fn convert(&self, encoded: Vec3, dst: &CustomEncoding) -> Vec3 {
+        dst.encode(&self.decode(encoded))
+    }
+}
+
+#[cfg(feature = "std")]
+impl DynamicColor {
+    /// The color of the `encoded` components of `encoding`, linear in its
+    /// space.
+    pub fn from_encoded(encoded: Vec3, encoding: &CustomEncoding) -> Self {
+        encoding.decode(encoded)
+    }
+
+    /// The components of this color in `encoding`, converted to its space
+    /// and encoded with its transfer function.
+    pub fn to_encoded(&self, encoding: &CustomEncoding) -> Vec3 {
+        encoding.encode(self)
+    }
+}
@@ -1,3 +1,7 @@
+use crate::component_structs::{Channel, RgbChannels};
+use crate::details::encodings::{EncodedSrgbaU8, Oklab};
+use crate::details::linear_spaces::{self, CieXYZ};
+use crate::quantize::{QuantizedEncoding, Rounding};
 use crate::traits::*;
 
 /*
@@ -87,6 +91,13 @@ use core::ops::*;
 /// assert_eq!(output, Color::srgb_u8(144, 207, 163));
 /// ```
 ///
+/// ### Memory Layout
+///
+/// `Color<E>` is guaranteed to be `#[repr(transparent)]` over `E::Repr`, so
+/// slices of colors and slices of reprs can be reinterpreted as each other
+/// without copying, see [`Color::slice_from_reprs`] and, with the `bytemuck`
+/// feature, [`Color::slice_as_bytes`].
+///
 /// [`LinearSrgb`]: crate::details::encodings::LinearSrgb
 /// [`SrgbU8`]: crate::details::encodings::SrgbU8
 #[repr(transparent)]
@@ -103,6 +114,30 @@ pub struct Color<E: ColorEncoding> {
     pub repr: E::Repr,
 }
 
+/// The schema of the serde representation, an object with the components of
+/// the repr as an array in its `repr` field.
+#[cfg(feature = "schemars")]
+impl<E> schemars::JsonSchema for Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: ArrayRepr,
+    <E::Repr as ArrayRepr>::Array: schemars::JsonSchema,
+{
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("Color_{}", E::NAME).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "object",
+            "properties": {
+                "repr": generator.subschema_for::<<E::Repr as ArrayRepr>::Array>(),
+            },
+            "required": ["repr"],
+        })
+    }
+}
+
 impl<E: ColorEncoding> Copy for Color<E> {}
 
 impl<E: ColorEncoding> Clone for Color<E> {
@@ -121,6 +156,22 @@ impl<E: ColorEncoding> Color<E> {
     }
 }
 
+/// The default [`Color`] has an all-zero [`Repr`][ColorEncoding::Repr].
+///
+/// For all built-in encodings this is black. For encodings with an alpha
+/// component it is *transparent* black. Use the `BLACK` constants, e.g.
+/// `Color::<Srgba>::BLACK`, for opaque black.
+impl<E> Default for Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::from_repr(E::Repr::default())
+    }
+}
+
 impl<SrcEnc: ColorEncoding> Color<SrcEnc> {
     /// Converts `self` from one color encoding to another.
     ///
@@ -180,6 +231,42 @@ impl<SrcEnc: ColorEncoding> Color<SrcEnc> {
         Color::from_repr(dst_repr)
     }
 
+    /// Converts `self` to an encoding with integer components, quantizing with
+    /// the given [`Rounding`] policy.
+    ///
+    /// [`convert`][Color::convert] always uses [`Rounding::HalfUp`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::basic_encodings::*;
+    /// # use colstodian::details::quantize::Rounding;
+    /// let col = Color::linear_srgb(0.2, 0.5, 0.8);
+    ///
+    /// assert_eq!(
+    ///     col.convert_with_rounding::<SrgbU8>(Rounding::HalfUp),
+    ///     col.convert::<SrgbU8>()
+    /// );
+    /// ```
+    pub fn convert_with_rounding<DstEnc>(self, rounding: Rounding) -> Color<DstEnc>
+    where
+        DstEnc: QuantizedEncoding + ConvertFrom<SrcEnc>,
+        DstEnc::LinearSpace: LinearConvertFromRaw<SrcEnc::LinearSpace>,
+    {
+        let mut repr = self.repr;
+
+        <DstEnc as ConvertFrom<SrcEnc>>::map_src(&mut repr);
+
+        let (mut raw, alpha) = SrcEnc::src_transform_raw(repr);
+
+        <DstEnc::LinearSpace as LinearConvertFromRaw<SrcEnc::LinearSpace>>::linear_part_raw(
+            &mut raw,
+        );
+
+        Color::from_repr(DstEnc::dst_transform_quantized(raw, alpha, rounding))
+    }
+
     /// Interprets this color as `DstEnc`. Requires that `DstEnc`'s
     /// `ColorEncoding::Repr` is the same as `self`'s.
     ///
@@ -211,6 +298,136 @@ where
     }
 }
 
+impl<E> Color<E>
+where
+    E: ColorEncoding + WorkingEncoding,
+    E::Repr: ComponentMath,
+{
+    /// The component-wise minimum of `self` and `other`.
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self::from_repr(self.repr.min(other.repr))
+    }
+
+    /// The component-wise maximum of `self` and `other`.
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self::from_repr(self.repr.max(other.repr))
+    }
+
+    /// Each component clamped between the components of `min` and `max`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let hdr = Color::linear_srgb(2.5, -0.1, 0.5);
+    ///
+    /// assert_eq!(
+    ///     hdr.clamp(Color::linear_srgb(0.0, 0.0, 0.0), Color::linear_srgb(1.0, 1.0, 1.0)),
+    ///     Color::linear_srgb(1.0, 0.0, 0.5)
+    /// );
+    /// ```
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Self::from_repr(self.repr.clamp(min.repr, max.repr))
+    }
+
+    /// The absolute value of each component.
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self::from_repr(self.repr.abs())
+    }
+
+    /// Each component, alpha included, raised to the power of `n`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// // A simple gamma tone curve.
+    /// let color = Color::linear_srgb(0.25, 1.0, 0.0);
+    ///
+    /// assert_eq!(color.powf(0.5), Color::linear_srgb(0.5, 1.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn powf(self, n: f32) -> Self {
+        Self::from_repr(self.repr.powf(n))
+    }
+}
+
+impl<E: AlphaEncoding> Color<E> {
+    /// The alpha, from `0.0` (transparent) to `1.0` (opaque).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// fn fade<E: AlphaEncoding>(color: Color<E>) -> Color<E> {
+    ///     color.with_alpha(color.alpha() / 2.0)
+    /// }
+    ///
+    /// assert_eq!(fade(Color::srgba_u8(255, 0, 0, 255)), Color::srgba_u8(255, 0, 0, 128));
+    /// assert_eq!(fade(Color::linear_srgba(1.0, 0.0, 0.0, 0.5)).alpha(), 0.25);
+    /// ```
+    #[inline]
+    pub fn alpha(self) -> f32 {
+        E::alpha(self)
+    }
+
+    /// Set the alpha to `alpha`, keeping the unpremultiplied color.
+    #[inline]
+    pub fn set_alpha(&mut self, alpha: f32) {
+        *self = E::with_alpha(*self, alpha);
+    }
+
+    /// This color with its alpha set to `alpha`, keeping the unpremultiplied
+    /// color.
+    #[inline]
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        E::with_alpha(self, alpha)
+    }
+}
+
+impl<E: PerceptualEncoding> Color<E> {
+    /// The perceived lightness, `0.0` for black and `1.0` for white.
+    #[inline]
+    pub fn lightness(self) -> f32 {
+        E::lightness(self)
+    }
+
+    /// The chroma, i.e. the distance from the neutral axis.
+    #[inline]
+    pub fn chroma(self) -> f32 {
+        E::chroma(self)
+    }
+
+    /// The hue angle in degrees in `0.0..360.0`, or `0.0` if `self` is
+    /// neutral.
+    #[inline]
+    pub fn hue(self) -> f32 {
+        E::hue(self)
+    }
+
+    /// `self` with its lightness set to `lightness`.
+    #[inline]
+    pub fn with_lightness(self, lightness: f32) -> Self {
+        E::with_lightness(self, lightness)
+    }
+
+    /// `self` with its chroma set to `chroma`, keeping its hue.
+    #[inline]
+    pub fn with_chroma(self, chroma: f32) -> Self {
+        E::with_chroma(self, chroma)
+    }
+
+    /// `self` with its hue set to `degrees`, keeping its chroma.
+    #[inline]
+    pub fn with_hue(self, degrees: f32) -> Self {
+        E::with_hue(self, degrees)
+    }
+}
+
 impl<E> Color<E>
 where
     E: ColorEncoding + PerceptualEncoding + LinearInterpolate,
@@ -247,6 +464,189 @@ where
     }
 }
 
+impl<E: ColorEncoding> Color<E> {
+    /// Mix `self` with `other` in the encoding `M`, like CSS `color-mix()`.
+    ///
+    /// Both colors are converted to `M`, their components are premultiplied by
+    /// alpha, interpolated by `factor` and divided by the interpolated alpha
+    /// again, and the result is converted back to `E`. Premultiplying keeps a
+    /// transparent color from tinting the mix.
+    ///
+    /// `factor` ranges from `0.0` (`self`) to `1.0` (`other`).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::details::encodings::Oklab;
+    /// let red = Color::srgb_u8(255, 0, 0);
+    /// let blue = Color::srgb_u8(0, 0, 255);
+    ///
+    /// let mix = red.mix_in::<Oklab>(blue, 0.5);
+    /// assert_eq!(mix, red.convert::<Oklab>().lerp(blue.convert(), 0.5).convert());
+    /// ```
+    pub fn mix_in<M>(self, other: Self, factor: f32) -> Self
+    where
+        M: ColorEncoding<Repr = Vec3>,
+        M::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<M::LinearSpace>,
+    {
+        self.mix_with::<M>(other, factor, None)
+    }
+
+    /// Mix `self` with `other` in the polar encoding `M`, interpolating hue
+    /// with `hue`.
+    ///
+    /// Like [`Color::mix_in`], except that the hue is not premultiplied and
+    /// goes around the hue circle as `hue` says.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::details::encodings::Oklch;
+    /// let magenta = Color::oklch(0.7, 0.1, 350.0).convert::<basic_encodings::SrgbU8>();
+    /// let orange = Color::oklch(0.7, 0.1, 50.0).convert::<basic_encodings::SrgbU8>();
+    ///
+    /// let mix = magenta.mix_in_polar::<Oklch>(orange, 0.5, HueInterpolation::Shorter);
+    /// assert!((mix.convert::<Oklch>().h - 20.0).abs() < 1.0);
+    /// ```
+    pub fn mix_in_polar<M>(self, other: Self, factor: f32, hue: HueInterpolation) -> Self
+    where
+        M: PolarEncoding,
+        M::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<M::LinearSpace>,
+    {
+        self.mix_with::<M>(other, factor, Some((M::HUE, hue)))
+    }
+
+    /// The perceptual difference between `self` and `other`, in any
+    /// encodings, as the Euclidean distance in [`Oklab`] (ΔEOK).
+    ///
+    /// Alpha is ignored. A difference of about `0.02` is just noticeable.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let red = Color::srgb_u8(255, 0, 0);
+    /// let almost_red = Color::srgb_f32(0.99, 0.0, 0.0);
+    ///
+    /// assert!(red.delta_e_ok(almost_red) < 0.01);
+    /// ```
+    pub fn delta_e_ok<O>(self, other: Color<O>) -> f32
+    where
+        O: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace> + LinearConvertFromRaw<O::LinearSpace>,
+    {
+        to_oklab::<E>(self.repr)
+            .0
+            .distance(to_oklab::<O>(other.repr).0)
+    }
+
+    /// Whether `self` and `other`, in any encodings, look the same, that is
+    /// whether their [`Color::delta_e_ok`] is at most `threshold`.
+    ///
+    /// Unlike `==`, this compares across encodings and ignores differences
+    /// too small to see, such as quantization error.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::details::encodings::Oklch;
+    /// let orange = Color::srgb_u8(255, 128, 0);
+    ///
+    /// assert!(orange.visually_eq(orange.convert::<Oklch>(), 0.001));
+    /// assert!(!orange.visually_eq(Color::srgb_u8(255, 140, 0), 0.02));
+    /// ```
+    #[inline]
+    pub fn visually_eq<O>(self, other: Color<O>, threshold: f32) -> bool
+    where
+        O: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace> + LinearConvertFromRaw<O::LinearSpace>,
+    {
+        self.delta_e_ok(other) <= threshold
+    }
+
+    /// The color as an 8-bit sRGB hex color, which formats like CSS, as
+    /// `#rrggbb` or, if not opaque, `#rrggbbaa`.
+    ///
+    /// Colors outside of the sRGB gamut are clipped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let purple = Color::srgb_u8(107, 70, 193);
+    ///
+    /// assert_eq!(purple.to_hex().to_string(), "#6b46c1");
+    /// assert_eq!(Color::srgba_u8(255, 0, 0, 128).to_hex().to_string(), "#ff000080");
+    /// ```
+    #[inline]
+    pub fn to_hex(self) -> Hex
+    where
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (mut raw, alpha) = E::src_transform_raw(self.repr);
+        <linear_spaces::Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+        Hex(EncodedSrgbaU8::dst_transform_raw(raw, alpha))
+    }
+
+    /// Mix with premultiplied alpha in `M`, interpolating the component at
+    /// the index of `hue`, if any, as a hue.
+    fn mix_with<M>(self, other: Self, factor: f32, hue: Option<(usize, HueInterpolation)>) -> Self
+    where
+        M: ColorEncoding<Repr = Vec3>,
+        M::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<M::LinearSpace>,
+    {
+        let to_mix = |color: Self| {
+            let (mut raw, alpha) = E::src_transform_raw(color.repr);
+            <M::LinearSpace as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            (M::dst_transform_raw(raw, alpha), alpha)
+        };
+        let (from, from_alpha) = to_mix(self);
+        let (to, to_alpha) = to_mix(other);
+
+        let alpha = from_alpha + (to_alpha - from_alpha) * factor;
+        let mut mixed = if alpha > 0.0 {
+            (from * from_alpha).lerp(to * to_alpha, factor) / alpha
+        } else {
+            from.lerp(to, factor)
+        };
+        if let Some((index, hue)) = hue {
+            mixed[index] = hue.lerp(from[index], to[index], factor);
+        }
+
+        let (mut raw, _) = M::src_transform_raw(mixed);
+        <E::LinearSpace as LinearConvertFromRaw<M::LinearSpace>>::linear_part_raw(&mut raw);
+        Color::from_repr(E::dst_transform_raw(raw, alpha))
+    }
+}
+
+/// The [`Oklab`] coordinates and the alpha of a color in the encoding `E`.
+pub(crate) fn to_oklab<E>(repr: E::Repr) -> (Vec3, f32)
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, alpha) = E::src_transform_raw(repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    (Oklab::dst_transform_raw(raw, alpha), alpha)
+}
+
+impl<E: PolarEncoding> Color<E> {
+    /// Interpolate from `self` to `other` by `factor`, going around the hue
+    /// circle as `hue` says and linearly for the other components.
+    #[inline]
+    pub fn lerp_hue(self, other: Self, factor: f32, hue: HueInterpolation) -> Self {
+        let mut repr = self.repr.lerp(other.repr, factor);
+        repr[E::HUE] = hue.lerp(self.repr[E::HUE], other.repr[E::HUE], factor);
+        Self::from_repr(repr)
+    }
+}
+
 impl<T, E> AsRef<T> for Color<E>
 where
     E: ColorEncoding,
@@ -287,6 +687,239 @@ where
     }
 }
 
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: ArrayRepr,
+{
+    /// The components of the repr as an array, in memory order.
+    #[inline(always)]
+    pub fn to_array(self) -> <E::Repr as ArrayRepr>::Array {
+        self.repr.to_array()
+    }
+
+    /// Create a [`Color`] from the components of its repr as an array, in
+    /// memory order.
+    ///
+    /// Together with [`Color::to_array`], this lets code built on a math
+    /// crate other than glam pass colors without going through glam types.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::basic_encodings::LinearSrgb;
+    /// let color = Color::<LinearSrgb>::from_array([0.25, 0.5, 1.0]);
+    ///
+    /// assert_eq!(color, Color::linear_srgb(0.25, 0.5, 1.0));
+    /// assert_eq!(color.to_array(), [0.25, 0.5, 1.0]);
+    /// ```
+    #[inline(always)]
+    pub fn from_array(array: <E::Repr as ArrayRepr>::Array) -> Self {
+        Self::from_repr(E::Repr::from_array(array))
+    }
+
+    /// An iterator over the components of the repr, in memory order.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let color = Color::srgb_u8(10, 200, 30);
+    ///
+    /// assert_eq!(color.iter().max(), Some(200));
+    /// assert_eq!(color.iter().map(u32::from).sum::<u32>(), 240);
+    /// ```
+    #[inline(always)]
+    pub fn iter(&self) -> <<E::Repr as ArrayRepr>::Array as IntoIterator>::IntoIter {
+        self.to_array().into_iter()
+    }
+}
+
+impl<E> IntoIterator for Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: ArrayRepr,
+{
+    type Item = <E::Repr as ColorRepr>::Element;
+    type IntoIter = <<E::Repr as ArrayRepr>::Array as IntoIterator>::IntoIter;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_array().into_iter()
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: RgbChannels,
+{
+    /// Returns `self` with the components `a` and `b` swapped.
+    ///
+    /// Channels are named, so this works the same regardless of the order
+    /// components are stored in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the color has no `a` or `b` component, i.e. when swapping
+    /// [`Channel::A`] of a color without alpha.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::details::component_structs::Channel;
+    /// let color = Color::srgb_u8(255, 128, 0);
+    ///
+    /// assert_eq!(color.with_swapped(Channel::R, Channel::B), Color::srgb_u8(0, 128, 255));
+    /// ```
+    pub fn with_swapped(mut self, a: Channel, b: Channel) -> Self {
+        let value_a = *self.channel_mut(a).expect("color has no such channel");
+        let value_b = core::mem::replace(
+            self.channel_mut(b).expect("color has no such channel"),
+            value_a,
+        );
+        *self.channel_mut(a).unwrap() = value_b;
+        self
+    }
+
+    /// Returns `self` with the red and blue components swapped.
+    #[inline]
+    pub fn bgr(self) -> Self {
+        self.with_swapped(Channel::R, Channel::B)
+    }
+
+    /// Returns `self` with the components moved from red, green, blue, alpha
+    /// to alpha, red, green, blue order.
+    ///
+    /// Use this to hand the components to an API that expects ARGB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the color has no alpha component.
+    #[inline]
+    pub fn rgba_to_argb(self) -> Self {
+        self.with_swapped(Channel::B, Channel::A)
+            .with_swapped(Channel::G, Channel::B)
+            .with_swapped(Channel::R, Channel::G)
+    }
+
+    /// Returns `self` with the components moved from alpha, red, green, blue
+    /// to red, green, blue, alpha order. The inverse of
+    /// [`rgba_to_argb`][Color::rgba_to_argb].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the color has no alpha component.
+    #[inline]
+    pub fn argb_to_rgba(self) -> Self {
+        self.with_swapped(Channel::R, Channel::G)
+            .with_swapped(Channel::G, Channel::B)
+            .with_swapped(Channel::B, Channel::A)
+    }
+
+    #[inline(always)]
+    fn channel_mut(
+        &mut self,
+        channel: Channel,
+    ) -> Option<&mut <E::ComponentStruct as RgbChannels>::Element> {
+        <E::ComponentStruct as RgbChannels>::channel_mut(self, channel)
+    }
+}
+
+impl<E: ColorEncoding> Color<E> {
+    /// Reinterpret a slice of reprs as a slice of colors, without copying.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::basic_encodings::SrgbU8;
+    /// let pixels = [[255, 0, 0], [0, 128, 255]];
+    /// let colors = Color::<SrgbU8>::slice_from_reprs(&pixels);
+    ///
+    /// assert_eq!(colors[1], Color::srgb_u8(0, 128, 255));
+    /// assert_eq!(Color::slice_as_reprs(colors), &pixels);
+    /// ```
+    #[inline(always)]
+    pub fn slice_from_reprs(reprs: &[E::Repr]) -> &[Self] {
+        // SAFETY: Color is transparent with the underlying repr.
+        unsafe { core::slice::from_raw_parts(reprs.as_ptr().cast(), reprs.len()) }
+    }
+
+    /// Reinterpret a mutable slice of reprs as a mutable slice of colors,
+    /// without copying.
+    #[inline(always)]
+    pub fn slice_from_reprs_mut(reprs: &mut [E::Repr]) -> &mut [Self] {
+        // SAFETY: Color is transparent with the underlying repr.
+        unsafe { core::slice::from_raw_parts_mut(reprs.as_mut_ptr().cast(), reprs.len()) }
+    }
+
+    /// Reinterpret a slice of colors as a slice of their reprs, without
+    /// copying.
+    #[inline(always)]
+    pub fn slice_as_reprs(colors: &[Self]) -> &[E::Repr] {
+        // SAFETY: Color is transparent with the underlying repr.
+        unsafe { core::slice::from_raw_parts(colors.as_ptr().cast(), colors.len()) }
+    }
+
+    /// Reinterpret a mutable slice of colors as a mutable slice of their
+    /// reprs, without copying.
+    #[inline(always)]
+    pub fn slice_as_reprs_mut(colors: &mut [Self]) -> &mut [E::Repr] {
+        // SAFETY: Color is transparent with the underlying repr.
+        unsafe { core::slice::from_raw_parts_mut(colors.as_mut_ptr().cast(), colors.len()) }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: bytemuck::Pod,
+{
+    /// Reinterpret a slice of colors as raw bytes, e.g. for GPU staging
+    /// memory or image files.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::basic_encodings::SrgbaU8;
+    /// let colors = [Color::srgba_u8(1, 2, 3, 4), Color::srgba_u8(5, 6, 7, 8)];
+    /// let bytes = Color::slice_as_bytes(&colors);
+    ///
+    /// assert_eq!(bytes, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    /// assert_eq!(Color::<SrgbaU8>::slice_from_bytes(bytes), Some(&colors[..]));
+    /// ```
+    #[inline]
+    pub fn slice_as_bytes(colors: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(colors)
+    }
+
+    /// Reinterpret a mutable slice of colors as mutable raw bytes.
+    #[inline]
+    pub fn slice_as_bytes_mut(colors: &mut [Self]) -> &mut [u8] {
+        bytemuck::cast_slice_mut(colors)
+    }
+
+    /// Reinterpret raw bytes as a slice of colors, or `None` if `bytes` is
+    /// not aligned for `E::Repr` or its length is not a multiple of the size
+    /// of `E::Repr`.
+    #[inline]
+    pub fn slice_from_bytes(bytes: &[u8]) -> Option<&[Self]> {
+        bytemuck::try_cast_slice(bytes).ok()
+    }
+
+    /// Reinterpret mutable raw bytes as a mutable slice of colors, or `None`
+    /// as with [`Color::slice_from_bytes`].
+    #[inline]
+    pub fn slice_from_bytes_mut(bytes: &mut [u8]) -> Option<&mut [Self]> {
+        bytemuck::try_cast_slice_mut(bytes).ok()
+    }
+}
+
 // SAFETY: Color is transparent with the underlying repr
 #[cfg(feature = "bytemuck")]
 unsafe impl<E> bytemuck::Zeroable for Color<E>
@@ -332,7 +965,11 @@ where
     E::ComponentStruct: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Color<{}>({:?})", E::NAME, <Self as Deref>::deref(self))
+        let components = <Self as Deref>::deref(self);
+        match E::hex(self.repr) {
+            Some(hex) => write!(f, "Color<{}>({hex}, {components:?})", E::NAME),
+            None => write!(f, "Color<{}>({components:?})", E::NAME),
+        }
     }
 }
 
@@ -343,10 +980,43 @@ where
     E::ComponentStruct: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Color<{}>({})", E::NAME, <Self as Deref>::deref(self))
+        match E::hex(self.repr) {
+            Some(hex) => write!(f, "{}({hex})", E::NAME),
+            None => write!(f, "{}({})", E::NAME, <Self as Deref>::deref(self)),
+        }
     }
 }
 
+/// An 8-bit sRGB color with alpha, formatted as a CSS hex color.
+///
+/// Returned by [`Color::to_hex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hex(pub [u8; 4]);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b, a] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")?;
+        if a != 255 {
+            write!(f, "{a:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The error of parsing a string that is not a CSS hex color into a [`Color`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseHexError;
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not a CSS hex color")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHexError {}
+
 // --------- MATH OP IMPLS -----------
 //
 // For working encodings, we want to be able to multiply or divide by
@@ -571,4 +1241,87 @@ where
     }
 }
 
+impl<E> core::iter::Sum for Color<E>
+where
+    E: ColorEncoding + WorkingEncoding,
+    E::Repr: Add<Output = E::Repr> + Default,
+{
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from_repr(E::Repr::default()), Add::add)
+    }
+}
+
+impl<'a, E> core::iter::Sum<&'a Color<E>> for Color<E>
+where
+    E: ColorEncoding + WorkingEncoding,
+    E::Repr: Add<Output = E::Repr> + Default,
+{
+    #[inline]
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding + WorkingEncoding,
+    E::Repr: Add<Output = E::Repr> + Div<f32, Output = E::Repr> + Default,
+{
+    /// The component-wise average of `colors`, or `None` if there are none.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let samples = [
+    ///     Color::linear_srgb(1.0, 0.0, 0.0),
+    ///     Color::linear_srgb(0.0, 1.0, 0.0),
+    ///     Color::linear_srgb(0.5, 0.5, 0.0),
+    ///     Color::linear_srgb(0.5, 0.5, 1.0),
+    /// ];
+    ///
+    /// assert_eq!(Color::average(samples), Some(Color::linear_srgb(0.5, 0.5, 0.25)));
+    /// assert_eq!(samples.iter().sum::<Color<_>>(), Color::linear_srgb(2.0, 2.0, 1.0));
+    /// ```
+    pub fn average(colors: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let (sum, count) = colors.into_iter().fold(
+            (Self::from_repr(E::Repr::default()), 0usize),
+            |(sum, count), color| (sum + color, count + 1),
+        );
+        (count > 0).then(|| sum / count as f32)
+    }
+
+    /// Mix `colors` by their weights, which are normalized to sum to one, or
+    /// return `None` if the weights sum to zero.
+    ///
+    /// This is an N-way [`lerp`](Color::lerp), e.g. for texture filtering or
+    /// interpolating vertex colors with barycentric coordinates. Negative
+    /// weights extrapolate.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// let red = Color::linear_srgb(1.0, 0.0, 0.0);
+    /// let blue = Color::linear_srgb(0.0, 0.0, 1.0);
+    ///
+    /// let mix = Color::weighted_mix(&[(red, 3.0), (blue, 1.0)]);
+    /// assert_eq!(mix, Some(Color::linear_srgb(0.75, 0.0, 0.25)));
+    /// assert_eq!(Color::weighted_mix(&[(red, 0.0)]), None);
+    /// ```
+    pub fn weighted_mix(colors: &[(Self, f32)]) -> Option<Self>
+    where
+        E::Repr: Mul<f32, Output = E::Repr>,
+    {
+        let total: f32 = colors.iter().map(|(_, weight)| weight).sum();
+        (total != 0.0).then(|| {
+            colors
+                .iter()
+                .map(|&(color, weight)| color * (weight / total))
+                .sum()
+        })
+    }
+}
+
 // --------- END MATH OP IMPLS -----------
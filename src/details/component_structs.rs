@@ -50,6 +50,18 @@ unsafe impl ComponentStructFor<U8Repr> for Rgb<u8> {
     }
 }
 
+unsafe impl ComponentStructFor<U16Repr> for Rgb<u16> {
+    fn cast(repr: &U16Repr) -> &Self {
+        // SAFETY: [u16; 3] is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const U16Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U16Repr) -> &mut Self {
+        // SAFETY: [u16; 3] is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut U16Repr as *mut Self) }
+    }
+}
+
 unsafe impl ComponentStructFor<F32Repr> for Rgb<f32> {
     fn cast(repr: &F32Repr) -> &Self {
         // SAFETY: Vec3 is guaranteed to have the same layout as Self
@@ -62,6 +74,18 @@ unsafe impl ComponentStructFor<F32Repr> for Rgb<f32> {
     }
 }
 
+unsafe impl ComponentStructFor<F64Repr> for Rgb<f64> {
+    fn cast(repr: &F64Repr) -> &Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F64Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F64Repr) -> &mut Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F64Repr as *mut Self) }
+    }
+}
+
 #[cfg(not(target_arch = "spirv"))]
 impl<T: fmt::Display> fmt::Display for Rgb<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -100,6 +124,18 @@ unsafe impl ComponentStructFor<U8aRepr> for Rgba<u8> {
     }
 }
 
+unsafe impl ComponentStructFor<U16aRepr> for Rgba<u16> {
+    fn cast(repr: &U16aRepr) -> &Self {
+        // SAFETY: [u16; 4] is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const U16aRepr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U16aRepr) -> &mut Self {
+        // SAFETY: [u16; 4] is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut U16aRepr as *mut Self) }
+    }
+}
+
 unsafe impl ComponentStructFor<F32aRepr> for Rgba<f32> {
     fn cast(repr: &F32aRepr) -> &Self {
         // SAFETY: Vec4 is guaranteed to have the same layout as Self
@@ -112,6 +148,18 @@ unsafe impl ComponentStructFor<F32aRepr> for Rgba<f32> {
     }
 }
 
+unsafe impl ComponentStructFor<F64aRepr> for Rgba<f64> {
+    fn cast(repr: &F64aRepr) -> &Self {
+        // SAFETY: DVec4 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F64aRepr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F64aRepr) -> &mut Self {
+        // SAFETY: DVec4 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F64aRepr as *mut Self) }
+    }
+}
+
 #[cfg(not(target_arch = "spirv"))]
 impl<T: fmt::Display> fmt::Display for Rgba<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -134,6 +182,126 @@ impl<T: fmt::Display> fmt::Debug for Rgba<T> {
     }
 }
 
+/// A bag of components with names B, G, R. `Color`s with BGR color encodings
+/// will `Deref`/`DerefMut` to this struct so that you can access their
+/// components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bgr<T> {
+    pub b: T,
+    pub g: T,
+    pub r: T,
+}
+
+unsafe impl ComponentStructFor<U8Repr> for Bgr<u8> {
+    fn cast(repr: &U8Repr) -> &Self {
+        // SAFETY: [u8; 3] is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const U8Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U8Repr) -> &mut Self {
+        // SAFETY: [u8; 3] is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut U8Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Bgr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B: {:.3}, G: {:.3}, R: {:.3}", self.b, self.g, self.r)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Bgr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B: {}, G: {}, R: {}", self.b, self.g, self.r)
+    }
+}
+
+/// A bag of components with names G, R, B. `Color`s with GRB color encodings
+/// will `Deref`/`DerefMut` to this struct so that you can access their
+/// components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grb<T> {
+    pub g: T,
+    pub r: T,
+    pub b: T,
+}
+
+unsafe impl ComponentStructFor<U8Repr> for Grb<u8> {
+    fn cast(repr: &U8Repr) -> &Self {
+        // SAFETY: [u8; 3] is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const U8Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U8Repr) -> &mut Self {
+        // SAFETY: [u8; 3] is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut U8Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Grb<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "G: {:.3}, R: {:.3}, B: {:.3}", self.g, self.r, self.b)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Grb<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "G: {}, R: {}, B: {}", self.g, self.r, self.b)
+    }
+}
+
+/// A bag of components with names B, G, R, A. `Color`s with BGRA color
+/// encodings will `Deref`/`DerefMut` to this struct so that you can access
+/// their components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bgra<T> {
+    pub b: T,
+    pub g: T,
+    pub r: T,
+    pub a: T,
+}
+
+unsafe impl ComponentStructFor<U8aRepr> for Bgra<u8> {
+    fn cast(repr: &U8aRepr) -> &Self {
+        // SAFETY: [u8; 4] is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const U8aRepr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U8aRepr) -> &mut Self {
+        // SAFETY: [u8; 4] is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut U8aRepr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Bgra<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "B: {:.3}, G: {:.3}, R: {:.3}, A: {:.3}",
+            self.b, self.g, self.r, self.a
+        )
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Bgra<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "B: {}, G: {}, R: {}, A: {}",
+            self.b, self.g, self.r, self.a
+        )
+    }
+}
+
 /// A bag of components with names L, A, B. Some `Color`s with Lab color
 /// encodings will `Deref`/`DerefMut` to this struct so that you can access
 /// their components with dot-syntax.
@@ -169,6 +337,18 @@ unsafe impl ComponentStructFor<F32Repr> for Lab<f32> {
     }
 }
 
+unsafe impl ComponentStructFor<F64Repr> for Lab<f64> {
+    fn cast(repr: &F64Repr) -> &Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F64Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F64Repr) -> &mut Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F64Repr as *mut Self) }
+    }
+}
+
 #[cfg(not(target_arch = "spirv"))]
 impl<T: fmt::Display> fmt::Display for Lab<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -182,3 +362,447 @@ impl<T: fmt::Display> fmt::Debug for Lab<T> {
         write!(f, "L: {}, a: {}, b: {}", self.l, self.a, self.b)
     }
 }
+
+/// A bag of components with names L, C, h. `Color`s with polar color
+/// encodings like [`Oklch`][crate::details::encodings::Oklch] will
+/// `Deref`/`DerefMut` to this struct so that you can access their components
+/// with dot-syntax, e.g. `color.h += 30.0`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LCh<T> {
+    pub l: T,
+    pub c: T,
+    pub h: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for LCh<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for LCh<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "L: {:.3}, C: {:.3}, h: {:.3}", self.l, self.c, self.h)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for LCh<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "L: {}, C: {}, h: {}", self.l, self.c, self.h)
+    }
+}
+
+/// A bag of components with names X, Y, Z. `Color`s with CIE XYZ color
+/// encodings will `Deref`/`DerefMut` to this struct so that you can access
+/// their components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xyz<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for Xyz<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+unsafe impl ComponentStructFor<F64Repr> for Xyz<f64> {
+    fn cast(repr: &F64Repr) -> &Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F64Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F64Repr) -> &mut Self {
+        // SAFETY: DVec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F64Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Xyz<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "X: {:.3}, Y: {:.3}, Z: {:.3}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Xyz<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "X: {}, Y: {}, Z: {}", self.x, self.y, self.z)
+    }
+}
+
+/// A bag of components with names Y, Cb, Cr. `Color`s with YCbCr color
+/// encodings will `Deref`/`DerefMut` to this struct so that you can access
+/// their components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct YCbCr<T> {
+    pub y: T,
+    pub cb: T,
+    pub cr: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for YCbCr<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for YCbCr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Y: {:.3}, Cb: {:.3}, Cr: {:.3}",
+            self.y, self.cb, self.cr
+        )
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for YCbCr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Y: {}, Cb: {}, Cr: {}", self.y, self.cb, self.cr)
+    }
+}
+
+/// A bag of components with names I, Ct, Cp. `Color`s with ICtCp color
+/// encodings will `Deref`/`DerefMut` to this struct so that you can access
+/// their components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ICtCp<T> {
+    pub i: T,
+    pub ct: T,
+    pub cp: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for ICtCp<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for ICtCp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "I: {:.3}, Ct: {:.3}, Cp: {:.3}",
+            self.i, self.ct, self.cp
+        )
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for ICtCp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "I: {}, Ct: {}, Cp: {}", self.i, self.ct, self.cp)
+    }
+}
+
+/// A bag of components with names H, S, I. `Color`s with HSI color encodings
+/// will `Deref`/`DerefMut` to this struct so that you can access their
+/// components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hsi<T> {
+    pub h: T,
+    pub s: T,
+    pub i: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for Hsi<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Hsi<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H: {:.3}, S: {:.3}, I: {:.3}", self.h, self.s, self.i)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Hsi<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H: {}, S: {}, I: {}", self.h, self.s, self.i)
+    }
+}
+
+/// A bag of components with names H, C, T. `Color`s in the
+/// [`Hct`][crate::details::encodings::Hct] encoding will `Deref`/`DerefMut` to
+/// this struct so that you can access their components with dot-syntax.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HueChromaTone<T> {
+    pub h: T,
+    pub c: T,
+    pub t: T,
+}
+
+unsafe impl ComponentStructFor<F32Repr> for HueChromaTone<f32> {
+    fn cast(repr: &F32Repr) -> &Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &*(repr as *const F32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32Repr) -> &mut Self {
+        // SAFETY: Vec3 is guaranteed to have the same layout as Self
+        unsafe { &mut *(repr as *mut F32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for HueChromaTone<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H: {:.3}, C: {:.3}, T: {:.3}", self.h, self.c, self.t)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for HueChromaTone<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H: {}, C: {}, T: {}", self.h, self.c, self.t)
+    }
+}
+
+/// A bag of a single component named V. `Color`s with grayscale color
+/// encodings will `Deref`/`DerefMut` to this struct so that you can access
+/// their value with dot-syntax.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gray<T> {
+    pub v: T,
+}
+
+unsafe impl ComponentStructFor<U8GrayRepr> for Gray<u8> {
+    fn cast(repr: &U8GrayRepr) -> &Self {
+        // SAFETY: Self is a transparent wrapper of u8
+        unsafe { &*(repr as *const U8GrayRepr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U8GrayRepr) -> &mut Self {
+        // SAFETY: Self is a transparent wrapper of u8
+        unsafe { &mut *(repr as *mut U8GrayRepr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Display for Gray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "V: {:.3}", self.v)
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display> fmt::Debug for Gray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "V: {}", self.v)
+    }
+}
+
+/// A bag of `N` spectral bands. `Color`s with multispectral color encodings
+/// will `Deref`/`DerefMut` to this struct so that you can access their bands
+/// with `color.bands[i]`.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Bands<T, const N: usize> {
+    pub bands: [T; N],
+}
+
+unsafe impl<const N: usize> ComponentStructFor<F32BandsRepr<N>> for Bands<f32, N> {
+    fn cast(repr: &F32BandsRepr<N>) -> &Self {
+        // SAFETY: Self is a transparent wrapper of [f32; N]
+        unsafe { &*(repr as *const F32BandsRepr<N> as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut F32BandsRepr<N>) -> &mut Self {
+        // SAFETY: Self is a transparent wrapper of [f32; N]
+        unsafe { &mut *(repr as *mut F32BandsRepr<N> as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display, const N: usize> fmt::Display for Bands<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bands:")?;
+        self.bands
+            .iter()
+            .try_for_each(|band| write!(f, " {band:.3}"))
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl<T: fmt::Display, const N: usize> fmt::Debug for Bands<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bands:")?;
+        self.bands.iter().try_for_each(|band| write!(f, " {band}"))
+    }
+}
+
+/// A 10-bit-per-channel RGB color with 2-bit alpha packed into a `u32`, from
+/// most to least significant bits A, R, G, B (`A2R10G10B10`).
+///
+/// `Color`s with packed encodings like
+/// [`EncodedHdr10`][crate::details::encodings::EncodedHdr10] will
+/// `Deref`/`DerefMut` to this struct, so that the channels can be read with
+/// [`r`](Self::r), [`g`](Self::g), [`b`](Self::b) and [`a`](Self::a).
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct A2Rgb10 {
+    pub bits: u32,
+}
+
+impl A2Rgb10 {
+    /// Pack the channels, keeping the low 10 bits of `r`, `g` and `b` and the
+    /// low 2 bits of `a`.
+    #[inline(always)]
+    pub const fn new(r: u16, g: u16, b: u16, a: u8) -> Self {
+        Self {
+            bits: (a as u32 & 0x3) << 30
+                | (r as u32 & 0x3ff) << 20
+                | (g as u32 & 0x3ff) << 10
+                | (b as u32 & 0x3ff),
+        }
+    }
+
+    /// The red channel, `0..=1023`.
+    #[inline(always)]
+    pub const fn r(self) -> u16 {
+        (self.bits >> 20 & 0x3ff) as u16
+    }
+
+    /// The green channel, `0..=1023`.
+    #[inline(always)]
+    pub const fn g(self) -> u16 {
+        (self.bits >> 10 & 0x3ff) as u16
+    }
+
+    /// The blue channel, `0..=1023`.
+    #[inline(always)]
+    pub const fn b(self) -> u16 {
+        (self.bits & 0x3ff) as u16
+    }
+
+    /// The alpha channel, `0..=3`.
+    #[inline(always)]
+    pub const fn a(self) -> u8 {
+        (self.bits >> 30) as u8
+    }
+}
+
+unsafe impl ComponentStructFor<U32Repr> for A2Rgb10 {
+    fn cast(repr: &U32Repr) -> &Self {
+        // SAFETY: Self is a transparent wrapper of u32
+        unsafe { &*(repr as *const U32Repr as *const Self) }
+    }
+
+    fn cast_mut(repr: &mut U32Repr) -> &mut Self {
+        // SAFETY: Self is a transparent wrapper of u32
+        unsafe { &mut *(repr as *mut U32Repr as *mut Self) }
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl fmt::Display for A2Rgb10 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "R: {}, G: {}, B: {}, A: {}",
+            self.r(),
+            self.g(),
+            self.b(),
+            self.a()
+        )
+    }
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl fmt::Debug for A2Rgb10 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A2R10G10B10: {:#010x}", self.bits)
+    }
+}
+
+/// A component of an RGB-like component struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    R,
+    G,
+    B,
+    A,
+}
+
+/// Implemented by component structs with red, green, blue and possibly alpha
+/// components, regardless of their order in memory.
+pub trait RgbChannels: Copy {
+    /// The type of a single component.
+    type Element: Copy;
+
+    /// The component for `channel`, or `None` if there is no such component.
+    fn channel_mut(&mut self, channel: Channel) -> Option<&mut Self::Element>;
+}
+
+macro_rules! impl_rgb_channels {
+    ($($ty:ident { $($channel:ident => $field:ident),+ }),+) => {$(
+        impl<T: Copy> RgbChannels for $ty<T> {
+            type Element = T;
+
+            #[inline(always)]
+            fn channel_mut(&mut self, channel: Channel) -> Option<&mut T> {
+                match channel {
+                    $(Channel::$channel => Some(&mut self.$field),)+
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+    )+};
+}
+
+impl_rgb_channels!(
+    Rgb { R => r, G => g, B => b },
+    Rgba { R => r, G => g, B => b, A => a },
+    Bgr { R => r, G => g, B => b },
+    Bgra { R => r, G => g, B => b, A => a }
+);
@@ -1,25 +1,27 @@
-use crate::Color;
+use crate::bit_depth::normalize;
 use crate::component_structs::*;
 use crate::linear_spaces;
+use crate::quantize::*;
 use crate::reprs::*;
 use crate::traits::*;
+use crate::transfer::{self, TransferFunction};
+use crate::white::D65_XY;
+use crate::{Color, Hex, ParseHexError};
 
+use core::str::FromStr;
+
+use glam::DVec2;
+use glam::DVec3;
+use glam::DVec4;
+use glam::Mat3;
+use glam::Vec2;
 use glam::Vec3;
+use glam::Vec3Swizzles;
 use glam::Vec4;
 use glam::Vec4Swizzles;
 use kolor::details::color::WhitePoint;
 use kolor::details::transform;
 
-#[inline(always)]
-fn u8_to_f32(x: u8) -> f32 {
-    x as f32 / 255.0
-}
-
-#[inline(always)]
-fn f32_to_u8(x: f32) -> u8 {
-    (x.clamp(0.0, 1.0) * 255.0).round() as u8
-}
-
 #[doc = include_str!("descriptions/srgb_u8.md")]
 pub struct EncodedSrgbU8;
 
@@ -41,6 +43,27 @@ impl Color<EncodedSrgbU8> {
     pub const fn srgb_u8(r: u8, g: u8, b: u8) -> Self {
         Self::encoded_srgb_u8(r, g, b)
     }
+
+    /// Parse a CSS-style hex color like `"#ff0080"` or `"f08"`, or return
+    /// `None` if `hex` is not one.
+    ///
+    /// The leading `#` is optional. Hex colors with alpha are rejected, parse
+    /// those with [`Color::<EncodedSrgbaU8>::from_hex`] instead.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        match parse_hex(hex)? {
+            (rgb, None) => Some(Color::from_repr(rgb)),
+            (_, Some(_)) => None,
+        }
+    }
+}
+
+/// Parses CSS-style hex colors without alpha, as [`Color::<EncodedSrgbU8>::from_hex`].
+impl FromStr for Color<EncodedSrgbU8> {
+    type Err = ParseHexError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(hex).ok_or(ParseHexError)
+    }
 }
 
 impl ColorEncoding for EncodedSrgbU8 {
@@ -49,24 +72,32 @@ impl ColorEncoding for EncodedSrgbU8 {
     type Repr = U8Repr;
 
     const NAME: &'static str = "EncodedSrgbU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
 
     #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
         let [x, y, z] = repr;
         let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
-        let optical = transform::srgb_eotf(raw_electro, WhitePoint::D65);
+        let optical = transfer::srgb_eotf(raw_electro);
         (optical, 1.0)
     }
 
     #[inline]
-    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        let electro = transform::srgb_oetf(raw, WhitePoint::D65);
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
 
-        [
-            f32_to_u8(electro.x),
-            f32_to_u8(electro.y),
-            f32_to_u8(electro.z),
-        ]
+impl QuantizedEncoding for EncodedSrgbU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+        let electro = transfer::srgb_oetf(raw);
+        vec3_to_u8(electro, rounding)
     }
 }
 
@@ -109,16 +140,22 @@ impl ColorEncoding for EncodedSrgbF32 {
     type Repr = F32Repr;
 
     const NAME: &'static str = "EncodedSrgbF32";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
 
     #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
-        let optical = transform::srgb_eotf(repr, WhitePoint::D65);
+        let optical = transfer::srgb_eotf(repr);
         (optical, 1.0)
     }
 
     #[inline]
     fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        transform::srgb_oetf(raw, WhitePoint::D65)
+        transfer::srgb_oetf(raw)
     }
 }
 
@@ -153,6 +190,47 @@ impl Color<EncodedSrgbaU8> {
     pub const fn srgba_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self::encoded_srgba_u8(r, g, b, a)
     }
+
+    /// Parse a CSS-style hex color like `"#ff008080"`, `"#ff0080"` or
+    /// `"f08"`, or return `None` if `hex` is not one.
+    ///
+    /// The leading `#` is optional. Colors without alpha are opaque.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let ([r, g, b], a) = parse_hex(hex)?;
+        Some(Color::from_repr([r, g, b, a.unwrap_or(255)]))
+    }
+}
+
+/// Parses CSS-style hex colors, as [`Color::<EncodedSrgbaU8>::from_hex`].
+impl FromStr for Color<EncodedSrgbaU8> {
+    type Err = ParseHexError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(hex).ok_or(ParseHexError)
+    }
+}
+
+/// Parse the 3, 4, 6 or 8 hex digits of a CSS hex color, with an optional
+/// leading `#`.
+fn parse_hex(hex: &str) -> Option<([u8; 3], Option<u8>)> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex).as_bytes();
+    let nibble = |i: usize| (digits[i] as char).to_digit(16).map(|d| d as u8);
+    let (components, width) = match digits.len() {
+        3 | 4 => Some((digits.len(), 1)),
+        6 | 8 => Some((digits.len() / 2, 2)),
+        _ => None,
+    }?;
+    let component = |i: usize| {
+        if i >= components {
+            Some(0)
+        } else if width == 1 {
+            Some(nibble(i)? * 17)
+        } else {
+            Some(nibble(2 * i)? * 16 + nibble(2 * i + 1)?)
+        }
+    };
+    let [r, g, b, a] = [component(0)?, component(1)?, component(2)?, component(3)?];
+    Some(([r, g, b], (components == 4).then_some(a)))
 }
 
 impl ColorEncoding for EncodedSrgbaU8 {
@@ -161,26 +239,35 @@ impl ColorEncoding for EncodedSrgbaU8 {
     type Repr = U8aRepr;
 
     const NAME: &'static str = "EncodedSrgbaU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
 
     #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
         let [x, y, z, a] = repr;
         let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
-        let optical = transform::srgb_eotf(raw_electro, WhitePoint::D65);
+        let optical = transfer::srgb_eotf(raw_electro);
         let a = u8_to_f32(a);
         (optical, a)
     }
 
     #[inline]
     fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
-        let electro = transform::srgb_oetf(raw, WhitePoint::D65);
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
 
-        [
-            f32_to_u8(electro.x),
-            f32_to_u8(electro.y),
-            f32_to_u8(electro.z),
-            f32_to_u8(alpha),
-        ]
+impl QuantizedEncoding for EncodedSrgbaU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let electro = transfer::srgb_oetf(raw);
+        let [r, g, b] = vec3_to_u8(electro, rounding);
+        [r, g, b, rounding.quantize_u8(alpha, 3)]
     }
 }
 
@@ -223,16 +310,23 @@ impl ColorEncoding for EncodedSrgbaF32 {
     type Repr = F32aRepr;
 
     const NAME: &'static str = "EncodedSrgbaF32";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
 
     #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
-        let optical = transform::srgb_eotf(repr.xyz(), WhitePoint::D65);
+        let optical = transfer::srgb_eotf(repr.xyz());
         (optical, repr.w)
     }
 
     #[inline]
     fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
-        let electro = transform::srgb_oetf(raw, WhitePoint::D65);
+        let electro = transfer::srgb_oetf(raw);
         electro.extend(alpha)
     }
 }
@@ -269,12 +363,20 @@ impl ColorEncoding for EncodedSrgbaPremultipliedU8 {
     type Repr = U8aRepr;
 
     const NAME: &'static str = "EncodedSrgbaPremultipliedU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+    const IS_PREMULTIPLIED: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
 
     #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
         let [x, y, z, a] = repr;
         let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
-        let optical = transform::srgb_eotf(raw_electro, WhitePoint::D65);
+        let optical = transfer::srgb_eotf(raw_electro);
         let a = u8_to_f32(a);
         let separated = optical / a;
         (separated, a)
@@ -282,15 +384,17 @@ impl ColorEncoding for EncodedSrgbaPremultipliedU8 {
 
     #[inline]
     fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
-        let premultiplied = raw * alpha;
-        let electro = transform::srgb_oetf(premultiplied, WhitePoint::D65);
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
 
-        [
-            f32_to_u8(electro.x),
-            f32_to_u8(electro.y),
-            f32_to_u8(electro.z),
-            f32_to_u8(alpha),
-        ]
+impl QuantizedEncoding for EncodedSrgbaPremultipliedU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let premultiplied = raw * alpha;
+        let electro = transfer::srgb_oetf(premultiplied);
+        let [r, g, b] = vec3_to_u8(electro, rounding);
+        [r, g, b, rounding.quantize_u8(alpha, 3)]
     }
 }
 
@@ -313,6 +417,136 @@ impl AlphaOver for EncodedSrgbaPremultipliedU8 {
     }
 }
 
+/// The fully-encoded form of the sRGB color encoding standard, with components
+/// stored in blue, green, red order.
+///
+/// This is the same as [`EncodedSrgbU8`] with the red and blue components
+/// swapped in memory. It is the layout of Windows bitmaps and of many video
+/// frames.
+pub struct EncodedBgrU8;
+
+impl Color<EncodedBgrU8> {
+    /// Create a [`Color`] in the [`EncodedBgrU8`] encoding.
+    ///
+    /// Components are given in red, green, blue order and stored in blue,
+    /// green, red order.
+    #[inline(always)]
+    pub const fn encoded_bgr_u8(r: u8, g: u8, b: u8) -> Self {
+        Color::from_repr([b, g, r])
+    }
+}
+
+impl ColorEncoding for EncodedBgrU8 {
+    type ComponentStruct = Bgr<u8>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U8Repr;
+
+    const NAME: &'static str = "EncodedBgrU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let [b, g, r] = repr;
+        EncodedSrgbU8::src_transform_raw([r, g, b])
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedBgrU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let [r, g, b] = EncodedSrgbU8::dst_transform_quantized(raw, alpha, rounding);
+        [b, g, r]
+    }
+}
+
+/// The fully-encoded form of the sRGB color encoding standard, with separate
+/// alpha component and components stored in blue, green, red, alpha order.
+///
+/// This is the same as [`EncodedSrgbaU8`] with the red and blue components
+/// swapped in memory. It is the layout of Windows bitmaps, many video frames
+/// and swapchain formats like `B8G8R8A8_SRGB`.
+pub struct EncodedBgraU8;
+
+impl Color<EncodedBgraU8> {
+    /// Create a [`Color`] in the [`EncodedBgraU8`] encoding.
+    ///
+    /// Components are given in red, green, blue, alpha order and stored in
+    /// blue, green, red, alpha order.
+    #[inline(always)]
+    pub const fn encoded_bgra_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color::from_repr([b, g, r, a])
+    }
+}
+
+impl ColorEncoding for EncodedBgraU8 {
+    type ComponentStruct = Bgra<u8>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U8aRepr;
+
+    const NAME: &'static str = "EncodedBgraU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let [b, g, r, a] = repr;
+        EncodedSrgbaU8::src_transform_raw([r, g, b, a])
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedBgraU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let [r, g, b, a] = EncodedSrgbaU8::dst_transform_quantized(raw, alpha, rounding);
+        [b, g, r, a]
+    }
+}
+
+macro_rules! impl_convert_from {
+    ($($src:ident),+ => $($dst:ident),+) => {
+        impl_convert_from!(@each [$($src),+] $($dst),+);
+    };
+    (@each $srcs:tt $($dst:ident),+) => {$(
+        impl_convert_from!(@one $srcs $dst);
+    )+};
+    (@one [$($src:ident),+] $dst:ident) => {$(
+        impl ConvertFrom<$src> for $dst {}
+    )+};
+}
+
+impl_convert_from!(
+    EncodedBgrU8, EncodedBgraU8 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab
+);
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab =>
+    EncodedBgrU8, EncodedBgraU8
+);
+impl ConvertFrom<EncodedBgraU8> for EncodedBgrU8 {}
+impl ConvertFrom<EncodedBgrU8> for EncodedBgraU8 {}
+
 /// The linear form of the sRGB color encoding standard.
 ///
 /// This is a moderately rare way to specify color values.
@@ -349,6 +583,8 @@ impl ColorEncoding for Srgb {
     type Repr = F32Repr;
 
     const NAME: &'static str = "Srgb";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -411,6 +647,9 @@ impl ColorEncoding for Srgba {
     type Repr = F32aRepr;
 
     const NAME: &'static str = "Srgba";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const HAS_ALPHA: bool = true;
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -493,6 +732,9 @@ impl ColorEncoding for SrgbaPremultiplied {
     type Repr = F32aRepr;
 
     const NAME: &'static str = "SrgbaPremultiplied";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const HAS_ALPHA: bool = true;
+    const IS_PREMULTIPLIED: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -538,6 +780,14 @@ impl Color<Oklab> {
     pub fn oklab(l: f32, a: f32, b: f32) -> Self {
         Color::from_repr(Vec3::new(l, a, b))
     }
+
+    /// The Euclidean distance to `other`, also known as ΔEOK.
+    ///
+    /// A distance of about `0.02` is a just noticeable difference.
+    #[inline]
+    pub fn distance(self, other: Self) -> f32 {
+        self.repr.distance(other.repr)
+    }
 }
 
 impl ColorEncoding for Oklab {
@@ -546,6 +796,8 @@ impl ColorEncoding for Oklab {
     type Repr = F32Repr;
 
     const NAME: &'static str = "Oklab";
+    const IS_WORKING: bool = true;
+    const IS_PERCEPTUAL: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -569,170 +821,1641 @@ impl ConvertFrom<Srgba> for Oklab {}
 impl ConvertFrom<SrgbaPremultiplied> for Oklab {}
 
 impl WorkingEncoding for Oklab {}
-impl PerceptualEncoding for Oklab {}
 
-// Transform functions for Adobe RGB and ProPhoto RGB
+// Double-precision encodings
+//
+// Only storage and arithmetic are `f64`. The conversion pipeline works in
+// `f32`, so every conversion, even between two of these encodings, rounds the
+// components to `f32`. A conversion path in `f64` is proposed in
+// `proposals/f64-conversions.md`.
 
-/// Adobe RGB OETF (gamma encoding).
-/// Uses a simple gamma of 563/256 (2.19921875).
-#[inline(always)]
-fn adobe_rgb_oetf(color: Vec3) -> Vec3 {
-    const GAMMA: f32 = 256.0 / 563.0; // 1/2.19921875
-    color.powf(GAMMA)
-}
-
-/// Adobe RGB EOTF (gamma decoding).
-/// Inverse of adobe_rgb_oetf.
-#[inline(always)]
-fn adobe_rgb_eotf(color: Vec3) -> Vec3 {
-    const GAMMA: f32 = 563.0 / 256.0; // 2.19921875
-    color.powf(GAMMA)
+/// The linear form of the sRGB color encoding standard, with `f64` storage.
+///
+/// This is [`Srgb`] with `f64` components, for sums and other arithmetic on
+/// colors that would accumulate error in `f32`. Only storage and arithmetic
+/// are `f64`: conversions to and from any encoding, including the other `f64`
+/// ones, go through `f32`.
+pub struct SrgbF64;
+
+impl Color<SrgbF64> {
+    /// Create a [`Color`] in the [`SrgbF64`] encoding.
+    #[inline(always)]
+    pub const fn srgb_f64(r: f64, g: f64, b: f64) -> Self {
+        Color::from_repr(DVec3::new(r, g, b))
+    }
 }
 
-/// ProPhoto RGB OETF (gamma encoding).
-/// Similar to sRGB but with different parameters.
-#[inline(always)]
-fn prophoto_rgb_oetf(color: Vec3) -> Vec3 {
-    const ET: f32 = 1.0 / 512.0; // Threshold
-    let lower = color * 16.0;
-    let higher = color.powf(1.0 / 1.8);
-    Vec3::select(color.cmple(Vec3::splat(ET)), lower, higher)
-}
+/// The linear form of the sRGB color encoding standard with a separate alpha
+/// component, with `f64` storage.
+///
+/// This is [`Srgba`] with `f64` components. Only storage and arithmetic are
+/// `f64`: conversions go through `f32`, as for [`SrgbF64`].
+pub struct SrgbaF64;
 
-/// ProPhoto RGB EOTF (gamma decoding).
-/// Inverse of prophoto_rgb_oetf.
-#[inline(always)]
-fn prophoto_rgb_eotf(color: Vec3) -> Vec3 {
-    const ET2: f32 = 16.0 / 512.0; // Encoded threshold
-    let lower = color / 16.0;
-    let higher = color.powf(1.8);
-    Vec3::select(color.cmple(Vec3::splat(ET2)), lower, higher)
+impl Color<SrgbaF64> {
+    /// Create a [`Color`] in the [`SrgbaF64`] encoding.
+    #[inline(always)]
+    pub const fn srgba_f64(r: f64, g: f64, b: f64, a: f64) -> Self {
+        Color::from_repr(DVec4::new(r, g, b, a))
+    }
 }
 
-/// Linear Adobe RGB color space.
-pub struct AdobeRgb;
+/// The CIE 1931 XYZ color space with a D65 white point, with `f64` storage.
+///
+/// This is [`CieXyz`] with `f64` components. Only storage and arithmetic are
+/// `f64`: conversions go through `f32`, as for [`SrgbF64`].
+pub struct CieXyzF64;
 
-impl Color<AdobeRgb> {
-    /// Create a [`Color`] in the [`AdobeRgb`] linear color space.
+impl Color<CieXyzF64> {
+    /// Create a [`Color`] in the [`CieXyzF64`] encoding.
     #[inline(always)]
-    pub fn adobe_rgb(r: f32, g: f32, b: f32) -> Self {
-        Color::from_repr(Vec3::new(r, g, b))
+    pub const fn cie_xyz_f64(x: f64, y: f64, z: f64) -> Self {
+        Color::from_repr(DVec3::new(x, y, z))
     }
+}
 
-    /// Backward compatibility alias for adobe_rgb.
+/// The Oklab perceptual color space, with `f64` storage.
+///
+/// This is [`Oklab`] with `f64` components. Only storage and arithmetic are
+/// `f64`: conversions go through `f32`, as for [`SrgbF64`].
+pub struct OklabF64;
+
+impl Color<OklabF64> {
+    /// Create a [`Color`] in the [`OklabF64`] encoding.
     #[inline(always)]
-    pub fn linear_adobe_rgb(r: f32, g: f32, b: f32) -> Self {
-        Self::adobe_rgb(r, g, b)
+    pub const fn oklab_f64(l: f64, a: f64, b: f64) -> Self {
+        Color::from_repr(DVec3::new(l, a, b))
     }
 }
 
-impl ColorEncoding for AdobeRgb {
-    type ComponentStruct = Rgb<f32>;
-    type LinearSpace = linear_spaces::AdobeRgb;
-    type Repr = F32Repr;
+/// Implements an encoding with `f64` storage as its single-precision
+/// counterpart, with the components cast to and from `f32` on conversion.
+macro_rules! impl_double_precision {
+    ($($enc:ident => $single:ident: $components:ty, $repr:ident, $widen:ident, $narrow:ident);+) => {$(
+        impl ColorEncoding for $enc {
+            type ComponentStruct = $components;
+            type LinearSpace = <$single as ColorEncoding>::LinearSpace;
+            type Repr = $repr;
+
+            const NAME: &'static str = stringify!($enc);
+            const TRANSFER: Option<TransferFunction> = <$single as ColorEncoding>::TRANSFER;
+            const HAS_ALPHA: bool = <$single as ColorEncoding>::HAS_ALPHA;
+            const IS_WORKING: bool = true;
+            const IS_PERCEPTUAL: bool = <$single as ColorEncoding>::IS_PERCEPTUAL;
+
+            #[inline(always)]
+            fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+                $single::src_transform_raw(repr.$narrow())
+            }
+
+            #[inline(always)]
+            fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+                $single::dst_transform_raw(raw, alpha).$widen()
+            }
+        }
+
+        impl WorkingEncoding for $enc {}
+    )+};
+}
 
-    const NAME: &'static str = "AdobeRgb";
+impl_double_precision!(
+    SrgbF64 => Srgb: Rgb<f64>, F64Repr, as_dvec3, as_vec3;
+    SrgbaF64 => Srgba: Rgba<f64>, F64aRepr, as_dvec4, as_vec4;
+    CieXyzF64 => CieXyz: Xyz<f64>, F64Repr, as_dvec3, as_vec3;
+    OklabF64 => Oklab: Lab<f64>, F64Repr, as_dvec3, as_vec3
+);
+
+macro_rules! impl_perceptual_lab {
+    ($($enc:ident: $vec2:ident, $vec3:ident, $float:ident),+) => {$(
+        impl PerceptualEncoding for $enc {
+            #[inline]
+            fn lightness(color: Color<Self>) -> f32 {
+                color.repr.x as f32
+            }
+
+            #[inline]
+            fn chroma(color: Color<Self>) -> f32 {
+                color.repr.yz().length() as f32
+            }
+
+            #[inline]
+            fn hue(color: Color<Self>) -> f32 {
+                wrap_degrees(color.repr.yz().to_angle().to_degrees() as f32)
+            }
+
+            #[inline]
+            fn with_lightness(color: Color<Self>, lightness: f32) -> Color<Self> {
+                let ab = color.repr.yz();
+                Color::from_repr($vec3::new(lightness as $float, ab.x, ab.y))
+            }
+
+            #[inline]
+            fn with_chroma(color: Color<Self>, chroma: f32) -> Color<Self> {
+                let ab = $vec2::from_angle(color.repr.yz().to_angle()) * chroma as $float;
+                Color::from_repr($vec3::new(color.repr.x, ab.x, ab.y))
+            }
+
+            #[inline]
+            fn with_hue(color: Color<Self>, degrees: f32) -> Color<Self> {
+                let angle = (degrees as $float).to_radians();
+                let ab = $vec2::from_angle(angle) * color.repr.yz().length();
+                Color::from_repr($vec3::new(color.repr.x, ab.x, ab.y))
+            }
+        }
+    )+};
+}
 
-    #[inline(always)]
+impl_perceptual_lab!(Oklab: Vec2, Vec3, f32, OklabF64: DVec2, DVec3, f64);
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab =>
+    SrgbF64, SrgbaF64, CieXyzF64, OklabF64
+);
+impl_convert_from!(
+    SrgbF64, SrgbaF64, CieXyzF64, OklabF64 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab
+);
+impl_convert_from!(SrgbaF64, CieXyzF64, OklabF64 => SrgbF64);
+impl_convert_from!(SrgbF64, CieXyzF64, OklabF64 => SrgbaF64);
+impl_convert_from!(SrgbF64, SrgbaF64, OklabF64 => CieXyzF64);
+impl_convert_from!(SrgbF64, SrgbaF64, CieXyzF64 => OklabF64);
+
+/// The fully-encoded form of the sRGB color encoding standard, with
+/// *premultiplied* alpha component, in 16 bits per component.
+///
+/// This is [`EncodedSrgbaPremultipliedU8`] with 16-bit components, for
+/// compositing pipelines where 8 bits band visibly in soft gradients like
+/// shadows, e.g. 16-bit PNGs with premultiplied alpha.
+///
+/// The color components are multiplied by the alpha component *before* the sRGB
+/// OETF is applied. The alpha component is linearly encoded.
+pub struct EncodedSrgbaPremultipliedU16;
+
+impl ColorEncoding for EncodedSrgbaPremultipliedU16 {
+    type ComponentStruct = Rgba<u16>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U16aRepr;
+
+    const NAME: &'static str = "EncodedSrgbaPremultipliedU16";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+    const IS_PREMULTIPLIED: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
+
+    #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
-        (repr, 1.0)
+        let [x, y, z, a] = repr;
+        let raw_electro = Vec3::new(u16_to_f32(x), u16_to_f32(y), u16_to_f32(z));
+        let optical = transfer::srgb_eotf(raw_electro);
+        let a = u16_to_f32(a);
+        let separated = optical / a;
+        (separated, a)
     }
 
-    #[inline(always)]
-    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        raw
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
     }
 }
 
-impl WorkingEncoding for AdobeRgb {}
-
-/// Linear ProPhoto RGB color space.
-pub struct ProPhotoRgb;
-
-impl Color<ProPhotoRgb> {
-    /// Create a [`Color`] in the [`ProPhotoRgb`] linear color space.
-    #[inline(always)]
-    pub fn prophoto_rgb(r: f32, g: f32, b: f32) -> Self {
-        Color::from_repr(Vec3::new(r, g, b))
+impl QuantizedEncoding for EncodedSrgbaPremultipliedU16 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let premultiplied = raw * alpha;
+        let electro = transfer::srgb_oetf(premultiplied);
+        let [r, g, b] = vec3_to_u16(electro, rounding);
+        [r, g, b, rounding.quantize_u16(alpha, 3)]
     }
+}
 
-    /// Backward compatibility alias for prophoto_rgb.
-    #[inline(always)]
-    pub fn linear_prophoto_rgb(r: f32, g: f32, b: f32) -> Self {
-        Self::prophoto_rgb(r, g, b)
+impl AlphaOver for EncodedSrgbaPremultipliedU16 {
+    fn composite(over: Color<Self>, under: Color<Self>) -> Color<Self> {
+        let over = over.convert::<SrgbaPremultiplied>();
+        let under = under.convert::<SrgbaPremultiplied>();
+        let comp = over.alpha_over(under);
+        comp.convert::<Self>()
     }
 }
 
-impl ColorEncoding for ProPhotoRgb {
-    type ComponentStruct = Rgb<f32>;
-    type LinearSpace = linear_spaces::ProPhotoRgb;
-    type Repr = F32Repr;
+/// The linear form of the sRGB color encoding standard with a *premultiplied*
+/// alpha component, in 16 bits per component.
+///
+/// This is [`SrgbaPremultiplied`] quantized to 16 bits per component, for
+/// storing linear premultiplied images at half the size of `f32` components.
+pub struct SrgbaPremultipliedU16;
 
-    const NAME: &'static str = "ProPhotoRgb";
+impl ColorEncoding for SrgbaPremultipliedU16 {
+    type ComponentStruct = Rgba<u16>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U16aRepr;
 
-    #[inline(always)]
+    const NAME: &'static str = "SrgbaPremultipliedU16";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const HAS_ALPHA: bool = true;
+    const IS_PREMULTIPLIED: bool = true;
+
+    #[inline]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
-        (repr, 1.0)
+        let [x, y, z, a] = repr;
+        let premultiplied = Vec3::new(u16_to_f32(x), u16_to_f32(y), u16_to_f32(z));
+        let a = u16_to_f32(a);
+        (premultiplied / a, a)
     }
 
-    #[inline(always)]
-    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        raw
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
     }
 }
 
-impl WorkingEncoding for ProPhotoRgb {}
-
-/// Non-linear Adobe RGB U8 encoding.
-pub struct EncodedAdobeRgbU8;
-
-impl Color<EncodedAdobeRgbU8> {
-    /// Create a [`Color`] in the [`EncodedAdobeRgbU8`] encoding.
-    #[inline(always)]
-    pub fn encoded_adobe_rgb_u8(r: u8, g: u8, b: u8) -> Self {
-        Color::from_repr([r, g, b])
+impl QuantizedEncoding for SrgbaPremultipliedU16 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let [r, g, b] = vec3_to_u16(raw * alpha, rounding);
+        [r, g, b, rounding.quantize_u16(alpha, 3)]
     }
+}
 
-    /// Backward compatibility alias for encoded_adobe_rgb_u8.
-    #[inline(always)]
-    pub fn adobe_rgb_u8(r: u8, g: u8, b: u8) -> Self {
-        Self::encoded_adobe_rgb_u8(r, g, b)
+impl AlphaOver for SrgbaPremultipliedU16 {
+    fn composite(over: Color<Self>, under: Color<Self>) -> Color<Self> {
+        let over = over.convert::<SrgbaPremultiplied>();
+        let under = under.convert::<SrgbaPremultiplied>();
+        let comp = over.alpha_over(under);
+        comp.convert::<Self>()
     }
 }
 
-impl ColorEncoding for EncodedAdobeRgbU8 {
-    type ComponentStruct = Rgb<u8>;
-    type LinearSpace = linear_spaces::AdobeRgb;
-    type Repr = U8Repr;
-
-    const NAME: &'static str = "EncodedAdobeRgbU8";
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, SrgbF64, SrgbaF64, CieXyzF64,
+    OklabF64 =>
+    EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16
+);
+impl_convert_from!(
+    EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, SrgbF64, SrgbaF64, CieXyzF64,
+    OklabF64
+);
+impl_convert_from!(EncodedSrgbaPremultipliedU16 => SrgbaPremultipliedU16);
+impl_convert_from!(SrgbaPremultipliedU16 => EncodedSrgbaPremultipliedU16);
+
+/// The fully-encoded form of the sRGB color encoding standard, in 16 bits per
+/// component.
+///
+/// This is [`EncodedSrgbU8`] with 16-bit components, as found in 16-bit PNG
+/// and TIFF files.
+pub struct EncodedSrgbU16;
 
+impl Color<EncodedSrgbU16> {
+    /// Create a [`Color`] in the [`EncodedSrgbU16`] encoding.
     #[inline(always)]
-    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
-        let [x, y, z] = repr;
-        let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
-        let optical = adobe_rgb_eotf(raw_electro);
-        (optical, 1.0)
+    pub const fn encoded_srgb_u16(r: u16, g: u16, b: u16) -> Self {
+        Color::from_repr([r, g, b])
     }
+}
+
+/// The fully-encoded form of the sRGB color encoding standard, in 10 bits per
+/// component stored in the low bits of `u16`s.
+///
+/// This is the layout of 10-bit video frames and swapchains after unpacking.
+pub struct EncodedSrgbU10;
 
+impl Color<EncodedSrgbU10> {
+    /// Create a [`Color`] in the [`EncodedSrgbU10`] encoding.
+    ///
+    /// Components are `0..=1023`.
     #[inline(always)]
-    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        let electro = adobe_rgb_oetf(raw);
-        [
-            f32_to_u8(electro.x),
-            f32_to_u8(electro.y),
-            f32_to_u8(electro.z),
-        ]
+    pub const fn encoded_srgb_u10(r: u16, g: u16, b: u16) -> Self {
+        Color::from_repr([r, g, b])
     }
 }
 
-/// Non-linear ProPhoto RGB U8 encoding.
-pub struct EncodedProPhotoRgbU8;
+/// The fully-encoded form of the sRGB color encoding standard, in 12 bits per
+/// component stored in the low bits of `u16`s.
+///
+/// This is the layout of 12-bit SDI video and image sensor data after
+/// unpacking. Components are scaled by 4095, not 65535, so they aren't dimmed
+/// as when read as full-range 16-bit values.
+pub struct EncodedSrgbU12;
 
-impl Color<EncodedProPhotoRgbU8> {
-    /// Create a [`Color`] in the [`EncodedProPhotoRgbU8`] encoding.
+impl Color<EncodedSrgbU12> {
+    /// Create a [`Color`] in the [`EncodedSrgbU12`] encoding.
+    ///
+    /// Components are `0..=4095`.
     #[inline(always)]
-    pub fn encoded_prophoto_rgb_u8(r: u8, g: u8, b: u8) -> Self {
+    pub const fn encoded_srgb_u12(r: u16, g: u16, b: u16) -> Self {
+        Color::from_repr([r, g, b])
+    }
+}
+
+/// The fully-encoded form of the sRGB color encoding standard, in 14 bits per
+/// component stored in the low bits of `u16`s.
+///
+/// This is the layout of 14-bit image sensor data after unpacking.
+/// Components are scaled by 16383, not 65535.
+pub struct EncodedSrgbU14;
+
+impl Color<EncodedSrgbU14> {
+    /// Create a [`Color`] in the [`EncodedSrgbU14`] encoding.
+    ///
+    /// Components are `0..=16383`.
+    #[inline(always)]
+    pub const fn encoded_srgb_u14(r: u16, g: u16, b: u16) -> Self {
+        Color::from_repr([r, g, b])
+    }
+}
+
+/// The fully-encoded form of the sRGB color encoding standard with a separate
+/// alpha component, in 16 bits per component.
+///
+/// The alpha component is linearly encoded, i.e. the sRGB OETF is not applied.
+pub struct EncodedSrgbaU16;
+
+impl Color<EncodedSrgbaU16> {
+    /// Create a [`Color`] in the [`EncodedSrgbaU16`] encoding.
+    #[inline(always)]
+    pub const fn encoded_srgba_u16(r: u16, g: u16, b: u16, a: u16) -> Self {
+        Color::from_repr([r, g, b, a])
+    }
+}
+
+macro_rules! impl_srgb_bits {
+    ($($enc:ident: $bits:literal),+) => {$(
+        impl ColorEncoding for $enc {
+            type ComponentStruct = Rgb<u16>;
+            type LinearSpace = linear_spaces::Srgb;
+            type Repr = U16Repr;
+
+            const NAME: &'static str = stringify!($enc);
+            const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+
+            #[inline]
+            fn hex(repr: Self::Repr) -> Option<Hex> {
+                Some(Color::<Self>::from_repr(repr).to_hex())
+            }
+
+            #[inline]
+            fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+                let raw_electro = Vec3::from_array(repr.map(|x| normalize(x, $bits)));
+                let optical = transfer::srgb_eotf(raw_electro);
+                (optical, 1.0)
+            }
+
+            #[inline]
+            fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+                Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+            }
+        }
+
+        impl QuantizedEncoding for $enc {
+            #[inline]
+            fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+                let electro = transfer::srgb_oetf(raw);
+                vec3_to_bits(electro, $bits, rounding)
+            }
+        }
+    )+};
+}
+
+impl_srgb_bits!(
+    EncodedSrgbU16: 16,
+    EncodedSrgbU10: 10,
+    EncodedSrgbU12: 12,
+    EncodedSrgbU14: 14
+);
+
+impl ColorEncoding for EncodedSrgbaU16 {
+    type ComponentStruct = Rgba<u16>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U16aRepr;
+
+    const NAME: &'static str = "EncodedSrgbaU16";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+    const HAS_ALPHA: bool = true;
+
+    #[inline]
+    fn hex(repr: Self::Repr) -> Option<Hex> {
+        Some(Color::<Self>::from_repr(repr).to_hex())
+    }
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let [x, y, z, a] = repr;
+        let (optical, _) = EncodedSrgbU16::src_transform_raw([x, y, z]);
+        (optical, u16_to_f32(a))
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedSrgbaU16 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let [r, g, b] = EncodedSrgbU16::dst_transform_quantized(raw, alpha, rounding);
+        [r, g, b, rounding.quantize_u16(alpha, 3)]
+    }
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, SrgbF64, SrgbaF64, CieXyzF64,
+    OklabF64, EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16 =>
+    EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16
+);
+impl_convert_from!(
+    EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, SrgbF64, SrgbaF64, CieXyzF64,
+    OklabF64, EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16
+);
+impl_convert_from!(EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16 => EncodedSrgbU16);
+impl_convert_from!(EncodedSrgbU16, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16 => EncodedSrgbU10);
+impl_convert_from!(EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU14, EncodedSrgbaU16 => EncodedSrgbU12);
+impl_convert_from!(EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbaU16 => EncodedSrgbU14);
+impl_convert_from!(EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14 => EncodedSrgbaU16);
+
+/// 8-bit grayscale with the sRGB transfer function, as in grayscale PNGs and
+/// most font atlases.
+///
+/// Colors convert to gray by their relative luminance, so a color and its gray
+/// look equally light, and gray converts to RGB as a neutral color. The value
+/// is *encoded*: `128` is about 22% of the light of `255`.
+///
+/// Coverage and alpha masks are linear and don't belong in this encoding, see
+/// [`crate::opacity`].
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::{EncodedGrayU8, EncodedSrgbU8};
+/// let gray = Color::srgb_u8(255, 0, 0).convert::<EncodedGrayU8>();
+/// assert_eq!(gray.v, 127);
+///
+/// assert_eq!(
+///     Color::encoded_gray_u8(128).convert::<EncodedSrgbU8>(),
+///     Color::srgb_u8(128, 128, 128)
+/// );
+/// ```
+pub struct EncodedGrayU8;
+
+impl Color<EncodedGrayU8> {
+    /// Create a [`Color`] in the [`EncodedGrayU8`] encoding from an sRGB
+    /// encoded value.
+    #[inline(always)]
+    pub const fn encoded_gray_u8(v: u8) -> Self {
+        Color::from_repr(v)
+    }
+}
+
+/// 4-bit grayscale with the sRGB transfer function, in the low bits of a `u8`,
+/// for e-ink and OLED displays with 16 gray levels.
+///
+/// See [`EncodedGrayU8`]. Dither images to this with [`crate::dither`].
+pub struct EncodedGrayU4;
+
+impl Color<EncodedGrayU4> {
+    /// Create a [`Color`] in the [`EncodedGrayU4`] encoding from an sRGB
+    /// encoded value in `0..=15`.
+    #[inline(always)]
+    pub const fn encoded_gray_u4(v: u8) -> Self {
+        Color::from_repr(v)
+    }
+}
+
+/// 2-bit grayscale with the sRGB transfer function, in the low bits of a `u8`,
+/// for e-ink displays with 4 gray levels.
+///
+/// See [`EncodedGrayU8`]. Dither images to this with [`crate::dither`].
+pub struct EncodedGrayU2;
+
+impl Color<EncodedGrayU2> {
+    /// Create a [`Color`] in the [`EncodedGrayU2`] encoding from an sRGB
+    /// encoded value in `0..=3`.
+    #[inline(always)]
+    pub const fn encoded_gray_u2(v: u8) -> Self {
+        Color::from_repr(v)
+    }
+}
+
+/// 1-bit black and white, in the low bit of a `u8`, for monochrome e-ink and
+/// OLED displays.
+///
+/// Colors are black or white depending on whether their relative luminance is
+/// above that of the sRGB midpoint. Dither images to this with
+/// [`crate::dither`].
+pub struct EncodedGrayU1;
+
+impl Color<EncodedGrayU1> {
+    /// Create a [`Color`] in the [`EncodedGrayU1`] encoding from `0` (black)
+    /// or `1` (white).
+    #[inline(always)]
+    pub const fn encoded_gray_u1(v: u8) -> Self {
+        Color::from_repr(v)
+    }
+}
+
+macro_rules! impl_gray_bits {
+    ($($enc:ident: $bits:literal),+) => {$(
+        impl Color<$enc> {
+            /// Black.
+            pub const BLACK: Self = Color::from_repr(0);
+            /// White.
+            pub const WHITE: Self = Color::from_repr(crate::bit_depth::max_value($bits) as u8);
+        }
+
+        impl ColorEncoding for $enc {
+            type ComponentStruct = Gray<u8>;
+            type LinearSpace = linear_spaces::Srgb;
+            type Repr = U8GrayRepr;
+
+            const NAME: &'static str = stringify!($enc);
+            const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Srgb);
+
+            #[inline]
+            fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+                let optical = transfer::srgb_eotf(Vec3::splat(normalize(repr as u16, $bits)));
+                (optical, 1.0)
+            }
+
+            #[inline]
+            fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+                Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+            }
+        }
+
+        impl QuantizedEncoding for $enc {
+            #[inline]
+            fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+                let luminance = raw.dot(Vec3::new(BT709_KR, 1.0 - BT709_KR - BT709_KB, BT709_KB));
+                let electro = transfer::srgb_oetf(Vec3::splat(luminance));
+                rounding.quantize_bits(electro.x, $bits, 0) as u8
+            }
+        }
+
+        impl GrayEncoding for $enc {
+            const BITS: u32 = $bits;
+        }
+    )+};
+}
+
+impl_gray_bits!(
+    EncodedGrayU8: 8,
+    EncodedGrayU4: 4,
+    EncodedGrayU2: 2,
+    EncodedGrayU1: 1
+);
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab, CieXyz, EncodedSrgbU16, EncodedSrgbaU16 =>
+    EncodedGrayU8, EncodedGrayU4, EncodedGrayU2, EncodedGrayU1
+);
+impl_convert_from!(
+    EncodedGrayU8, EncodedGrayU4, EncodedGrayU2, EncodedGrayU1 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab, CieXyz, EncodedSrgbU16, EncodedSrgbaU16
+);
+impl_convert_from!(EncodedGrayU4, EncodedGrayU2, EncodedGrayU1 => EncodedGrayU8);
+impl_convert_from!(EncodedGrayU8, EncodedGrayU2, EncodedGrayU1 => EncodedGrayU4);
+impl_convert_from!(EncodedGrayU8, EncodedGrayU4, EncodedGrayU1 => EncodedGrayU2);
+impl_convert_from!(EncodedGrayU8, EncodedGrayU4, EncodedGrayU2 => EncodedGrayU1);
+
+/// The PWM duty cycles of an addressable RGB LED like the WS2812, with
+/// components stored in green, red, blue order.
+///
+/// The LEDs emit light in proportion to their duty cycle, so the components
+/// are *linear*: converting from a linear working color only quantizes it,
+/// and sRGB-encoded colors are decoded to linear light on the way, which is
+/// what the gamma tables of LED libraries approximate. Scale the light down
+/// to a brightness or power budget with [`crate::led`], before quantizing.
+///
+/// The primaries of the LEDs are assumed to be those of sRGB.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::EncodedLedGrbU8;
+/// let led = Color::srgb_u8(255, 128, 0).convert::<EncodedLedGrbU8>();
+/// assert_eq!(led.repr, [55, 255, 0]);
+/// assert_eq!((led.r, led.g, led.b), (255, 55, 0));
+/// ```
+pub struct EncodedLedGrbU8;
+
+impl Color<EncodedLedGrbU8> {
+    /// All LEDs off.
+    pub const BLACK: Self = Color::from_repr([0; 3]);
+
+    /// Create a [`Color`] in the [`EncodedLedGrbU8`] encoding from linear duty
+    /// cycles.
+    ///
+    /// Components are given in red, green, blue order and stored in green,
+    /// red, blue order.
+    #[inline(always)]
+    pub const fn encoded_led_grb_u8(r: u8, g: u8, b: u8) -> Self {
+        Color::from_repr([g, r, b])
+    }
+}
+
+impl ColorEncoding for EncodedLedGrbU8 {
+    type ComponentStruct = Grb<u8>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U8Repr;
+
+    const NAME: &'static str = "EncodedLedGrbU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let [g, r, b] = repr;
+        (Vec3::new(u8_to_f32(r), u8_to_f32(g), u8_to_f32(b)), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedLedGrbU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+        let [r, g, b] = vec3_to_u8(raw, rounding);
+        [g, r, b]
+    }
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab, CieXyz, EncodedBgrU8, EncodedBgraU8 =>
+    EncodedLedGrbU8
+);
+impl_convert_from!(
+    EncodedLedGrbU8 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    Srgb, Srgba, SrgbaPremultiplied, Oklab, CieXyz, EncodedBgrU8, EncodedBgraU8
+);
+
+/// Full-range YCbCr with BT.709 coefficients, derived from sRGB-encoded
+/// components.
+///
+/// Y (luma) is in `0.0..=1.0`, Cb and Cr (blue- and red-difference chroma) are
+/// in `-0.5..=0.5`. Luma is a weighted sum of the *encoded* components, so it is
+/// not the relative luminance of the color.
+///
+/// This is the form in which video frames are encoded, usually with
+/// subsampled chroma, see [`crate::chroma`].
+pub struct YCbCrBt709;
+
+impl Color<YCbCrBt709> {
+    /// Create a [`Color`] in the [`YCbCrBt709`] encoding.
+    #[inline(always)]
+    pub const fn ycbcr_bt709(y: f32, cb: f32, cr: f32) -> Self {
+        Color::from_repr(Vec3::new(y, cb, cr))
+    }
+}
+
+const BT709_KR: f32 = 0.2126;
+const BT709_KB: f32 = 0.0722;
+
+impl ColorEncoding for YCbCrBt709 {
+    type ComponentStruct = YCbCr<f32>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "YCbCrBt709";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let r = repr.x + 2.0 * (1.0 - BT709_KR) * repr.z;
+        let b = repr.x + 2.0 * (1.0 - BT709_KB) * repr.y;
+        let g = (repr.x - BT709_KR * r - BT709_KB * b) / (1.0 - BT709_KR - BT709_KB);
+        let optical = transfer::srgb_eotf(Vec3::new(r, g, b));
+        (optical, 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        let electro = transfer::srgb_oetf(raw);
+        let y = electro.dot(Vec3::new(BT709_KR, 1.0 - BT709_KR - BT709_KB, BT709_KB));
+        let cb = (electro.z - y) / (2.0 * (1.0 - BT709_KB));
+        let cr = (electro.x - y) / (2.0 * (1.0 - BT709_KR));
+        Vec3::new(y, cb, cr)
+    }
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16 =>
+    YCbCrBt709
+);
+impl_convert_from!(
+    YCbCrBt709 =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16
+);
+
+/// `degrees` wrapped to `0.0..360.0`. `f32::rem_euclid` is not available
+/// without `std`.
+#[inline(always)]
+pub(crate) fn wrap_degrees(degrees: f32) -> f32 {
+    Vec2::splat(degrees).rem_euclid(Vec2::splat(360.0)).x
+}
+
+/// The HSI (hue, saturation, intensity) form of sRGB-encoded components, as
+/// used in machine vision.
+///
+/// Hue is in degrees in `0.0..360.0` with red at `0.0`, saturation and
+/// intensity are in `0.0..=1.0`. Intensity is the mean of the encoded
+/// components and saturation is one minus the smallest component divided by
+/// the intensity. Grays have a hue and black has a saturation of `0.0`.
+///
+/// This is not perceptually uniform. Prefer [`Oklab`] for anything other than
+/// matching machine-vision algorithms specified in HSI.
+pub struct SrgbHsi;
+
+impl Color<SrgbHsi> {
+    /// Create a [`Color`] in the [`SrgbHsi`] encoding.
+    #[inline(always)]
+    pub const fn srgb_hsi(h: f32, s: f32, i: f32) -> Self {
+        Color::from_repr(Vec3::new(h, s, i))
+    }
+}
+
+impl ColorEncoding for SrgbHsi {
+    type ComponentStruct = Hsi<f32>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "SrgbHsi";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let Vec3 { x: h, y: s, z: i } = repr;
+        let h = wrap_degrees(h);
+        let sector = (h / 120.0).min(2.0) as usize;
+        let angle = (h - sector as f32 * 120.0).to_radians();
+        let low = i * (1.0 - s);
+        let high = i
+            * (1.0
+                + s * glam::Vec2::from_angle(angle).x
+                    / glam::Vec2::from_angle(60f32.to_radians() - angle).x);
+        let mid = 3.0 * i - low - high;
+        // Within each sector, the component the hue starts at is highest and
+        // the one before it is lowest.
+        let electro = match sector {
+            0 => Vec3::new(high, mid, low),
+            1 => Vec3::new(low, high, mid),
+            _ => Vec3::new(mid, low, high),
+        };
+        let optical = transfer::srgb_eotf(electro);
+        (optical, 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        let electro = transfer::srgb_oetf(raw);
+        let i = electro.element_sum() / 3.0;
+        let s = if i > 0.0 {
+            1.0 - electro.min_element() / i
+        } else {
+            0.0
+        };
+        let chroma = glam::Vec2::new(
+            electro.x - 0.5 * (electro.y + electro.z),
+            // sqrt(3) / 2
+            0.866_025_4 * (electro.y - electro.z),
+        );
+        let h = if s > 0.0 {
+            wrap_degrees(chroma.to_angle().to_degrees())
+        } else {
+            0.0
+        };
+        Vec3::new(h, s, i)
+    }
+}
+
+impl PolarEncoding for SrgbHsi {
+    const HUE: usize = 0;
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, YCbCrBt709 =>
+    SrgbHsi
+);
+impl_convert_from!(
+    SrgbHsi =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, YCbCrBt709
+);
+
+/// Oklch, the polar form of [`Oklab`].
+///
+/// Lightness is the same as in [`Oklab`], chroma is the distance from the
+/// neutral axis and hue is the angle around it, in degrees in `0.0..360.0`.
+/// Adjusting hue or chroma in Oklch keeps the other two perceptually constant,
+/// which makes it the encoding of choice for color pickers and theming.
+///
+/// The hue of neutral colors is meaningless and set to `0.0`.
+pub struct Oklch;
+
+impl Color<Oklch> {
+    /// Create a [`Color`] in the [`Oklch`] color encoding.
+    #[inline(always)]
+    pub const fn oklch(l: f32, c: f32, h: f32) -> Self {
+        Color::from_repr(Vec3::new(l, c, h))
+    }
+}
+
+impl ColorEncoding for Oklch {
+    type ComponentStruct = LCh<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "Oklch";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let ab = glam::Vec2::from_angle(repr.z.to_radians()) * repr.y;
+        Oklab::src_transform_raw(Vec3::new(repr.x, ab.x, ab.y))
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        let lab = Oklab::dst_transform_raw(raw, alpha);
+        let ab = glam::Vec2::new(lab.y, lab.z);
+        let h = wrap_degrees(ab.to_angle().to_degrees());
+        Vec3::new(lab.x, ab.length(), h)
+    }
+}
+
+impl PolarEncoding for Oklch {
+    const HUE: usize = 2;
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, OklabF64 =>
+    Oklch
+);
+impl_convert_from!(
+    Oklch =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, OklabF64
+);
+
+/// HCT, the hue, chroma and tone space of Material Design.
+///
+/// Hue in degrees in `0.0..360.0` and chroma are those of the CAM16 color
+/// appearance model, tone is CIE L* in `0.0..=100.0`. Colors with the same
+/// tone have the same luminance, so tone differences predict contrast.
+///
+/// Converting from HCT reduces chroma at constant hue and tone as far as
+/// needed to fit in the sRGB gamut. See [`crate::hct`] for the viewing
+/// conditions and Material tonal palettes.
+pub struct Hct;
+
+impl Color<Hct> {
+    /// Create a [`Color`] in the [`Hct`] color encoding.
+    #[inline(always)]
+    pub const fn hct(h: f32, c: f32, t: f32) -> Self {
+        Color::from_repr(Vec3::new(h, c, t))
+    }
+}
+
+impl ColorEncoding for Hct {
+    type ComponentStruct = HueChromaTone<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "Hct";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (crate::hct::hct_to_xyz(repr), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        crate::hct::xyz_to_hct(raw)
+    }
+}
+
+impl PolarEncoding for Hct {
+    const HUE: usize = 0;
+}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, Oklch, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, OklabF64 =>
+    Hct
+);
+impl_convert_from!(
+    Hct =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, Oklch, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14,
+    EncodedSrgbaU16, OklabF64
+);
+
+impl AlphaEncoding for EncodedSrgbaU8 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        u8_to_f32(color.repr[3])
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr[3] = Rounding::HalfUp.quantize_u8(alpha, 3);
+        color
+    }
+}
+
+impl AlphaEncoding for EncodedBgraU8 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        u8_to_f32(color.repr[3])
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr[3] = Rounding::HalfUp.quantize_u8(alpha, 3);
+        color
+    }
+}
+
+impl AlphaEncoding for EncodedSrgbaU16 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        u16_to_f32(color.repr[3])
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr[3] = Rounding::HalfUp.quantize_u16(alpha, 3);
+        color
+    }
+}
+
+impl AlphaEncoding for EncodedSrgbaF32 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        color.repr.w
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr.w = alpha;
+        color
+    }
+}
+
+impl AlphaEncoding for Srgba {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        color.repr.w
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr.w = alpha;
+        color
+    }
+}
+
+impl AlphaEncoding for SrgbaF64 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        color.repr.w as f32
+    }
+
+    #[inline]
+    fn with_alpha(mut color: Color<Self>, alpha: f32) -> Color<Self> {
+        color.repr.w = alpha as f64;
+        color
+    }
+}
+
+impl AlphaEncoding for EncodedSrgbaPremultipliedU8 {}
+impl AlphaEncoding for SrgbaPremultiplied {}
+impl AlphaEncoding for EncodedSrgbaPremultipliedU16 {}
+impl AlphaEncoding for SrgbaPremultipliedU16 {}
+
+/// The CIE 1931 XYZ color space with a D65 white point.
+///
+/// `Y` is the relative luminance, `1.0` for the white of linear sRGB.
+pub struct CieXyz;
+
+impl Color<CieXyz> {
+    /// Create a [`Color`] in the [`CieXyz`] encoding.
+    #[inline(always)]
+    pub const fn cie_xyz(x: f32, y: f32, z: f32) -> Self {
+        Color::from_repr(Vec3::new(x, y, z))
+    }
+
+    /// Create a [`Color`] from CIE 1931 xy chromaticity coordinates and the
+    /// luminance `Y` (xyY), as given by display measurements and lighting
+    /// specifications.
+    ///
+    /// A `y` of zero has no defined color, and gives black.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::details::encodings::CieXyz;
+    /// // The green primary of sRGB at full luminance.
+    /// let green = Color::from_xy_luminance(0.30, 0.60, 0.7152);
+    ///
+    /// let srgb = green.convert::<basic_encodings::SrgbU8>();
+    /// assert_eq!(srgb, Color::srgb_u8(0, 255, 0));
+    /// ```
+    pub fn from_xy_luminance(x: f32, y: f32, luminance: f32) -> Self {
+        if y == 0.0 {
+            Self::cie_xyz(0.0, 0.0, 0.0)
+        } else {
+            let scale = luminance / y;
+            Self::cie_xyz(x * scale, luminance, (1.0 - x - y) * scale)
+        }
+    }
+
+    /// The CIE 1931 xy chromaticity coordinates and the luminance `Y` (xyY).
+    ///
+    /// Black has no chromaticity and gets that of the D65 white point.
+    pub fn to_xy_luminance(self) -> (f32, f32, f32) {
+        let sum = self.repr.element_sum();
+        if sum == 0.0 {
+            (D65_XY[0], D65_XY[1], self.repr.y)
+        } else {
+            (self.repr.x / sum, self.repr.y / sum, self.repr.y)
+        }
+    }
+}
+
+impl ColorEncoding for CieXyz {
+    type ComponentStruct = Xyz<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "CieXyz";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (repr, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        raw
+    }
+}
+
+impl WorkingEncoding for CieXyz {}
+
+impl_convert_from!(
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, Oklch, SrgbF64, SrgbaF64,
+    CieXyzF64, OklabF64, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16 =>
+    CieXyz
+);
+impl_convert_from!(
+    CieXyz =>
+    EncodedSrgbU8, EncodedSrgbF32, EncodedSrgbaU8, EncodedSrgbaF32, EncodedSrgbaPremultipliedU8,
+    EncodedBgrU8, EncodedBgraU8, Srgb, Srgba, SrgbaPremultiplied, Oklab, Oklch, SrgbF64, SrgbaF64,
+    CieXyzF64, OklabF64, EncodedSrgbU16, EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbaU16
+);
+
+/// Multispectral samples with `N` linear bands, described by the
+/// [`SpectralBands`] `B`.
+///
+/// Multispectral and hyperspectral cameras sample more bands than red, green
+/// and blue. Each band contributes its [`TO_XYZ`](SpectralBands::TO_XYZ)
+/// response, which makes the samples convert to and from the other encodings
+/// through CIE XYZ. Converting to this encoding picks one of the many spectra
+/// with that XYZ, as chosen by [`SpectralBands::from_xyz`].
+///
+/// See [`SpectralBands`] for an example.
+pub struct Multispectral<B, const N: usize>(core::marker::PhantomData<B>);
+
+impl<B: SpectralBands<N>, const N: usize> Color<Multispectral<B, N>> {
+    /// Create a [`Color`] in the [`Multispectral`] encoding.
+    #[inline(always)]
+    pub const fn multispectral(bands: [f32; N]) -> Self {
+        Color::from_repr(bands)
+    }
+}
+
+impl<B: SpectralBands<N>, const N: usize> ColorEncoding for Multispectral<B, N> {
+    type ComponentStruct = Bands<f32, N>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32BandsRepr<N>;
+
+    const NAME: &'static str = B::NAME;
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let xyz = repr
+            .iter()
+            .zip(B::TO_XYZ)
+            .fold(Vec3::ZERO, |xyz, (&band, response)| xyz + response * band);
+        (xyz, 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        B::from_xyz(raw)
+    }
+}
+
+macro_rules! impl_convert_multispectral {
+    ($($other:ident),+) => {$(
+        impl<B: SpectralBands<N>, const N: usize> ConvertFrom<$other> for Multispectral<B, N> {}
+        impl<B: SpectralBands<N>, const N: usize> ConvertFrom<Multispectral<B, N>> for $other {}
+    )+};
+}
+
+impl_convert_multispectral!(
+    EncodedSrgbU8,
+    EncodedSrgbF32,
+    EncodedSrgbaU8,
+    EncodedSrgbaF32,
+    Srgb,
+    Srgba,
+    Oklab,
+    CieXyz
+);
+
+/// The Kodak Cineon printing density log encoding of film scans and DPX
+/// files, in 10-bit code values stored in the low bits of `u16`s.
+///
+/// Code value 95 is black and 685 is reference white; the code values above
+/// hold highlights up to about 13.5 times white. Cineon describes printing
+/// density rather than colorimetry, so the linear components are taken to have
+/// BT.709 primaries, as is conventional when bringing scans into a Rec. 709 or
+/// sRGB working space.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::{AcesCg, EncodedCineonU10};
+/// let white = Color::encoded_cineon_u10(685, 685, 685).convert::<basic_encodings::LinearSrgb>();
+/// assert!((white.r - 1.0).abs() < 1e-5);
+///
+/// let gray = Color::aces_cg(0.18, 0.18, 0.18).convert::<EncodedCineonU10>();
+/// assert_eq!(gray.r, 468);
+/// ```
+pub struct EncodedCineonU10;
+
+impl Color<EncodedCineonU10> {
+    /// Create a [`Color`] in the [`EncodedCineonU10`] encoding.
+    ///
+    /// Components are `0..=1023`.
+    #[inline(always)]
+    pub const fn encoded_cineon_u10(r: u16, g: u16, b: u16) -> Self {
+        Color::from_repr([r, g, b])
+    }
+}
+
+impl ColorEncoding for EncodedCineonU10 {
+    type ComponentStruct = Rgb<u16>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = U16Repr;
+
+    const NAME: &'static str = "EncodedCineonU10";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Cineon);
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let encoded = Vec3::from_array(repr.map(|x| normalize(x, 10)));
+        (transfer::cineon_eotf(encoded), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedCineonU10 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+        vec3_to_bits(transfer::cineon_oetf(raw), 10, rounding)
+    }
+}
+
+/// The Cineon log encoding of [`EncodedCineonU10`] with `f32` components,
+/// the code values divided by 1023.
+///
+/// This is the form of Cineon in float image buffers and OpenEXR files.
+pub struct EncodedCineonF32;
+
+impl Color<EncodedCineonF32> {
+    /// Create a [`Color`] in the [`EncodedCineonF32`] encoding.
+    #[inline(always)]
+    pub fn encoded_cineon_f32(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for EncodedCineonF32 {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::Srgb;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "EncodedCineonF32";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Cineon);
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (transfer::cineon_eotf(repr), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        transfer::cineon_oetf(raw)
+    }
+}
+
+impl_convert_from!(
+    EncodedCineonU10, EncodedCineonF32 =>
+    Srgb, Srgba, CieXyz, Oklab, AcesCg, Aces2065, Bt2020, DisplayP3, EncodedSrgbU8, EncodedSrgbF32,
+    EncodedSrgbU16
+);
+impl_convert_from!(
+    Srgb, Srgba, CieXyz, Oklab, AcesCg, Aces2065, Bt2020, DisplayP3, EncodedSrgbU8, EncodedSrgbF32,
+    EncodedSrgbU16 =>
+    EncodedCineonU10, EncodedCineonF32
+);
+impl ConvertFrom<EncodedCineonU10> for EncodedCineonF32 {}
+impl ConvertFrom<EncodedCineonF32> for EncodedCineonU10 {}
+
+// Camera log encodings
+//
+// The gamuts of these aren't among the primaries of the linear spaces, so
+// their encodings convert to and from CIE XYZ with their own matrices.
+
+/// The Panasonic V-Gamut primaries to CIE XYZ.
+const V_GAMUT_TO_XYZ: Mat3 = Mat3::from_cols(
+    Vec3::new(0.679_644_5, 0.260_685_55, -0.009_310_198),
+    Vec3::new(0.152_211_4, 0.774_894_5, -0.004_612_467),
+    Vec3::new(0.118_600_04, -0.035_580_01, 1.102_980_4),
+);
+/// CIE XYZ to the Panasonic V-Gamut primaries.
+const XYZ_TO_V_GAMUT: Mat3 = Mat3::from_cols(
+    Vec3::new(1.589_011_8, -0.534_052_9, 0.011_179_449),
+    Vec3::new(-0.313_204_48, 1.396_011_4, 0.003_194_128),
+    Vec3::new(-0.180_964_85, 0.102_457_67, 0.905_535_36),
+);
+/// The Canon Cinema Gamut primaries to CIE XYZ.
+const CINEMA_GAMUT_TO_XYZ: Mat3 = Mat3::from_cols(
+    Vec3::new(0.716_049_6, 0.261_261_36, -0.009_676_347),
+    Vec3::new(0.129_683_48, 0.869_642_15, -0.236_481_64),
+    Vec3::new(0.104_722_8, -0.130_903_5, 1.335_215_7),
+);
+/// CIE XYZ to the Canon Cinema Gamut primaries.
+const XYZ_TO_CINEMA_GAMUT: Mat3 = Mat3::from_cols(
+    Vec3::new(1.489_818_3, -0.458_166_57, -0.070_349_67),
+    Vec3::new(-0.260_895_9, 1.261_627_8, 0.221_557_67),
+    Vec3::new(-0.142_426_52, 0.159_623_63, 0.776_181_6),
+);
+
+/// Linear components with the Panasonic V-Gamut primaries and a D65 white
+/// point, the gamut of Panasonic cinema cameras.
+pub struct VGamut;
+
+impl Color<VGamut> {
+    /// Create a [`Color`] in the [`VGamut`] linear color space.
+    #[inline(always)]
+    pub fn v_gamut(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for VGamut {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "VGamut";
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (V_GAMUT_TO_XYZ * repr, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        XYZ_TO_V_GAMUT * raw
+    }
+}
+
+/// The Panasonic V-Log encoding of [`VGamut`] components, as normalized code
+/// values.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::{AcesCg, VLog};
+/// let gray = Color::aces_cg(0.18, 0.18, 0.18).convert::<VLog>();
+/// assert!((gray.r - 0.423).abs() < 1e-3);
+/// ```
+pub struct VLog;
+
+impl Color<VLog> {
+    /// Create a [`Color`] in the [`VLog`] encoding.
+    #[inline(always)]
+    pub fn v_log(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for VLog {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "VLog";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (V_GAMUT_TO_XYZ * transfer::v_log_eotf(repr), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        transfer::v_log_oetf(XYZ_TO_V_GAMUT * raw)
+    }
+}
+
+/// Linear components with the Canon Cinema Gamut primaries and a D65 white
+/// point, the gamut of Canon cinema cameras.
+pub struct CinemaGamut;
+
+impl Color<CinemaGamut> {
+    /// Create a [`Color`] in the [`CinemaGamut`] linear color space.
+    #[inline(always)]
+    pub fn cinema_gamut(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for CinemaGamut {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "CinemaGamut";
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (CINEMA_GAMUT_TO_XYZ * repr, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        XYZ_TO_CINEMA_GAMUT * raw
+    }
+}
+
+/// The Canon Log 2 encoding of [`CinemaGamut`] components, as legal range
+/// normalized 10-bit code values.
+///
+/// Canon Log 2 has more dynamic range than [`CanonLog3`] and is meant for
+/// grading.
+pub struct CanonLog2;
+
+impl Color<CanonLog2> {
+    /// Create a [`Color`] in the [`CanonLog2`] encoding.
+    #[inline(always)]
+    pub fn canon_log2(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for CanonLog2 {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "CanonLog2";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (CINEMA_GAMUT_TO_XYZ * transfer::canon_log2_eotf(repr), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        transfer::canon_log2_oetf(XYZ_TO_CINEMA_GAMUT * raw)
+    }
+}
+
+/// The Canon Log 3 encoding of [`CinemaGamut`] components, as legal range
+/// normalized 10-bit code values.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::{CanonLog3, Srgb};
+/// let gray = Color::srgb(0.18, 0.18, 0.18).convert::<CanonLog3>();
+/// assert!((gray.g - 0.343).abs() < 1e-3);
+/// ```
+pub struct CanonLog3;
+
+impl Color<CanonLog3> {
+    /// Create a [`Color`] in the [`CanonLog3`] encoding.
+    #[inline(always)]
+    pub fn canon_log3(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+}
+
+impl ColorEncoding for CanonLog3 {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::CieXYZ;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "CanonLog3";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (CINEMA_GAMUT_TO_XYZ * transfer::canon_log3_eotf(repr), 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        transfer::canon_log3_oetf(XYZ_TO_CINEMA_GAMUT * raw)
+    }
+}
+
+impl_convert_from!(
+    VGamut, VLog, CinemaGamut, CanonLog2, CanonLog3 =>
+    Srgb, Srgba, CieXyz, Oklab, AcesCg, Aces2065, Bt2020, DisplayP3, EncodedSrgbU8, EncodedSrgbF32
+);
+impl_convert_from!(
+    Srgb, Srgba, CieXyz, Oklab, AcesCg, Aces2065, Bt2020, DisplayP3, EncodedSrgbU8, EncodedSrgbF32 =>
+    VGamut, VLog, CinemaGamut, CanonLog2, CanonLog3
+);
+impl_convert_from!(VLog, CinemaGamut, CanonLog2, CanonLog3 => VGamut);
+impl_convert_from!(VGamut, CinemaGamut, CanonLog2, CanonLog3 => VLog);
+impl_convert_from!(VGamut, VLog, CanonLog2, CanonLog3 => CinemaGamut);
+impl_convert_from!(VGamut, VLog, CinemaGamut, CanonLog3 => CanonLog2);
+impl_convert_from!(VGamut, VLog, CinemaGamut, CanonLog2 => CanonLog3);
+
+/// Linear Adobe RGB color space.
+pub struct AdobeRgb;
+
+impl Color<AdobeRgb> {
+    /// Create a [`Color`] in the [`AdobeRgb`] linear color space.
+    #[inline(always)]
+    pub fn adobe_rgb(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+
+    /// Backward compatibility alias for adobe_rgb.
+    #[inline(always)]
+    pub fn linear_adobe_rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::adobe_rgb(r, g, b)
+    }
+}
+
+impl ColorEncoding for AdobeRgb {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::AdobeRgb;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "AdobeRgb";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (repr, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        raw
+    }
+}
+
+impl WorkingEncoding for AdobeRgb {}
+
+/// Linear ProPhoto RGB color space.
+pub struct ProPhotoRgb;
+
+impl Color<ProPhotoRgb> {
+    /// Create a [`Color`] in the [`ProPhotoRgb`] linear color space.
+    #[inline(always)]
+    pub fn prophoto_rgb(r: f32, g: f32, b: f32) -> Self {
+        Color::from_repr(Vec3::new(r, g, b))
+    }
+
+    /// Backward compatibility alias for prophoto_rgb.
+    #[inline(always)]
+    pub fn linear_prophoto_rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::prophoto_rgb(r, g, b)
+    }
+}
+
+impl ColorEncoding for ProPhotoRgb {
+    type ComponentStruct = Rgb<f32>;
+    type LinearSpace = linear_spaces::ProPhotoRgb;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "ProPhotoRgb";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        (repr, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        raw
+    }
+}
+
+impl WorkingEncoding for ProPhotoRgb {}
+
+/// Non-linear Adobe RGB U8 encoding.
+pub struct EncodedAdobeRgbU8;
+
+impl Color<EncodedAdobeRgbU8> {
+    /// Create a [`Color`] in the [`EncodedAdobeRgbU8`] encoding.
+    #[inline(always)]
+    pub fn encoded_adobe_rgb_u8(r: u8, g: u8, b: u8) -> Self {
+        Color::from_repr([r, g, b])
+    }
+
+    /// Backward compatibility alias for encoded_adobe_rgb_u8.
+    #[inline(always)]
+    pub fn adobe_rgb_u8(r: u8, g: u8, b: u8) -> Self {
+        Self::encoded_adobe_rgb_u8(r, g, b)
+    }
+}
+
+impl ColorEncoding for EncodedAdobeRgbU8 {
+    type ComponentStruct = Rgb<u8>;
+    type LinearSpace = linear_spaces::AdobeRgb;
+    type Repr = U8Repr;
+
+    const NAME: &'static str = "EncodedAdobeRgbU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::AdobeRgb);
+
+    #[inline(always)]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let [x, y, z] = repr;
+        let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
+        let optical = transfer::adobe_rgb_eotf(raw_electro);
+        (optical, 1.0)
+    }
+
+    #[inline(always)]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedAdobeRgbU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+        vec3_to_u8(transfer::adobe_rgb_oetf(raw), rounding)
+    }
+}
+
+/// Non-linear ProPhoto RGB U8 encoding.
+pub struct EncodedProPhotoRgbU8;
+
+impl Color<EncodedProPhotoRgbU8> {
+    /// Create a [`Color`] in the [`EncodedProPhotoRgbU8`] encoding.
+    #[inline(always)]
+    pub fn encoded_prophoto_rgb_u8(r: u8, g: u8, b: u8) -> Self {
         Color::from_repr([r, g, b])
     }
 
@@ -749,23 +2472,26 @@ impl ColorEncoding for EncodedProPhotoRgbU8 {
     type Repr = U8Repr;
 
     const NAME: &'static str = "EncodedProPhotoRgbU8";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::ProPhotoRgb);
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
         let [x, y, z] = repr;
         let raw_electro = Vec3::new(u8_to_f32(x), u8_to_f32(y), u8_to_f32(z));
-        let optical = prophoto_rgb_eotf(raw_electro);
+        let optical = transfer::prophoto_rgb_eotf(raw_electro);
         (optical, 1.0)
     }
 
     #[inline(always)]
-    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
-        let electro = prophoto_rgb_oetf(raw);
-        [
-            f32_to_u8(electro.x),
-            f32_to_u8(electro.y),
-            f32_to_u8(electro.z),
-        ]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedProPhotoRgbU8 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, _: f32, rounding: Rounding) -> Self::Repr {
+        vec3_to_u8(transfer::prophoto_rgb_oetf(raw), rounding)
     }
 }
 /// Linear Display P3 color space.
@@ -785,6 +2511,8 @@ impl ColorEncoding for DisplayP3 {
     type Repr = F32Repr;
 
     const NAME: &'static str = "DisplayP3";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -816,6 +2544,8 @@ impl ColorEncoding for AcesCg {
     type Repr = F32Repr;
 
     const NAME: &'static str = "AcesCg";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -847,6 +2577,8 @@ impl ColorEncoding for Aces2065 {
     type Repr = F32Repr;
 
     const NAME: &'static str = "Aces2065";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -878,6 +2610,8 @@ impl ColorEncoding for Bt2020 {
     type Repr = F32Repr;
 
     const NAME: &'static str = "Bt2020";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Linear);
+    const IS_WORKING: bool = true;
 
     #[inline(always)]
     fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
@@ -891,3 +2625,397 @@ impl ColorEncoding for Bt2020 {
 }
 
 impl WorkingEncoding for Bt2020 {}
+
+/// HDR10 pixels: BT.2020 primaries with the SMPTE ST 2084 (PQ) transfer
+/// function, packed as 10-bit RGB with 2-bit alpha into a `u32`, from most to
+/// least significant bits A, R, G, B (`A2R10G10B10`).
+///
+/// This is the layout of `VK_FORMAT_A2R10G10B10_UNORM_PACK32` swapchain
+/// images with the `HDR10_ST2084` color space. Linear values of `1.0` are
+/// [`HDR_REFERENCE_WHITE`] nits, so that a linear color converts with the
+/// brightness of SDR white in HDR content.
+///
+/// Colors `Deref` to [`A2Rgb10`] to read the packed channels.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::EncodedHdr10;
+/// let white = Color::bt2020(1.0, 1.0, 1.0).convert::<EncodedHdr10>();
+///
+/// assert_eq!((white.r(), white.g(), white.b(), white.a()), (594, 594, 594, 3));
+/// ```
+pub struct EncodedHdr10;
+
+/// The nits of a linear value of `1.0` in [`EncodedHdr10`], the HDR reference
+/// white of ITU-R BT.2408.
+pub const HDR_REFERENCE_WHITE: f32 = 203.0;
+
+impl Color<EncodedHdr10> {
+    /// Create a [`Color`] in the [`EncodedHdr10`] encoding from PQ-encoded
+    /// `0..=1023` color components and a `0..=3` alpha.
+    #[inline(always)]
+    pub const fn encoded_hdr10(r: u16, g: u16, b: u16, a: u8) -> Self {
+        Color::from_repr(A2Rgb10::new(r, g, b, a).bits)
+    }
+}
+
+impl ColorEncoding for EncodedHdr10 {
+    type ComponentStruct = A2Rgb10;
+    type LinearSpace = linear_spaces::Bt2020;
+    type Repr = U32Repr;
+
+    const NAME: &'static str = "EncodedHdr10";
+    const TRANSFER: Option<TransferFunction> = Some(TransferFunction::Pq);
+    const HAS_ALPHA: bool = true;
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let packed = A2Rgb10 { bits: repr };
+        let encoded = Vec3::new(
+            normalize(packed.r(), 10),
+            normalize(packed.g(), 10),
+            normalize(packed.b(), 10),
+        );
+        let nits = transfer::pq_eotf(encoded);
+        (nits / HDR_REFERENCE_WHITE, normalize(packed.a() as u16, 2))
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, alpha: f32) -> Self::Repr {
+        Self::dst_transform_quantized(raw, alpha, Rounding::HalfUp)
+    }
+}
+
+impl QuantizedEncoding for EncodedHdr10 {
+    #[inline]
+    fn dst_transform_quantized(raw: glam::Vec3, alpha: f32, rounding: Rounding) -> Self::Repr {
+        let encoded = transfer::pq_inverse_eotf(raw * HDR_REFERENCE_WHITE);
+        let [r, g, b] = vec3_to_bits(encoded, 10, rounding);
+        let a = rounding.quantize_bits(alpha, 2, 3) as u8;
+        A2Rgb10::new(r, g, b, a).bits
+    }
+}
+
+impl AlphaEncoding for EncodedHdr10 {
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        normalize(color.a() as u16, 2)
+    }
+
+    #[inline]
+    fn with_alpha(color: Color<Self>, alpha: f32) -> Color<Self> {
+        let a = Rounding::HalfUp.quantize_bits(alpha, 2, 3) as u8;
+        Color::encoded_hdr10(color.r(), color.g(), color.b(), a)
+    }
+}
+
+impl_convert_from!(Bt2020, AcesCg, Aces2065, DisplayP3, Srgb, CieXyz => EncodedHdr10);
+impl_convert_from!(EncodedHdr10 => Bt2020, AcesCg, Aces2065, DisplayP3, Srgb, CieXyz);
+
+/// BT.2020 to the LMS cone responses of ITU-R BT.2100 ICtCp.
+const BT2020_TO_LMS: Mat3 = Mat3::from_cols(
+    Vec3::new(1688.0 / 4096.0, 683.0 / 4096.0, 99.0 / 4096.0),
+    Vec3::new(2146.0 / 4096.0, 2951.0 / 4096.0, 309.0 / 4096.0),
+    Vec3::new(262.0 / 4096.0, 462.0 / 4096.0, 3688.0 / 4096.0),
+);
+/// The LMS cone responses of ICtCp to BT.2020.
+const LMS_TO_BT2020: Mat3 = Mat3::from_cols(
+    Vec3::new(3.436_606_7, -0.791_329_56, -0.025_949_9),
+    Vec3::new(-2.506_452, 1.983_600_4, -0.098_913_71),
+    Vec3::new(0.069_845_42, -0.192_270_9, 1.124_863_6),
+);
+/// PQ-encoded LMS to ICtCp.
+const PQ_LMS_TO_ICTCP: Mat3 = Mat3::from_cols(
+    Vec3::new(2048.0 / 4096.0, 6610.0 / 4096.0, 17933.0 / 4096.0),
+    Vec3::new(2048.0 / 4096.0, -13613.0 / 4096.0, -17390.0 / 4096.0),
+    Vec3::new(0.0, 7003.0 / 4096.0, -543.0 / 4096.0),
+);
+/// ICtCp to PQ-encoded LMS.
+const ICTCP_TO_PQ_LMS: Mat3 = Mat3::from_cols(
+    Vec3::new(1.0, 1.0, 1.0),
+    Vec3::new(0.008_609_037, -0.008_609_037, 0.560_031_3),
+    Vec3::new(0.111_029_63, -0.111_029_63, -0.320_627_18),
+);
+
+/// ICtCp with the PQ transfer function, the HDR color representation of
+/// ITU-R BT.2100.
+///
+/// I (intensity) is in `0.0..=1.0`, the PQ signal of the luminance of grays,
+/// Ct (blue-yellow) and Cp (red-green) are in about `-0.5..=0.5`. Intensity is
+/// decorrelated from chroma much better than in YCbCr, so that changing it
+/// doesn't shift hue, see [`crate::hdr_brightness`]. As in [`EncodedHdr10`],
+/// linear values of `1.0` are [`HDR_REFERENCE_WHITE`] nits.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::ICtCpPq;
+/// let white = Color::bt2020(1.0, 1.0, 1.0).convert::<ICtCpPq>();
+///
+/// assert!((white.i - 0.5806).abs() < 1e-4);
+/// assert!(white.ct.abs() < 1e-4 && white.cp.abs() < 1e-4);
+/// ```
+pub struct ICtCpPq;
+
+impl Color<ICtCpPq> {
+    /// Create a [`Color`] in the [`ICtCpPq`] encoding.
+    #[inline(always)]
+    pub const fn ictcp_pq(i: f32, ct: f32, cp: f32) -> Self {
+        Color::from_repr(Vec3::new(i, ct, cp))
+    }
+}
+
+impl ColorEncoding for ICtCpPq {
+    type ComponentStruct = ICtCp<f32>;
+    type LinearSpace = linear_spaces::Bt2020;
+    type Repr = F32Repr;
+
+    const NAME: &'static str = "ICtCpPq";
+
+    #[inline]
+    fn src_transform_raw(repr: Self::Repr) -> (glam::Vec3, f32) {
+        let lms = transfer::pq_eotf(ICTCP_TO_PQ_LMS * repr) / HDR_REFERENCE_WHITE;
+        (LMS_TO_BT2020 * lms, 1.0)
+    }
+
+    #[inline]
+    fn dst_transform_raw(raw: glam::Vec3, _: f32) -> Self::Repr {
+        let lms = BT2020_TO_LMS * raw;
+        PQ_LMS_TO_ICTCP * transfer::pq_inverse_eotf(lms * HDR_REFERENCE_WHITE)
+    }
+}
+
+impl_convert_from!(
+    Bt2020, AcesCg, Aces2065, DisplayP3, Srgb, Srgba, CieXyz, Oklab, EncodedSrgbU8, EncodedSrgbF32,
+    EncodedHdr10 => ICtCpPq
+);
+impl_convert_from!(
+    ICtCpPq => Bt2020, AcesCg, Aces2065, DisplayP3, Srgb, Srgba, CieXyz, Oklab, EncodedSrgbU8,
+    EncodedSrgbF32, EncodedHdr10
+);
+
+macro_rules! impl_constants {
+    (rgb_u8: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr([0, 0, 0]);
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr([255, 255, 255]);
+
+            /// Create a neutral gray [`Color`] with all components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: u8) -> Self {
+                Color::from_repr([v, v, v])
+            }
+        }
+    )+};
+    (rgba_u8: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr([0, 0, 0, 255]);
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr([255, 255, 255, 255]);
+            /// Fully transparent black.
+            pub const TRANSPARENT: Self = Color::from_repr([0, 0, 0, 0]);
+
+            /// Create an opaque neutral gray [`Color`] with all color
+            /// components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: u8) -> Self {
+                Color::from_repr([v, v, v, 255])
+            }
+        }
+    )+};
+    (rgba_u16: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr([0, 0, 0, u16::MAX]);
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr([u16::MAX; 4]);
+            /// Fully transparent black.
+            pub const TRANSPARENT: Self = Color::from_repr([0; 4]);
+
+            /// Create an opaque neutral gray [`Color`] with all color
+            /// components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: u16) -> Self {
+                Color::from_repr([v, v, v, u16::MAX])
+            }
+        }
+    )+};
+    (rgb_f32: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr(Vec3::ZERO);
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr(Vec3::ONE);
+
+            /// Create a neutral gray [`Color`] with all components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: f32) -> Self {
+                Color::from_repr(Vec3::splat(v))
+            }
+        }
+    )+};
+    (rgba_f32: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr(Vec4::new(0.0, 0.0, 0.0, 1.0));
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr(Vec4::ONE);
+            /// Fully transparent black.
+            pub const TRANSPARENT: Self = Color::from_repr(Vec4::ZERO);
+
+            /// Create an opaque neutral gray [`Color`] with all color
+            /// components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: f32) -> Self {
+                Color::from_repr(Vec4::new(v, v, v, 1.0))
+            }
+        }
+    )+};
+    (rgb_f64: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr(DVec3::ZERO);
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr(DVec3::ONE);
+
+            /// Create a neutral gray [`Color`] with all components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: f64) -> Self {
+                Color::from_repr(DVec3::splat(v))
+            }
+        }
+    )+};
+    (rgba_f64: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque black.
+            pub const BLACK: Self = Color::from_repr(DVec4::new(0.0, 0.0, 0.0, 1.0));
+            /// Opaque white.
+            pub const WHITE: Self = Color::from_repr(DVec4::ONE);
+            /// Fully transparent black.
+            pub const TRANSPARENT: Self = Color::from_repr(DVec4::ZERO);
+
+            /// Create an opaque neutral gray [`Color`] with all color
+            /// components set to `v`.
+            #[inline(always)]
+            pub const fn gray(v: f64) -> Self {
+                Color::from_repr(DVec4::new(v, v, v, 1.0))
+            }
+        }
+    )+};
+}
+
+impl_constants!(
+    rgb_u8: EncodedSrgbU8,
+    EncodedBgrU8,
+    EncodedAdobeRgbU8,
+    EncodedProPhotoRgbU8
+);
+impl_constants!(rgba_u8: EncodedSrgbaU8, EncodedBgraU8, EncodedSrgbaPremultipliedU8);
+impl_constants!(rgba_u16: EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16);
+impl_constants!(
+    rgb_f32: EncodedSrgbF32,
+    Srgb,
+    AdobeRgb,
+    ProPhotoRgb,
+    DisplayP3,
+    AcesCg,
+    Aces2065,
+    Bt2020
+);
+impl_constants!(rgba_f32: EncodedSrgbaF32, Srgba, SrgbaPremultiplied);
+impl_constants!(rgb_f64: SrgbF64);
+impl_constants!(rgba_f64: SrgbaF64);
+
+impl Color<Oklab> {
+    /// Black.
+    pub const BLACK: Self = Color::from_repr(Vec3::ZERO);
+    /// The D65 white point.
+    pub const WHITE: Self = Color::from_repr(Vec3::X);
+
+    /// Create a neutral gray [`Color`] with lightness `l`.
+    #[inline(always)]
+    pub const fn gray(l: f32) -> Self {
+        Color::from_repr(Vec3::new(l, 0.0, 0.0))
+    }
+}
+
+macro_rules! impl_primaries {
+    (rgb: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Red primary.
+            pub const RED: Self = Color::from_repr(Vec3::X);
+            /// Green primary.
+            pub const GREEN: Self = Color::from_repr(Vec3::Y);
+            /// Blue primary.
+            pub const BLUE: Self = Color::from_repr(Vec3::Z);
+            /// Green plus blue.
+            pub const CYAN: Self = Color::from_repr(Vec3::new(0.0, 1.0, 1.0));
+            /// Red plus blue.
+            pub const MAGENTA: Self = Color::from_repr(Vec3::new(1.0, 0.0, 1.0));
+            /// Red plus green.
+            pub const YELLOW: Self = Color::from_repr(Vec3::new(1.0, 1.0, 0.0));
+
+            /// Create a [`Color`] with all components set to `v`.
+            #[inline(always)]
+            pub const fn splat(v: f32) -> Self {
+                Color::from_repr(Vec3::splat(v))
+            }
+        }
+    )+};
+    (rgba: $($enc:ident),+) => {$(
+        impl Color<$enc> {
+            /// Opaque red primary.
+            pub const RED: Self = Color::from_repr(Vec4::new(1.0, 0.0, 0.0, 1.0));
+            /// Opaque green primary.
+            pub const GREEN: Self = Color::from_repr(Vec4::new(0.0, 1.0, 0.0, 1.0));
+            /// Opaque blue primary.
+            pub const BLUE: Self = Color::from_repr(Vec4::new(0.0, 0.0, 1.0, 1.0));
+            /// Opaque green plus blue.
+            pub const CYAN: Self = Color::from_repr(Vec4::new(0.0, 1.0, 1.0, 1.0));
+            /// Opaque red plus blue.
+            pub const MAGENTA: Self = Color::from_repr(Vec4::new(1.0, 0.0, 1.0, 1.0));
+            /// Opaque red plus green.
+            pub const YELLOW: Self = Color::from_repr(Vec4::new(1.0, 1.0, 0.0, 1.0));
+
+            /// Create an opaque [`Color`] with all color components set to
+            /// `v`.
+            #[inline(always)]
+            pub const fn splat(v: f32) -> Self {
+                Color::from_repr(Vec4::new(v, v, v, 1.0))
+            }
+        }
+    )+};
+}
+
+impl_primaries!(rgb: Srgb, AdobeRgb, ProPhotoRgb, DisplayP3, AcesCg, Aces2065, Bt2020);
+impl_primaries!(rgba: Srgba);
+
+macro_rules! impl_from_primitive {
+    ($($src:ty => $enc:ident, $doc:literal, |$v:ident| $repr:expr;)+) => {$(
+        #[doc = $doc]
+        impl From<$src> for Color<$enc> {
+            #[inline(always)]
+            fn from($v: $src) -> Self {
+                Color::from_repr($repr)
+            }
+        }
+    )+};
+}
+
+impl_from_primitive!(
+    [u8; 3] => EncodedSrgbU8, "Encoded sRGB components.", |v| v;
+    (u8, u8, u8) => EncodedSrgbU8, "Encoded sRGB components.", |v| [v.0, v.1, v.2];
+    [u8; 4] => EncodedSrgbaU8, "Encoded sRGB components and alpha.", |v| v;
+    (u8, u8, u8, u8) => EncodedSrgbaU8, "Encoded sRGB components and alpha.",
+        |v| [v.0, v.1, v.2, v.3];
+    [f32; 3] => Srgb, "*Linear* sRGB components.", |v| Vec3::from(v);
+    (f32, f32, f32) => Srgb, "*Linear* sRGB components.", |v| Vec3::from(v);
+    [f32; 4] => Srgba, "*Linear* sRGB components and alpha.", |v| Vec4::from(v);
+    (f32, f32, f32, f32) => Srgba, "*Linear* sRGB components and alpha.", |v| Vec4::from(v);
+);
@@ -0,0 +1,175 @@
+use crate::traits::ColorEncoding;
+
+use glam::{Vec2, Vec3};
+
+/// The rounding policy used when quantizing `0.0..=1.0` float components to
+/// integer components.
+///
+/// Values are always clamped to `0.0..=1.0` before rounding.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round to nearest, ties away from zero, i.e. `(x * 255.0).round()`.
+    ///
+    /// This is the policy used by [`Color::convert`][crate::Color::convert].
+    #[default]
+    HalfUp,
+    /// Round to nearest, ties to even.
+    HalfEven,
+    /// Add `0.5` and truncate, i.e. `(x * 255.0 + 0.5) as u8`.
+    ///
+    /// This matches the quantization of many C/C++ image libraries, which can
+    /// differ from [`Rounding::HalfUp`] due to float rounding of the addition.
+    TruncateBiased,
+    /// Round up with a probability equal to the fractional part of the scaled
+    /// value.
+    ///
+    /// The noise is derived from a hash of `seed`, the component index and the
+    /// value, so results are reproducible. Vary `seed` per pixel (for example
+    /// with the pixel index) to decorrelate the noise across an image.
+    Stochastic { seed: u32 },
+}
+
+impl Rounding {
+    /// Quantize `value` to a `u8` using this rounding policy.
+    ///
+    /// `channel` is the index of the component being quantized. It is only
+    /// used to decorrelate the noise of [`Rounding::Stochastic`] between
+    /// components.
+    #[inline]
+    pub fn quantize_u8(self, value: f32, channel: u32) -> u8 {
+        self.quantize(value, u8::MAX as f32, channel) as u8
+    }
+
+    /// Quantize `value` to a `u16` using this rounding policy.
+    ///
+    /// See [`Rounding::quantize_u8`].
+    #[inline]
+    pub fn quantize_u16(self, value: f32, channel: u32) -> u16 {
+        self.quantize(value, u16::MAX as f32, channel) as u16
+    }
+
+    /// Quantize `value` to an integer with `bits` bits, stored in the low bits
+    /// of a `u16`, using this rounding policy.
+    ///
+    /// See [`Rounding::quantize_u8`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not in `1..=16`.
+    #[inline]
+    pub fn quantize_bits(self, value: f32, bits: u32, channel: u32) -> u16 {
+        self.quantize(value, crate::bit_depth::max_value(bits) as f32, channel) as u16
+    }
+
+    /// Scale `value` to `0.0..=max` and round it to an integer.
+    #[inline(always)]
+    fn quantize(self, value: f32, max: f32, channel: u32) -> f32 {
+        let scaled = value.clamp(0.0, 1.0) * max;
+        match self {
+            Rounding::HalfUp => round(scaled),
+            Rounding::HalfEven => {
+                let rounded = floor(scaled + 0.5);
+                if rounded - scaled == 0.5 && rounded % 2.0 != 0.0 {
+                    rounded - 1.0
+                } else {
+                    rounded
+                }
+            }
+            Rounding::TruncateBiased => floor(scaled + 0.5),
+            Rounding::Stochastic { seed } => {
+                let floor = floor(scaled);
+                let noise = unit_noise(seed, channel, value.to_bits());
+                if noise < scaled - floor {
+                    floor + 1.0
+                } else {
+                    floor
+                }
+            }
+        }
+    }
+}
+
+/// `f32::round`, which is not available without `std`.
+#[inline(always)]
+pub(crate) fn round(x: f32) -> f32 {
+    Vec2::splat(x).round().x
+}
+
+/// `f32::floor`, which is not available without `std`.
+#[inline(always)]
+fn floor(x: f32) -> f32 {
+    Vec2::splat(x).floor().x
+}
+
+/// Hash-based noise in `0.0..1.0`.
+#[inline(always)]
+fn unit_noise(seed: u32, channel: u32, bits: u32) -> f32 {
+    let hash = mix(seed ^ mix(bits ^ channel.wrapping_mul(0x9e37_79b9)));
+    (hash >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// The `lowbias32` integer hash.
+#[inline(always)]
+fn mix(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^ (x >> 16)
+}
+
+#[inline(always)]
+pub(crate) fn u8_to_f32(x: u8) -> f32 {
+    x as f32 / 255.0
+}
+
+#[inline(always)]
+pub(crate) fn vec3_to_u8(v: Vec3, rounding: Rounding) -> [u8; 3] {
+    [
+        rounding.quantize_u8(v.x, 0),
+        rounding.quantize_u8(v.y, 1),
+        rounding.quantize_u8(v.z, 2),
+    ]
+}
+
+#[inline(always)]
+pub(crate) fn u16_to_f32(x: u16) -> f32 {
+    x as f32 / 65535.0
+}
+
+#[inline(always)]
+pub(crate) fn vec3_to_u16(v: Vec3, rounding: Rounding) -> [u16; 3] {
+    [
+        rounding.quantize_u16(v.x, 0),
+        rounding.quantize_u16(v.y, 1),
+        rounding.quantize_u16(v.z, 2),
+    ]
+}
+
+#[inline(always)]
+pub(crate) fn vec3_to_bits(v: Vec3, bits: u32, rounding: Rounding) -> [u16; 3] {
+    [
+        rounding.quantize_bits(v.x, bits, 0),
+        rounding.quantize_bits(v.y, bits, 1),
+        rounding.quantize_bits(v.z, bits, 2),
+    ]
+}
+
+/// Implemented by color encodings with integer components, allowing the
+/// [`Rounding`] used during quantization to be chosen.
+///
+/// See [`Color::convert_with_rounding`][crate::Color::convert_with_rounding].
+pub trait QuantizedEncoding: ColorEncoding {
+    /// Same as [`ColorEncoding::dst_transform_raw`] but quantizes with the
+    /// given `rounding`.
+    fn dst_transform_quantized(raw: Vec3, alpha: f32, rounding: Rounding) -> Self::Repr;
+}
+
+/// Implemented by grayscale encodings with a single integer component of up
+/// to 8 bits, like [`EncodedGrayU8`][crate::details::encodings::EncodedGrayU8].
+///
+/// See [`crate::dither`] for output to the low bit depths.
+pub trait GrayEncoding: QuantizedEncoding<Repr = u8> {
+    /// The number of bits of the component.
+    const BITS: u32;
+}
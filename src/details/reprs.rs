@@ -7,6 +7,20 @@ impl ColorRepr for U8Repr {
     type Element = u8;
 }
 
+impl ArrayRepr for U8Repr {
+    type Array = [u8; 3];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        self
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array
+    }
+}
+
 /// Just a `[u8; 4]`. Used for 8-bits-per-channel, four channel encodings.
 pub type U8aRepr = [u8; 4];
 
@@ -14,6 +28,107 @@ impl ColorRepr for U8aRepr {
     type Element = u8;
 }
 
+impl ArrayRepr for U8aRepr {
+    type Array = [u8; 4];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        self
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array
+    }
+}
+
+/// Three `u16`s. Used for encodings with up to 16 bits per channel and three
+/// channels.
+pub type U16Repr = [u16; 3];
+
+impl ColorRepr for U16Repr {
+    type Element = u16;
+}
+
+impl ArrayRepr for U16Repr {
+    type Array = [u16; 3];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        self
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array
+    }
+}
+
+/// Four `u16`s. Used for 16-bits-per-channel, four channel encodings.
+pub type U16aRepr = [u16; 4];
+
+impl ColorRepr for U16aRepr {
+    type Element = u16;
+}
+
+impl ArrayRepr for U16aRepr {
+    type Array = [u16; 4];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        self
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array
+    }
+}
+
+/// A single `u32`. Used for encodings that pack all channels into 32 bits,
+/// like [`EncodedHdr10`][crate::details::encodings::EncodedHdr10].
+pub type U32Repr = u32;
+
+impl ColorRepr for U32Repr {
+    type Element = u32;
+}
+
+impl ArrayRepr for U32Repr {
+    type Array = [u32; 1];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        [self]
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array[0]
+    }
+}
+
+/// A single `u8`. Used for 8-bit, single channel encodings like
+/// [`EncodedGrayU8`][crate::details::encodings::EncodedGrayU8].
+pub type U8GrayRepr = u8;
+
+impl ColorRepr for U8GrayRepr {
+    type Element = u8;
+}
+
+impl ArrayRepr for U8GrayRepr {
+    type Array = [u8; 1];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        [self]
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array[0]
+    }
+}
+
 /// Just a [`glam::Vec3`] (also equivalent in layout to a `[f32; 3]`). Used for
 /// 32-bits-per-channel, three channel encodings.
 pub type F32Repr = glam::Vec3;
@@ -22,6 +137,20 @@ impl ColorRepr for F32Repr {
     type Element = f32;
 }
 
+impl ArrayRepr for F32Repr {
+    type Array = [f32; 3];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        glam::Vec3::to_array(&self)
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        glam::Vec3::from_array(array)
+    }
+}
+
 /// Just a [`glam::Vec4`] (also equivalent in layot to a `[f32; 4]`). Used for
 /// 32-bits-per-channel, four channel encodings.
 pub type F32aRepr = glam::Vec4;
@@ -29,3 +158,116 @@ pub type F32aRepr = glam::Vec4;
 impl ColorRepr for F32aRepr {
     type Element = f32;
 }
+
+impl ArrayRepr for F32aRepr {
+    type Array = [f32; 4];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        glam::Vec4::to_array(&self)
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        glam::Vec4::from_array(array)
+    }
+}
+
+/// Just a [`glam::DVec3`] (also equivalent in layout to a `[f64; 3]`). Used
+/// for 64-bits-per-channel, three channel encodings.
+pub type F64Repr = glam::DVec3;
+
+impl ColorRepr for F64Repr {
+    type Element = f64;
+}
+
+impl ArrayRepr for F64Repr {
+    type Array = [f64; 3];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        glam::DVec3::to_array(&self)
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        glam::DVec3::from_array(array)
+    }
+}
+
+/// Just a [`glam::DVec4`] (also equivalent in layout to a `[f64; 4]`). Used
+/// for 64-bits-per-channel, four channel encodings.
+pub type F64aRepr = glam::DVec4;
+
+impl ColorRepr for F64aRepr {
+    type Element = f64;
+}
+
+impl ArrayRepr for F64aRepr {
+    type Array = [f64; 4];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        glam::DVec4::to_array(&self)
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        glam::DVec4::from_array(array)
+    }
+}
+
+/// `N` `f32`s. Used for multispectral encodings with `N` bands, like
+/// [`Multispectral`][crate::details::encodings::Multispectral].
+pub type F32BandsRepr<const N: usize> = [f32; N];
+
+impl<const N: usize> ColorRepr for F32BandsRepr<N> {
+    type Element = f32;
+}
+
+impl<const N: usize> ArrayRepr for F32BandsRepr<N> {
+    type Array = [f32; N];
+
+    #[inline(always)]
+    fn to_array(self) -> Self::Array {
+        self
+    }
+
+    #[inline(always)]
+    fn from_array(array: Self::Array) -> Self {
+        array
+    }
+}
+
+macro_rules! impl_component_math {
+    ($($repr:ty: $float:ty),+) => {$(
+        impl ComponentMath for $repr {
+            #[inline(always)]
+            fn min(self, other: Self) -> Self {
+                <$repr>::min(self, other)
+            }
+
+            #[inline(always)]
+            fn max(self, other: Self) -> Self {
+                <$repr>::max(self, other)
+            }
+
+            #[inline(always)]
+            fn clamp(self, min: Self, max: Self) -> Self {
+                <$repr>::clamp(self, min, max)
+            }
+
+            #[inline(always)]
+            fn abs(self) -> Self {
+                <$repr>::abs(self)
+            }
+
+            #[inline(always)]
+            fn powf(self, n: f32) -> Self {
+                <$repr>::powf(self, n as $float)
+            }
+        }
+    )+};
+}
+
+impl_component_math!(F32Repr: f32, F32aRepr: f32, F64Repr: f64, F64aRepr: f64);
@@ -1,6 +1,9 @@
 use core::ops::{Add, Mul, Sub};
 
-use crate::Color;
+use crate::custom::CustomColorSpace;
+use crate::details::encodings::wrap_degrees;
+use crate::transfer::TransferFunction;
+use crate::{Color, Hex};
 
 use glam::Vec3;
 use kolor::details::color::{RgbPrimaries, WhitePoint};
@@ -42,6 +45,58 @@ pub trait ColorEncoding: Sized + 'static {
     /// component to a `Self::Repr` fully encoded in `Self`'s color
     /// encoding. If this encoding does not have alpha, you can disregard it.
     fn dst_transform_raw(raw: Vec3, alpha: f32) -> Self::Repr;
+
+    /// The transfer function from `Self::LinearSpace` to the components, if
+    /// they are the linear components shaped by one curve.
+    ///
+    /// Used by [`EncodingInfo`].
+    const TRANSFER: Option<TransferFunction> = None;
+
+    /// Whether this encoding has an alpha component.
+    ///
+    /// Used by [`EncodingInfo`].
+    const HAS_ALPHA: bool = false;
+
+    /// Whether the color components are multiplied by alpha, as opposed to
+    /// straight (unassociated) alpha.
+    ///
+    /// Used by [`EncodingInfo`].
+    const IS_PREMULTIPLIED: bool = false;
+
+    /// Whether this encoding implements [`WorkingEncoding`].
+    ///
+    /// Used by [`EncodingInfo`].
+    const IS_WORKING: bool = false;
+
+    /// Whether this encoding implements [`PerceptualEncoding`].
+    ///
+    /// Used by [`EncodingInfo`].
+    const IS_PERCEPTUAL: bool = false;
+
+    /// A runtime description of this encoding.
+    fn info() -> EncodingInfo {
+        EncodingInfo {
+            name: Self::NAME,
+            primaries: Self::LinearSpace::PRIMARIES,
+            white_point: Self::LinearSpace::WHITE_POINT,
+            transfer: Self::TRANSFER,
+            components: size_of::<Self::Repr>() / size_of::<<Self::Repr as ColorRepr>::Element>(),
+            component_type: core::any::type_name::<<Self::Repr as ColorRepr>::Element>(),
+            has_alpha: Self::HAS_ALPHA,
+            is_premultiplied: Self::IS_PREMULTIPLIED,
+            is_working: Self::IS_WORKING,
+            is_perceptual: Self::IS_PERCEPTUAL,
+        }
+    }
+
+    /// The [`Hex`] form of a color, for encodings whose components are
+    /// display-encoded sRGB, or `None`.
+    ///
+    /// Used in `Debug` and `Display` implementations.
+    #[inline(always)]
+    fn hex(_repr: Self::Repr) -> Option<Hex> {
+        None
+    }
 }
 
 /// Implementing this trait for a struct marks that it is safe to pointer cast
@@ -67,6 +122,110 @@ pub trait ColorRepr: Sized + Clone + Copy + 'static {
     type Element: Sized + Clone + Copy + 'static;
 }
 
+/// Implemented by reprs that convert to and from an array of their elements,
+/// which gives [`Color::to_array`], [`Color::from_array`] and iteration over
+/// the components of a color.
+///
+/// All the reprs in [`crate::details::reprs`] implement it.
+pub trait ArrayRepr: ColorRepr {
+    /// The elements of this repr as an array, e.g. `[u8; 3]`.
+    type Array: Copy
+        + IntoIterator<Item = Self::Element>
+        + AsRef<[Self::Element]>
+        + AsMut<[Self::Element]>;
+
+    /// Copy the elements of this repr into an array.
+    fn to_array(self) -> Self::Array;
+
+    /// Create this repr from an array of its elements.
+    fn from_array(array: Self::Array) -> Self;
+}
+
+/// A runtime description of a [`ColorEncoding`], returned by
+/// [`ColorEncoding::info`].
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::transfer::TransferFunction;
+/// let info = basic_encodings::SrgbaU8::info();
+///
+/// assert_eq!(info.name, "EncodedSrgbaU8");
+/// assert_eq!(info.transfer, Some(TransferFunction::Srgb));
+/// assert_eq!((info.components, info.component_type), (4, "u8"));
+/// assert!(info.has_alpha && !info.is_working);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncodingInfo {
+    /// The name of the encoding, [`ColorEncoding::NAME`].
+    pub name: &'static str,
+    /// The primaries of the linear space of the encoding.
+    pub primaries: RgbPrimaries,
+    /// The white point of the linear space of the encoding.
+    pub white_point: WhitePoint,
+    /// The transfer function, [`ColorEncoding::TRANSFER`].
+    pub transfer: Option<TransferFunction>,
+    /// The number of components of the repr.
+    pub components: usize,
+    /// The type of each component of the repr, like `"u8"` or `"f32"`.
+    pub component_type: &'static str,
+    /// Whether the encoding has an alpha component.
+    pub has_alpha: bool,
+    /// Whether the color components are premultiplied by alpha,
+    /// [`ColorEncoding::IS_PREMULTIPLIED`].
+    pub is_premultiplied: bool,
+    /// Whether the encoding is a [`WorkingEncoding`].
+    pub is_working: bool,
+    /// Whether the encoding is a [`PerceptualEncoding`].
+    pub is_perceptual: bool,
+}
+
+/// Implemented by color encodings with an alpha component, so that alpha can
+/// be read and written without knowing the encoding.
+///
+/// Alpha is normalized to `0.0` (transparent) to `1.0` (opaque), whatever the
+/// component type. The provided methods go through the conversion pipeline,
+/// which makes them work for premultiplied encodings too.
+pub trait AlphaEncoding: ColorEncoding {
+    /// The alpha of `color`.
+    #[inline]
+    fn alpha(color: Color<Self>) -> f32 {
+        Self::src_transform_raw(color.repr).1
+    }
+
+    /// `color` with its alpha set to `alpha`, keeping its unpremultiplied
+    /// color.
+    ///
+    /// A transparent premultiplied color has no color left, so it becomes
+    /// black.
+    #[inline]
+    fn with_alpha(color: Color<Self>, alpha: f32) -> Color<Self> {
+        let (raw, _) = Self::src_transform_raw(color.repr);
+        let raw = if raw.is_finite() { raw } else { Vec3::ZERO };
+        Color::from_repr(Self::dst_transform_raw(raw, alpha))
+    }
+}
+
+/// Implemented by float reprs for component-wise math, as on the `glam` vector
+/// types.
+pub trait ComponentMath: ColorRepr {
+    /// The component-wise minimum of `self` and `other`.
+    fn min(self, other: Self) -> Self;
+
+    /// The component-wise maximum of `self` and `other`.
+    fn max(self, other: Self) -> Self;
+
+    /// Each component clamped between the components of `min` and `max`.
+    fn clamp(self, min: Self, max: Self) -> Self;
+
+    /// The absolute value of each component.
+    fn abs(self) -> Self;
+
+    /// Each component raised to the power of `n`.
+    fn powf(self, n: f32) -> Self;
+}
+
 /// Implemented by color encodings that can do alpha compositing.
 pub trait AlphaOver: ColorEncoding {
     fn composite(over: Color<Self>, under: Color<Self>) -> Color<Self>;
@@ -106,7 +265,75 @@ where
 /// gradients) in many situations. However they are certainly not, silver
 /// bullets, and often don't fully deliver on the promise of perceptual
 /// uniformity.
-pub trait PerceptualEncoding: ColorEncoding + WorkingEncoding {}
+///
+/// Perceptual encodings expose the lightness, chroma and hue of their colors,
+/// so that code like theming can be written against this trait instead of a
+/// concrete encoding.
+pub trait PerceptualEncoding: ColorEncoding + WorkingEncoding {
+    /// The perceived lightness of `color`, `0.0` for black and `1.0` for
+    /// white.
+    fn lightness(color: Color<Self>) -> f32;
+
+    /// The chroma of `color`, i.e. its distance from the neutral axis.
+    fn chroma(color: Color<Self>) -> f32;
+
+    /// The hue angle of `color` in degrees in `0.0..360.0`, or `0.0` if it is
+    /// neutral.
+    fn hue(color: Color<Self>) -> f32;
+
+    /// `color` with its lightness set to `lightness`.
+    fn with_lightness(color: Color<Self>, lightness: f32) -> Color<Self>;
+
+    /// `color` with its chroma set to `chroma`, keeping its hue.
+    fn with_chroma(color: Color<Self>, chroma: f32) -> Color<Self>;
+
+    /// `color` with its hue set to `degrees`, keeping its chroma.
+    fn with_hue(color: Color<Self>, degrees: f32) -> Color<Self>;
+}
+
+/// Implemented by color encodings with a hue angle component in degrees, like
+/// [`Oklch`](crate::details::encodings::Oklch).
+///
+/// Hues wrap around, so colors in these encodings are interpolated with a
+/// [`HueInterpolation`] instead of linearly.
+pub trait PolarEncoding: ColorEncoding<Repr = Vec3> {
+    /// The index of the hue component.
+    const HUE: usize;
+}
+
+/// Which way around the hue circle to interpolate between two hues, as in CSS.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HueInterpolation {
+    /// Take the shorter arc, e.g. from `350°` to `10°` through `0°`.
+    #[default]
+    Shorter,
+    /// Take the longer arc.
+    Longer,
+    /// Go around with increasing hue.
+    Increasing,
+    /// Go around with decreasing hue.
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Interpolate from hue `from` to hue `to`, in degrees, by `factor`.
+    ///
+    /// The result is in `0.0..360.0`.
+    pub fn lerp(self, from: f32, to: f32, factor: f32) -> f32 {
+        let (from, to) = (wrap_degrees(from), wrap_degrees(to));
+        let delta = to - from;
+        let (from, to) = match self {
+            HueInterpolation::Shorter if delta > 180.0 => (from + 360.0, to),
+            HueInterpolation::Shorter if delta < -180.0 => (from, to + 360.0),
+            HueInterpolation::Longer if 0.0 < delta && delta < 180.0 => (from + 360.0, to),
+            HueInterpolation::Longer if -180.0 < delta && delta <= 0.0 => (from, to + 360.0),
+            HueInterpolation::Increasing if to < from => (from, to + 360.0),
+            HueInterpolation::Decreasing if from < to => (from + 360.0, to),
+            _ => (from, to),
+        };
+        wrap_degrees(from + (to - from) * factor)
+    }
+}
 
 /// Marks a type as representing a color encoding in which it makes sense to be
 /// able to perform mathematical operations on the contained color values
@@ -135,6 +362,17 @@ pub trait LinearColorSpace {
     fn white_point() -> WhitePoint {
         Self::WHITE_POINT
     }
+
+    /// Whether the gamut of this linear color space contains the gamut of
+    /// `Other`.
+    ///
+    /// See [`CustomColorSpace::gamut_contains`].
+    fn gamut_contains<Other: LinearColorSpace>() -> bool
+    where
+        Self: Sized,
+    {
+        CustomColorSpace::of::<Self>().gamut_contains(&CustomColorSpace::of::<Other>())
+    }
 }
 
 /// A trait that marks `Self` as being a color encoding which is able to be
@@ -176,3 +414,54 @@ where
 pub trait LinearConvertFromRaw<SrcSpace: LinearColorSpace>: LinearColorSpace {
     fn linear_part_raw(raw: &mut Vec3);
 }
+
+/// Describes the `N` bands of a multispectral
+/// [`Multispectral`][crate::details::encodings::Multispectral] encoding by
+/// their responses in CIE XYZ.
+///
+/// ## Example
+///
+/// ```
+/// # use colstodian::*;
+/// # use colstodian::details::encodings::Multispectral;
+/// # use colstodian::details::traits::SpectralBands;
+/// # use glam::Vec3;
+/// /// A camera with four bands, splitting green into two.
+/// struct FourBand;
+///
+/// impl SpectralBands<4> for FourBand {
+///     const NAME: &'static str = "FourBand";
+///     const TO_XYZ: [Vec3; 4] = [
+///         Vec3::new(0.4124, 0.2126, 0.0193),
+///         Vec3::new(0.1788, 0.3576, 0.0596),
+///         Vec3::new(0.1788, 0.3576, 0.0596),
+///         Vec3::new(0.1805, 0.0722, 0.9505),
+///     ];
+/// }
+///
+/// let white = Color::<Multispectral<FourBand, 4>>::multispectral([1.0; 4]);
+/// let srgb = white.convert::<basic_encodings::SrgbU8>();
+/// assert_eq!(srgb, Color::srgb_u8(255, 255, 255));
+/// ```
+pub trait SpectralBands<const N: usize>: 'static {
+    /// Used in `Debug` and `Display` implementations.
+    const NAME: &'static str;
+
+    /// The CIE XYZ of each band at a value of `1.0`, so that the XYZ of a
+    /// color is the sum of its bands weighted by these.
+    const TO_XYZ: [Vec3; N];
+
+    /// The bands of the CIE XYZ color `xyz`.
+    ///
+    /// With more than three bands, many colors of bands have the same XYZ.
+    /// The default is the one with the smallest sum of squared bands, which
+    /// may have negative bands; implement this to choose realistic spectra
+    /// instead.
+    fn from_xyz(xyz: Vec3) -> [f32; N] {
+        let gram = Self::TO_XYZ.iter().fold(glam::Mat3::ZERO, |gram, &band| {
+            gram + glam::Mat3::from_cols(band * band.x, band * band.y, band * band.z)
+        });
+        let weights = gram.inverse() * xyz;
+        Self::TO_XYZ.map(|band| band.dot(weights))
+    }
+}
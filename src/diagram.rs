@@ -0,0 +1,93 @@
+//! Data for plotting chromaticity diagrams.
+//!
+//! The outlines drawn in a chromaticity diagram, the spectral locus, the
+//! Planckian locus and the gamut triangles of color spaces, are given as
+//! arrays of coordinates, in either CIE 1931 xy or CIE 1976 u′v′, to pass to
+//! a plotting library as polylines.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::details::linear_spaces::{DisplayP3, Srgb};
+//! use colstodian::diagram::{self, Diagram};
+//!
+//! let locus = diagram::spectral_locus(Diagram::UvPrime);
+//! let planckian: [(f32, f32); 50] = diagram::planckian_locus(Diagram::UvPrime, 1000.0, 15000.0);
+//! let srgb = diagram::gamut_triangle::<Srgb>(Diagram::UvPrime);
+//! let p3 = diagram::gamut_triangle::<DisplayP3>(Diagram::UvPrime);
+//!
+//! // The triangles are closed, and Display P3 reaches further towards green.
+//! assert_eq!(srgb[0], srgb[3]);
+//! assert!(p3[1].0 < srgb[1].0);
+//! ```
+
+use crate::chromaticity::{planckian_uv, uv_prime_to_xy, xy_to_uv_prime};
+use crate::custom::CustomColorSpace;
+use crate::details::traits::LinearColorSpace;
+use crate::spectral::{self, SAMPLES};
+
+/// The coordinates of a chromaticity diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diagram {
+    /// The CIE 1931 xy chromaticity diagram.
+    Xy,
+    /// The CIE 1976 u′v′ chromaticity diagram, which is more perceptually
+    /// uniform.
+    UvPrime,
+}
+
+impl Diagram {
+    /// The coordinates of the CIE 1931 xy chromaticity `(x, y)` in this
+    /// diagram.
+    #[inline]
+    pub fn coordinates(self, (x, y): (f32, f32)) -> (f32, f32) {
+        match self {
+            Self::Xy => (x, y),
+            Self::UvPrime => xy_to_uv_prime(x, y),
+        }
+    }
+}
+
+/// The spectral locus, the chromaticities of monochromatic light from 380 nm
+/// to 780 nm in steps of [`WAVELENGTH_STEP`](spectral::WAVELENGTH_STEP).
+///
+/// Closing the polyline draws the line of purples between its ends.
+pub fn spectral_locus(diagram: Diagram) -> [(f32, f32); SAMPLES] {
+    core::array::from_fn(|index| {
+        let xyz = spectral::observer(index);
+        let sum = xyz.element_sum();
+        diagram.coordinates((xyz.x / sum, xyz.y / sum))
+    })
+}
+
+/// The Planckian locus from `from` to `to` kelvin in `N` points, spaced evenly
+/// in mireds.
+///
+/// The locus is approximated from 1000 K to 15000 K, as with
+/// [`planckian_uv`].
+pub fn planckian_locus<const N: usize>(diagram: Diagram, from: f32, to: f32) -> [(f32, f32); N] {
+    let (from, to) = (1e6 / from, 1e6 / to);
+    core::array::from_fn(|index| {
+        let t = index as f32 / N.saturating_sub(1).max(1) as f32;
+        let (u, v) = planckian_uv(1e6 / (from + (to - from) * t));
+        match diagram {
+            Diagram::Xy => uv_prime_to_xy(u, 1.5 * v),
+            Diagram::UvPrime => (u, 1.5 * v),
+        }
+    })
+}
+
+/// The triangle spanned by the red, green and blue primaries of `L`, closed
+/// by repeating red at the end.
+pub fn gamut_triangle<L: LinearColorSpace>(diagram: Diagram) -> [(f32, f32); 4] {
+    let [red, green, blue] = CustomColorSpace::of::<L>()
+        .primaries_xy()
+        .map(|[x, y]| diagram.coordinates((x, y)));
+    [red, green, blue, red]
+}
+
+/// The white point of `L`.
+pub fn white_point<L: LinearColorSpace>(diagram: Diagram) -> (f32, f32) {
+    let [x, y] = CustomColorSpace::of::<L>().white_point_xy();
+    diagram.coordinates((x, y))
+}
@@ -0,0 +1,231 @@
+//! Display profiles applied as the last step of conversion to a display.
+//!
+//! Standard display encodings like [`EncodedSrgbU8`] describe an ideal
+//! monitor. A [`DisplayProfile`] describes an actual one, as the matrix from
+//! its linear components to CIE XYZ and the tone curve of each channel,
+//! either parsed from the matrix/TRC form of an ICC profile or built from
+//! measurements. [`DisplayProfile::correct`] turns colors meant for the
+//! ideal monitor into the values that make the actual one show them.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::calibration::ColorMatrix;
+//! use colstodian::details::linear_spaces::{CieXYZ, Srgb};
+//! use colstodian::display::{DisplayProfile, ToneCurve};
+//! use colstodian::transfer::TransferParameters;
+//!
+//! // A display with sRGB primaries but a pure gamma of 2.2.
+//! let display = DisplayProfile::new(
+//!     ColorMatrix::conversion::<Srgb, CieXYZ>().matrix,
+//!     [0, 1, 2].map(|_| ToneCurve::Parametric(TransferParameters::gamma(2.2))),
+//! )
+//! .unwrap();
+//!
+//! // Dark tones are sent a little lighter to make up for the darker curve.
+//! let corrected = display.correct(Color::srgb_u8(40, 40, 40));
+//! assert_eq!(corrected, Color::srgb_u8(44, 44, 44));
+//! assert_eq!(display.correct(Color::srgb_u8(255, 0, 0)), Color::srgb_u8(255, 0, 0));
+//! ```
+//!
+//! [`EncodedSrgbU8`]: crate::details::encodings::EncodedSrgbU8
+
+use crate::calibration::ColorMatrix;
+use crate::custom::{inverse_lookup, lookup};
+use crate::details::encodings::CieXyz;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::icc::read::{self, Curve};
+use crate::transfer::TransferParameters;
+use crate::white::{D50, D65};
+use crate::{Color, ColorEncoding};
+
+use glam::{Mat3, Vec3};
+
+/// The tone curve of a display channel, from encoded values in `0.0..=1.0` to
+/// linear values relative to white.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToneCurve {
+    /// A parametric curve.
+    Parametric(TransferParameters),
+    /// A table of linear values at evenly spaced encoded values, interpolated
+    /// linearly. It should not decrease anywhere.
+    Table(Vec<f32>),
+}
+
+impl ToneCurve {
+    /// Decode an encoded value into a linear value.
+    pub fn decode(&self, encoded: f32) -> f32 {
+        match self {
+            ToneCurve::Parametric(parameters) => parameters.decode(Vec3::splat(encoded)).x,
+            ToneCurve::Table(table) => lookup(table, encoded),
+        }
+    }
+
+    /// Encode a linear value, the inverse of [`ToneCurve::decode`], clamped to
+    /// `0.0..=1.0`.
+    pub fn encode(&self, linear: f32) -> f32 {
+        match self {
+            ToneCurve::Parametric(parameters) => parameters.encode(Vec3::splat(linear)).x,
+            ToneCurve::Table(table) => inverse_lookup(table, linear),
+        }
+    }
+
+    /// Whether this curve can be evaluated, i.e. a table has two entries or
+    /// more.
+    fn is_valid(&self) -> bool {
+        match self {
+            ToneCurve::Parametric(_) => true,
+            ToneCurve::Table(table) => table.len() >= 2,
+        }
+    }
+}
+
+/// The characterization of a display, as a matrix from its linear components
+/// to CIE XYZ and the tone curves of its channels.
+///
+/// Colors are mapped relative to the white of the display, which shows the
+/// D65 white of the standard encodings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayProfile {
+    to_xyz: Mat3,
+    from_xyz: Mat3,
+    curves: [ToneCurve; 3],
+}
+
+impl DisplayProfile {
+    /// A profile from the matrix converting the linear components of the
+    /// display to CIE XYZ relative to D65, and the curves of the red, green
+    /// and blue channels.
+    ///
+    /// Returns `None` if the matrix is singular or a table has fewer than two
+    /// entries.
+    pub fn new(to_xyz: Mat3, curves: [ToneCurve; 3]) -> Option<Self> {
+        let determinant = to_xyz.determinant();
+        (determinant != 0.0 && determinant.is_finite() && curves.iter().all(ToneCurve::is_valid))
+            .then(|| Self {
+                to_xyz,
+                from_xyz: to_xyz.inverse(),
+                curves,
+            })
+    }
+
+    /// A profile from the measured CIE XYZ of the full red, green and blue of
+    /// the display, in any unit, and the curves of the channels.
+    ///
+    /// The white of the display, the sum of the primaries, is adapted to D65
+    /// with the Bradford transform and scaled to a luminance of `1.0`.
+    pub fn from_measurements(primaries: [Vec3; 3], curves: [ToneCurve; 3]) -> Option<Self> {
+        let [red, green, blue] = primaries;
+        let white = red + green + blue;
+        let adaptation = ColorMatrix::bradford(white, D65 * white.y).matrix;
+        Self::new(
+            adaptation * Mat3::from_cols(red, green, blue) / white.y,
+            curves,
+        )
+    }
+
+    /// A profile from the bytes of an ICC display profile in matrix/TRC form,
+    /// with the `rXYZ`, `gXYZ`, `bXYZ`, `rTRC`, `gTRC` and `bTRC` tags.
+    ///
+    /// Returns `None` for other profiles, like ones made of lookup tables, and
+    /// for malformed data.
+    pub fn from_icc(bytes: &[u8]) -> Option<Self> {
+        (bytes.get(16..20)? == b"RGB " && bytes.get(20..24)? == b"XYZ ").then_some(())?;
+        let tags = read::tags(bytes)?;
+        let tag = |signature: &[u8; 4]| {
+            tags.iter()
+                .find_map(|(found, data)| (found == signature).then_some(*data)?)
+        };
+
+        let [red, green, blue] =
+            [b"rXYZ", b"gXYZ", b"bXYZ"].map(|signature| read::xyz(tag(signature)?));
+        // The colorants are adapted to the D50 white of the connection space.
+        let adaptation = ColorMatrix::bradford(D50, D65).matrix;
+        let to_xyz = adaptation * Mat3::from_cols(red?, green?, blue?);
+
+        let [red, green, blue] = [b"rTRC", b"gTRC", b"bTRC"].map(|signature| {
+            let (curve, _) = read::curve(tag(signature)?, 0)?;
+            Some(tone_curve(curve))
+        });
+        Self::new(to_xyz, [red?, green?, blue?])
+    }
+
+    /// The matrix converting the linear components of the display to CIE XYZ.
+    pub fn matrix(&self) -> Mat3 {
+        self.to_xyz
+    }
+
+    /// The tone curves of the red, green and blue channels.
+    pub fn curves(&self) -> &[ToneCurve; 3] {
+        &self.curves
+    }
+
+    /// The values to send to the display to show `color`, clipped to
+    /// `0.0..=1.0`.
+    pub fn device_values<E>(&self, color: Color<E>) -> Vec3
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (mut raw, _) = E::src_transform_raw(color.repr);
+        <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+        self.encode(raw)
+    }
+
+    /// The color the display shows for the values `device`.
+    pub fn displayed(&self, device: Vec3) -> Color<CieXyz> {
+        let [r, g, b] = &self.curves;
+        let linear = Vec3::new(r.decode(device.x), g.decode(device.y), b.decode(device.z));
+        Color::from_repr(self.to_xyz * linear)
+    }
+
+    /// Correct `color`, encoded for the ideal display of the encoding `D`, for
+    /// this display, as the last step of a conversion to `D`.
+    ///
+    /// The result holds the values to send to this display in place of the
+    /// components of `D`. Alpha is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `D` has no [`ColorEncoding::TRANSFER`], as then its components
+    /// aren't display values.
+    pub fn correct<D>(&self, color: Color<D>) -> Color<D>
+    where
+        D: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<D::LinearSpace>,
+    {
+        let transfer = D::TRANSFER.expect("display encodings have a transfer function");
+        let (mut raw, alpha) = D::src_transform_raw(color.repr);
+        <CieXYZ as LinearConvertFromRaw<D::LinearSpace>>::linear_part_raw(&mut raw);
+        let device = self.encode(raw);
+        // Encoding with `D` undoes this decoding, leaving the device values.
+        Color::from_repr(D::dst_transform_raw(transfer.decode(device), alpha))
+    }
+
+    /// Encode CIE XYZ for the display.
+    fn encode(&self, xyz: Vec3) -> Vec3 {
+        let linear = self.from_xyz * xyz;
+        let [r, g, b] = &self.curves;
+        Vec3::new(r.encode(linear.x), g.encode(linear.y), b.encode(linear.z))
+    }
+}
+
+/// The tone curve of an ICC `curv` or `para` tag.
+fn tone_curve(curve: Curve) -> ToneCurve {
+    match curve {
+        Curve::Identity => ToneCurve::Parametric(TransferParameters::gamma(1.0)),
+        Curve::Gamma(gamma) => ToneCurve::Parametric(TransferParameters::gamma(gamma)),
+        Curve::Table(table) => ToneCurve::Table(table),
+        Curve::Parametric(function, [g, a, b, c, d, e, f]) => {
+            ToneCurve::Parametric(match function {
+                0 => TransferParameters::gamma(g),
+                1 => TransferParameters::new(g, a, b, 0.0, -b / a, 0.0, 0.0),
+                2 => TransferParameters::new(g, a, b, 0.0, -b / a, c, c),
+                3 => TransferParameters::new(g, a, b, c, d, 0.0, 0.0),
+                _ => TransferParameters::new(g, a, b, c, d, e, f),
+            })
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Pairwise color differences of sets of colors.
+//!
+//! Checking that the colors of a palette are distinguishable, or finding
+//! duplicates among them, compares every color with every other. A
+//! [`DistanceMatrix`] holds all of these differences, and [`min_distance`]
+//! finds the closest pair without storing them.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::distance::{self, DeltaE, DistanceMatrix};
+//!
+//! let palette = [
+//!     Color::srgb_u8(230, 25, 75),
+//!     Color::srgb_u8(60, 180, 75),
+//!     Color::srgb_u8(0, 130, 200),
+//!     Color::srgb_u8(232, 28, 75),
+//! ];
+//!
+//! let matrix = DistanceMatrix::new(&palette, DeltaE::Cie2000);
+//! assert_eq!(matrix.get(1, 2), matrix.get(2, 1));
+//!
+//! // The first and last colors are nearly the same.
+//! let (i, j, delta_e) = distance::min_distance(&palette, DeltaE::Cie2000).unwrap();
+//! assert_eq!((i, j), (0, 3));
+//! assert!(delta_e < 1.0);
+//! ```
+
+use crate::colorchecker::{delta_e_2000, lab_d50};
+use crate::details::encodings::Oklab;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// A color difference metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeltaE {
+    /// The Euclidean distance in [`Oklab`], as with [`Color::delta_e_ok`].
+    /// A difference of about `0.02` is just noticeable.
+    #[default]
+    Ok,
+    /// The Euclidean distance in CIELAB under D50, the CIE 1976 ΔE*ab.
+    Cie76,
+    /// The CIEDE2000 color difference, as with [`delta_e_2000`].
+    Cie2000,
+}
+
+impl DeltaE {
+    /// The difference between `a` and `b` by this metric.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use colstodian::*;
+    /// # use colstodian::distance::DeltaE;
+    /// let a = Color::srgb_u8(230, 25, 75);
+    /// let b = Color::srgb_u8(232, 28, 75);
+    /// assert_eq!(DeltaE::Ok.delta_e(a, b), a.delta_e_ok(b));
+    /// assert!(DeltaE::Cie2000.delta_e(a, b) < 1.0);
+    /// ```
+    pub fn delta_e<E>(self, a: Color<E>, b: Color<E>) -> f32
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (a, b) = (self.coordinates(a), self.coordinates(b));
+        match self {
+            Self::Ok | Self::Cie76 => a.distance(b),
+            Self::Cie2000 => delta_e_2000(a, b),
+        }
+    }
+
+    /// The coordinates of `color` that this metric compares.
+    fn coordinates<E>(self, color: Color<E>) -> Vec3
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        match self {
+            Self::Ok => {
+                let (mut raw, alpha) = E::src_transform_raw(color.repr);
+                <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+                Oklab::dst_transform_raw(raw, alpha)
+            }
+            Self::Cie76 | Self::Cie2000 => lab_d50(color),
+        }
+    }
+}
+
+/// The coordinates of colors for a metric, stored by component so that
+/// distances to many colors at once vectorize.
+struct Coordinates {
+    metric: DeltaE,
+    components: [Vec<f32>; 3],
+}
+
+impl Coordinates {
+    fn new<E>(colors: &[Color<E>], metric: DeltaE) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let points = colors.iter().map(|&color| metric.coordinates(color));
+        let mut components = [0, 1, 2].map(|_| Vec::with_capacity(colors.len()));
+        points.for_each(|point| {
+            components
+                .iter_mut()
+                .zip(point.to_array())
+                .for_each(|(component, value)| component.push(value));
+        });
+        Self { metric, components }
+    }
+
+    fn len(&self) -> usize {
+        self.components[0].len()
+    }
+
+    fn point(&self, index: usize) -> Vec3 {
+        Vec3::from_array(self.components.each_ref().map(|component| component[index]))
+    }
+
+    /// Write the distances of the color `index` to the colors from `start`
+    /// on into `out`.
+    fn distances(&self, index: usize, start: usize, out: &mut [f32]) {
+        let [l, a, b] = self
+            .components
+            .each_ref()
+            .map(|component| &component[start..]);
+        match self.metric {
+            DeltaE::Ok | DeltaE::Cie76 => {
+                let point = self.point(index);
+                out.iter_mut()
+                    .zip(l)
+                    .zip(a)
+                    .zip(b)
+                    .for_each(|(((out, l), a), b)| {
+                        let (dl, da, db) = (l - point.x, a - point.y, b - point.z);
+                        *out = (dl * dl + da * da + db * db).sqrt();
+                    });
+            }
+            DeltaE::Cie2000 => {
+                let point = self.point(index);
+                out.iter_mut().enumerate().for_each(|(offset, out)| {
+                    *out = delta_e_2000(point, self.point(start + offset));
+                });
+            }
+        }
+    }
+}
+
+/// The color differences between every pair of a set of colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix {
+    len: usize,
+    distances: Vec<f32>,
+}
+
+impl DistanceMatrix {
+    /// The differences between every pair of `colors` by `metric`.
+    pub fn new<E>(colors: &[Color<E>], metric: DeltaE) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let coordinates = Coordinates::new(colors, metric);
+        let len = coordinates.len();
+        let mut distances = vec![0.0; len * len];
+
+        // Compute the upper triangle row by row, and mirror it.
+        #[cfg(feature = "rayon")]
+        let rows = distances.par_chunks_mut(len.max(1));
+        #[cfg(not(feature = "rayon"))]
+        let rows = distances.chunks_mut(len.max(1));
+        rows.enumerate()
+            .for_each(|(i, row)| coordinates.distances(i, i + 1, &mut row[i + 1..]));
+        (0..len).for_each(|i| {
+            (i + 1..len).for_each(|j| distances[j * len + i] = distances[i * len + j]);
+        });
+        Self { len, distances }
+    }
+
+    /// The number of colors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no colors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The difference between the colors `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> f32 {
+        assert!(i < self.len && j < self.len, "color index out of bounds");
+        self.distances[i * self.len + j]
+    }
+
+    /// The differences of the color `i` to every color.
+    #[inline]
+    pub fn row(&self, i: usize) -> &[f32] {
+        &self.distances[i * self.len..(i + 1) * self.len]
+    }
+
+    /// All differences, row by row.
+    #[inline]
+    pub fn as_slice(&self) -> &[f32] {
+        &self.distances
+    }
+
+    /// The indices `i < j` of the closest pair of colors and their
+    /// difference, or `None` if there are fewer than two colors.
+    pub fn min_pair(&self) -> Option<(usize, usize, f32)> {
+        (0..self.len)
+            .flat_map(|i| (i + 1..self.len).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, self.distances[i * self.len + j]))
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+}
+
+/// The indices `i < j` of the closest pair of `colors` by `metric` and their
+/// difference, or `None` if there are fewer than two colors.
+///
+/// This is [`DistanceMatrix::min_pair`] without storing the matrix.
+pub fn min_distance<E>(colors: &[Color<E>], metric: DeltaE) -> Option<(usize, usize, f32)>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let coordinates = Coordinates::new(colors, metric);
+    let len = coordinates.len();
+    // The closest color after the color `i`, using `row` as scratch space.
+    let closest_after = |row: &mut Vec<f32>, i: usize| {
+        let row = &mut row[i + 1..];
+        coordinates.distances(i, i + 1, row);
+        row.iter()
+            .enumerate()
+            .map(|(offset, &distance)| (i, i + 1 + offset, distance))
+            .min_by(closer)
+    };
+
+    #[cfg(feature = "rayon")]
+    let closest = (0..len)
+        .into_par_iter()
+        .map_init(|| vec![0.0; len], closest_after)
+        .flatten()
+        .min_by(closer);
+    #[cfg(not(feature = "rayon"))]
+    let closest = {
+        let mut row = vec![0.0; len];
+        (0..len)
+            .filter_map(|i| closest_after(&mut row, i))
+            .min_by(closer)
+    };
+    closest
+}
+
+/// Orders pairs by their difference, and equal differences by their indices,
+/// so that the closest pair doesn't depend on the order they are found in.
+fn closer(a: &(usize, usize, f32), b: &(usize, usize, f32)) -> core::cmp::Ordering {
+    a.2.total_cmp(&b.2).then((a.0, a.1).cmp(&(b.0, b.1)))
+}
@@ -0,0 +1,244 @@
+//! Dithering to low-bit grayscale encodings.
+//!
+//! Quantizing an image to the few gray levels of an e-ink or monochrome OLED
+//! display, like [`EncodedGrayU1`], [`EncodedGrayU2`] or [`EncodedGrayU4`],
+//! bands smooth gradients. Dithering trades the banding for a fine pattern
+//! whose average matches the luminance of the image.
+//!
+//! [`Color::dithered`] dithers a single pixel with an ordered pattern, for
+//! drawing pixel by pixel. [`dither`] dithers a whole image with any of the
+//! [`Dither`] methods, using a scratch buffer provided by the caller so that it
+//! works without an allocator. With the `std` feature,
+//! [`ColorBuffer::dithered`](crate::buffer::ColorBuffer::dithered) allocates
+//! it.
+//!
+//! Levels are chosen in linear light, so the average luminance is kept.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::EncodedGrayU1;
+//! use colstodian::dither::{Dither, dither};
+//!
+//! // 10×10 pixels of 18% gray, dithered to black and white.
+//! let src = [Color::linear_srgb(0.18, 0.18, 0.18); 100];
+//! let mut dst = [Color::<EncodedGrayU1>::BLACK; 100];
+//! let mut scratch = [0.0; Dither::FloydSteinberg.scratch_len(10)];
+//! dither(&src, 10, Dither::FloydSteinberg, &mut scratch, &mut dst);
+//!
+//! // About 18 of the 100 pixels are white.
+//! let white = dst.iter().filter(|pixel| pixel.v == 1).count();
+//! assert!((15..=21).contains(&white));
+//! ```
+//!
+//! [`EncodedGrayU1`]: crate::details::encodings::EncodedGrayU1
+//! [`EncodedGrayU2`]: crate::details::encodings::EncodedGrayU2
+//! [`EncodedGrayU4`]: crate::details::encodings::EncodedGrayU4
+
+use crate::bit_depth::max_value;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::quantize::GrayEncoding;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::transfer::{srgb_eotf, srgb_oetf};
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+/// The 8×8 Bayer matrix of ordered dithering.
+const BAYER: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// The neighbors that Floyd–Steinberg diffuses the error to, as column and
+/// row offsets and weights.
+const FLOYD_STEINBERG: [(isize, usize, f32); 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+
+/// The neighbors that Atkinson diffuses the error to.
+const ATKINSON: [(isize, usize, f32); 6] = [
+    (1, 0, 1.0 / 8.0),
+    (2, 0, 1.0 / 8.0),
+    (-1, 1, 1.0 / 8.0),
+    (0, 1, 1.0 / 8.0),
+    (1, 1, 1.0 / 8.0),
+    (0, 2, 1.0 / 8.0),
+];
+
+/// A dithering method.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dither {
+    /// Ordered dithering with an 8×8 Bayer matrix.
+    ///
+    /// Each pixel is dithered on its own, so the pattern is regular and
+    /// stable under changes elsewhere in the image, which suits partial
+    /// refreshes of e-ink displays.
+    #[default]
+    Ordered,
+    /// Floyd–Steinberg error diffusion, which gives the most accurate tones.
+    FloydSteinberg,
+    /// Atkinson error diffusion, as on 1-bit displays of old.
+    ///
+    /// Only three quarters of the error are diffused, which gives crisper
+    /// contrast at the cost of detail in shadows and highlights.
+    Atkinson,
+}
+
+impl Dither {
+    /// The length of the scratch buffer [`dither`] needs for images `width`
+    /// pixels wide.
+    pub const fn scratch_len(self, width: usize) -> usize {
+        match self {
+            Dither::Ordered => 0,
+            Dither::FloydSteinberg => 2 * width,
+            Dither::Atkinson => 3 * width,
+        }
+    }
+
+    /// The error diffusion kernel and the number of rows it spans.
+    fn kernel(self) -> (&'static [(isize, usize, f32)], usize) {
+        match self {
+            Dither::Ordered => (&[], 1),
+            Dither::FloydSteinberg => (&FLOYD_STEINBERG, 2),
+            Dither::Atkinson => (&ATKINSON, 3),
+        }
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// This color, at the pixel `x`, `y` of an image, ordered-dithered to the
+    /// gray encoding `D`.
+    #[inline]
+    pub fn dithered<D: GrayEncoding>(self, x: usize, y: usize) -> Color<D> {
+        let threshold = (BAYER[y % 8][x % 8] as f32 + 0.5) / 64.0;
+        quantize::<D>(luminance(self), threshold).0
+    }
+}
+
+/// Dither the image `src`, stored row by row and `width` pixels wide, to the
+/// gray encoding `D` in `dst`.
+///
+/// `scratch` holds the diffused error and needs at least
+/// [`Dither::scratch_len`] elements.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` differ in length, if that isn't a multiple of
+/// `width` or if `scratch` is too short.
+pub fn dither<S, D>(
+    src: &[Color<S>],
+    width: usize,
+    method: Dither,
+    scratch: &mut [f32],
+    dst: &mut [Color<D>],
+) where
+    S: ColorEncoding,
+    D: GrayEncoding,
+    CieXYZ: LinearConvertFromRaw<S::LinearSpace>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination differ in size"
+    );
+    if !src.is_empty() {
+        assert!(
+            src.len().is_multiple_of(width),
+            "image is not made of rows of `width` pixels"
+        );
+        if method == Dither::Ordered {
+            src.iter()
+                .zip(dst)
+                .enumerate()
+                .for_each(|(index, (src, dst))| *dst = src.dithered(index % width, index / width));
+        } else {
+            diffuse(src, width, method, scratch, dst);
+        }
+    }
+}
+
+/// Spread the error of quantizing each pixel of `src` to its neighbours
+/// with the kernel of `method`.
+fn diffuse<S, D>(
+    src: &[Color<S>],
+    width: usize,
+    method: Dither,
+    scratch: &mut [f32],
+    dst: &mut [Color<D>],
+) where
+    S: ColorEncoding,
+    D: GrayEncoding,
+    CieXYZ: LinearConvertFromRaw<S::LinearSpace>,
+{
+    let len = method.scratch_len(width);
+    assert!(scratch.len() >= len, "scratch buffer is too short");
+    let (kernel, rows) = method.kernel();
+    let errors = &mut scratch[..len];
+    errors.fill(0.0);
+    let row_of = |y: usize| (y % rows) * width;
+
+    src.chunks(width)
+        .zip(dst.chunks_mut(width))
+        .enumerate()
+        .for_each(|(y, (src, dst))| {
+            src.iter().zip(dst).enumerate().for_each(|(x, (src, dst))| {
+                let value = (luminance(*src) + errors[row_of(y) + x]).clamp(0.0, 1.0);
+                let (color, shown) = quantize::<D>(value, 0.5);
+                *dst = color;
+                kernel.iter().for_each(|&(dx, dy, weight)| {
+                    if let Some(nx) = x.checked_add_signed(dx).filter(|&nx| nx < width) {
+                        errors[row_of(y + dy) + nx] += (value - shown) * weight;
+                    }
+                });
+            });
+            // The row becomes the one `rows` below.
+            let start = row_of(y);
+            errors[start..start + width].fill(0.0);
+        });
+}
+
+/// The relative luminance of `color`.
+#[inline]
+fn luminance<E>(color: Color<E>) -> f32
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    raw.y
+}
+
+/// The level of `D` for `luminance`: the level above it if it lies more than
+/// `threshold` of the way there in linear light, the level below otherwise.
+/// Also returns the luminance of the level.
+#[inline]
+fn quantize<D: GrayEncoding>(luminance: f32, threshold: f32) -> (Color<D>, f32) {
+    let max = max_value(D::BITS) as f32;
+    let luminance = luminance.clamp(0.0, 1.0);
+    let below = (srgb_oetf(Vec3::splat(luminance)) * max)
+        .floor()
+        .x
+        .min(max - 1.0);
+    let [low, high] = [below, below + 1.0].map(|level| srgb_eotf(Vec3::splat(level / max)).x);
+    if luminance - low > threshold * (high - low) {
+        (Color::from_repr(below as u8 + 1), high)
+    } else {
+        (Color::from_repr(below as u8), low)
+    }
+}
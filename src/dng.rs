@@ -0,0 +1,184 @@
+//! Camera RAW color transforms from DNG metadata.
+//!
+//! DNG and EXIF metadata describe the color response of a camera with a
+//! `ColorMatrix`, mapping CIE XYZ to camera native values, and optionally a
+//! `ForwardMatrix`, mapping white balanced camera values to CIE XYZ under
+//! D50, plus per-unit `CameraCalibration` and `AnalogBalance` corrections.
+//! [`DngProfile`] holds these and builds the [`CameraTransform`] of a shot
+//! from its camera neutral (`AsShotNeutral`), following chapter 6 of the DNG
+//! specification.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::LinearSrgb;
+//! use colstodian::dng::DngProfile;
+//! use glam::{Mat3, Vec3};
+//!
+//! let profile = DngProfile::new(Mat3::from_cols(
+//!     Vec3::new(1.25, -0.35, 0.05),
+//!     Vec3::new(-0.2, 1.45, -0.15),
+//!     Vec3::new(-0.05, 0.1, 0.95),
+//! ));
+//! let neutral = Vec3::new(0.45, 1.0, 0.7);
+//! let transform = profile.camera_transform(neutral).unwrap();
+//!
+//! // The camera neutral becomes white.
+//! let white = transform.apply(neutral).convert::<LinearSrgb>();
+//! assert!(white.repr.abs_diff_eq(Vec3::ONE, 1e-3));
+//! ```
+
+use crate::Color;
+use crate::calibration::ColorMatrix;
+use crate::details::encodings::CieXyz;
+use crate::white::{D50, D65};
+
+use glam::{Mat3, Vec3};
+
+/// The color matrices of a camera for one calibration illuminant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DngProfile {
+    /// The `ColorMatrix`, from CIE XYZ to camera native values.
+    pub color_matrix: Mat3,
+    /// The `ForwardMatrix`, from white balanced camera values to CIE XYZ
+    /// under D50, if present.
+    pub forward_matrix: Option<Mat3>,
+    /// The `CameraCalibration` of the individual camera.
+    pub camera_calibration: Mat3,
+    /// The `AnalogBalance` applied before digitizing.
+    pub analog_balance: Vec3,
+}
+
+impl DngProfile {
+    /// A profile with only a `ColorMatrix`.
+    #[inline]
+    pub fn new(color_matrix: Mat3) -> Self {
+        Self {
+            color_matrix,
+            forward_matrix: None,
+            camera_calibration: Mat3::IDENTITY,
+            analog_balance: Vec3::ONE,
+        }
+    }
+
+    /// Set the `ForwardMatrix`.
+    #[inline]
+    pub fn with_forward_matrix(self, forward_matrix: Mat3) -> Self {
+        Self {
+            forward_matrix: Some(forward_matrix),
+            ..self
+        }
+    }
+
+    /// Set the `CameraCalibration`.
+    #[inline]
+    pub fn with_camera_calibration(self, camera_calibration: Mat3) -> Self {
+        Self {
+            camera_calibration,
+            ..self
+        }
+    }
+
+    /// Set the `AnalogBalance`.
+    #[inline]
+    pub fn with_analog_balance(self, analog_balance: Vec3) -> Self {
+        Self {
+            analog_balance,
+            ..self
+        }
+    }
+
+    /// The profile for a scene with correlated color temperature `kelvin`,
+    /// interpolated between the profiles of two calibration illuminants.
+    ///
+    /// As the DNG specification requires, the matrices are interpolated
+    /// linearly in inverse temperature and clamped to the range of the two
+    /// illuminants. The forward matrix is only kept if both profiles have
+    /// one.
+    pub fn interpolate(
+        first: &Self,
+        first_kelvin: f32,
+        second: &Self,
+        second_kelvin: f32,
+        kelvin: f32,
+    ) -> Self {
+        let weight = ((1.0 / kelvin - 1.0 / second_kelvin)
+            / (1.0 / first_kelvin - 1.0 / second_kelvin))
+            .clamp(0.0, 1.0);
+        let mix = |a: Mat3, b: Mat3| a * weight + b * (1.0 - weight);
+        Self {
+            color_matrix: mix(first.color_matrix, second.color_matrix),
+            forward_matrix: first
+                .forward_matrix
+                .zip(second.forward_matrix)
+                .map(|(a, b)| mix(a, b)),
+            camera_calibration: mix(first.camera_calibration, second.camera_calibration),
+            analog_balance: first
+                .analog_balance
+                .lerp(second.analog_balance, 1.0 - weight),
+        }
+    }
+
+    /// The CIE XYZ white point, with a luminance of one, of the camera
+    /// native `neutral`, or `None` if the matrices are singular.
+    pub fn white_point(&self, neutral: Vec3) -> Option<Vec3> {
+        let xyz = inverse(self.xyz_to_camera())? * neutral;
+        (xyz.y > 0.0).then(|| xyz / xyz.y)
+    }
+
+    /// The transform from camera native values of a shot with the camera
+    /// native `neutral` to CIE XYZ, or `None` if the matrices are singular.
+    pub fn camera_transform(&self, neutral: Vec3) -> Option<CameraTransform> {
+        let balance = Mat3::from_diagonal(self.analog_balance) * self.camera_calibration;
+        let to_xyz_d50 = match self.forward_matrix {
+            Some(forward_matrix) => {
+                let balance_inverse = inverse(balance)?;
+                let reference_neutral = balance_inverse * neutral;
+                forward_matrix * inverse(Mat3::from_diagonal(reference_neutral))? * balance_inverse
+            }
+            None => {
+                let to_xyz = inverse(self.xyz_to_camera())?;
+                let white = to_xyz * neutral;
+                (white.y > 0.0).then_some(())?;
+                ColorMatrix::bradford(white / white.y, D50).matrix * to_xyz / white.y
+            }
+        };
+        Some(CameraTransform { to_xyz_d50 })
+    }
+
+    /// The combined matrix from CIE XYZ to camera native values.
+    fn xyz_to_camera(&self) -> Mat3 {
+        Mat3::from_diagonal(self.analog_balance) * self.camera_calibration * self.color_matrix
+    }
+}
+
+/// The inverse of `matrix`, or `None` if it is singular.
+fn inverse(matrix: Mat3) -> Option<Mat3> {
+    let determinant = matrix.determinant();
+    (determinant != 0.0 && determinant.is_finite()).then(|| matrix.inverse())
+}
+
+/// The transform from camera native values of one shot to CIE XYZ.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraTransform {
+    to_xyz_d50: Mat3,
+}
+
+impl CameraTransform {
+    /// The matrix from camera native values to CIE XYZ under D50, the DNG
+    /// connection space.
+    #[inline]
+    pub fn to_xyz_d50(&self) -> Mat3 {
+        self.to_xyz_d50
+    }
+
+    /// Develop the camera native value `camera` into a [`Color`], adapting
+    /// the D50 white of the connection space to D65.
+    ///
+    /// Convert the result into the working encoding of the pipeline.
+    #[inline]
+    pub fn apply(&self, camera: Vec3) -> Color<CieXyz> {
+        let adapt = ColorMatrix::bradford(D50, D65).matrix;
+        Color::from_repr(adapt * self.to_xyz_d50 * camera)
+    }
+}
@@ -0,0 +1,191 @@
+//! Fixed-point conversions for microcontrollers without an FPU.
+//!
+//! On Cortex-M0 class hardware, `f32` math is emulated in software and too
+//! slow to convert the colors of LED matrices at frame rate. The functions
+//! here use only integer math, on linear light as unsigned Q0.16, where `0`
+//! is black and `65535` full intensity:
+//!
+//! - [`srgb_decode`] is a table lookup, exact to the nearest step.
+//! - [`srgb_encode`] is a binary search, and gives the same 8-bit values as
+//!   rounding the exact sRGB curve.
+//! - [`FixedMatrix`] applies matrices with Q12 coefficients, accurate to
+//!   within `4e-4` of full intensity, a tenth of an 8-bit step.
+//!
+//! Only the setup of a [`FixedMatrix`] from `f32` values uses floating point.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::linear_spaces::{Bt2020, Srgb};
+//! use colstodian::fixed::{self, FixedMatrix};
+//!
+//! // Once, at startup.
+//! let to_bt2020 = FixedMatrix::conversion::<Srgb, Bt2020>().unwrap();
+//!
+//! // Per pixel, without floating point.
+//! let linear = Color::srgb_u8(255, 128, 0).to_linear_fixed();
+//! assert_eq!(linear, [65535, 14146, 0]);
+//! let wide = to_bt2020.transform(linear);
+//! assert_eq!(fixed::srgb_encode(wide[0]), 218);
+//!
+//! // Half brightness for an LED, whose duty cycle is linear.
+//! let dimmed = FixedMatrix::scale(0.5).unwrap().transform(linear);
+//! assert_eq!(fixed::linear_to_u8(dimmed[0]), 128);
+//! ```
+
+use crate::Color;
+use crate::calibration::ColorMatrix;
+use crate::details::encodings::EncodedSrgbU8;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+
+use glam::{Mat3, Vec3};
+
+/// The linear light of each 8-bit sRGB value, in Q0.16.
+const SRGB_DECODE: [u16; 256] = [
+    0, 20, 40, 60, 80, 99, 119, 139, 159, 179, 199, 219, 241, 264, 288, 313, 340, 367, 396, 427,
+    458, 491, 526, 562, 599, 637, 677, 718, 761, 805, 851, 898, 947, 997, 1048, 1101, 1156, 1212,
+    1270, 1330, 1391, 1453, 1517, 1583, 1651, 1720, 1790, 1863, 1937, 2013, 2090, 2170, 2250, 2333,
+    2418, 2504, 2592, 2681, 2773, 2866, 2961, 3058, 3157, 3258, 3360, 3464, 3570, 3678, 3788, 3900,
+    4014, 4129, 4247, 4366, 4488, 4611, 4736, 4864, 4993, 5124, 5257, 5392, 5530, 5669, 5810, 5953,
+    6099, 6246, 6395, 6547, 6700, 6856, 7014, 7174, 7335, 7500, 7666, 7834, 8004, 8177, 8352, 8528,
+    8708, 8889, 9072, 9258, 9445, 9635, 9828, 10022, 10219, 10417, 10619, 10822, 11028, 11235,
+    11446, 11658, 11873, 12090, 12309, 12530, 12754, 12980, 13209, 13440, 13673, 13909, 14146,
+    14387, 14629, 14874, 15122, 15371, 15623, 15878, 16135, 16394, 16656, 16920, 17187, 17456,
+    17727, 18001, 18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281, 20577, 20876, 21177,
+    21481, 21787, 22096, 22407, 22721, 23038, 23357, 23678, 24002, 24329, 24658, 24990, 25325,
+    25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094, 28452, 28813, 29176, 29542, 29911,
+    30282, 30656, 31033, 31412, 31794, 32179, 32567, 32957, 33350, 33745, 34143, 34544, 34948,
+    35355, 35764, 36176, 36591, 37008, 37429, 37852, 38278, 38706, 39138, 39572, 40009, 40449,
+    40891, 41337, 41785, 42236, 42690, 43147, 43606, 44069, 44534, 45002, 45473, 45947, 46423,
+    46903, 47385, 47871, 48359, 48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369, 52884,
+    53401, 53921, 54445, 54971, 55500, 56032, 56567, 57105, 57646, 58190, 58737, 59287, 59840,
+    60396, 60955, 61517, 62082, 62650, 63221, 63795, 64372, 64952, 65535,
+];
+
+/// The lowest linear light, in Q0.16, that encodes to each 8-bit sRGB value
+/// from `1` on.
+const SRGB_THRESHOLDS: [u16; 255] = [
+    10, 30, 50, 70, 90, 110, 130, 150, 170, 189, 209, 230, 253, 276, 301, 327, 354, 382, 412, 443,
+    475, 509, 544, 580, 618, 657, 698, 740, 783, 828, 875, 923, 972, 1023, 1075, 1129, 1185, 1242,
+    1300, 1360, 1422, 1486, 1551, 1617, 1685, 1755, 1827, 1900, 1975, 2052, 2130, 2210, 2292, 2376,
+    2461, 2548, 2637, 2727, 2820, 2914, 3010, 3108, 3208, 3309, 3412, 3518, 3625, 3734, 3844, 3957,
+    4072, 4188, 4307, 4427, 4550, 4674, 4800, 4928, 5059, 5191, 5325, 5461, 5599, 5740, 5882, 6026,
+    6173, 6321, 6471, 6624, 6778, 6935, 7094, 7255, 7418, 7583, 7750, 7919, 8091, 8265, 8440, 8618,
+    8798, 8981, 9165, 9352, 9541, 9732, 9925, 10121, 10318, 10518, 10720, 10925, 11132, 11341,
+    11552, 11765, 11981, 12199, 12420, 12643, 12868, 13095, 13325, 13557, 13791, 14028, 14267,
+    14508, 14752, 14998, 15247, 15498, 15751, 16007, 16265, 16525, 16788, 17054, 17321, 17592,
+    17864, 18139, 18417, 18697, 18980, 19264, 19552, 19842, 20134, 20429, 20727, 21027, 21329,
+    21634, 21942, 22252, 22564, 22880, 23197, 23518, 23840, 24166, 24494, 24824, 25158, 25493,
+    25832, 26173, 26516, 26862, 27211, 27563, 27917, 28273, 28633, 28995, 29359, 29727, 30097,
+    30469, 30845, 31223, 31603, 31987, 32373, 32762, 33153, 33547, 33944, 34344, 34747, 35152,
+    35560, 35970, 36384, 36800, 37219, 37640, 38065, 38492, 38922, 39355, 39790, 40229, 40670,
+    41114, 41561, 42011, 42463, 42918, 43377, 43838, 44301, 44768, 45238, 45710, 46185, 46663,
+    47144, 47628, 48115, 48605, 49097, 49593, 50091, 50592, 51096, 51604, 52114, 52627, 53142,
+    53661, 54183, 54708, 55235, 55766, 56300, 56836, 57376, 57918, 58464, 59012, 59564, 60118,
+    60675, 61236, 61799, 62366, 62935, 63508, 64083, 64662, 65244,
+];
+
+/// The linear light of the 8-bit sRGB value `encoded`, in Q0.16.
+#[inline]
+pub const fn srgb_decode(encoded: u8) -> u16 {
+    SRGB_DECODE[encoded as usize]
+}
+
+/// The 8-bit sRGB value of the linear light `linear`, in Q0.16.
+#[inline]
+pub fn srgb_encode(linear: u16) -> u8 {
+    SRGB_THRESHOLDS.partition_point(|&threshold| threshold <= linear) as u8
+}
+
+/// The linear light `linear`, in Q0.16, as an 8-bit linear value, like the
+/// duty cycle of an LED.
+#[inline]
+pub const fn linear_to_u8(linear: u16) -> u8 {
+    ((linear as u32 * 255 + 32767) / 65535) as u8
+}
+
+/// A 3×3 matrix with Q12 coefficients, applied to linear light in Q0.16.
+///
+/// The absolute coefficients of each row must sum to less than `8.0`, so
+/// that no intermediate value overflows an `i32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedMatrix {
+    /// The rows of the matrix, in Q12, so that `4096` is `1.0`.
+    pub rows: [[i32; 3]; 3],
+}
+
+impl FixedMatrix {
+    /// The number of fractional bits of the coefficients.
+    pub const FRACTION_BITS: u32 = 12;
+
+    /// The matrix that leaves colors unchanged.
+    pub const IDENTITY: Self = Self::scale_q12(1 << Self::FRACTION_BITS);
+
+    /// A matrix scaling all channels by `factor`, in Q12.
+    #[inline]
+    pub const fn scale_q12(factor: i32) -> Self {
+        Self {
+            rows: [[factor, 0, 0], [0, factor, 0], [0, 0, factor]],
+        }
+    }
+
+    /// A matrix scaling all channels by `factor`, like a global brightness,
+    /// or `None` if `factor` is `8.0` or more in magnitude.
+    #[inline]
+    pub fn scale(factor: f32) -> Option<Self> {
+        Self::from_mat3(Mat3::from_diagonal(Vec3::splat(factor)))
+    }
+
+    /// The fixed-point version of `matrix`, or `None` if the absolute
+    /// coefficients of a row sum to `8.0` or more.
+    pub fn from_mat3(matrix: Mat3) -> Option<Self> {
+        let scale = (1 << Self::FRACTION_BITS) as f32;
+        let rows = [0, 1, 2].map(|row| {
+            matrix
+                .row(row)
+                .to_array()
+                .map(|coefficient| (coefficient * scale + 0.5f32.copysign(coefficient)) as i32)
+        });
+        rows.iter()
+            .all(|row| row.iter().map(|c| c.unsigned_abs()).sum::<u32>() < 8 << Self::FRACTION_BITS)
+            .then_some(Self { rows })
+    }
+
+    /// The conversion of linear light from the color space `Src` to `Dst`,
+    /// or `None` if its coefficients are too large.
+    pub fn conversion<Src, Dst>() -> Option<Self>
+    where
+        Src: LinearColorSpace,
+        Dst: LinearConvertFromRaw<Src>,
+    {
+        Self::from_mat3(ColorMatrix::conversion::<Src, Dst>().matrix)
+    }
+
+    /// Apply the matrix to the linear light `rgb`, in Q0.16, rounding and
+    /// clamping the results to `0..=65535`.
+    #[inline]
+    pub fn transform(self, rgb: [u16; 3]) -> [u16; 3] {
+        let [r, g, b] = rgb.map(i32::from);
+        self.rows.map(|[x, y, z]| {
+            let sum = x * r + y * g + z * b + (1 << (Self::FRACTION_BITS - 1));
+            (sum >> Self::FRACTION_BITS).clamp(0, u16::MAX as i32) as u16
+        })
+    }
+}
+
+impl Color<EncodedSrgbU8> {
+    /// The linear light of the components, in Q0.16, as with
+    /// [`srgb_decode`].
+    #[inline]
+    pub fn to_linear_fixed(self) -> [u16; 3] {
+        self.repr.map(srgb_decode)
+    }
+
+    /// The color with the linear light `rgb`, in Q0.16, as with
+    /// [`srgb_encode`].
+    #[inline]
+    pub fn from_linear_fixed(rgb: [u16; 3]) -> Self {
+        Self::from_repr(rgb.map(srgb_encode))
+    }
+}
@@ -0,0 +1,188 @@
+//! Gamut size and coverage.
+//!
+//! Gamuts are compared either by the triangle their primaries span in the CIE
+//! xy chromaticity diagram, as display specifications do, or by the volume of
+//! their RGB cube in [`Oklab`](crate::details::encodings::Oklab), which also
+//! accounts for lightness.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::custom::CustomColorSpace;
+//! use colstodian::details::linear_spaces::{DisplayP3, Srgb};
+//! use colstodian::gamut;
+//!
+//! let srgb = CustomColorSpace::of::<Srgb>();
+//! let p3 = CustomColorSpace::of::<DisplayP3>();
+//!
+//! // Display P3 covers all of sRGB.
+//! assert!(gamut::xy_coverage(&p3, &srgb) > 0.999);
+//! // sRGB covers about three quarters of Display P3.
+//! assert!((gamut::xy_coverage(&srgb, &p3) - 0.74).abs() < 0.02);
+//! ```
+//!
+//! Whether one gamut contains another, or a single color, can be checked at
+//! compile time with [`LinearColorSpace::gamut_contains`] and
+//! [`Color::fits_in`], or at runtime with
+//! [`CustomColorSpace::gamut_contains`] and
+//! [`DynamicColor::fits_in`](crate::custom::DynamicColor::fits_in).
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::details::linear_spaces::{Bt2020, Srgb};
+//! use colstodian::details::traits::LinearColorSpace;
+//!
+//! assert!(Bt2020::gamut_contains::<Srgb>());
+//! assert!(!Srgb::gamut_contains::<Bt2020>());
+//!
+//! let saturated_green = Color::bt2020(0.0, 1.0, 0.0);
+//! assert!(!saturated_green.fits_in::<SrgbU8>());
+//! ```
+
+use crate::custom::{CustomColorSpace, RgbPrimaries, WhitePoint, in_unit_cube};
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+#[cfg(doc)]
+use crate::details::traits::LinearColorSpace;
+
+use glam::{Vec2, Vec3};
+use kolor::details::conversion::LinearColorConversion;
+use kolor::details::transform;
+
+/// The area of the triangle spanned by the primaries of `space` in the CIE xy
+/// chromaticity diagram.
+pub fn xy_area(space: &CustomColorSpace) -> f32 {
+    signed_area(&space.primaries_xy().map(Vec2::from)).abs()
+}
+
+/// The fraction of the xy triangle of `reference` that `space` covers, from
+/// `0.0` to `1.0`.
+pub fn xy_coverage(space: &CustomColorSpace, reference: &CustomColorSpace) -> f32 {
+    let subject = space.primaries_xy().map(Vec2::from);
+    let mut clip = reference.primaries_xy().map(Vec2::from);
+    let reference_area = signed_area(&clip);
+    if reference_area < 0.0 {
+        clip.reverse();
+    }
+
+    // Sutherland–Hodgman clipping of one triangle by another yields at most
+    // six vertices.
+    let mut polygon = Polygon::default();
+    subject.into_iter().for_each(|v| polygon.push(v));
+
+    (0..3).for_each(|i| {
+        let (a, b) = (clip[i], clip[(i + 1) % 3]);
+        let inside = |p: Vec2| (b - a).perp_dot(p - a) >= 0.0;
+        let input = polygon;
+        polygon = Polygon::default();
+        (0..input.len).for_each(|j| {
+            let current = input.vertices[j];
+            let previous = input.vertices[(j + input.len - 1) % input.len];
+            let intersection = || {
+                let d = current - previous;
+                let t = (b - a).perp_dot(a - previous) / (b - a).perp_dot(d);
+                previous + d * t
+            };
+            match (inside(previous), inside(current)) {
+                (true, true) => polygon.push(current),
+                (true, false) => polygon.push(intersection()),
+                (false, true) => {
+                    polygon.push(intersection());
+                    polygon.push(current);
+                }
+                (false, false) => {}
+            }
+        });
+    });
+
+    signed_area(&polygon.vertices[..polygon.len]).abs() / reference_area.abs()
+}
+
+/// The volume of the RGB cube of `space` in Oklab.
+///
+/// Each face of the cube is tessellated into a grid of triangles. This is
+/// accurate to a fraction of a percent for display gamuts, and to a few
+/// percent for gamuts with imaginary primaries like ACES AP0. For reference,
+/// sRGB has a volume of about `0.054`.
+pub fn oklab_volume(space: &CustomColorSpace) -> f32 {
+    const N: usize = 32;
+
+    let to_xyz = LinearColorConversion::new(
+        kolor::ColorSpace::new(space.primaries, space.white_point, None),
+        kolor::ColorSpace::new(RgbPrimaries::CieXyz, WhitePoint::D65, None),
+    );
+    let to_oklab = |rgb: Vec3| transform::xyz_to_ok_lab(to_xyz.convert(rgb), WhitePoint::D65);
+
+    // Sum the signed volumes of the tetrahedra between the origin and each
+    // boundary triangle.
+    let volume: f32 = (0..6)
+        .map(|face| {
+            let (axis, side) = (face / 2, (face % 2) as f32);
+            let point = |u: usize, v: usize| {
+                let mut rgb = Vec3::ZERO;
+                rgb[axis] = side;
+                rgb[(axis + 1) % 3] = u as f32 / N as f32;
+                rgb[(axis + 2) % 3] = v as f32 / N as f32;
+                to_oklab(rgb)
+            };
+            let face_volume: f32 = (0..N * N)
+                .map(|i| {
+                    let (u, v) = (i % N, i / N);
+                    let [p00, p10, p11, p01] =
+                        [(u, v), (u + 1, v), (u + 1, v + 1), (u, v + 1)].map(|(u, v)| point(u, v));
+                    p00.dot(p10.cross(p11)) + p00.dot(p11.cross(p01))
+                })
+                .sum();
+            if side == 0.0 {
+                -face_volume
+            } else {
+                face_volume
+            }
+        })
+        .sum();
+
+    volume.abs() / 6.0
+}
+
+impl<E: ColorEncoding> Color<E> {
+    /// Whether this color lies within the gamut of `Dst`, i.e. whether its
+    /// linear components in `Dst`'s linear color space are in `0.0..=1.0`.
+    ///
+    /// Use this to find out whether converting to an encoding with a bounded
+    /// range, like [`EncodedSrgbU8`](crate::details::encodings::EncodedSrgbU8),
+    /// clips this color.
+    pub fn fits_in<Dst>(self) -> bool
+    where
+        Dst: ColorEncoding,
+        Dst::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (mut raw, _) = E::src_transform_raw(self.repr);
+        <Dst::LinearSpace as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+        in_unit_cube(raw)
+    }
+}
+
+/// A convex polygon with up to nine vertices.
+#[derive(Clone, Copy, Default)]
+struct Polygon {
+    vertices: [Vec2; 9],
+    len: usize,
+}
+
+impl Polygon {
+    fn push(&mut self, vertex: Vec2) {
+        self.vertices[self.len] = vertex;
+        self.len += 1;
+    }
+}
+
+/// The signed area of a polygon, positive for counter-clockwise winding.
+fn signed_area(vertices: &[Vec2]) -> f32 {
+    let n = vertices.len();
+    (0..n)
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % n]))
+        .sum::<f32>()
+        / 2.0
+}
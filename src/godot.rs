@@ -0,0 +1,88 @@
+//! Conversions to and from the `Color` type of the
+//! [`godot`](https://docs.rs/godot) crate (gdext).
+//!
+//! Godot colors, as set in the editor, in `Color` constants and in most of
+//! the engine API, hold *encoded* sRGB components; the renderer decodes them to
+//! linear itself. The [`From`] conversions follow this convention and go
+//! through [`EncodedSrgbaF32`], so a color keeps its appearance. Converting
+//! linear components as if they were encoded makes colors look washed out.
+//!
+//! For the places that take linear components instead, like shader uniforms
+//! without a `source_color` hint, use [`Color::to_godot_linear`] and
+//! [`Color::from_godot_linear`].
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::LinearSrgba;
+//!
+//! let gray = Color::linear_srgba(0.2, 0.2, 0.2, 1.0);
+//!
+//! // Encoded, as Godot expects.
+//! let godot: godot::builtin::Color = gray.into();
+//! assert!((godot.r - 0.4845).abs() < 1e-4);
+//!
+//! let back: Color<LinearSrgba> = godot.into();
+//! assert!((back.r - 0.2).abs() < 1e-5);
+//! ```
+
+use crate::details::encodings::{EncodedSrgbaF32, Srgba};
+use crate::details::linear_spaces::Srgb as SrgbLinearSpace;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use ::godot::builtin::Color as GodotColor;
+use glam::Vec4;
+
+impl<E> From<Color<E>> for GodotColor
+where
+    E: ColorEncoding,
+    EncodedSrgbaF32: ConvertFrom<E>,
+    SrgbLinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// Encoded sRGB components and alpha, as Godot expects.
+    fn from(color: Color<E>) -> Self {
+        let [r, g, b, a] = color.convert::<EncodedSrgbaF32>().repr.to_array();
+        GodotColor::from_rgba(r, g, b, a)
+    }
+}
+
+impl<E> From<GodotColor> for Color<E>
+where
+    E: ConvertFrom<EncodedSrgbaF32>,
+    E::LinearSpace: LinearConvertFromRaw<SrgbLinearSpace>,
+{
+    /// Interpret the components as encoded sRGB, as Godot does.
+    fn from(color: GodotColor) -> Self {
+        Color::<EncodedSrgbaF32>::from_repr(Vec4::new(color.r, color.g, color.b, color.a)).convert()
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Srgba: ConvertFrom<E>,
+    SrgbLinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// A Godot color holding *linear* sRGB components and alpha, for the
+    /// places that expect linear values.
+    ///
+    /// Use [`From`] for everything else.
+    pub fn to_godot_linear(self) -> GodotColor {
+        let [r, g, b, a] = self.convert::<Srgba>().repr.to_array();
+        GodotColor::from_rgba(r, g, b, a)
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ConvertFrom<Srgba>,
+    E::LinearSpace: LinearConvertFromRaw<SrgbLinearSpace>,
+{
+    /// Interpret the components of a Godot color as *linear* sRGB, the
+    /// inverse of [`Color::to_godot_linear`].
+    pub fn from_godot_linear(color: GodotColor) -> Self {
+        Color::<Srgba>::from_repr(Vec4::new(color.r, color.g, color.b, color.a)).convert()
+    }
+}
@@ -0,0 +1,343 @@
+//! Running conversion pipelines on the GPU with `wgpu`.
+//!
+//! A [`GpuPipeline`] compiles a [`Pipeline`] to a compute shader with
+//! [`codegen::compute_shader`] and runs it over textures, for video frames
+//! and other images too large to convert in time on the CPU. Pipelines that
+//! can't run in a shader, because they have function steps or encodings
+//! without RGB transfer functions, run on the CPU instead, as do shaders the
+//! device fails to compile and buffers the GPU can't take.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::LinearSrgba;
+//! use colstodian::buffer::ColorBuffer;
+//! use colstodian::details::encodings::EncodedSrgbaF32;
+//! use colstodian::details::linear_spaces::Srgb;
+//! use colstodian::gpu::GpuPipeline;
+//! use colstodian::pipeline::Pipeline;
+//!
+//! /// `frame` brightened by a stop and encoded to sRGB, on the GPU.
+//! fn brighten(
+//!     device: &wgpu::Device,
+//!     queue: &wgpu::Queue,
+//!     frame: &ColorBuffer<LinearSrgba>,
+//! ) -> ColorBuffer<EncodedSrgbaF32> {
+//!     let pipeline = Pipeline::<LinearSrgba, EncodedSrgbaF32, Srgb>::new().exposure(1.0);
+//!     let gpu = GpuPipeline::new(device, pipeline, wgpu::TextureFormat::Rgba32Float);
+//!     assert!(gpu.runs_on_gpu());
+//!     gpu.convert_buffer(device, queue, frame)
+//! }
+//! ```
+
+use crate::buffer::ColorBuffer;
+use crate::codegen;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::pipeline::Pipeline;
+use crate::{ColorEncoding, EncodingInfo};
+
+use core::fmt;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::mpsc;
+
+/// The compiled compute shader of a [`GpuPipeline`].
+struct Compute {
+    pipeline: wgpu::ComputePipeline,
+    layout: wgpu::BindGroupLayout,
+    format: wgpu::TextureFormat,
+}
+
+/// A [`Pipeline`] compiled to a compute shader for a `wgpu` device, or kept
+/// on the CPU if it can't be.
+pub struct GpuPipeline<Src, Dst, L> {
+    pipeline: Pipeline<Src, Dst, L>,
+    compute: Option<Compute>,
+}
+
+impl<Src, Dst, L> GpuPipeline<Src, Dst, L>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    L: LinearColorSpace + LinearConvertFromRaw<Src::LinearSpace>,
+    Dst::LinearSpace: LinearConvertFromRaw<L>,
+{
+    /// Compile `pipeline` for `device`, writing textures of the format
+    /// `output_format`.
+    ///
+    /// The pipeline runs on the CPU if it can't be a compute shader, if
+    /// `output_format` is not `Rgba8Unorm`, `Rgba16Float` or `Rgba32Float`,
+    /// or if `device` reports an error compiling the shader.
+    pub fn new(
+        device: &wgpu::Device,
+        pipeline: Pipeline<Src, Dst, L>,
+        output_format: wgpu::TextureFormat,
+    ) -> Self {
+        let format_name = match output_format {
+            wgpu::TextureFormat::Rgba8Unorm => Some("rgba8unorm"),
+            wgpu::TextureFormat::Rgba16Float => Some("rgba16float"),
+            wgpu::TextureFormat::Rgba32Float => Some("rgba32float"),
+            _ => None,
+        };
+        let compute = format_name
+            .and_then(|name| codegen::compute_shader(&pipeline, name))
+            .and_then(|shader| compile(device, &shader, output_format));
+        Self { pipeline, compute }
+    }
+
+    /// The pipeline run on the CPU.
+    #[inline]
+    pub fn pipeline(&self) -> &Pipeline<Src, Dst, L> {
+        &self.pipeline
+    }
+
+    /// Whether the pipeline was compiled to a compute shader.
+    #[inline]
+    pub fn runs_on_gpu(&self) -> bool {
+        self.compute.is_some()
+    }
+
+    /// Record the conversion of the `width` by `height` texture `input` to
+    /// the storage texture `output` in `encoder`.
+    ///
+    /// `input` is read with `textureLoad`, so integer encodings need a
+    /// normalized format like `Rgba8Unorm`, and `output` has the format the
+    /// pipeline was compiled for. Returns `false`, recording nothing, if the
+    /// pipeline runs on the CPU.
+    pub fn encode(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+        width: u32,
+        height: u32,
+    ) -> bool {
+        self.compute
+            .as_ref()
+            .map(|compute| {
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("colstodian pipeline"),
+                    layout: &compute.layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(input),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(output),
+                        },
+                    ],
+                });
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("colstodian pipeline"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&compute.pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(width.div_ceil(8), height.div_ceil(8), 1);
+            })
+            .is_some()
+    }
+
+    /// Run the pipeline on every pixel of `buffer`, on the GPU if both
+    /// encodings are RGBA with `f32` components and the pipeline was compiled
+    /// for `Rgba32Float`, and on the CPU otherwise.
+    ///
+    /// Buffers larger than the textures `device` supports, and conversions
+    /// failing on the GPU, also fall back to the CPU.
+    pub fn convert_buffer(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &ColorBuffer<Src>,
+    ) -> ColorBuffer<Dst>
+    where
+        Src::Repr: bytemuck::Pod,
+        Dst::Repr: bytemuck::Pod,
+    {
+        let float_rgba = |info: EncodingInfo| info.components == 4 && info.component_type == "f32";
+        self.compute
+            .as_ref()
+            .filter(|compute| {
+                compute.format == wgpu::TextureFormat::Rgba32Float
+                    && float_rgba(Src::info())
+                    && float_rgba(Dst::info())
+            })
+            .and_then(|_| self.convert_on_gpu(device, queue, buffer))
+            .unwrap_or_else(|| self.pipeline.convert_buffer(buffer))
+    }
+
+    /// Upload `buffer` as an `Rgba32Float` texture, convert it, and read the
+    /// result back, or `None` if that fails.
+    fn convert_on_gpu(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &ColorBuffer<Src>,
+    ) -> Option<ColorBuffer<Dst>>
+    where
+        Src::Repr: bytemuck::Pod,
+        Dst::Repr: bytemuck::Pod,
+    {
+        let width = u32::try_from(buffer.width()).ok()?;
+        let height = u32::try_from(buffer.height()).ok()?;
+        let max = device.limits().max_texture_dimension_2d;
+        (width > 0 && height > 0 && width <= max && height <= max).then_some(())?;
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = |usage: wgpu::TextureUsages| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("colstodian pipeline"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba32Float,
+                usage,
+                view_formats: &[],
+            })
+        };
+        let input = texture(wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST);
+        let output = texture(wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC);
+
+        let row_bytes = width * 16;
+        queue.write_texture(
+            input.as_image_copy(),
+            buffer.as_bytes(),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(row_bytes),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        // Rows copied to buffers are padded to the copy alignment.
+        let padded_row_bytes = row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("colstodian pipeline"),
+            size: u64::from(padded_row_bytes) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        self.encode(
+            device,
+            &mut encoder,
+            &input.create_view(&wgpu::TextureViewDescriptor::default()),
+            &output.create_view(&wgpu::TextureViewDescriptor::default()),
+            width,
+            height,
+        );
+        encoder.copy_texture_to_buffer(
+            output.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            size,
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.poll(wgpu::PollType::Wait).ok()?;
+        receiver.recv().ok()?.ok()?;
+
+        let mut bytes = Vec::with_capacity(row_bytes as usize * height as usize);
+        slice
+            .get_mapped_range()
+            .chunks(padded_row_bytes as usize)
+            .for_each(|row| bytes.extend_from_slice(&row[..row_bytes as usize]));
+        staging.unmap();
+        ColorBuffer::from_bytes(buffer.width(), buffer.height(), &bytes)
+    }
+}
+
+impl<Src: ColorEncoding, Dst: ColorEncoding, L> fmt::Debug for GpuPipeline<Src, Dst, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuPipeline")
+            .field("pipeline", &self.pipeline)
+            .field(
+                "format",
+                &self.compute.as_ref().map(|compute| compute.format),
+            )
+            .finish()
+    }
+}
+
+/// Compile the compute shader `shader` writing textures of the format
+/// `format`, or `None` if `device` reports an error.
+fn compile(device: &wgpu::Device, shader: &str, format: wgpu::TextureFormat) -> Option<Compute> {
+    device.push_error_scope(wgpu::ErrorFilter::Internal);
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("colstodian pipeline"),
+        source: wgpu::ShaderSource::Wgsl(shader.into()),
+    });
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("colstodian pipeline"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::StorageTexture {
+                    access: wgpu::StorageTextureAccess::WriteOnly,
+                    format,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("colstodian pipeline"),
+        bind_group_layouts: &[&layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("colstodian pipeline"),
+        layout: Some(&pipeline_layout),
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        cache: None,
+    });
+    let failed = [device.pop_error_scope(), device.pop_error_scope()]
+        .into_iter()
+        .any(|error| {
+            // Native devices report errors right away. On the web they are
+            // reported later, and the shader is taken to be valid.
+            let mut context = Context::from_waker(Waker::noop());
+            matches!(pin!(error).poll(&mut context), Poll::Ready(Some(_)))
+        });
+    (!failed).then_some(Compute {
+        pipeline,
+        layout,
+        format,
+    })
+}
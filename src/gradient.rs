@@ -0,0 +1,546 @@
+//! Multi-stop color gradients.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::Oklab;
+//! use colstodian::gradient::Gradient;
+//!
+//! let gradient = Gradient::evenly_spaced([
+//!     Color::srgb_u8(68, 1, 84).convert::<Oklab>(),
+//!     Color::srgb_u8(33, 145, 140).convert::<Oklab>(),
+//!     Color::srgb_u8(253, 231, 37).convert::<Oklab>(),
+//! ]);
+//!
+//! let middle = gradient.sample(0.5);
+//! assert_eq!(middle, gradient.stops()[1].1);
+//! ```
+//!
+//! Gradients exported from design tools usually interpolate in a fixed space
+//! and ease between stops:
+//!
+//! ```
+//! use colstodian::basic_encodings::SrgbaU8;
+//! use colstodian::gradient::{Easing, Gradient, InterpolationSpace};
+//! use colstodian::{Color, HueInterpolation};
+//!
+//! let gradient = Gradient::evenly_spaced([
+//!     Color::<SrgbaU8>::srgba_u8(255, 0, 0, 255),
+//!     Color::srgba_u8(0, 0, 255, 255),
+//! ])
+//! .with_space(InterpolationSpace::Oklch(HueInterpolation::Shorter))
+//! .with_easing(Easing::EaseInOut);
+//!
+//! assert_eq!(gradient.sample_mixed(0.0), Color::srgba_u8(255, 0, 0, 255));
+//! ```
+//!
+//! Gradients round-trip through CSS color stop lists:
+//!
+//! ```
+//! use colstodian::basic_encodings::SrgbaU8;
+//! use colstodian::gradient::{Gradient, InterpolationSpace};
+//!
+//! let gradient = Gradient::<SrgbaU8>::from_css_stops("red, #0000ff80 75%")
+//!     .unwrap()
+//!     .with_space(InterpolationSpace::Oklab);
+//!
+//! assert_eq!(
+//!     gradient.to_css_linear_gradient(90.0),
+//!     "linear-gradient(90deg in oklab, #ff0000 0%, #0000ff80 75%)"
+//! );
+//! ```
+
+use crate::ansi::Swatches;
+use crate::details::encodings::{EncodedSrgbU8, EncodedSrgbaU8, Oklab, Oklch, Srgb};
+use crate::details::linear_spaces::{self, CieXYZ};
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw, LinearInterpolate};
+use crate::{Color, ColorEncoding, HueInterpolation, PolarEncoding, named};
+
+use core::fmt;
+
+/// A gradient through colors placed at positions.
+///
+/// Sampling interpolates linearly between neighboring stops in the encoding
+/// `E`, so gradients in a [`PerceptualEncoding`](crate::PerceptualEncoding)
+/// like [`Oklab`] look the most even.
+/// Gradients in a [`PolarEncoding`] like
+/// [`Oklch`] are sampled with
+/// [`Gradient::sample_hue`] instead, so that hues wrap around.
+///
+/// A gradient can also interpolate in another [`InterpolationSpace`] than its
+/// encoding, set with [`Gradient::with_space`], and ease each segment between
+/// two stops with an [`Easing`]. Gradients in encodings that cannot be
+/// interpolated directly, like [`SrgbaU8`](crate::basic_encodings::SrgbaU8),
+/// are sampled with [`Gradient::sample_mixed`].
+pub struct Gradient<E: ColorEncoding> {
+    stops: Vec<(f32, Color<E>)>,
+    easings: Vec<Easing>,
+    space: InterpolationSpace,
+    mix: Option<Mix<E>>,
+}
+
+/// Interpolates between two colors by a factor.
+type Mix<E> = fn(Color<E>, Color<E>, f32) -> Color<E>;
+
+impl<E: ColorEncoding> Gradient<E> {
+    /// Create a gradient from `(position, color)` stops.
+    ///
+    /// Stops are sorted by position. Positions usually range from `0.0` to
+    /// `1.0`, but any range works.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no stops or a position is NaN.
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color<E>)>) -> Self {
+        let mut stops: Vec<_> = stops.into_iter().collect();
+        assert!(!stops.is_empty(), "a gradient needs at least one stop");
+        assert!(
+            stops.iter().all(|(position, _)| !position.is_nan()),
+            "gradient stop positions must not be NaN"
+        );
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self {
+            easings: vec![Easing::Linear; stops.len() - 1],
+            stops,
+            space: InterpolationSpace::Encoding,
+            mix: None,
+        }
+    }
+
+    /// Create a gradient with `colors` spread evenly from `0.0` to `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no colors.
+    pub fn evenly_spaced(colors: impl IntoIterator<Item = Color<E>>) -> Self {
+        let colors: Vec<_> = colors.into_iter().collect();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 / last, color)),
+        )
+    }
+
+    /// The stops of this gradient, sorted by position.
+    #[inline]
+    pub fn stops(&self) -> &[(f32, Color<E>)] {
+        &self.stops
+    }
+
+    /// Ease every segment between two stops with `easing`.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easings.fill(easing);
+        self
+    }
+
+    /// Ease the segment between stop `segment` and the next stop with
+    /// `easing`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no stop after stop `segment`.
+    pub fn with_segment_easing(mut self, segment: usize, easing: Easing) -> Self {
+        assert!(
+            segment < self.easings.len(),
+            "gradient segment out of bounds"
+        );
+        self.easings[segment] = easing;
+        self
+    }
+
+    /// The easing of each segment between two stops.
+    #[inline]
+    pub fn easings(&self) -> &[Easing] {
+        &self.easings
+    }
+
+    /// Interpolate in `space` when sampling.
+    pub fn with_space(mut self, space: InterpolationSpace) -> Self
+    where
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+    {
+        use HueInterpolation::*;
+
+        self.space = space;
+        self.mix = match space {
+            InterpolationSpace::Encoding => None,
+            InterpolationSpace::LinearSrgb => Some(|a, b, t| a.mix_in::<Srgb>(b, t)),
+            InterpolationSpace::Oklab => Some(|a, b, t| a.mix_in::<Oklab>(b, t)),
+            InterpolationSpace::Oklch(Shorter) => {
+                Some(|a, b, t| a.mix_in_polar::<Oklch>(b, t, Shorter))
+            }
+            InterpolationSpace::Oklch(Longer) => {
+                Some(|a, b, t| a.mix_in_polar::<Oklch>(b, t, Longer))
+            }
+            InterpolationSpace::Oklch(Increasing) => {
+                Some(|a, b, t| a.mix_in_polar::<Oklch>(b, t, Increasing))
+            }
+            InterpolationSpace::Oklch(Decreasing) => {
+                Some(|a, b, t| a.mix_in_polar::<Oklch>(b, t, Decreasing))
+            }
+        };
+        self
+    }
+
+    /// The space this gradient interpolates in.
+    #[inline]
+    pub fn space(&self) -> InterpolationSpace {
+        self.space
+    }
+
+    /// The color at `position`, interpolated in the space set with
+    /// [`Gradient::with_space`] as with [`Color::mix_in`].
+    ///
+    /// Without a space, or with [`InterpolationSpace::Encoding`], the stops are
+    /// mixed in linear sRGB.
+    ///
+    /// Positions before the first or after the last stop get the color of
+    /// that stop.
+    pub fn sample_mixed(&self, position: f32) -> Color<E>
+    where
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+    {
+        self.sample_with(
+            position,
+            self.mix.unwrap_or(|a, b, t| a.mix_in::<Srgb>(b, t)),
+        )
+    }
+
+    /// The color at `position`, interpolating between the neighboring stops
+    /// with `lerp`.
+    fn sample_with(
+        &self,
+        position: f32,
+        lerp: impl FnOnce(Color<E>, Color<E>, f32) -> Color<E>,
+    ) -> Color<E> {
+        let next = self.stops.partition_point(|(p, _)| *p <= position);
+        match (next.checked_sub(1), self.stops.get(next)) {
+            (None, _) => self.stops[0].1,
+            (Some(i), None) => self.stops[i].1,
+            (Some(i), Some(&(to_position, to))) => {
+                let (from_position, from) = self.stops[i];
+                let factor = (position - from_position) / (to_position - from_position);
+                lerp(from, to, self.easings[i].apply(factor))
+            }
+        }
+    }
+}
+
+impl<E: ColorEncoding + LinearInterpolate> Gradient<E> {
+    /// The color at `position`, interpolated linearly in `E`, or in the space
+    /// set with [`Gradient::with_space`], if any.
+    ///
+    /// Positions before the first or after the last stop get the color of
+    /// that stop.
+    pub fn sample(&self, position: f32) -> Color<E> {
+        self.sample_with(position, self.mix.unwrap_or(<E as LinearInterpolate>::lerp))
+    }
+}
+
+impl<E: PolarEncoding> Gradient<E> {
+    /// The color at `position`, interpolating hue around the hue circle as
+    /// `hue` says.
+    ///
+    /// Positions before the first or after the last stop get the color of
+    /// that stop.
+    pub fn sample_hue(&self, position: f32, hue: HueInterpolation) -> Color<E> {
+        self.sample_with(position, |from, to, factor| from.lerp_hue(to, factor, hue))
+    }
+}
+
+impl<E> Gradient<E>
+where
+    E: ColorEncoding,
+    linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The stops as a CSS `<color-stop-list>`, like
+    /// `"#ff0000 0%, #0000ff80 100%"`.
+    ///
+    /// Colors are written as with [`Color::to_hex`].
+    /// Easings have no CSS equivalent and are left out.
+    pub fn to_css_stops(&self) -> String {
+        self.stops
+            .iter()
+            .map(|&(position, color)| {
+                // Adding zero turns `-0` into `0`.
+                let percent = (position * 10000.0).round() / 100.0 + 0.0;
+                format!("{} {percent}%", color.to_hex())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// This gradient as a CSS `linear-gradient()` along `angle` degrees.
+    ///
+    /// The interpolation method comes from [`InterpolationSpace::css_method`].
+    pub fn to_css_linear_gradient(&self, angle: f32) -> String {
+        format!(
+            "linear-gradient({angle}deg{}, {})",
+            self.css_method(),
+            self.to_css_stops()
+        )
+    }
+
+    /// This gradient as a CSS `conic-gradient()` starting at `from` degrees.
+    ///
+    /// The interpolation method comes from [`InterpolationSpace::css_method`].
+    pub fn to_css_conic_gradient(&self, from: f32) -> String {
+        format!(
+            "conic-gradient(from {from}deg{}, {})",
+            self.css_method(),
+            self.to_css_stops()
+        )
+    }
+
+    /// The interpolation method with a leading space, or nothing.
+    fn css_method(&self) -> String {
+        self.space
+            .css_method()
+            .map(|method| format!(" {method}"))
+            .unwrap_or_default()
+    }
+}
+
+impl<E> Gradient<E>
+where
+    E: ColorEncoding,
+    EncodedSrgbU8: ConvertFrom<E>,
+    linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+{
+    /// A preview of this gradient as `width` one-column blocks of 24-bit
+    /// terminal colors, from the first to the last stop.
+    ///
+    /// Blocks are sampled at their centers with [`Gradient::sample_mixed`].
+    /// See [`Swatches`] for the output format.
+    pub fn to_ansi_preview(&self, width: usize) -> String {
+        let first = self.stops[0].0;
+        let last = self.stops[self.stops.len() - 1].0;
+        let colors = (0..width)
+            .map(|x| self.sample_mixed(first + (last - first) * (x as f32 + 0.5) / width as f32))
+            .collect::<Vec<_>>();
+        Swatches::new(&colors).block_width(1).to_string()
+    }
+}
+
+impl<E> Gradient<E>
+where
+    E: ColorEncoding + ConvertFrom<EncodedSrgbaU8>,
+    E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+{
+    /// Parse a CSS `<color-stop-list>` like `"red, #00ff0080 40%, blue"`, or
+    /// return `None` if `stops` is not one.
+    ///
+    /// Colors are hex colors, [CSS named colors](named::css) or
+    /// `transparent`. A stop can have up to two percentages, which makes two
+    /// stops of the same color. Missing positions are filled in as CSS does:
+    /// the first stop defaults to `0%`, the last to `100%`, positions smaller
+    /// than an earlier one are raised to it, and stops without a position are
+    /// spread evenly between their neighbors.
+    ///
+    /// Interpolation hints and functional colors like `rgb()` are not
+    /// supported.
+    pub fn from_css_stops(stops: &str) -> Option<Self> {
+        let mut stops = stops
+            .split(',')
+            .map(parse_css_stop)
+            .collect::<Option<Vec<_>>>()?
+            .concat();
+        let last = stops.len().checked_sub(1)?;
+        stops[0].0.get_or_insert(0.0);
+        stops[last].0.get_or_insert(1.0);
+
+        stops
+            .iter_mut()
+            .filter_map(|(position, _)| position.as_mut())
+            .fold(f32::NEG_INFINITY, |largest, position| {
+                *position = position.max(largest);
+                *position
+            });
+
+        let positioned: Vec<_> = (0..=last).filter(|&i| stops[i].0.is_some()).collect();
+        positioned.windows(2).for_each(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            let (from, to) = (stops[start].0.unwrap(), stops[end].0.unwrap());
+            (start + 1..end).for_each(|i| {
+                let factor = (i - start) as f32 / (end - start) as f32;
+                stops[i].0 = Some(from + (to - from) * factor);
+            });
+        });
+
+        Some(Self::new(stops.into_iter().map(|(position, color)| {
+            (position.unwrap(), color.convert())
+        })))
+    }
+}
+
+/// Parse one stop of a CSS color stop list into one or two stops.
+fn parse_css_stop(stop: &str) -> Option<Vec<(Option<f32>, Color<EncodedSrgbaU8>)>> {
+    let mut tokens = stop.split_whitespace();
+    let color = parse_css_color(tokens.next()?)?;
+    let positions = tokens
+        .map(|token| {
+            let percent = token
+                .strip_suffix('%')?
+                .parse::<f32>()
+                .ok()
+                .filter(|percent| percent.is_finite())?;
+            Some((Some(percent / 100.0), color))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    match positions.len() {
+        0 => Some(vec![(None, color)]),
+        1 | 2 => Some(positions),
+        _ => None,
+    }
+}
+
+/// Parse a hex color, CSS named color or `transparent`.
+fn parse_css_color(color: &str) -> Option<Color<EncodedSrgbaU8>> {
+    if color.starts_with('#') {
+        Color::<EncodedSrgbaU8>::from_hex(color)
+    } else if color.eq_ignore_ascii_case("transparent") {
+        Some(Color::from_repr([0, 0, 0, 0]))
+    } else {
+        named::css(color).map(Color::convert)
+    }
+}
+
+impl<E: ColorEncoding> Clone for Gradient<E> {
+    fn clone(&self) -> Self {
+        Self {
+            stops: self.stops.clone(),
+            easings: self.easings.clone(),
+            space: self.space,
+            mix: self.mix,
+        }
+    }
+}
+
+impl<E> PartialEq for Gradient<E>
+where
+    E: ColorEncoding,
+    E::Repr: PartialEq,
+{
+    /// Gradients are equal if their stops, easings and interpolation spaces
+    /// are.
+    fn eq(&self, other: &Self) -> bool {
+        self.stops == other.stops && self.easings == other.easings && self.space == other.space
+    }
+}
+
+impl<E> fmt::Debug for Gradient<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gradient")
+            .field("stops", &self.stops)
+            .field("easings", &self.easings)
+            .field("space", &self.space)
+            .finish()
+    }
+}
+
+/// The space a [`Gradient`] interpolates in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterpolationSpace {
+    /// The encoding of the gradient.
+    #[default]
+    Encoding,
+    /// Linear sRGB, which mixes light physically.
+    LinearSrgb,
+    /// [`Oklab`], which mixes perceptually.
+    Oklab,
+    /// [`Oklch`], which keeps chroma while going around the hue circle.
+    Oklch(HueInterpolation),
+}
+
+impl InterpolationSpace {
+    /// The CSS `<color-interpolation-method>` of this space, like
+    /// `"in oklch longer hue"`.
+    ///
+    /// [`InterpolationSpace::Encoding`] has none, so CSS interpolates in its
+    /// default space, which for hex colors is gamma-encoded sRGB.
+    pub fn css_method(self) -> Option<&'static str> {
+        use HueInterpolation::*;
+
+        match self {
+            InterpolationSpace::Encoding => None,
+            InterpolationSpace::LinearSrgb => Some("in srgb-linear"),
+            InterpolationSpace::Oklab => Some("in oklab"),
+            InterpolationSpace::Oklch(Shorter) => Some("in oklch"),
+            InterpolationSpace::Oklch(Longer) => Some("in oklch longer hue"),
+            InterpolationSpace::Oklch(Increasing) => Some("in oklch increasing hue"),
+            InterpolationSpace::Oklch(Decreasing) => Some("in oklch decreasing hue"),
+        }
+    }
+}
+
+/// How the interpolation factor progresses between two stops, as with CSS
+/// easing functions.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// Progress evenly.
+    #[default]
+    Linear,
+    /// CSS `ease`, which is `CubicBezier(0.25, 0.1, 0.25, 1.0)`.
+    Ease,
+    /// CSS `ease-in`, which is `CubicBezier(0.42, 0.0, 1.0, 1.0)`.
+    EaseIn,
+    /// CSS `ease-out`, which is `CubicBezier(0.0, 0.0, 0.58, 1.0)`.
+    EaseOut,
+    /// CSS `ease-in-out`, which is `CubicBezier(0.42, 0.0, 0.58, 1.0)`.
+    EaseInOut,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` with the control points
+    /// `(x1, y1)` and `(x2, y2)`, as CSS `cubic-bezier(x1, y1, x2, y2)`.
+    ///
+    /// `x1` and `x2` must be in `0.0..=1.0`.
+    CubicBezier(f32, f32, f32, f32),
+    /// Jump in this many equal steps, at the end of each step, as CSS
+    /// `steps(n)`.
+    Steps(u32),
+}
+
+impl Easing {
+    /// Map `t` in `0.0..=1.0` to the eased factor.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            Easing::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Easing::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+            Easing::Steps(0) => t,
+            Easing::Steps(steps) => (t * steps as f32).floor().min(steps as f32) / steps as f32,
+        }
+    }
+}
+
+/// The `y` of the point with `x` on a CSS cubic Bézier timing curve.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let curve = |p1: f32, p2: f32, t: f32| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+    // The curve is monotonic in x for control points in 0..=1, so bisect.
+    let (low, high) = (0..32).fold((0.0f32, 1.0f32), |(low, high), _| {
+        let mid = (low + high) / 2.0;
+        if curve(x1, x2, mid) < x {
+            (mid, high)
+        } else {
+            (low, mid)
+        }
+    });
+    curve(y1, y2, (low + high) / 2.0)
+}
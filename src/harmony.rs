@@ -0,0 +1,83 @@
+//! Color harmonies.
+//!
+//! Harmonies rotate the hue of a base color in Oklch, the polar form of
+//! [`Oklab`], keeping its lightness and chroma. Hue steps are therefore
+//! perceptually even, unlike harmonies computed in HSL or HSV.
+//!
+//! All harmonies return the base color first. Rotated colors outside the gamut
+//! of the encoding are clamped by its quantization, if any, and left as-is
+//! otherwise. Alpha is preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//!
+//! let base = Color::srgb_u8(40, 110, 200);
+//! let [_, second, third] = base.triadic();
+//! ```
+
+use crate::details::encodings::Oklab;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use glam::{Vec2, Vec3};
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    /// Rotate the hue by `degrees` in Oklch, keeping lightness and chroma.
+    pub fn rotate_hue(self, degrees: f32) -> Self {
+        let (mut raw, alpha) = E::src_transform_raw(self.repr);
+        <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+        let lab = Oklab::dst_transform_raw(raw, alpha);
+
+        let ab = Vec2::from_angle(degrees.to_radians()).rotate(Vec2::new(lab.y, lab.z));
+
+        let (mut raw, _) = Oklab::src_transform_raw(Vec3::new(lab.x, ab.x, ab.y));
+        <E::LinearSpace as LinearConvertFromRaw<CieXYZ>>::linear_part_raw(&mut raw);
+        Color::from_repr(E::dst_transform_raw(raw, alpha))
+    }
+
+    /// The base color and the color with the opposite hue.
+    #[inline]
+    pub fn complementary(self) -> [Self; 2] {
+        [self, self.rotate_hue(180.0)]
+    }
+
+    /// The base color and the two colors `30°` to either side of its
+    /// complement.
+    #[inline]
+    pub fn split_complementary(self) -> [Self; 3] {
+        [self, self.rotate_hue(150.0), self.rotate_hue(210.0)]
+    }
+
+    /// The base color and two colors evenly spaced around the hue circle.
+    #[inline]
+    pub fn triadic(self) -> [Self; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// The base color and three colors evenly spaced around the hue circle.
+    #[inline]
+    pub fn tetradic(self) -> [Self; 4] {
+        [
+            self,
+            self.rotate_hue(90.0),
+            self.rotate_hue(180.0),
+            self.rotate_hue(270.0),
+        ]
+    }
+
+    /// The base color and the two colors `spread` degrees to either side.
+    ///
+    /// A `spread` of `30.0` is common.
+    #[inline]
+    pub fn analogous(self, spread: f32) -> [Self; 3] {
+        [self, self.rotate_hue(-spread), self.rotate_hue(spread)]
+    }
+}
@@ -0,0 +1,365 @@
+//! HCT, the hue, chroma and tone space of Material Design, and tonal palettes
+//! for Material You dynamic color.
+//!
+//! HCT takes hue and chroma from the CAM16 color appearance model and tone
+//! from CIE L*, so that tone alone predicts contrast. Colors are converted with
+//! the [`Hct`] encoding, which maps colors to the sRGB gamut by reducing
+//! chroma at constant hue and tone.
+//!
+//! A [`TonalPalette`] holds hue and chroma and picks tones of them, and a
+//! [`CorePalette`] derives the palettes of a Material theme from a source
+//! color.
+//!
+//! CAM16 uses the default viewing conditions of Material: a D65 white, an
+//! adapting luminance of about 11.7 nits, a background of L* 50 and an
+//! average surround.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::Hct;
+//! use colstodian::hct::CorePalette;
+//!
+//! let blue = Color::srgb_u8(0, 0, 255).convert::<Hct>();
+//! assert!((blue.h - 282.8).abs() < 0.1 && (blue.c - 87.2).abs() < 0.1);
+//! assert!((blue.t - 32.3).abs() < 0.1);
+//!
+//! // The primary container color of a light theme.
+//! let palette = CorePalette::new(Color::srgb_u8(66, 133, 244));
+//! let container = palette.primary.tone(90.0);
+//! ```
+//!
+//! [`Hct`]: crate::details::encodings::Hct
+
+use crate::details::encodings::{EncodedSrgbU8, Hct, wrap_degrees};
+use crate::details::linear_spaces::{CieXYZ, Srgb};
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use core::f32::consts::PI;
+
+use glam::{Mat3, Vec2, Vec3};
+
+/// The CAT16 chromatic adaptation matrix, from XYZ to sharpened RGB.
+const CAT16: Mat3 = Mat3::from_cols(
+    Vec3::new(0.401288, -0.250268, -0.002079),
+    Vec3::new(0.650173, 1.204414, 0.048952),
+    Vec3::new(-0.051461, 0.045854, 0.953127),
+);
+
+/// The inverse of [`CAT16`].
+const CAT16_INVERSE: Mat3 = Mat3::from_cols(
+    Vec3::new(1.862_068, 0.387_526_5, -0.015_841_5),
+    Vec3::new(-1.011_254_7, 0.621_447_4, -0.034_122_94),
+    Vec3::new(0.149_186_8, -0.008_973_98, 1.049_964_4),
+);
+
+/// The D65 white point, scaled to a luminance of 100.
+const WHITE: Vec3 = Vec3::new(95.047, 100.0, 108.883);
+
+/// The number of bisection steps when solving for CAM16 lightness and when
+/// reducing chroma into the gamut.
+const SOLVER_STEPS: usize = 32;
+
+/// The CAM16 viewing conditions and the values derived from them.
+struct ViewingConditions {
+    n: f32,
+    aw: f32,
+    nbb: f32,
+    c: f32,
+    nc: f32,
+    z: f32,
+    fl: f32,
+    rgb_d: Vec3,
+}
+
+impl ViewingConditions {
+    /// The default viewing conditions of Material.
+    fn material() -> Self {
+        let adapting_luminance = 200.0 / PI * y_from_lstar(50.0) / 100.0;
+        let background = y_from_lstar(50.0);
+        let surround = 2.0;
+
+        let rgb_w = CAT16 * WHITE;
+        let f = 0.8 + surround / 10.0;
+        let c = if f >= 0.9 {
+            0.59 + (0.69 - 0.59) * (f - 0.9) * 10.0
+        } else {
+            0.525 + (0.59 - 0.525) * (f - 0.8) * 10.0
+        };
+        let d = f * (1.0 - (1.0 / 3.6) * exp((-adapting_luminance - 42.0) / 92.0));
+        let d = d.clamp(0.0, 1.0);
+        let rgb_d = d * 100.0 / rgb_w + 1.0 - d;
+
+        let k = 1.0 / (5.0 * adapting_luminance + 1.0);
+        let k4 = k * k * k * k;
+        let k4f = 1.0 - k4;
+        let fl =
+            k4 * adapting_luminance + 0.1 * k4f * k4f * powf(5.0 * adapting_luminance, 1.0 / 3.0);
+
+        let n = background / WHITE.y;
+        let z = 1.48 + powf(n, 0.5);
+        let nbb = 0.725 / powf(n, 0.2);
+
+        let rgb_a = adapted_response(fl * rgb_d * rgb_w / 100.0);
+        let aw = (2.0 * rgb_a.x + rgb_a.y + 0.05 * rgb_a.z) * nbb;
+
+        Self {
+            n,
+            aw,
+            nbb,
+            c,
+            nc: f,
+            z,
+            fl,
+            rgb_d,
+        }
+    }
+
+    /// The eccentricity of `hue` in radians, times the constant factors of
+    /// the CAM16 correlate `t`.
+    fn p1(&self, hue: f32) -> f32 {
+        let e_hue = 0.25 * (Vec2::from_angle(hue + 2.0).x + 3.8);
+        50000.0 / 13.0 * e_hue * self.nc * self.nbb
+    }
+
+    /// The factor from `t^0.9 * sqrt(J / 100)` to chroma.
+    fn alpha_factor(&self) -> f32 {
+        powf(1.64 - powf(0.29, self.n), 0.73)
+    }
+}
+
+/// The post-adaptation cone responses of adapted `rgb`.
+fn adapted_response(rgb: Vec3) -> Vec3 {
+    let factor = rgb.abs().powf(0.42);
+    Vec3::select(rgb.cmplt(Vec3::ZERO), -Vec3::ONE, Vec3::ONE) * 400.0 * factor / (factor + 27.13)
+}
+
+/// CIE XYZ with a white luminance of 100 to CAM16 lightness J, chroma and hue
+/// in degrees.
+fn xyz_to_cam16(xyz: Vec3, vc: &ViewingConditions) -> Vec3 {
+    let rgb_a = adapted_response(vc.fl * vc.rgb_d * (CAT16 * xyz) / 100.0);
+    let a = (11.0 * rgb_a.x - 12.0 * rgb_a.y + rgb_a.z) / 11.0;
+    let b = (rgb_a.x + rgb_a.y - 2.0 * rgb_a.z) / 9.0;
+    let u = (20.0 * rgb_a.x + 20.0 * rgb_a.y + 21.0 * rgb_a.z) / 20.0;
+    let p2 = (40.0 * rgb_a.x + 20.0 * rgb_a.y + rgb_a.z) / 20.0;
+
+    let hue = wrap_degrees(Vec2::new(a, b).to_angle().to_degrees());
+    let ac = p2 * vc.nbb;
+    let j = 100.0 * powf((ac / vc.aw).max(0.0), vc.c * vc.z);
+
+    let hue_prime = if hue < 20.14 { hue + 360.0 } else { hue };
+    let t = vc.p1(hue_prime.to_radians()) * Vec2::new(a, b).length() / (u + 0.305);
+    let chroma = powf(t, 0.9) * vc.alpha_factor() * powf(j / 100.0, 0.5);
+    Vec3::new(j, chroma, hue)
+}
+
+/// CAM16 lightness J, chroma and hue in degrees to CIE XYZ with a white
+/// luminance of 100.
+fn cam16_to_xyz(j: f32, chroma: f32, hue: f32, vc: &ViewingConditions) -> Vec3 {
+    let alpha = if chroma == 0.0 || j == 0.0 {
+        0.0
+    } else {
+        chroma / powf(j / 100.0, 0.5)
+    };
+    let t = powf(alpha / vc.alpha_factor(), 1.0 / 0.9);
+    let hue = hue.to_radians();
+    let ac = vc.aw * powf(j / 100.0, 1.0 / vc.c / vc.z);
+    let p1 = vc.p1(hue);
+    let p2 = ac / vc.nbb;
+    let Vec2 { x: cos, y: sin } = Vec2::from_angle(hue);
+
+    let gamma = 23.0 * (p2 + 0.305) * t / (23.0 * p1 + 11.0 * t * cos + 108.0 * t * sin);
+    let (a, b) = (gamma * cos, gamma * sin);
+    let rgb_a = Vec3::new(
+        460.0 * p2 + 451.0 * a + 288.0 * b,
+        460.0 * p2 - 891.0 * a - 261.0 * b,
+        460.0 * p2 - 220.0 * a - 6300.0 * b,
+    ) / 1403.0;
+
+    let base = (27.13 * rgb_a.abs() / (400.0 - rgb_a.abs())).max(Vec3::ZERO);
+    let sign = Vec3::select(rgb_a.cmplt(Vec3::ZERO), -Vec3::ONE, Vec3::ONE);
+    let rgb_c = sign * (100.0 / vc.fl) * base.powf(1.0 / 0.42);
+    CAT16_INVERSE * (rgb_c / vc.rgb_d)
+}
+
+/// CIE XYZ with a white luminance of 1 to hue, chroma and tone.
+pub(crate) fn xyz_to_hct(xyz: Vec3) -> Vec3 {
+    let cam = xyz_to_cam16(xyz * 100.0, &ViewingConditions::material());
+    Vec3::new(cam.z, cam.y, lstar_from_y(xyz.y * 100.0))
+}
+
+/// Hue, chroma and tone to CIE XYZ with a white luminance of 1, with the
+/// chroma reduced as far as needed to fit in the sRGB gamut.
+pub(crate) fn hct_to_xyz(hct: Vec3) -> Vec3 {
+    let Vec3 {
+        x: hue,
+        y: chroma,
+        z: tone,
+    } = hct;
+    let y = y_from_lstar(tone.clamp(0.0, 100.0));
+
+    let vc = ViewingConditions::material();
+    // The color with `chroma` at the tone, finding the lightness J that gives
+    // its luminance.
+    let solve = |chroma: f32| {
+        let (low, high) = bisect(0.0, 100.0, |j| cam16_to_xyz(j, chroma, hue, &vc).y < y);
+        cam16_to_xyz((low + high) * 0.5, chroma, hue, &vc) / 100.0
+    };
+    let fits = |xyz: Vec3| {
+        let mut raw = xyz;
+        <Srgb as LinearConvertFromRaw<CieXYZ>>::linear_part_raw(&mut raw);
+        raw.cmpge(Vec3::splat(-1e-4)).all() && raw.cmple(Vec3::splat(1.0 + 1e-4)).all()
+    };
+
+    if chroma <= 0.0 || y <= 0.0 || y >= 100.0 {
+        WHITE * y / 10000.0
+    } else {
+        let xyz = solve(chroma);
+        if fits(xyz) {
+            xyz
+        } else {
+            let (low, _) = bisect(0.0, chroma, |chroma| fits(solve(chroma)));
+            solve(low)
+        }
+    }
+}
+
+/// The bounds around where `below` turns false in `low..high`, after
+/// [`SOLVER_STEPS`] bisections.
+fn bisect(low: f32, high: f32, below: impl Fn(f32) -> bool) -> (f32, f32) {
+    (0..SOLVER_STEPS).fold((low, high), |(low, high), _| {
+        let middle = (low + high) * 0.5;
+        if below(middle) {
+            (middle, high)
+        } else {
+            (low, middle)
+        }
+    })
+}
+
+/// The luminance Y, with a white of 100, of CIE L* `lstar`.
+fn y_from_lstar(lstar: f32) -> f32 {
+    let ft = (lstar + 16.0) / 116.0;
+    let ft3 = ft * ft * ft;
+    100.0
+        * if ft3 > 216.0 / 24389.0 {
+            ft3
+        } else {
+            (116.0 * ft - 16.0) / (24389.0 / 27.0)
+        }
+}
+
+/// The CIE L* of luminance `y`, with a white of 100.
+fn lstar_from_y(y: f32) -> f32 {
+    let t = y / 100.0;
+    if t > 216.0 / 24389.0 {
+        116.0 * powf(t, 1.0 / 3.0) - 16.0
+    } else {
+        24389.0 / 27.0 * t
+    }
+}
+
+/// `x` to the power of `n`.
+#[inline(always)]
+fn powf(x: f32, n: f32) -> f32 {
+    Vec2::splat(x).powf(n).x
+}
+
+/// `e` to the power of `x`.
+#[inline(always)]
+fn exp(x: f32) -> f32 {
+    Vec2::splat(x).exp().x
+}
+
+/// The colors of one hue and chroma at varying tones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonalPalette {
+    /// The HCT hue in degrees.
+    pub hue: f32,
+    /// The HCT chroma. Tones whose color can't reach it get the highest chroma
+    /// in the sRGB gamut.
+    pub chroma: f32,
+}
+
+impl TonalPalette {
+    /// The tones of Material tonal palettes, from black to white.
+    pub const TONES: [f32; 13] = [
+        0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 95.0, 99.0, 100.0,
+    ];
+
+    /// A palette of `hue` and `chroma`.
+    #[inline]
+    pub const fn new(hue: f32, chroma: f32) -> Self {
+        Self { hue, chroma }
+    }
+
+    /// The palette of the hue and chroma of `color`.
+    pub fn of<E>(color: Color<E>) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let hct = hct_of(color);
+        Self::new(hct.x, hct.y)
+    }
+
+    /// The color at `tone`, from `0.0` (black) to `100.0` (white).
+    pub fn tone(&self, tone: f32) -> Color<EncodedSrgbU8> {
+        Color::<Hct>::hct(self.hue, self.chroma, tone).convert()
+    }
+
+    /// The colors at [`TonalPalette::TONES`].
+    pub fn tones(&self) -> [Color<EncodedSrgbU8>; 13] {
+        Self::TONES.map(|tone| self.tone(tone))
+    }
+}
+
+/// The tonal palettes of a Material You theme, derived from a source color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorePalette {
+    /// The palette of the source hue, with a chroma of at least 48.
+    pub primary: TonalPalette,
+    /// The source hue at chroma 16.
+    pub secondary: TonalPalette,
+    /// The source hue rotated by 60° at chroma 24.
+    pub tertiary: TonalPalette,
+    /// The source hue at chroma 4, for surfaces and backgrounds.
+    pub neutral: TonalPalette,
+    /// The source hue at chroma 8, for outlines and variant surfaces.
+    pub neutral_variant: TonalPalette,
+    /// The fixed red palette for errors.
+    pub error: TonalPalette,
+}
+
+impl CorePalette {
+    /// The palettes of the source color `source`.
+    pub fn new<E>(source: Color<E>) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let hct = hct_of(source);
+        let hue = hct.x;
+        Self {
+            primary: TonalPalette::new(hue, hct.y.max(48.0)),
+            secondary: TonalPalette::new(hue, 16.0),
+            tertiary: TonalPalette::new(wrap_degrees(hue + 60.0), 24.0),
+            neutral: TonalPalette::new(hue, 4.0),
+            neutral_variant: TonalPalette::new(hue, 8.0),
+            error: TonalPalette::new(25.0, 84.0),
+        }
+    }
+}
+
+/// The hue, chroma and tone of `color`.
+fn hct_of<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    xyz_to_hct(raw)
+}
@@ -0,0 +1,187 @@
+//! HDR10 static metadata.
+//!
+//! [`ContentLightLevel`] computes MaxCLL and MaxFALL (CTA-861.3) from frames of
+//! linear or PQ-encoded colors. [`MasteringDisplay`] describes the mastering
+//! display color volume (SMPTE ST 2086) and encodes it for the bitstream.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::linear_spaces::Bt2020;
+//! use colstodian::hdr10::{ContentLightLevel, MasteringDisplay};
+//!
+//! // Linear BT.2020 frames where `1.0` is 203 nits, the HDR reference white.
+//! let frame = [Color::bt2020(1.0, 1.0, 1.0), Color::bt2020(4.0, 0.5, 0.0)];
+//! let mut cll = ContentLightLevel::default();
+//! cll.add_frame(frame, 203.0);
+//!
+//! assert_eq!(cll.max_cll, 812);
+//! assert_eq!(cll.max_fall, 508);
+//!
+//! let display = MasteringDisplay::of::<Bt2020>(1000.0, 0.0001);
+//! assert_eq!(
+//!     display.to_st2086().to_string(),
+//!     "G(8500,39850)B(6550,2300)R(35400,14600)WP(15635,16450)L(10000000,1)"
+//! );
+//! ```
+
+use crate::cicp::{self, Cicp};
+use crate::custom::CustomColorSpace;
+use crate::details::linear_spaces::Bt2020;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::quantize::round;
+use crate::transfer::pq_eotf;
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+
+use glam::Vec3;
+
+/// The content light level of a sequence of frames, in nits (cd/m²).
+///
+/// Light levels are the maximum of the red, green and blue components of a
+/// pixel in linear BT.2020.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentLightLevel {
+    /// The maximum content light level, i.e. the light level of the brightest
+    /// pixel of all frames.
+    pub max_cll: u16,
+    /// The maximum frame-average light level, i.e. the highest average light
+    /// level of any frame.
+    pub max_fall: u16,
+}
+
+impl ContentLightLevel {
+    /// Add a frame of linear colors, where a component value of `1.0` is
+    /// `nits_per_unit` nits.
+    pub fn add_frame<E>(&mut self, frame: impl IntoIterator<Item = Color<E>>, nits_per_unit: f32)
+    where
+        E: ColorEncoding,
+        Bt2020: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        self.add_light_levels(frame.into_iter().map(|color| {
+            let (mut raw, _) = E::src_transform_raw(color.repr);
+            <Bt2020 as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            raw.max_element() * nits_per_unit
+        }));
+    }
+
+    /// Add a frame of PQ-encoded (SMPTE ST 2084) BT.2020 components in
+    /// `0.0..=1.0`.
+    pub fn add_pq_frame(&mut self, frame: impl IntoIterator<Item = Vec3>) {
+        self.add_light_levels(frame.into_iter().map(|pq| pq_eotf(pq).max_element()));
+    }
+
+    fn add_light_levels(&mut self, levels: impl Iterator<Item = f32>) {
+        let (max, sum, count) =
+            levels.fold((0.0f32, 0.0f64, 0usize), |(max, sum, count), level| {
+                let level = level.max(0.0);
+                (max.max(level), sum + level as f64, count + 1)
+            });
+        if count > 0 {
+            let average = (sum / count as f64) as f32;
+            self.max_cll = self.max_cll.max(to_nits(max));
+            self.max_fall = self.max_fall.max(to_nits(average));
+        }
+    }
+}
+
+/// Round to whole nits, saturating at the largest representable level.
+#[inline(always)]
+fn to_nits(level: f32) -> u16 {
+    round(level).min(u16::MAX as f32) as u16
+}
+
+/// The color volume of a mastering display (SMPTE ST 2086).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MasteringDisplay {
+    /// The CIE xy chromaticities of the red, green and blue primaries.
+    pub primaries: [[f32; 2]; 3],
+    /// The CIE xy chromaticity of the white point.
+    pub white_point: [f32; 2],
+    /// The maximum luminance in nits.
+    pub max_luminance: f32,
+    /// The minimum luminance in nits.
+    pub min_luminance: f32,
+}
+
+impl MasteringDisplay {
+    /// A mastering display with the gamut of `space`.
+    ///
+    /// Spaces with CICP color primaries get their exact chromaticities, as
+    /// the ones derived from the conversion matrices are off by rounding.
+    pub fn from_custom_color_space(
+        space: &CustomColorSpace,
+        max_luminance: f32,
+        min_luminance: f32,
+    ) -> Self {
+        let (primaries, white_point) = Cicp::color_primaries_of(space)
+            .and_then(cicp::primaries_xy)
+            .unwrap_or_else(|| (space.primaries_xy(), space.white_point_xy()));
+        Self {
+            primaries,
+            white_point,
+            max_luminance,
+            min_luminance,
+        }
+    }
+
+    /// A mastering display with the gamut of the linear color space `L`.
+    #[inline]
+    pub fn of<L: LinearColorSpace>(max_luminance: f32, min_luminance: f32) -> Self {
+        Self::from_custom_color_space(&CustomColorSpace::of::<L>(), max_luminance, min_luminance)
+    }
+
+    /// The integer fields of the ST 2086 metadata, as stored in HEVC SEI
+    /// messages, AV1 metadata OBUs and the PNG `mDCV` chunk.
+    pub fn to_st2086(&self) -> St2086 {
+        let chromaticity = |[x, y]: [f32; 2]| [x, y].map(|v| round(v * 50_000.0) as u16);
+        let luminance = |nits: f32| round(nits * 10_000.0) as u32;
+        let [red, green, blue] = self.primaries.map(chromaticity);
+        St2086 {
+            red,
+            green,
+            blue,
+            white_point: chromaticity(self.white_point),
+            max_luminance: luminance(self.max_luminance),
+            min_luminance: luminance(self.min_luminance),
+        }
+    }
+}
+
+/// ST 2086 metadata in integer units.
+///
+/// Chromaticities are in units of `0.00002` and luminances in units of
+/// `0.0001` nits. Containers differ in the order of the primaries: HEVC stores
+/// green, blue, red while AV1 stores red, green, blue.
+///
+/// [`Display`](fmt::Display) formats this in the `master-display` syntax of
+/// x265 and similar encoders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct St2086 {
+    /// The red primary.
+    pub red: [u16; 2],
+    /// The green primary.
+    pub green: [u16; 2],
+    /// The blue primary.
+    pub blue: [u16; 2],
+    /// The white point.
+    pub white_point: [u16; 2],
+    /// The maximum luminance.
+    pub max_luminance: u32,
+    /// The minimum luminance.
+    pub min_luminance: u32,
+}
+
+impl fmt::Display for St2086 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [[gx, gy], [bx, by], [rx, ry], [wx, wy]] =
+            [self.green, self.blue, self.red, self.white_point];
+        write!(
+            f,
+            "G({gx},{gy})B({bx},{by})R({rx},{ry})WP({wx},{wy})L({},{})",
+            self.max_luminance, self.min_luminance
+        )
+    }
+}
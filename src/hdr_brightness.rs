@@ -0,0 +1,95 @@
+//! Hue-preserving brightness and exposure adjustments of HDR colors.
+//!
+//! Scaling linear RGB components makes bright, saturated HDR colors drift in
+//! hue, as perceived hue isn't constant along lines through black once the
+//! components reach the levels of HDR highlights. Following ITU-R BT.2408,
+//! these adjustments change only the intensity I of the color in
+//! [`ICtCpPq`] and keep its Ct and Cp, which hold the hue and saturation.
+//!
+//! [`Color::hdr_exposure`] scales the light of the color by a number of stops,
+//! [`Color::hdr_brightness`] offsets its PQ intensity, like a lift of the PQ
+//! signal in grading. Both work on any encoding that converts to and from
+//! BT.2020, with linear values of `1.0` at
+//! [`HDR_REFERENCE_WHITE`](crate::details::encodings::HDR_REFERENCE_WHITE)
+//! nits. Alpha is kept.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::ICtCpPq;
+//!
+//! let gray = Color::bt2020(1.0, 1.0, 1.0);
+//! assert!((gray.hdr_exposure(1.0).r - 2.0).abs() < 1e-3);
+//!
+//! // A saturated highlight only changes in intensity.
+//! let red = Color::bt2020(8.0, 0.5, 0.2);
+//! let before = red.convert::<ICtCpPq>();
+//! let after = red.hdr_exposure(2.0).convert::<ICtCpPq>();
+//! assert!(after.i > before.i);
+//! assert!((after.ct - before.ct).abs() < 1e-4 && (after.cp - before.cp).abs() < 1e-4);
+//! ```
+
+use crate::details::encodings::ICtCpPq;
+use crate::details::linear_spaces::Bt2020;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::transfer::{pq_eotf, pq_inverse_eotf};
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Bt2020: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<Bt2020>,
+{
+    /// The PQ intensity I of this color in [`ICtCpPq`], in `0.0..=1.0`.
+    pub fn hdr_intensity(self) -> f32 {
+        to_ictcp(self).0.x
+    }
+
+    /// Scale the light of this color by `2^stops` along the intensity axis,
+    /// keeping its hue and saturation.
+    ///
+    /// The luminance of grays is scaled exactly, that of other colors closely.
+    /// Intensity is clipped at the 10,000 nits of the PQ signal.
+    pub fn hdr_exposure(self, stops: f32) -> Self {
+        let gain = Vec3::splat(stops).exp2();
+        adjust_intensity(self, |i| pq_inverse_eotf(pq_eotf(Vec3::splat(i)) * gain).x)
+    }
+
+    /// Offset the PQ intensity of this color by `offset`, keeping its hue and
+    /// saturation.
+    ///
+    /// As PQ follows perceived lightness, the same offset looks alike at all
+    /// levels. The intensity is clamped to `0.0..=1.0`.
+    pub fn hdr_brightness(self, offset: f32) -> Self {
+        adjust_intensity(self, |i| i + offset)
+    }
+}
+
+/// `color` in [`ICtCpPq`], and its alpha.
+fn to_ictcp<E>(color: Color<E>) -> (Vec3, f32)
+where
+    E: ColorEncoding,
+    Bt2020: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, alpha) = E::src_transform_raw(color.repr);
+    <Bt2020 as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    (ICtCpPq::dst_transform_raw(raw, alpha), alpha)
+}
+
+/// `color` with its intensity changed by `adjust`.
+fn adjust_intensity<E>(color: Color<E>, adjust: impl Fn(f32) -> f32) -> Color<E>
+where
+    E: ColorEncoding,
+    Bt2020: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<Bt2020>,
+{
+    let (mut ictcp, alpha) = to_ictcp(color);
+    ictcp.x = adjust(ictcp.x).clamp(0.0, 1.0);
+    let mut raw = ICtCpPq::src_transform_raw(ictcp).0;
+    <E::LinearSpace as LinearConvertFromRaw<Bt2020>>::linear_part_raw(&mut raw);
+    Color::from_repr(E::dst_transform_raw(raw, alpha))
+}
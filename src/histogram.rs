@@ -0,0 +1,276 @@
+//! Histograms of color buffers.
+//!
+//! Histograms bin either the luminance of colors or the three components of
+//! colors converted to an encoding with float components. Binning in a
+//! perceptual encoding like [`Oklab`](crate::details::encodings::Oklab) spreads
+//! bins evenly over perceived lightness and chroma.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::{EncodedSrgbF32, Oklab};
+//! use colstodian::histogram::Histogram;
+//!
+//! let pixels = [
+//!     Color::srgb_u8(0, 0, 0),
+//!     Color::srgb_u8(128, 128, 128),
+//!     Color::srgb_u8(255, 0, 0),
+//! ];
+//!
+//! // Per-channel histograms of the encoded sRGB values.
+//! let unit = [0.0..=1.0, 0.0..=1.0, 0.0..=1.0];
+//! let [red, _, _] = Histogram::channels::<_, EncodedSrgbF32>(pixels, 4, unit);
+//! assert_eq!(red.counts(), &[1, 0, 1, 1]);
+//!
+//! // A histogram of perceptual lightness.
+//! let [lightness, _, _] =
+//!     Histogram::channels::<_, Oklab>(pixels, 10, [0.0..=1.0, -0.4..=0.4, -0.4..=0.4]);
+//! assert_eq!(lightness.total(), 3);
+//! ```
+
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use core::ops::{Range, RangeInclusive};
+
+use glam::Vec3;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// A histogram of values with equally wide bins.
+///
+/// Values outside the range are counted in the first or last bin. NaN values
+/// are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    counts: Vec<u32>,
+    min: f32,
+    max: f32,
+}
+
+impl Histogram {
+    /// Create an empty histogram with `bins` bins spanning `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or `range` is empty.
+    pub fn new(bins: usize, range: RangeInclusive<f32>) -> Self {
+        let (min, max) = range.into_inner();
+        assert!(bins > 0, "a histogram needs at least one bin");
+        assert!(min < max, "the histogram range must not be empty");
+        Self {
+            counts: vec![0; bins],
+            min,
+            max,
+        }
+    }
+
+    /// A histogram of the relative luminance (CIE Y) of `colors`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or `range` is empty.
+    pub fn luminance<E>(
+        colors: impl IntoIterator<Item = Color<E>>,
+        bins: usize,
+        range: RangeInclusive<f32>,
+    ) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let mut histogram = Self::new(bins, range);
+        colors
+            .into_iter()
+            .for_each(|color| histogram.add(luminance_of(color)));
+        histogram
+    }
+
+    /// A histogram of the relative luminance (CIE Y) of `colors`, counted in
+    /// parallel.
+    ///
+    /// See [`Histogram::luminance`].
+    #[cfg(feature = "rayon")]
+    pub fn par_luminance<E>(
+        colors: impl IntoParallelIterator<Item = Color<E>>,
+        bins: usize,
+        range: RangeInclusive<f32>,
+    ) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let empty = Self::new(bins, range);
+        colors
+            .into_par_iter()
+            .fold(
+                || empty.clone(),
+                |mut histogram, color| {
+                    histogram.add(luminance_of(color));
+                    histogram
+                },
+            )
+            .reduce(
+                || empty.clone(),
+                |mut histogram, other| {
+                    histogram.merge(&other);
+                    histogram
+                },
+            )
+    }
+
+    /// Histograms of the three components of `colors` converted to the
+    /// encoding `H`, one per component with the matching range of `ranges`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or any range is empty.
+    pub fn channels<E, H>(
+        colors: impl IntoIterator<Item = Color<E>>,
+        bins: usize,
+        ranges: [RangeInclusive<f32>; 3],
+    ) -> [Self; 3]
+    where
+        E: ColorEncoding,
+        H: ColorEncoding<Repr = Vec3>,
+        H::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let mut histograms = ranges.map(|range| Self::new(bins, range));
+        colors
+            .into_iter()
+            .for_each(|color| add_components(&mut histograms, components_of::<E, H>(color)));
+        histograms
+    }
+
+    /// Histograms of the three components of `colors` converted to the
+    /// encoding `H`, counted in parallel.
+    ///
+    /// See [`Histogram::channels`].
+    #[cfg(feature = "rayon")]
+    pub fn par_channels<E, H>(
+        colors: impl IntoParallelIterator<Item = Color<E>>,
+        bins: usize,
+        ranges: [RangeInclusive<f32>; 3],
+    ) -> [Self; 3]
+    where
+        E: ColorEncoding,
+        H: ColorEncoding<Repr = Vec3>,
+        H::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let empty = ranges.map(|range| Self::new(bins, range));
+        colors
+            .into_par_iter()
+            .fold(
+                || empty.clone(),
+                |mut histograms, color| {
+                    add_components(&mut histograms, components_of::<E, H>(color));
+                    histograms
+                },
+            )
+            .reduce(
+                || empty.clone(),
+                |mut histograms, others| {
+                    histograms
+                        .iter_mut()
+                        .zip(&others)
+                        .for_each(|(histogram, other)| histogram.merge(other));
+                    histograms
+                },
+            )
+    }
+
+    /// Count `value`.
+    #[inline]
+    pub fn add(&mut self, value: f32) {
+        if let Some(bin) = self.bin_of(value) {
+            self.counts[bin] += 1;
+        }
+    }
+
+    /// Add the counts of `other`, a histogram of other values with the same
+    /// bins, like one of another part of an image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the histograms have different bins or ranges.
+    pub fn merge(&mut self, other: &Self) {
+        assert!(
+            self.counts.len() == other.counts.len() && self.range() == other.range(),
+            "merged histograms must have the same bins"
+        );
+        self.counts
+            .iter_mut()
+            .zip(&other.counts)
+            .for_each(|(count, other)| *count += other);
+    }
+
+    /// The bin `value` falls into, or `None` if it is NaN.
+    pub fn bin_of(&self, value: f32) -> Option<usize> {
+        (!value.is_nan()).then(|| {
+            let position = (value - self.min) / (self.max - self.min) * self.counts.len() as f32;
+            (position.max(0.0) as usize).min(self.counts.len() - 1)
+        })
+    }
+
+    /// The range of values counted in `bin`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin` is out of bounds.
+    pub fn bin_range(&self, bin: usize) -> Range<f32> {
+        assert!(bin < self.counts.len(), "histogram bin out of bounds");
+        let width = (self.max - self.min) / self.counts.len() as f32;
+        self.min + bin as f32 * width..self.min + (bin + 1) as f32 * width
+    }
+
+    /// The number of values in each bin.
+    #[inline]
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// The number of values counted.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|&count| count as u64).sum()
+    }
+
+    /// The range of values the bins span.
+    #[inline]
+    pub fn range(&self) -> RangeInclusive<f32> {
+        self.min..=self.max
+    }
+}
+
+/// The relative luminance (CIE Y) of `color`.
+fn luminance_of<E>(color: Color<E>) -> f32
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    raw.y
+}
+
+/// The components of `color` converted to the encoding `H`.
+fn components_of<E, H>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    H: ColorEncoding<Repr = Vec3>,
+    H::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, alpha) = E::src_transform_raw(color.repr);
+    <H::LinearSpace as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    H::dst_transform_raw(raw, alpha)
+}
+
+/// Count each of `components` in the histogram of its component.
+fn add_components(histograms: &mut [Histogram; 3], components: Vec3) {
+    histograms
+        .iter_mut()
+        .zip(components.to_array())
+        .for_each(|(histogram, value)| histogram.add(value));
+}
@@ -0,0 +1,543 @@
+//! Reading the lookup table transforms of ICC profiles.
+//!
+//! Output profiles, like those of printing conditions, describe their device
+//! with lookup tables rather than matrices: `AToB` tables map device values to
+//! the profile connection space (PCS), and `BToA` tables map the PCS back to
+//! device values, one of each per rendering intent. [`IccProfile`] reads these
+//! tables from ICC v2 and v4 profiles, in the `lut8Type`, `lut16Type`,
+//! `lutAToBType` and `lutBToAType` formats, and evaluates them.
+//!
+//! The PCS is CIELAB or CIE XYZ under D50. [`IccProfile::color_to_pcs`] and
+//! [`IccProfile::pcs_to_color`] convert between it and [`Color`]s, adapting
+//! the white point with the Bradford transform. Absolute colorimetric
+//! rendering uses the colorimetric tables, scaled by the media white point of
+//! the profile. Matrix/TRC profiles are not supported.
+//!
+//! See [`crate::cmyk`] for separating colors with a CMYK output profile.
+
+pub(crate) mod read;
+
+use self::read::{Curve, read_fixed_array, read_u16, read_u32, read_values};
+use crate::calibration::ColorMatrix;
+use crate::colorchecker::{lab_d50, lab_to_xyz, xyz_to_lab};
+use crate::details::encodings::CieXyz;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::white::{D50, D65};
+use crate::{Color, ColorEncoding};
+
+use glam::{Mat3, Vec3};
+
+/// The most device channels a profile may have.
+pub const MAX_CHANNELS: usize = 8;
+
+/// A rendering intent, picking the tables of a profile.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderingIntent {
+    /// Compress the gamut to keep the overall look.
+    #[default]
+    Perceptual,
+    /// Reproduce colors inside the gamut exactly, relative to the white of
+    /// the medium, and clip the others.
+    RelativeColorimetric,
+    /// Keep colors saturated, at the cost of accuracy.
+    Saturation,
+    /// Reproduce colors inside the gamut exactly, including the white of the
+    /// medium, and clip the others.
+    AbsoluteColorimetric,
+}
+
+impl RenderingIntent {
+    /// The index of the tables of the intent.
+    #[inline]
+    fn table(self) -> usize {
+        match self {
+            RenderingIntent::AbsoluteColorimetric => 1,
+            intent => intent as usize,
+        }
+    }
+}
+
+/// The profile connection space of a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pcs {
+    /// CIELAB under D50.
+    Lab,
+    /// CIE XYZ under D50.
+    Xyz,
+}
+
+/// The lookup table transforms of an ICC profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    color_space: [u8; 4],
+    channels: usize,
+    pcs: Pcs,
+    /// The media white point, from the `wtpt` tag.
+    media_white: Vec3,
+    /// The `AToB0`, `AToB1` and `AToB2` tables.
+    a_to_b: [Option<Lut>; 3],
+    /// The `BToA0`, `BToA1` and `BToA2` tables.
+    b_to_a: [Option<Lut>; 3],
+}
+
+impl IccProfile {
+    /// Read the lookup tables of the ICC profile `data`, or return `None` if
+    /// it isn't a profile, or a table is malformed.
+    ///
+    /// Profiles without lookup tables are read, but can't transform colors.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        (data.len() >= 132 && data.get(36..40)? == b"acsp").then_some(())?;
+        let color_space: [u8; 4] = data[16..20].try_into().ok()?;
+        let channels = channels_of(color_space)?;
+        let pcs = match &data[20..24] {
+            b"Lab " => Some(Pcs::Lab),
+            b"XYZ " => Some(Pcs::Xyz),
+            _ => None,
+        }?;
+
+        let (a_to_b, b_to_a, media_white) = read::tags(data)?.into_iter().try_fold(
+            ([None, None, None], [None, None, None], D50),
+            |(mut a_to_b, mut b_to_a, mut media_white), (signature, tag)| {
+                let lut = |inputs, outputs, pcs_in| {
+                    let tag = tag?;
+                    let mut lut = Lut::parse(tag, pcs_in)
+                        .filter(|lut| lut.inputs == inputs && lut.outputs == outputs)?;
+                    lut.legacy_lab = pcs == Pcs::Lab && &tag[..4] == b"mft2";
+                    Some(lut)
+                };
+                match signature {
+                    [b'A', b'2', b'B', n @ b'0'..=b'2'] => {
+                        a_to_b[(n - b'0') as usize] = Some(lut(channels, 3, false)?);
+                    }
+                    [b'B', b'2', b'A', n @ b'0'..=b'2'] => {
+                        b_to_a[(n - b'0') as usize] = Some(lut(3, channels, pcs == Pcs::Xyz)?);
+                    }
+                    [b'w', b't', b'p', b't'] => media_white = read::xyz(tag?)?,
+                    _ => {}
+                }
+                Some((a_to_b, b_to_a, media_white))
+            },
+        )?;
+
+        Some(Self {
+            color_space,
+            channels,
+            pcs,
+            media_white,
+            a_to_b,
+            b_to_a,
+        })
+    }
+
+    /// The signature of the device color space, like `*b"CMYK"`.
+    #[inline]
+    pub fn color_space(&self) -> [u8; 4] {
+        self.color_space
+    }
+
+    /// The number of device channels.
+    #[inline]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The profile connection space.
+    #[inline]
+    pub fn pcs(&self) -> Pcs {
+        self.pcs
+    }
+
+    /// The CIE XYZ value of the white of the medium under D50, or the D50
+    /// white of the PCS if the profile doesn't have one.
+    #[inline]
+    pub fn media_white(&self) -> Vec3 {
+        self.media_white
+    }
+
+    /// Whether the profile can map device values to the PCS and back.
+    #[inline]
+    pub fn has_luts(&self) -> bool {
+        self.a_to_b[0].is_some() && self.b_to_a[0].is_some()
+    }
+
+    /// The PCS value of the device values `device`, or `None` if the profile
+    /// has no `AToB` table.
+    ///
+    /// Tables missing for `intent` fall back to the perceptual one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `device` doesn't have [`Self::channels`] values.
+    pub fn to_pcs(&self, device: &[f32], intent: RenderingIntent) -> Option<Vec3> {
+        assert_eq!(device.len(), self.channels, "wrong number of channels");
+        let lut = self.a_to_b[intent.table()]
+            .as_ref()
+            .or(self.a_to_b[0].as_ref())?;
+        let mut out = [0.0; MAX_CHANNELS];
+        lut.apply(device, &mut out);
+        let pcs = self.decode_pcs(Vec3::from_slice(&out), lut.legacy_lab);
+        Some(match intent {
+            RenderingIntent::AbsoluteColorimetric => self.scale_pcs(pcs, self.media_white / D50),
+            _ => pcs,
+        })
+    }
+
+    /// The device values of the PCS value `pcs`, or `None` if the profile has
+    /// no `BToA` table.
+    ///
+    /// The first [`Self::channels`] values are used. Tables missing for
+    /// `intent` fall back to the perceptual one.
+    pub fn from_pcs(&self, pcs: Vec3, intent: RenderingIntent) -> Option<[f32; MAX_CHANNELS]> {
+        let lut = self.b_to_a[intent.table()]
+            .as_ref()
+            .or(self.b_to_a[0].as_ref())?;
+        let pcs = match intent {
+            RenderingIntent::AbsoluteColorimetric => self.scale_pcs(pcs, D50 / self.media_white),
+            _ => pcs,
+        };
+        let mut out = [0.0; MAX_CHANNELS];
+        lut.apply(&self.encode_pcs(pcs, lut.legacy_lab).to_array(), &mut out);
+        Some(out)
+    }
+
+    /// The PCS value of `color`.
+    pub fn color_to_pcs<E>(&self, color: Color<E>) -> Vec3
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        match self.pcs {
+            Pcs::Lab => lab_d50(color),
+            Pcs::Xyz => {
+                let (mut xyz, _) = E::src_transform_raw(color.repr);
+                <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut xyz);
+                ColorMatrix::bradford(D65, D50).matrix * xyz
+            }
+        }
+    }
+
+    /// The color of the PCS value `pcs`.
+    pub fn pcs_to_color(&self, pcs: Vec3) -> Color<CieXyz> {
+        let xyz = match self.pcs {
+            Pcs::Lab => lab_to_xyz(pcs, D50),
+            Pcs::Xyz => pcs,
+        };
+        Color::from_repr(ColorMatrix::bradford(D50, D65).matrix * xyz)
+    }
+
+    /// The PCS value `pcs` with its CIE XYZ value scaled by `scale`.
+    fn scale_pcs(&self, pcs: Vec3, scale: Vec3) -> Vec3 {
+        match self.pcs {
+            Pcs::Lab => xyz_to_lab(lab_to_xyz(pcs, D50) * scale, D50),
+            Pcs::Xyz => pcs * scale,
+        }
+    }
+
+    /// The PCS value of the table output `encoded`.
+    fn decode_pcs(&self, encoded: Vec3, legacy_lab: bool) -> Vec3 {
+        match self.pcs {
+            Pcs::Lab => {
+                let encoded = if legacy_lab {
+                    encoded * (65535.0 / 65280.0)
+                } else {
+                    encoded
+                };
+                Vec3::new(100.0, 255.0, 255.0) * encoded - Vec3::new(0.0, 128.0, 128.0)
+            }
+            Pcs::Xyz => encoded * (65535.0 / 32768.0),
+        }
+    }
+
+    /// The table input of the PCS value `pcs`.
+    fn encode_pcs(&self, pcs: Vec3, legacy_lab: bool) -> Vec3 {
+        match self.pcs {
+            Pcs::Lab => {
+                let encoded = (pcs + Vec3::new(0.0, 128.0, 128.0)) / Vec3::new(100.0, 255.0, 255.0);
+                if legacy_lab {
+                    encoded * (65280.0 / 65535.0)
+                } else {
+                    encoded
+                }
+            }
+            Pcs::Xyz => pcs * (32768.0 / 65535.0),
+        }
+    }
+}
+
+/// The number of channels of the color space `signature`.
+fn channels_of(signature: [u8; 4]) -> Option<usize> {
+    match &signature {
+        b"GRAY" => Some(1),
+        b"RGB " | b"CMY " | b"Lab " | b"XYZ " | b"HSV " | b"HLS " | b"YCbr" | b"Luv " => Some(3),
+        b"CMYK" => Some(4),
+        [n @ (b'2'..=b'8'), b'C', b'L', b'R'] => Some((n - b'0') as usize),
+        _ => None,
+    }
+}
+
+/// A lookup table transform, a sequence of curves, matrices and a
+/// multidimensional table.
+#[derive(Debug, Clone, PartialEq)]
+struct Lut {
+    inputs: usize,
+    outputs: usize,
+    stages: Vec<Stage>,
+    /// Whether the PCS side uses the legacy 16-bit CIELAB encoding of
+    /// `lut16Type`.
+    legacy_lab: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Curves(Vec<Curve>),
+    Matrix(Mat3, Vec3),
+    Clut(Clut),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clut {
+    grid: Vec<usize>,
+    outputs: usize,
+    /// The values, with the first input varying slowest.
+    values: Vec<f32>,
+}
+
+impl Lut {
+    /// Parse a `lut8Type`, `lut16Type`, `lutAToBType` or `lutBToAType` tag.
+    ///
+    /// The matrix of `lut8Type` and `lut16Type` only applies if `xyz_input`.
+    fn parse(tag: &[u8], xyz_input: bool) -> Option<Self> {
+        match tag.get(..4)? {
+            b"mft1" => Self::parse_mft(tag, 1, xyz_input),
+            b"mft2" => Self::parse_mft(tag, 2, xyz_input),
+            b"mAB " => Self::parse_mab(tag, false),
+            b"mBA " => Self::parse_mab(tag, true),
+            _ => None,
+        }
+    }
+
+    fn parse_mft(tag: &[u8], precision: usize, xyz_input: bool) -> Option<Self> {
+        let (inputs, outputs, points) = (
+            *tag.get(8)? as usize,
+            *tag.get(9)? as usize,
+            *tag.get(10)? as usize,
+        );
+        check_channels(inputs, outputs)?;
+        let (input_entries, output_entries, mut at) = if precision == 1 {
+            (256, 256, 48)
+        } else {
+            (read_u16(tag, 48)? as usize, read_u16(tag, 50)? as usize, 52)
+        };
+        (input_entries >= 2 && output_entries >= 2 && points >= 2).then_some(())?;
+
+        let mut stages = Vec::new();
+        if xyz_input && inputs == 3 {
+            let matrix = Mat3::from_cols_array(&read_fixed_array(tag, 12)?).transpose();
+            stages.push(Stage::Matrix(matrix, Vec3::ZERO));
+        }
+        let tables = |count: usize, entries: usize, at: &mut usize| {
+            let curves = (0..count)
+                .map(|_| {
+                    let table = read_values(tag, *at, entries, precision)?;
+                    *at += entries * precision;
+                    Some(Curve::Table(table))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Stage::Curves(curves))
+        };
+        stages.push(tables(inputs, input_entries, &mut at)?);
+        let grid = vec![points; inputs];
+        let clut = Clut::read(tag, at, grid, outputs, precision)?;
+        at += clut.values.len() * precision;
+        stages.push(Stage::Clut(clut));
+        stages.push(tables(outputs, output_entries, &mut at)?);
+
+        Some(Self {
+            inputs,
+            outputs,
+            stages,
+            legacy_lab: false,
+        })
+    }
+
+    fn parse_mab(tag: &[u8], b_to_a: bool) -> Option<Self> {
+        let (inputs, outputs) = (*tag.get(8)? as usize, *tag.get(9)? as usize);
+        check_channels(inputs, outputs)?;
+        let offset = |at: usize| read_u32(tag, at).map(|offset| offset as usize);
+        let (b, matrix, m, clut, a) = (
+            offset(12)?,
+            offset(16)?,
+            offset(20)?,
+            offset(24)?,
+            offset(28)?,
+        );
+
+        // The channels between the CLUT and the matrix.
+        let middle = if b_to_a { inputs } else { outputs };
+        let curves = |at: usize, count: usize| read_curves(tag, at, count).map(Stage::Curves);
+        let clut_stage = |at: usize| Clut::read_mab(tag, at, inputs, outputs).map(Stage::Clut);
+        let matrix_stage = |at: usize| {
+            (middle == 3).then_some(())?;
+            let values: [f32; 12] = read_fixed_array(tag, at)?;
+            let matrix = Mat3::from_cols_slice(&values[..9]).transpose();
+            Some(Stage::Matrix(matrix, Vec3::from_slice(&values[9..])))
+        };
+
+        let stages = if b_to_a {
+            [
+                Some(curves(b, inputs)?),
+                optional_stage(matrix, matrix_stage)?,
+                optional_stage(m, |at| curves(at, inputs))?,
+                optional_stage(clut, clut_stage)?,
+                optional_stage(a, |at| curves(at, outputs))?,
+            ]
+        } else {
+            [
+                optional_stage(a, |at| curves(at, inputs))?,
+                optional_stage(clut, clut_stage)?,
+                optional_stage(m, |at| curves(at, outputs))?,
+                optional_stage(matrix, matrix_stage)?,
+                Some(curves(b, outputs)?),
+            ]
+        };
+        // Without a CLUT, the channels can't change.
+        (clut != 0 || inputs == outputs).then_some(())?;
+
+        Some(Self {
+            inputs,
+            outputs,
+            stages: stages.into_iter().flatten().collect(),
+            legacy_lab: false,
+        })
+    }
+
+    /// Apply the transform to `input`, writing the first `self.outputs`
+    /// values of `output`.
+    fn apply(&self, input: &[f32], output: &mut [f32; MAX_CHANNELS]) {
+        let mut values = [0.0; MAX_CHANNELS];
+        values[..self.inputs].copy_from_slice(&input[..self.inputs]);
+        let (values, _) =
+            self.stages
+                .iter()
+                .fold(
+                    (values, self.inputs),
+                    |(mut values, channels), stage| match stage {
+                        Stage::Curves(curves) => {
+                            values
+                                .iter_mut()
+                                .zip(curves)
+                                .for_each(|(value, curve)| *value = curve.apply(*value));
+                            (values, channels)
+                        }
+                        Stage::Matrix(matrix, offset) => {
+                            let result = *matrix * Vec3::from_slice(&values) + *offset;
+                            values[..3].copy_from_slice(&result.to_array());
+                            (values, channels)
+                        }
+                        Stage::Clut(clut) => {
+                            let mut result = [0.0; MAX_CHANNELS];
+                            clut.apply(&values[..channels], &mut result);
+                            (result, clut.outputs)
+                        }
+                    },
+                );
+        *output = values.map(|value| value.clamp(0.0, 1.0));
+    }
+}
+
+impl Clut {
+    /// Read a table of `grid` points per input, with `precision` bytes per
+    /// value, at `at`.
+    fn read(
+        tag: &[u8],
+        at: usize,
+        grid: Vec<usize>,
+        outputs: usize,
+        precision: usize,
+    ) -> Option<Self> {
+        let count = grid
+            .iter()
+            .try_fold(outputs, |count, points| count.checked_mul(*points))?;
+        let values = read_values(tag, at, count, precision)?;
+        Some(Self {
+            grid,
+            outputs,
+            values,
+        })
+    }
+
+    /// Read the table of a `lutAToBType` or `lutBToAType` tag at `at`.
+    fn read_mab(tag: &[u8], at: usize, inputs: usize, outputs: usize) -> Option<Self> {
+        let grid: Vec<usize> = tag
+            .get(at..at + inputs)?
+            .iter()
+            .map(|&points| points as usize)
+            .collect();
+        let precision = *tag.get(at + 16)? as usize;
+        (!grid.contains(&0) && matches!(precision, 1 | 2)).then_some(())?;
+        Self::read(tag, at + 20, grid, outputs, precision)
+    }
+
+    /// Interpolate the table at `input` multilinearly.
+    fn apply(&self, input: &[f32], output: &mut [f32; MAX_CHANNELS]) {
+        // The cell containing `input`, the distances to its next corner along
+        // each dimension, and the position of `input` in it.
+        let (base, strides, fractions, _) = (0..self.grid.len()).rev().fold(
+            (0, [0; MAX_CHANNELS], [0.0; MAX_CHANNELS], self.outputs),
+            |(base, mut strides, mut fractions, stride), dimension| {
+                let points = self.grid[dimension];
+                let position = input[dimension].clamp(0.0, 1.0) * (points - 1) as f32;
+                let index = (position as usize).min(points.saturating_sub(2));
+                fractions[dimension] = position - index as f32;
+                strides[dimension] = if points > 1 { stride } else { 0 };
+                (base + index * stride, strides, fractions, stride * points)
+            },
+        );
+
+        (0..1usize << self.grid.len()).for_each(|corner| {
+            let (weight, at) = (0..self.grid.len()).fold((1.0, base), |(weight, at), dimension| {
+                if corner & (1 << dimension) != 0 {
+                    (weight * fractions[dimension], at + strides[dimension])
+                } else {
+                    (weight * (1.0 - fractions[dimension]), at)
+                }
+            });
+            if weight > 0.0 {
+                output
+                    .iter_mut()
+                    .zip(&self.values[at..at + self.outputs])
+                    .for_each(|(out, value)| *out += weight * value);
+            }
+        });
+    }
+}
+
+/// Check the channel counts of a table.
+fn check_channels(inputs: usize, outputs: usize) -> Option<()> {
+    (1..=MAX_CHANNELS).contains(&inputs).then_some(())?;
+    (1..=MAX_CHANNELS).contains(&outputs).then_some(())
+}
+
+/// The stage `stage` reads at `offset`, `Some(None)` if `offset` is zero,
+/// or `None` if the stage is malformed.
+fn optional_stage(
+    offset: usize,
+    stage: impl FnOnce(usize) -> Option<Stage>,
+) -> Option<Option<Stage>> {
+    match offset {
+        0 => Some(None),
+        offset => stage(offset).map(Some),
+    }
+}
+
+/// Read `count` curves starting at `at`, each aligned to 4 bytes.
+fn read_curves(tag: &[u8], at: usize, count: usize) -> Option<Vec<Curve>> {
+    let (curves, _) =
+        (0..count).try_fold((Vec::with_capacity(count), at), |(mut curves, at), _| {
+            let (curve, size) = read::curve(tag, at)?;
+            curves.push(curve);
+            Some((curves, at + size.next_multiple_of(4)))
+        })?;
+    Some(curves)
+}
@@ -0,0 +1,139 @@
+//! Reading the tag table, the XYZ and curve tags and the numbers of ICC
+//! profiles, for lookup table and matrix/TRC profiles alike.
+
+use glam::Vec3;
+
+/// A tag of a profile: its signature, and its data or `None` if it lies
+/// outside the profile.
+pub(crate) type Tag<'a> = ([u8; 4], Option<&'a [u8]>);
+
+/// A one-dimensional curve of a `curveType` or `parametricCurveType` tag.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Curve {
+    Identity,
+    Gamma(f32),
+    /// Samples evenly spaced over `0.0..=1.0`.
+    Table(Vec<f32>),
+    /// The function type and its parameters `g, a, b, c, d, e, f`.
+    Parametric(u16, [f32; 7]),
+}
+
+impl Curve {
+    /// Evaluate the curve at `x`, clamped to `0.0..=1.0`.
+    pub(crate) fn apply(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            Curve::Identity => x,
+            Curve::Gamma(gamma) => x.powf(*gamma),
+            Curve::Table(table) => {
+                let position = x * (table.len() - 1) as f32;
+                let index = (position as usize).min(table.len() - 2);
+                let t = position - index as f32;
+                table[index] + (table[index + 1] - table[index]) * t
+            }
+            Curve::Parametric(function, [g, a, b, c, d, e, f]) => {
+                let power = |base: f32| base.max(0.0).powf(*g);
+                match function {
+                    0 => power(x),
+                    1 if x >= -b / a => power(a * x + b),
+                    1 => 0.0,
+                    2 if x >= -b / a => power(a * x + b) + c,
+                    2 => *c,
+                    3 if x >= *d => power(a * x + b),
+                    3 => c * x,
+                    _ if x >= *d => power(a * x + b) + e,
+                    _ => c * x + f,
+                }
+            }
+        }
+    }
+}
+
+/// The tags of the profile `data`, or `None` if its tag table is malformed.
+pub(crate) fn tags(data: &[u8]) -> Option<Vec<Tag<'_>>> {
+    let count = read_u32(data, 128)? as usize;
+    (0..count)
+        .map(|entry| {
+            let at = 132 + 12 * entry;
+            let signature = data.get(at..at + 4)?.try_into().ok()?;
+            let offset = read_u32(data, at + 4)? as usize;
+            let size = read_u32(data, at + 8)? as usize;
+            Some((signature, data.get(offset..offset.checked_add(size)?)))
+        })
+        .collect()
+}
+
+/// The value of an `XYZType` tag.
+pub(crate) fn xyz(tag: &[u8]) -> Option<Vec3> {
+    (tag.get(..4)? == b"XYZ ").then_some(())?;
+    read_fixed_array(tag, 8).map(Vec3::from_array)
+}
+
+/// Read the `curveType` or `parametricCurveType` curve at `at`, and its size
+/// in bytes.
+pub(crate) fn curve(tag: &[u8], at: usize) -> Option<(Curve, usize)> {
+    match tag.get(at..at + 4)? {
+        b"curv" => {
+            let entries = read_u32(tag, at + 8)? as usize;
+            let curve = match entries {
+                0 => Curve::Identity,
+                1 => Curve::Gamma(read_u16(tag, at + 12)? as f32 / 256.0),
+                _ => Curve::Table(read_values(tag, at + 12, entries, 2)?),
+            };
+            Some((curve, 12 + 2 * entries))
+        }
+        b"para" => {
+            let function = read_u16(tag, at + 8)?;
+            let count = [1, 3, 4, 5, 7].get(function as usize)?;
+            let mut parameters = [0.0; 7];
+            read_fixed_into(tag, at + 12, &mut parameters[..*count])?;
+            Some((Curve::Parametric(function, parameters), 12 + 4 * count))
+        }
+        _ => None,
+    }
+}
+
+/// Read `count` unsigned values of `precision` bytes at `at`, normalized to
+/// `0.0..=1.0`.
+pub(crate) fn read_values(
+    tag: &[u8],
+    at: usize,
+    count: usize,
+    precision: usize,
+) -> Option<Vec<f32>> {
+    let bytes = tag.get(at..at.checked_add(count.checked_mul(precision)?)?)?;
+    Some(match precision {
+        1 => bytes.iter().map(|&value| value as f32 / 255.0).collect(),
+        _ => bytes
+            .chunks_exact(2)
+            .map(|value| u16::from_be_bytes([value[0], value[1]]) as f32 / 65535.0)
+            .collect(),
+    })
+}
+
+pub(crate) fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+pub(crate) fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+pub(crate) fn read_s15_fixed16(data: &[u8], at: usize) -> Option<f32> {
+    Some(i32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?) as f32 / 65536.0)
+}
+
+/// Read `N` consecutive `s15Fixed16Number`s at `at`.
+pub(crate) fn read_fixed_array<const N: usize>(data: &[u8], at: usize) -> Option<[f32; N]> {
+    let mut values = [0.0; N];
+    read_fixed_into(data, at, &mut values)?;
+    Some(values)
+}
+
+/// Read consecutive `s15Fixed16Number`s at `at` into `values`.
+pub(crate) fn read_fixed_into(data: &[u8], at: usize, values: &mut [f32]) -> Option<()> {
+    values.iter_mut().enumerate().try_for_each(|(i, value)| {
+        *value = read_s15_fixed16(data, at + 4 * i)?;
+        Some(())
+    })
+}
@@ -0,0 +1,151 @@
+//! Driving addressable RGB LEDs.
+//!
+//! Strips of WS2812 and similar LEDs take the PWM duty cycles of each LED in
+//! [`EncodedLedGrbU8`]. The light of an LED, and the current it draws, are
+//! proportional to its duty cycles, so dimming a strip or keeping it within
+//! the power budget of its supply has to scale linear light. Scaling the
+//! sRGB-encoded values of a color instead shifts its hue and lightness.
+//!
+//! [`LedLimits`] scales colors down by a global brightness, limits each
+//! channel, for example to balance the white of the LEDs, and keeps whole
+//! frames within a power budget with [`encode_leds`].
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::EncodedLedGrbU8;
+//! use colstodian::led::{LedLimits, encode_leds};
+//!
+//! let frame = [Color::srgb_u8(255, 255, 255); 60];
+//! let mut leds = [Color::<EncodedLedGrbU8>::BLACK; 60];
+//!
+//! // A supply that can light half the strip at full white.
+//! let limits = LedLimits {
+//!     max_power: 0.5,
+//!     ..LedLimits::default()
+//! };
+//! encode_leds(&frame, &limits, &mut leds);
+//! assert_eq!(leds[0], Color::encoded_led_grb_u8(128, 128, 128));
+//!
+//! // Half brightness halves the light, the sRGB-encoded value would be 188.
+//! let limits = LedLimits {
+//!     brightness: 0.5,
+//!     ..LedLimits::default()
+//! };
+//! assert_eq!(
+//!     frame[0].to_led(&limits),
+//!     Color::encoded_led_grb_u8(128, 128, 128)
+//! );
+//! ```
+//!
+//! [`EncodedLedGrbU8`]: crate::details::encodings::EncodedLedGrbU8
+
+use crate::details::encodings::EncodedLedGrbU8;
+use crate::details::linear_spaces::Srgb;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+/// Limits on the light of LEDs, applied in linear light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LedLimits {
+    /// The global brightness, scaling all channels, in `0.0..=1.0`.
+    pub brightness: f32,
+    /// The highest duty cycle of the red, green and blue channels, in
+    /// `0.0..=1.0`.
+    ///
+    /// Each channel is scaled so that its full level reaches this, which
+    /// keeps the ratios of its levels.
+    pub channel_max: Vec3,
+    /// The power budget of a frame, as a fraction of the power of all LEDs at
+    /// full white, used by [`encode_leds`].
+    pub max_power: f32,
+}
+
+impl Default for LedLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl LedLimits {
+    /// No limits.
+    pub const NONE: Self = Self {
+        brightness: 1.0,
+        channel_max: Vec3::ONE,
+        max_power: 1.0,
+    };
+
+    /// The scale of the linear red, green and blue components.
+    #[inline]
+    fn scale(&self) -> Vec3 {
+        self.channel_max.clamp(Vec3::ZERO, Vec3::ONE) * self.brightness.clamp(0.0, 1.0)
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The LED duty cycles of this color, with the brightness and channel
+    /// limits of `limits` applied.
+    ///
+    /// The power budget only applies to whole frames, see [`encode_leds`].
+    #[inline]
+    pub fn to_led(self, limits: &LedLimits) -> Color<EncodedLedGrbU8> {
+        encode(limited(self, limits))
+    }
+}
+
+/// Encode the frame `src` into the LED duty cycles `dst`, with the limits of
+/// `limits` applied.
+///
+/// If the frame would draw more than [`LedLimits::max_power`], all LEDs are
+/// dimmed alike to fit. Power is taken to be proportional to the sum of the
+/// duty cycles.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` differ in length.
+pub fn encode_leds<E>(src: &[Color<E>], limits: &LedLimits, dst: &mut [Color<EncodedLedGrbU8>])
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination differ in size"
+    );
+    let power: f32 = src
+        .iter()
+        .map(|color| limited(*color, limits).element_sum())
+        .sum();
+    let budget = limits.max_power.max(0.0) * 3.0 * src.len() as f32;
+    let scale = if power > budget { budget / power } else { 1.0 };
+    src.iter()
+        .zip(dst)
+        .for_each(|(src, dst)| *dst = encode(limited(*src, limits) * scale));
+}
+
+/// The linear components of `color` in `0.0..=1.0`, scaled by `limits`.
+#[inline]
+fn limited<E>(color: Color<E>, limits: &LedLimits) -> Vec3
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    raw.clamp(Vec3::ZERO, Vec3::ONE) * limits.scale()
+}
+
+/// `linear` quantized to LED duty cycles.
+#[inline(always)]
+fn encode(linear: Vec3) -> Color<EncodedLedGrbU8> {
+    Color::from_repr(EncodedLedGrbU8::dst_transform_raw(linear, 1.0))
+}
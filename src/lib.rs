@@ -123,13 +123,190 @@ pub mod details {
     /// The underlying data representations ([`ColorRepr`][traits::ColorRepr]s)
     /// used by different [`ColorEncoding`][traits::ColorEncoding]s.
     pub mod reprs;
+
+    /// Control over how float components are quantized to integer components.
+    pub mod quantize;
 }
 
 pub(crate) use details::*;
 
+mod macros;
+
+mod white;
+
+/// Transfer functions (OETFs and EOTFs) on bare values.
+pub mod transfer;
+
+/// Opacity and operations on the alpha of colors.
+pub mod opacity;
+
+/// Conversion constants as plain data for hand-written shaders.
+pub mod uniforms;
+
 /// Support for custom color spaces with user-defined primaries and white points.
 pub mod custom;
 
+/// Mapping between color encodings and CICP code points (ITU-T H.273).
+pub mod cicp;
+
+/// Helpers for the PNG color chunks `cICP`, `iCCP`, `sRGB`, `gAMA` and `cHRM`.
+pub mod png;
+
+/// Interop with the OpenEXR `chromaticities` header attribute.
+pub mod openexr;
+
+/// ANSI terminal escape sequences for [`Color`]s.
+pub mod ansi;
+
+/// Named color lookup.
+pub mod named;
+
+/// Palette generation and fixed palettes.
+pub mod palette;
+
+/// Color harmonies.
+pub mod harmony;
+
+/// Text contrast metrics and contrast-aware palette adjustment.
+pub mod contrast;
+
+/// The HCT color space and Material You tonal palettes.
+pub mod hct;
+
+/// Gamut size and coverage.
+pub mod gamut;
+
+/// HDR10 static metadata.
+pub mod hdr10;
+
+/// Hue-preserving brightness adjustments of HDR colors.
+pub mod hdr_brightness;
+
+/// Tone mapping for target displays.
+pub mod tone_map;
+
+/// Compensation for dim and dark viewing surrounds.
+pub mod surround;
+
+/// Conversions between integer bit depths.
+pub mod bit_depth;
+
+/// Dithering to low-bit grayscale encodings.
+pub mod dither;
+
+/// Driving addressable RGB LEDs.
+pub mod led;
+
+/// Splitting colors into the channels of RGBW and RGBWW lighting fixtures.
+pub mod rgbw;
+
+/// CIE xy and brightness for smart lights.
+pub mod smart_light;
+
+/// Fixed-point sRGB and matrix conversions for microcontrollers without an FPU.
+pub mod fixed;
+
+/// Chromaticity coordinates, correlated color temperature, Duv and whiteness.
+pub mod chromaticity;
+
+/// ColorChecker chart reference values and color difference evaluation.
+pub mod colorchecker;
+
+/// Reflectance spectra, their colors under different illuminants and metamerism.
+pub mod spectral;
+
+/// Spectral locus, Planckian locus and gamut outlines for chromaticity diagrams.
+pub mod diagram;
+
+/// Color correction matrices and fitting them to measurements.
+pub mod calibration;
+
+/// Profile connection spaces that conversions are chained through.
+pub mod pcs;
+
+/// Camera RAW color transforms from DNG metadata.
+pub mod dng;
+
+/// Multi-stop color gradients.
+#[cfg(feature = "std")]
+pub mod gradient;
+
+/// Reading the lookup table transforms of ICC profiles.
+#[cfg(feature = "std")]
+pub mod icc;
+
+/// CMYK separation, with device CMYK and with ICC output profiles.
+#[cfg(feature = "std")]
+pub mod cmyk;
+
+/// Soft proofing of output profiles on displays.
+#[cfg(feature = "std")]
+pub mod proof;
+
+/// Keyframe color animation.
+#[cfg(feature = "std")]
+pub mod track;
+
+/// Mapping scalar values to colors, as in heatmaps.
+#[cfg(feature = "std")]
+pub mod value_map;
+
+/// Histograms of color buffers.
+#[cfg(feature = "std")]
+pub mod histogram;
+
+/// Statistics of color buffers.
+#[cfg(feature = "std")]
+pub mod stats;
+
+/// Pairwise color difference matrices.
+#[cfg(feature = "std")]
+pub mod distance;
+
+/// Vectorscope, waveform and zebra data for grading scopes and overlays.
+#[cfg(feature = "std")]
+pub mod scope;
+
+/// 2D slices of color spaces for color pickers.
+#[cfg(feature = "std")]
+pub mod picker;
+
+/// Display profiles applied as the last step of conversion to a display.
+#[cfg(feature = "std")]
+pub mod display;
+
+/// Conversion pipelines that fuse linear steps.
+#[cfg(feature = "std")]
+pub mod pipeline;
+
+/// Measuring the precision lost by conversion chains.
+#[cfg(feature = "std")]
+pub mod precision;
+
+/// Running conversion pipelines on the GPU.
+#[cfg(feature = "wgpu")]
+pub mod gpu;
+
+/// Shader code generation for encodings and conversions.
+#[cfg(feature = "std")]
+pub mod codegen;
+
+/// Typed image buffers.
+#[cfg(feature = "std")]
+pub mod buffer;
+
+/// Chroma subsampling of color buffers.
+#[cfg(feature = "std")]
+pub mod chroma;
+
+/// Views of colors in external memory.
+#[cfg(feature = "bytemuck")]
+pub mod view;
+
+/// Conversions to and from the `Color` type of the `godot` crate.
+#[cfg(feature = "godot")]
+pub mod godot;
+
 /// Contains a basic set of [`ColorEncoding`]s to get most people going.
 ///
 /// These are all re-exported from inside the [`details::encodings`]
@@ -152,18 +329,39 @@ pub mod basic_encodings {
 #[doc(inline)]
 pub use color::Color;
 
+#[doc(inline)]
+pub use color::Hex;
+
+#[doc(inline)]
+pub use color::ParseHexError;
+
 #[doc(inline)]
 pub use traits::ColorEncoding;
 
+#[doc(inline)]
+pub use traits::EncodingInfo;
+
 #[doc(inline)]
 pub use traits::WorkingEncoding;
 
 #[cfg(any(test, feature = "approx"))]
 mod approx_impls;
 
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls;
+
 #[doc(inline)]
 pub use traits::PerceptualEncoding;
 
+#[doc(inline)]
+pub use traits::AlphaEncoding;
+
+#[doc(inline)]
+pub use opacity::Opacity;
+
+#[doc(inline)]
+pub use traits::{HueInterpolation, PolarEncoding};
+
 /// Like [`Into`] but specialized for use with `colstodian` [`Color`] types.
 ///
 /// This trait exists so that functions can accept colors in a variety of
@@ -175,11 +373,20 @@ pub use traits::PerceptualEncoding;
 ///
 /// See [the docs of the `convert` method on `Color`][Color::convert] for more.
 ///
+/// Plain values are accepted as well, and interpreted in the only encoding
+/// that makes sense for them:
+///
+/// * `u8` tuples and arrays of three or four components are
+///   [`EncodedSrgbU8`](basic_encodings::EncodedSrgbU8) or
+///   [`EncodedSrgbaU8`](basic_encodings::EncodedSrgbaU8).
+/// * `f32` tuples and arrays of three or four components are *linear*
+///   [`Srgb`](basic_encodings::Srgb) or [`Srgba`](basic_encodings::Srgba).
+///
 /// # Example
 ///
 /// ```
 /// # use colstodian::*;
-/// # use colstodian::basic_encodings::LinearSrgb;
+/// # use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
 /// type MyColor = Color<LinearSrgb>;
 ///
 /// fn test_fn(input: impl ColorInto<MyColor>) {
@@ -193,6 +400,8 @@ pub use traits::PerceptualEncoding;
 ///
 /// test_fn(Color::srgb_u8(107, 174, 130));
 /// test_fn(Color::srgb_f32(0.41961, 0.68235, 0.5098));
+/// test_fn((107, 174, 130));
+/// test_fn("#6bae82".parse::<Color<SrgbU8>>().unwrap());
 /// ```
 pub trait ColorInto<DstCol> {
     fn color_into(self) -> DstCol;
@@ -212,3 +421,29 @@ where
         self.convert()
     }
 }
+
+macro_rules! impl_color_into {
+    ($($src:ty => $enc:ident),+) => {$(
+        impl<DstEnc> ColorInto<Color<DstEnc>> for $src
+        where
+            DstEnc: ColorEncoding + ConvertFrom<details::encodings::$enc>,
+            DstEnc::LinearSpace: LinearConvertFromRaw<details::linear_spaces::Srgb>,
+        {
+            #[inline(always)]
+            fn color_into(self) -> Color<DstEnc> {
+                Color::<details::encodings::$enc>::from(self).convert()
+            }
+        }
+    )+};
+}
+
+impl_color_into!(
+    [u8; 3] => EncodedSrgbU8,
+    (u8, u8, u8) => EncodedSrgbU8,
+    [u8; 4] => EncodedSrgbaU8,
+    (u8, u8, u8, u8) => EncodedSrgbaU8,
+    [f32; 3] => Srgb,
+    (f32, f32, f32) => Srgb,
+    [f32; 4] => Srgba,
+    (f32, f32, f32, f32) => Srgba
+);
@@ -0,0 +1,46 @@
+/// Assert that two colors, in any encodings, look the same.
+///
+/// Both colors are compared with [`Color::delta_e_ok`](crate::Color::delta_e_ok),
+/// scaled by `100.0` to the scale of CIE ΔE, on which `1.0` is about the
+/// smallest visible difference. The assertion passes if the difference is at
+/// most the tolerance `de`, which defaults to `2.0`. On failure, both colors
+/// are printed as hex and with their components, along with the measured
+/// difference.
+///
+/// # Examples
+///
+/// ```
+/// use colstodian::details::encodings::Oklch;
+/// use colstodian::{Color, assert_color_eq};
+///
+/// let purple = Color::srgb_u8(107, 70, 193);
+///
+/// assert_color_eq!(purple, purple.convert::<Oklch>());
+/// assert_color_eq!(purple, Color::srgb_u8(110, 70, 190), de = 1.0);
+/// ```
+///
+/// ```should_panic
+/// # use colstodian::{Color, assert_color_eq};
+/// assert_color_eq!(Color::srgb_u8(255, 0, 0), Color::srgb_u8(0, 0, 255));
+/// ```
+#[macro_export]
+macro_rules! assert_color_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_color_eq!($left, $right, de = 2.0)
+    };
+    ($left:expr, $right:expr, de = $de:expr $(,)?) => {{
+        let (left, right, de) = ($left, $right, $de);
+        let delta_e = left.delta_e_ok(right) * 100.0;
+        if !(delta_e <= de) {
+            panic!(
+                "assertion `left ≈ right` failed: ΔE {} > {}\n  left: {} {:?}\n right: {} {:?}",
+                delta_e,
+                de,
+                left.to_hex(),
+                left,
+                right.to_hex(),
+                right,
+            );
+        }
+    }};
+}
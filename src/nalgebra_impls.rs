@@ -0,0 +1,33 @@
+// Conversions between working encoding colors and nalgebra vectors
+use crate::Color;
+use crate::traits::WorkingEncoding;
+
+use glam::{DVec3, DVec4, Vec3, Vec4};
+use nalgebra::{Vector3, Vector4};
+
+macro_rules! impl_nalgebra {
+    ($($repr:ident, $vector:ident<$t:ty> { $($c:ident),+ };)+) => {$(
+        impl<E: WorkingEncoding<Repr = $repr>> From<Color<E>> for $vector<$t> {
+            /// The components of the color.
+            #[inline]
+            fn from(color: Color<E>) -> Self {
+                $vector::new($(color.repr.$c),+)
+            }
+        }
+
+        impl<E: WorkingEncoding<Repr = $repr>> From<$vector<$t>> for Color<E> {
+            /// A color with the components of the vector.
+            #[inline]
+            fn from(vector: $vector<$t>) -> Self {
+                Color::from_repr($repr::new($(vector.$c),+))
+            }
+        }
+    )+};
+}
+
+impl_nalgebra!(
+    Vec3, Vector3<f32> { x, y, z };
+    Vec4, Vector4<f32> { x, y, z, w };
+    DVec3, Vector3<f64> { x, y, z };
+    DVec4, Vector4<f64> { x, y, z, w };
+);
@@ -0,0 +1,153 @@
+//! Opacity and operations on the alpha of colors.
+//!
+//! An [`Opacity`] is a coverage from `0.0` (transparent) to `1.0` (opaque),
+//! kept in that range so that it can't silently turn into a gain on color.
+//!
+//! The alpha operations on [`Color`] work on any [`AlphaEncoding`] and keep
+//! the color: with straight alpha only the alpha component changes, while the
+//! color components of premultiplied encodings, see
+//! [`ColorEncoding::IS_PREMULTIPLIED`](crate::ColorEncoding::IS_PREMULTIPLIED),
+//! are scaled along with it.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::{Color, Opacity};
+//! use colstodian::details::encodings::SrgbaPremultiplied;
+//! use glam::Vec4;
+//!
+//! let straight = Color::linear_srgba(1.0, 0.5, 0.0, 0.8);
+//! let half = Opacity::new(0.5);
+//!
+//! assert_eq!(straight.faded(half).repr, Vec4::new(1.0, 0.5, 0.0, 0.4));
+//!
+//! let premultiplied = straight.convert::<SrgbaPremultiplied>();
+//! assert_eq!(premultiplied.faded(half).repr, Vec4::new(0.4, 0.2, 0.0, 0.4));
+//!
+//! // A layer at 50% in a group at 50% covers a quarter.
+//! assert_eq!(half * half, Opacity::new(0.25));
+//! ```
+
+use crate::{AlphaEncoding, Color};
+
+use core::ops::{Mul, MulAssign};
+
+/// A coverage from `0.0` (transparent) to `1.0` (opaque).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Opacity(f32);
+
+impl Opacity {
+    /// Fully transparent.
+    pub const TRANSPARENT: Self = Opacity(0.0);
+    /// Fully opaque.
+    pub const OPAQUE: Self = Opacity(1.0);
+
+    /// An opacity of `value`, clamped to `0.0..=1.0`.
+    ///
+    /// NaN is taken as transparent.
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        if value >= 0.0 {
+            Opacity(value.min(1.0))
+        } else {
+            Opacity(0.0)
+        }
+    }
+
+    /// The opacity as a number in `0.0..=1.0`.
+    #[inline]
+    pub const fn get(self) -> f32 {
+        self.0
+    }
+
+    /// Whether this is fully opaque.
+    #[inline]
+    pub fn is_opaque(self) -> bool {
+        self.0 == 1.0
+    }
+
+    /// Whether this is fully transparent.
+    #[inline]
+    pub fn is_transparent(self) -> bool {
+        self.0 == 0.0
+    }
+
+    /// The fraction let through, `1.0` minus this opacity.
+    #[inline]
+    pub fn complement(self) -> Self {
+        Opacity(1.0 - self.0)
+    }
+
+    /// The combined coverage of a layer with this opacity over one with the
+    /// opacity `under`.
+    ///
+    /// This is the alpha that compositing the two layers results in.
+    #[inline]
+    pub fn over(self, under: Self) -> Self {
+        Opacity(self.0 + under.0 * (1.0 - self.0))
+    }
+}
+
+impl From<Opacity> for f32 {
+    #[inline]
+    fn from(opacity: Opacity) -> Self {
+        opacity.0
+    }
+}
+
+/// The opacity of a layer with the opacity `self` inside a group with the
+/// opacity `rhs`.
+impl Mul for Opacity {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Opacity(self.0 * rhs.0)
+    }
+}
+
+impl MulAssign for Opacity {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        self.0 *= rhs.0;
+    }
+}
+
+impl<E: AlphaEncoding> Color<E> {
+    /// The alpha as an [`Opacity`].
+    #[inline]
+    pub fn opacity(self) -> Opacity {
+        Opacity::new(self.alpha())
+    }
+
+    /// This color with its alpha set to `opacity`, keeping the
+    /// unpremultiplied color.
+    #[inline]
+    pub fn with_opacity(self, opacity: Opacity) -> Self {
+        self.with_alpha(opacity.get())
+    }
+
+    /// This color drawn at `opacity`, i.e. with its alpha multiplied by it.
+    ///
+    /// A premultiplied color faded to transparent has no color left.
+    #[inline]
+    pub fn faded(self, opacity: Opacity) -> Self {
+        self.with_alpha(self.alpha() * opacity.get())
+    }
+
+    /// This color with its alpha multiplied by `factor` and clamped to
+    /// `0.0..=1.0`.
+    ///
+    /// Unlike [`Color::faded`], `factor` can be above `1.0` to make a color
+    /// more opaque.
+    #[inline]
+    pub fn multiplied_alpha(self, factor: f32) -> Self {
+        self.with_opacity(Opacity::new(self.alpha() * factor))
+    }
+
+    /// This color made fully opaque, keeping the unpremultiplied color.
+    #[inline]
+    pub fn opaque(self) -> Self {
+        self.with_alpha(1.0)
+    }
+}
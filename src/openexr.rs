@@ -0,0 +1,141 @@
+//! Interop with the OpenEXR `chromaticities` header attribute.
+//!
+//! OpenEXR files store the primaries and white point of their RGB channels in
+//! the `chromaticities` attribute. Files without it are BT.709 with a D65 white
+//! point.
+//!
+//! With the `exr` feature enabled, [`Chromaticities`] converts to and from the
+//! attribute type of the [`exr`](https://docs.rs/exr) crate.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::details::linear_spaces::{AcesCg, Srgb};
+//! use colstodian::openexr::Chromaticities;
+//!
+//! // Writing a file from `AcesCg` working data.
+//! let header = Chromaticities::of::<AcesCg>();
+//!
+//! // Reading the file back.
+//! assert!(header.is::<AcesCg>());
+//! assert!(!header.is::<Srgb>());
+//! ```
+
+use crate::custom::CustomColorSpace;
+use crate::details::traits::LinearColorSpace;
+use crate::white::D65_XY;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The CIE xy chromaticities stored in an OpenEXR `chromaticities` attribute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Chromaticities {
+    /// The red primary.
+    pub red: [f32; 2],
+    /// The green primary.
+    pub green: [f32; 2],
+    /// The blue primary.
+    pub blue: [f32; 2],
+    /// The white point.
+    pub white: [f32; 2],
+}
+
+/// BT.709 primaries with a D65 white point, the OpenEXR default.
+impl Default for Chromaticities {
+    fn default() -> Self {
+        Self {
+            red: [0.64, 0.33],
+            green: [0.30, 0.60],
+            blue: [0.15, 0.06],
+            white: D65_XY,
+        }
+    }
+}
+
+impl Chromaticities {
+    /// The chromaticities of the linear color space `L`.
+    #[inline]
+    pub fn of<L: LinearColorSpace>() -> Self {
+        Self::from_custom_color_space(&CustomColorSpace::of::<L>())
+    }
+
+    /// The chromaticities of `space`.
+    pub fn from_custom_color_space(space: &CustomColorSpace) -> Self {
+        let [red, green, blue] = space.primaries_xy();
+        Self {
+            red,
+            green,
+            blue,
+            white: space.white_point_xy(),
+        }
+    }
+
+    /// The color space these chromaticities describe.
+    pub fn to_custom_color_space(&self) -> CustomColorSpace {
+        CustomColorSpace::from_primaries_and_white_point(
+            self.red,
+            self.green,
+            self.blue,
+            self.white[0],
+            self.white[1],
+        )
+    }
+
+    /// Whether these chromaticities describe the linear color space `L`.
+    ///
+    /// Chromaticities are compared with a tolerance of `1e-3`, which absorbs
+    /// the rounding found in files written by other tools.
+    pub fn is<L: LinearColorSpace>(&self) -> bool {
+        let other = Self::of::<L>();
+        [
+            (self.red, other.red),
+            (self.green, other.green),
+            (self.blue, other.blue),
+            (self.white, other.white),
+        ]
+        .into_iter()
+        .all(|(a, b)| (a[0] - b[0]).abs() < 1e-3 && (a[1] - b[1]).abs() < 1e-3)
+    }
+}
+
+impl From<CustomColorSpace> for Chromaticities {
+    #[inline]
+    fn from(space: CustomColorSpace) -> Self {
+        Self::from_custom_color_space(&space)
+    }
+}
+
+impl From<Chromaticities> for CustomColorSpace {
+    #[inline]
+    fn from(chromaticities: Chromaticities) -> Self {
+        chromaticities.to_custom_color_space()
+    }
+}
+
+#[cfg(feature = "exr")]
+impl From<Chromaticities> for exr::meta::attribute::Chromaticities {
+    fn from(c: Chromaticities) -> Self {
+        use exr::math::Vec2;
+
+        Self {
+            red: Vec2(c.red[0], c.red[1]),
+            green: Vec2(c.green[0], c.green[1]),
+            blue: Vec2(c.blue[0], c.blue[1]),
+            white: Vec2(c.white[0], c.white[1]),
+        }
+    }
+}
+
+#[cfg(feature = "exr")]
+impl From<exr::meta::attribute::Chromaticities> for Chromaticities {
+    fn from(c: exr::meta::attribute::Chromaticities) -> Self {
+        Self {
+            red: [c.red.0, c.red.1],
+            green: [c.green.0, c.green.1],
+            blue: [c.blue.0, c.blue.1],
+            white: [c.white.0, c.white.1],
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! Palette generation and fixed palettes.
+//!
+//! [`Palette`] holds a fixed set of colors, like the built-in palettes of
+//! classic hardware, and maps colors to their perceptually nearest entry.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::palette::{CategoricalOptions, categorical};
+//!
+//! // Eight colors for a chart, distinguishable with color vision deficiencies.
+//! let colors = categorical::<8>(&CategoricalOptions {
+//!     cvd_weight: 0.5,
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//! ```
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::palette::{RampOptions, tonal_ramp};
+//!
+//! // A 50–900 scale of ten tints and shades of a brand color.
+//! let blues = tonal_ramp::<_, 10>(Color::srgb_u8(37, 99, 235), &RampOptions::default());
+//! ```
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::palette::Palette;
+//!
+//! // A sunset orange on the Game Boy and the PICO-8.
+//! let orange = Color::srgb_u8(250, 120, 40);
+//! assert_eq!(Palette::GAME_BOY.nearest_index(orange), 2);
+//! assert_eq!(Palette::PICO_8.nearest(orange), Color::srgb_u8(255, 163, 0));
+//! ```
+
+use crate::details::encodings::{EncodedSrgbU8, Oklab, Oklch, Srgb};
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use core::ops::RangeInclusive;
+
+use glam::{Mat3, Vec2, Vec3};
+
+/// Options for [`categorical`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoricalOptions {
+    /// The range of [`Oklab`] lightness the colors are picked from.
+    ///
+    /// The default of `0.45..=0.85` keeps colors legible on both light and
+    /// dark backgrounds.
+    pub lightness: RangeInclusive<f32>,
+    /// How much distinctness under simulated color vision deficiencies counts,
+    /// from `0.0` (not at all) to `1.0` (exclusively).
+    ///
+    /// Protanopia, deuteranopia and tritanopia are simulated with the
+    /// matrices of Machado et al. (2009). A pair of colors is only as distinct
+    /// as under the deficiency that confuses it most.
+    pub cvd_weight: f32,
+}
+
+impl Default for CategoricalOptions {
+    fn default() -> Self {
+        Self {
+            lightness: 0.45..=0.85,
+            cvd_weight: 0.0,
+        }
+    }
+}
+
+/// Generate `N` categorical colors with maximal pairwise ΔEOK, or `None` if
+/// no sRGB color lies in `options.lightness`.
+///
+/// Colors are picked greedily from a grid of sRGB colors: the first is the
+/// most chromatic, each following one is farthest from all colors picked so
+/// far. The result is deterministic and a palette of `N` colors starts with
+/// the palette of fewer colors.
+pub fn categorical<const N: usize>(
+    options: &CategoricalOptions,
+) -> Option<[Color<EncodedSrgbU8>; N]> {
+    let mut colors = [Color::<EncodedSrgbU8>::BLACK; N];
+    pick_categorical(options, &mut colors).map(|()| colors)
+}
+
+/// Generate `count` categorical colors, as with [`categorical`].
+#[cfg(feature = "std")]
+pub fn categorical_vec(
+    count: usize,
+    options: &CategoricalOptions,
+) -> Option<Vec<Color<EncodedSrgbU8>>> {
+    let mut colors = vec![Color::<EncodedSrgbU8>::BLACK; count];
+    pick_categorical(options, &mut colors).map(|()| colors)
+}
+
+/// Fill `colors` with categorical colors, or return `None` if no sRGB color
+/// lies in `options.lightness`.
+fn pick_categorical(
+    options: &CategoricalOptions,
+    colors: &mut [Color<EncodedSrgbU8>],
+) -> Option<()> {
+    let candidates = || {
+        (0..GRID.pow(3)).filter_map(|i| {
+            let rgb =
+                [i / (GRID * GRID), i / GRID % GRID, i % GRID].map(|level| level as u8 * STEP);
+            let color = Color::<EncodedSrgbU8>::from_repr(rgb);
+            let sample = Sample::new(color.convert());
+            options
+                .lightness
+                .contains(&sample.normal.x)
+                .then_some((i as usize, color, sample))
+        })
+    };
+    candidates().next()?;
+
+    // The distance of each candidate to the nearest color picked so far.
+    let mut nearest = [f32::INFINITY; GRID.pow(3) as usize];
+    colors.iter_mut().enumerate().try_for_each(|(i, color)| {
+        let (_, picked, sample) = if i == 0 {
+            candidates().max_by(|(_, _, a), (_, _, b)| a.chroma().total_cmp(&b.chroma()))
+        } else {
+            candidates().max_by(|(a, ..), (b, ..)| nearest[*a].total_cmp(&nearest[*b]))
+        }?;
+        candidates().for_each(|(index, _, candidate)| {
+            nearest[index] = nearest[index].min(candidate.distance(&sample, options.cvd_weight));
+        });
+        *color = picked;
+        Some(())
+    })
+}
+
+/// Options for [`tonal_ramp`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RampOptions {
+    /// The [`Oklch`] lightness of the first, lightest step.
+    pub lightest: f32,
+    /// The [`Oklch`] lightness of the last, darkest step.
+    pub darkest: f32,
+}
+
+impl Default for RampOptions {
+    fn default() -> Self {
+        Self {
+            lightest: 0.97,
+            darkest: 0.25,
+        }
+    }
+}
+
+/// Generate a ramp of `N` tints and shades of `base`, from light to dark, like
+/// the 50–900 scales of design systems.
+///
+/// The steps are evenly spaced in [`Oklch`] lightness from
+/// `options.lightest` to `options.darkest` and all have the hue of `base`.
+/// Each step has the chroma of `base`, reduced as far as needed to fit in the
+/// sRGB gamut, so chroma never exceeds that of `base` and tapers off smoothly
+/// towards white and black.
+pub fn tonal_ramp<E, const N: usize>(
+    base: Color<E>,
+    options: &RampOptions,
+) -> [Color<EncodedSrgbU8>; N]
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, alpha) = E::src_transform_raw(base.repr);
+    <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    let [_, chroma, hue] = Oklch::dst_transform_raw(raw, alpha).to_array();
+
+    core::array::from_fn(|i| {
+        let t = i as f32 / (N - 1).max(1) as f32;
+        let lightness = options.lightest + (options.darkest - options.lightest) * t;
+        let fits = |c| Color::oklch(lightness, c, hue).fits_in::<Srgb>();
+        let chroma = if fits(chroma) {
+            chroma
+        } else {
+            let (low, _) = (0..CHROMA_STEPS).fold((0.0, chroma), |(low, high), _| {
+                let middle = (low + high) * 0.5;
+                if fits(middle) {
+                    (middle, high)
+                } else {
+                    (low, middle)
+                }
+            });
+            low
+        };
+        Color::oklch(lightness, chroma, hue).convert()
+    })
+}
+
+/// A fixed palette of sRGB colors, as used by indexed-color graphics.
+///
+/// Colors are mapped to the entry nearest to them in [`Oklab`], not in
+/// encoded RGB, which picks entries that look alike rather than ones with
+/// similar numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette<'a> {
+    colors: &'a [Color<EncodedSrgbU8>],
+}
+
+impl<'a> Palette<'a> {
+    /// The 16 colors of CGA text mode, also the default palette of EGA.
+    pub const CGA: Palette<'static> = Palette::new(&CGA_COLORS);
+    /// The 64 colors EGA can display, indexed by the `rgbRGB` bits of its
+    /// palette registers.
+    pub const EGA: Palette<'static> = Palette::new(&EGA_COLORS);
+    /// The 256 colors of the default VGA palette (mode 13h), with the 6-bit
+    /// DAC values scaled to 8 bits.
+    pub const VGA: Palette<'static> = Palette::new(&VGA_COLORS);
+    /// The four greens of the original Game Boy screen, from darkest to
+    /// lightest.
+    pub const GAME_BOY: Palette<'static> = Palette::new(&GAME_BOY_COLORS);
+    /// The 16 colors of the PICO-8 fantasy console.
+    pub const PICO_8: Palette<'static> = Palette::new(&PICO_8_COLORS);
+
+    /// A palette of `colors`.
+    #[inline]
+    pub const fn new(colors: &'a [Color<EncodedSrgbU8>]) -> Self {
+        Self { colors }
+    }
+
+    /// The colors of the palette.
+    #[inline]
+    pub const fn colors(&self) -> &'a [Color<EncodedSrgbU8>] {
+        self.colors
+    }
+
+    /// The number of colors.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Whether the palette has no colors.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The index of the entry perceptually nearest to `color`.
+    ///
+    /// Of several equally near entries, the first is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty.
+    pub fn nearest_index<E>(&self, color: Color<E>) -> usize
+    where
+        E: ColorEncoding,
+        Oklab: ConvertFrom<E>,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let color = color.convert::<Oklab>();
+        self.colors
+            .iter()
+            .map(|entry| color.distance(entry.convert()))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("palette is not empty")
+    }
+
+    /// The entry perceptually nearest to `color`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty.
+    #[inline]
+    pub fn nearest<E>(&self, color: Color<E>) -> Color<EncodedSrgbU8>
+    where
+        E: ColorEncoding,
+        Oklab: ConvertFrom<E>,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        self.colors[self.nearest_index(color)]
+    }
+}
+
+/// The number of bisection steps when fitting chroma into the gamut.
+const CHROMA_STEPS: usize = 20;
+
+/// The number of levels per component of the candidate grid.
+const GRID: u32 = 16;
+/// The distance between levels of the candidate grid.
+const STEP: u8 = 17;
+
+/// Machado et al. (2009) protanopia simulation in linear sRGB.
+const PROTANOPIA: Mat3 = Mat3::from_cols(
+    Vec3::new(0.152286, 0.114503, -0.003882),
+    Vec3::new(1.052583, 0.786281, -0.048116),
+    Vec3::new(-0.204868, 0.099216, 1.051998),
+);
+/// Machado et al. (2009) deuteranopia simulation in linear sRGB.
+const DEUTERANOPIA: Mat3 = Mat3::from_cols(
+    Vec3::new(0.367322, 0.280085, -0.011820),
+    Vec3::new(0.860646, 0.672501, 0.042940),
+    Vec3::new(-0.227968, 0.047413, 0.968881),
+);
+/// Machado et al. (2009) tritanopia simulation in linear sRGB.
+const TRITANOPIA: Mat3 = Mat3::from_cols(
+    Vec3::new(1.255528, -0.078411, 0.004733),
+    Vec3::new(-0.076749, 0.930809, 0.691367),
+    Vec3::new(-0.178779, 0.147602, 0.303900),
+);
+
+/// A candidate color in Oklab, as seen with normal vision and the simulated
+/// deficiencies.
+#[derive(Clone, Copy, Default)]
+struct Sample {
+    normal: Vec3,
+    deficient: [Vec3; 3],
+}
+
+impl Sample {
+    fn new(linear: Color<Srgb>) -> Self {
+        let oklab = |rgb: Vec3| {
+            Color::<Srgb>::from_repr(rgb.clamp(Vec3::ZERO, Vec3::ONE))
+                .convert::<Oklab>()
+                .repr
+        };
+        Self {
+            normal: oklab(linear.repr),
+            deficient: [PROTANOPIA, DEUTERANOPIA, TRITANOPIA].map(|m| oklab(m * linear.repr)),
+        }
+    }
+
+    fn chroma(&self) -> f32 {
+        Vec2::new(self.normal.y, self.normal.z).length()
+    }
+
+    fn distance(&self, other: &Self, cvd_weight: f32) -> f32 {
+        let normal = self.normal.distance(other.normal);
+        if cvd_weight == 0.0 {
+            normal
+        } else {
+            let deficient = self
+                .deficient
+                .iter()
+                .zip(&other.deficient)
+                .map(|(a, b)| a.distance(*b))
+                .fold(normal, f32::min);
+            normal + (deficient - normal) * cvd_weight
+        }
+    }
+}
+
+/// An sRGB color from a `0xRRGGBB` value.
+const fn rgb(hex: u32) -> Color<EncodedSrgbU8> {
+    Color::from_repr([(hex >> 16) as u8, (hex >> 8) as u8, hex as u8])
+}
+
+/// The CGA colors, in the order of their 4-bit `IRGB` indices.
+const CGA_COLORS: [Color<EncodedSrgbU8>; 16] = [
+    rgb(0x000000),
+    rgb(0x0000aa),
+    rgb(0x00aa00),
+    rgb(0x00aaaa),
+    rgb(0xaa0000),
+    rgb(0xaa00aa),
+    rgb(0xaa5500),
+    rgb(0xaaaaaa),
+    rgb(0x555555),
+    rgb(0x5555ff),
+    rgb(0x55ff55),
+    rgb(0x55ffff),
+    rgb(0xff5555),
+    rgb(0xff55ff),
+    rgb(0xffff55),
+    rgb(0xffffff),
+];
+
+/// The EGA colors: each component is the sum of `0xaa` for its primary bit
+/// and `0x55` for its secondary bit.
+const EGA_COLORS: [Color<EncodedSrgbU8>; 64] = {
+    // The level of the component with the given primary and secondary bits.
+    const fn level(index: usize, primary: usize, secondary: usize) -> u8 {
+        (index >> primary & 1) as u8 * 0xaa + (index >> secondary & 1) as u8 * 0x55
+    }
+
+    let mut colors = [Color::<EncodedSrgbU8>::BLACK; 64];
+    let mut index = 0;
+    while index < 64 {
+        colors[index] =
+            Color::from_repr([level(index, 2, 5), level(index, 1, 4), level(index, 0, 3)]);
+        index += 1;
+    }
+    colors
+};
+
+/// The default VGA palette: the CGA colors, a gray ramp, then 24 hues at
+/// three saturations and three intensities, then black.
+const VGA_COLORS: [Color<EncodedSrgbU8>; 256] = {
+    // The 6-bit DAC values of the gray ramp.
+    const GRAYS: [u8; 16] = [0, 5, 8, 11, 14, 17, 20, 24, 28, 32, 36, 40, 45, 50, 56, 63];
+    // The 6-bit component levels of each intensity and saturation, from the
+    // lowest to the highest.
+    const LEVELS: [[[u8; 5]; 3]; 3] = [
+        [
+            [0, 16, 31, 47, 63],
+            [31, 39, 47, 55, 63],
+            [45, 49, 54, 58, 63],
+        ],
+        [
+            [0, 7, 14, 21, 28],
+            [14, 17, 21, 24, 28],
+            [20, 22, 24, 26, 28],
+        ],
+        [[0, 4, 8, 12, 16], [8, 10, 12, 14, 16], [11, 12, 13, 15, 16]],
+    ];
+    // The level indices of each component around the hue circle, from blue
+    // through magenta, red, yellow, green and cyan.
+    const HUES: [[usize; 3]; 24] = [
+        [0, 0, 4],
+        [1, 0, 4],
+        [2, 0, 4],
+        [3, 0, 4],
+        [4, 0, 4],
+        [4, 0, 3],
+        [4, 0, 2],
+        [4, 0, 1],
+        [4, 0, 0],
+        [4, 1, 0],
+        [4, 2, 0],
+        [4, 3, 0],
+        [4, 4, 0],
+        [3, 4, 0],
+        [2, 4, 0],
+        [1, 4, 0],
+        [0, 4, 0],
+        [0, 4, 1],
+        [0, 4, 2],
+        [0, 4, 3],
+        [0, 4, 4],
+        [0, 3, 4],
+        [0, 2, 4],
+        [0, 1, 4],
+    ];
+    // 6-bit DAC values to 8 bits, replicating the top bits.
+    const fn widen(value: u8) -> u8 {
+        value << 2 | value >> 4
+    }
+
+    let mut colors = [Color::<EncodedSrgbU8>::BLACK; 256];
+    let mut index = 0;
+    while index < 16 {
+        colors[index] = CGA_COLORS[index];
+        colors[16 + index] = Color::from_repr([widen(GRAYS[index]); 3]);
+        index += 1;
+    }
+    let mut index = 0;
+    while index < 216 {
+        let levels = LEVELS[index / 72][index / 24 % 3];
+        let [r, g, b] = HUES[index % 24];
+        colors[32 + index] =
+            Color::from_repr([widen(levels[r]), widen(levels[g]), widen(levels[b])]);
+        index += 1;
+    }
+    colors
+};
+
+/// The Game Boy greens.
+const GAME_BOY_COLORS: [Color<EncodedSrgbU8>; 4] =
+    [rgb(0x0f380f), rgb(0x306230), rgb(0x8bac0f), rgb(0x9bbc0f)];
+
+/// The PICO-8 colors.
+const PICO_8_COLORS: [Color<EncodedSrgbU8>; 16] = [
+    rgb(0x000000),
+    rgb(0x1d2b53),
+    rgb(0x7e2553),
+    rgb(0x008751),
+    rgb(0xab5236),
+    rgb(0x5f574f),
+    rgb(0xc2c3c7),
+    rgb(0xfff1e8),
+    rgb(0xff004d),
+    rgb(0xffa300),
+    rgb(0xffec27),
+    rgb(0x00e436),
+    rgb(0x29adff),
+    rgb(0x83769c),
+    rgb(0xff77a8),
+    rgb(0xffccaa),
+];
@@ -0,0 +1,100 @@
+//! Profile connection spaces that conversions are chained through.
+//!
+//! Converting between two color spaces goes through a profile connection
+//! space (PCS), CIE XYZ by default. Linear conversions give the same result
+//! whatever the PCS, but steps applied on the way, like gamut mapping or
+//! adjustments of lightness and chroma, depend on it: in a perceptual PCS like
+//! Oklab, moving towards gray keeps the hue and lightness that XYZ would not.
+//!
+//! A [`ConnectionSpace`] is either CIE XYZ, Oklab, or a linear space given by
+//! a matrix from XYZ. It is taken by
+//! [`DynamicColor::convert_via`](crate::custom::DynamicColor::convert_via) and
+//! by the steps of conversion pipelines.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::custom::{CustomColorSpace, DynamicColor};
+//! use colstodian::details::linear_spaces::{Bt2020, Srgb};
+//! use colstodian::pcs::ConnectionSpace;
+//! use glam::Vec3;
+//!
+//! let green = DynamicColor::new(0.0, 1.0, 0.0, CustomColorSpace::of::<Bt2020>());
+//!
+//! // Halve the chroma in Oklab, on the way to sRGB.
+//! let srgb = CustomColorSpace::of::<Srgb>();
+//! let muted = green.convert_via(&srgb, ConnectionSpace::Oklab, |lab| {
+//!     Vec3::new(lab.x, lab.y * 0.5, lab.z * 0.5)
+//! });
+//!
+//! // Without a step, the PCS makes no difference.
+//! let plain = green.convert_via(&srgb, ConnectionSpace::Oklab, |lab| lab);
+//! let direct = green.convert_via(&srgb, ConnectionSpace::Xyz, |xyz| xyz);
+//! assert!(plain.value.abs_diff_eq(direct.value, 1e-4));
+//! assert!(!muted.value.abs_diff_eq(plain.value, 1e-2));
+//! ```
+
+use crate::ColorEncoding;
+use crate::calibration::ColorMatrix;
+use crate::details::encodings::Oklab;
+
+use glam::Vec3;
+
+/// The space that conversions are chained through.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConnectionSpace {
+    /// CIE XYZ with a D65 white point.
+    #[default]
+    Xyz,
+    /// [`Oklab`], a perceptual space whose components are lightness and two
+    /// opponent axes.
+    Oklab,
+    /// A linear space, given by the matrices from CIE XYZ to it and back.
+    /// Build it with [`ConnectionSpace::linear`].
+    Linear {
+        /// The matrix from CIE XYZ.
+        from_xyz: ColorMatrix,
+        /// The matrix to CIE XYZ, the inverse of `from_xyz`.
+        to_xyz: ColorMatrix,
+    },
+}
+
+impl ConnectionSpace {
+    /// The linear space that `from_xyz` maps CIE XYZ to, or `None` if
+    /// `from_xyz` can't be undone.
+    pub fn linear(from_xyz: ColorMatrix) -> Option<Self> {
+        let to_xyz = from_xyz.inverse()?;
+        Some(Self::Linear { from_xyz, to_xyz })
+    }
+
+    /// The CIE XYZ color `xyz` in this space.
+    #[inline]
+    pub fn encode(self, xyz: Vec3) -> Vec3 {
+        match self {
+            Self::Xyz => xyz,
+            Self::Oklab => Oklab::dst_transform_raw(xyz, 1.0),
+            Self::Linear { from_xyz, .. } => from_xyz.matrix * xyz + from_xyz.offset,
+        }
+    }
+
+    /// The CIE XYZ of the color `value` in this space.
+    #[inline]
+    pub fn decode(self, value: Vec3) -> Vec3 {
+        match self {
+            Self::Xyz => value,
+            Self::Oklab => Oklab::src_transform_raw(value).0,
+            Self::Linear { to_xyz, .. } => to_xyz.matrix * value + to_xyz.offset,
+        }
+    }
+
+    /// The matrices from CIE XYZ to this space and back, or `None` if it is
+    /// not linear.
+    #[inline]
+    pub fn matrices(self) -> Option<(ColorMatrix, ColorMatrix)> {
+        match self {
+            Self::Xyz => Some((ColorMatrix::IDENTITY, ColorMatrix::IDENTITY)),
+            Self::Oklab => None,
+            Self::Linear { from_xyz, to_xyz } => Some((from_xyz, to_xyz)),
+        }
+    }
+}
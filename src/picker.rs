@@ -0,0 +1,132 @@
+//! 2D slices of color spaces for color picker UIs.
+//!
+//! A [`Plane`] maps normalized picker coordinates to colors, like the a/b
+//! plane of Oklab at a fixed lightness or the saturation/value square of HSV
+//! at a fixed hue, and back with [`Plane::position`] to place a cursor.
+//! [`render`] rasterizes a plane into display-encoded pixels, masking colors
+//! that are out of the gamut of the display encoding.
+//!
+//! Coordinates are in `0.0..=1.0` from the top left corner, so `y` grows
+//! downward and planes put the brighter or more saturated end at the top.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbaU8;
+//! use colstodian::picker::{self, Plane};
+//!
+//! let plane = Plane::OklabAb { l: 0.7, extent: 0.4 };
+//! let pixels = picker::render::<SrgbaU8>(plane, 64, 64, Color::srgba_u8(0, 0, 0, 0));
+//!
+//! // The center is neutral gray, the corners are far out of the sRGB gamut.
+//! assert_eq!(pixels[(32, 32)].repr[3], 255);
+//! assert_eq!(pixels[(0, 0)], Color::srgba_u8(0, 0, 0, 0));
+//! ```
+
+use crate::buffer::ColorBuffer;
+use crate::details::encodings::{EncodedSrgbF32, Oklab, Oklch};
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use glam::{Vec2, Vec3};
+
+/// A 2D slice of a color space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Plane {
+    /// The a (`x`) and b (`y`, upward) plane of Oklab at lightness `l`, with
+    /// both in `-extent..=extent`.
+    OklabAb { l: f32, extent: f32 },
+    /// Oklch chroma (`x`, `0.0..=max_chroma`) and lightness (`y`, upward) at
+    /// hue `h` in degrees.
+    OklchChromaLightness { h: f32, max_chroma: f32 },
+    /// Oklch hue (`x`, `0.0..360.0` degrees) and chroma (`y`, upward,
+    /// `0.0..=max_chroma`) at lightness `l`.
+    OklchHueChroma { l: f32, max_chroma: f32 },
+    /// HSV saturation (`x`) and value (`y`, upward) of encoded sRGB at hue `h`
+    /// in degrees.
+    HsvSaturationValue { h: f32 },
+}
+
+impl Plane {
+    /// The color at the normalized position `(x, y)`.
+    pub fn color_at(self, x: f32, y: f32) -> Color<Oklab> {
+        let up = 1.0 - y;
+        match self {
+            Plane::OklabAb { l, extent } => {
+                Color::oklab(l, (x * 2.0 - 1.0) * extent, (up * 2.0 - 1.0) * extent)
+            }
+            Plane::OklchChromaLightness { h, max_chroma } => {
+                Color::oklch(up, x * max_chroma, h).convert()
+            }
+            Plane::OklchHueChroma { l, max_chroma } => {
+                Color::oklch(l, up * max_chroma, x * 360.0).convert()
+            }
+            Plane::HsvSaturationValue { h } => {
+                Color::<EncodedSrgbF32>::from_repr(hsv_to_rgb(h, x, up)).convert()
+            }
+        }
+    }
+
+    /// The normalized position of `color` in this plane, ignoring the
+    /// component that the plane holds fixed.
+    ///
+    /// Positions can lie outside `0.0..=1.0` for colors beyond the extent of
+    /// the plane.
+    pub fn position(self, color: Color<Oklab>) -> Vec2 {
+        let (x, up) = match self {
+            Plane::OklabAb { extent, .. } => {
+                let ab = (Vec2::new(color.a, color.b) / extent + 1.0) * 0.5;
+                (ab.x, ab.y)
+            }
+            Plane::OklchChromaLightness { max_chroma, .. } => {
+                let lch = color.convert::<Oklch>();
+                (lch.c / max_chroma, lch.l)
+            }
+            Plane::OklchHueChroma { max_chroma, .. } => {
+                let lch = color.convert::<Oklch>();
+                (lch.h / 360.0, lch.c / max_chroma)
+            }
+            Plane::HsvSaturationValue { .. } => {
+                let rgb = color.convert::<EncodedSrgbF32>().repr;
+                let (max, min) = (rgb.max_element(), rgb.min_element());
+                let saturation = if max > 0.0 { (max - min) / max } else { 0.0 };
+                (saturation, max)
+            }
+        };
+        Vec2::new(x, 1.0 - up)
+    }
+}
+
+/// Rasterize `plane` into a `width` by `height` buffer, sampling at pixel
+/// centers.
+///
+/// Pixels whose color doesn't fit in the gamut of `D` are set to `outside`,
+/// e.g. a transparent color.
+pub fn render<D>(plane: Plane, width: usize, height: usize, outside: Color<D>) -> ColorBuffer<D>
+where
+    D: ColorEncoding + ConvertFrom<Oklab>,
+    D::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    ColorBuffer::from_fn(width, height, |x, y| {
+        let color = plane.color_at(
+            (x as f32 + 0.5) / width as f32,
+            (y as f32 + 0.5) / height as f32,
+        );
+        if color.fits_in::<D>() {
+            color.convert()
+        } else {
+            outside
+        }
+    })
+}
+
+/// HSV with hue in degrees to RGB.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Vec3 {
+    let channel = |n: f32| {
+        let k = (n + h / 60.0).rem_euclid(6.0);
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    Vec3::new(channel(5.0), channel(3.0), channel(1.0))
+}
@@ -0,0 +1,418 @@
+//! Conversion pipelines that fuse linear steps.
+//!
+//! A [`Pipeline`] decodes colors of the encoding `Src`, runs a chain of steps
+//! on their linear components in the working color space `L`, and encodes the
+//! result to `Dst`. Matrix steps, including the conversions between the
+//! linear color spaces, are combined as they are added, so a run of adjacent
+//! matrices costs a single matrix multiplication per pixel. Non-linear steps,
+//! like grades or tone mapping, are plain functions, run on the linear
+//! components or in a [profile connection space](crate::pcs).
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::calibration::ColorMatrix;
+//! use colstodian::details::encodings::Bt2020;
+//! use colstodian::details::linear_spaces::Srgb;
+//! use colstodian::pipeline::Pipeline;
+//! use glam::{Mat3, Vec3};
+//!
+//! let pipeline = Pipeline::<Bt2020, SrgbU8, Srgb>::new()
+//!     .exposure(1.0)
+//!     .matrix(ColorMatrix::new(Mat3::from_diagonal(Vec3::new(1.0, 1.0, 0.9))))
+//!     .map(|rgb| rgb / (rgb + 1.0))
+//!     .exposure(1.0);
+//!
+//! // Decoding, the two matrices and the first exposure fuse into one
+//! // step, as do the second exposure and encoding.
+//! assert_eq!(pipeline.step_count(), 3);
+//!
+//! let color = pipeline.convert(Color::bt2020(0.5, 0.5, 0.5));
+//! assert_eq!(color, Color::srgb_u8(255, 255, 249));
+//! ```
+//!
+//! Images too large to hold converted in memory, like gigapixel scans or a
+//! stream of video frames, are converted a row or a chunk at a time with
+//! [`Pipeline::convert_rows`] and [`Pipeline::convert_stream`].
+//!
+//! ```
+//! use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+//! use colstodian::details::linear_spaces::Srgb;
+//! use colstodian::pipeline::Pipeline;
+//!
+//! // Raw 8-bit sRGB pixels in, raw 32-bit float linear pixels out.
+//! let input = [255u8; 3 * 1000];
+//! let mut output = Vec::new();
+//!
+//! let pipeline = Pipeline::<SrgbU8, LinearSrgb, Srgb>::new();
+//! let pixels = pipeline.convert_stream(&input[..], &mut output, 256).unwrap();
+//! assert_eq!(pixels, 1000);
+//! assert_eq!(output.len(), 12 * 1000);
+//! ```
+
+use crate::buffer::ColorBuffer;
+use crate::calibration::ColorMatrix;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::pcs::ConnectionSpace;
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "bytemuck")]
+use core::ops::ControlFlow;
+#[cfg(feature = "bytemuck")]
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use glam::{Mat3, Vec3};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// A non-linear step on linear components.
+type Function = Arc<dyn Fn(Vec3) -> Vec3 + Send + Sync>;
+
+#[derive(Clone)]
+enum Step {
+    Matrix(ColorMatrix),
+    Function(Function),
+}
+
+/// A chain of steps converting colors from `Src` to `Dst`, working on the
+/// linear components of the color space `L`.
+pub struct Pipeline<Src, Dst, L> {
+    steps: Vec<Step>,
+    /// The matrix steps since the last function, not yet in `steps`.
+    pending: ColorMatrix,
+    _encodings: PhantomData<fn(Src) -> (Dst, L)>,
+}
+
+impl<Src, Dst, L> Pipeline<Src, Dst, L>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    L: LinearColorSpace + LinearConvertFromRaw<Src::LinearSpace>,
+    Dst::LinearSpace: LinearConvertFromRaw<L>,
+{
+    /// A pipeline that only converts from `Src` to `Dst` through `L`.
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            pending: ColorMatrix::conversion::<Src::LinearSpace, L>(),
+            _encodings: PhantomData,
+        }
+    }
+
+    /// Add a matrix, fused with adjacent matrices.
+    pub fn matrix(self, matrix: ColorMatrix) -> Self {
+        Self {
+            pending: self.pending.then(matrix),
+            ..self
+        }
+    }
+
+    /// Scale the linear components by `ev` stops, fused with adjacent
+    /// matrices.
+    #[inline]
+    pub fn exposure(self, ev: f32) -> Self {
+        self.matrix(ColorMatrix::new(Mat3::from_diagonal(Vec3::splat(
+            ev.exp2(),
+        ))))
+    }
+
+    /// Add a non-linear step, like a grade or a tone mapping operator.
+    ///
+    /// Alpha is passed through unchanged.
+    pub fn map(mut self, f: impl Fn(Vec3) -> Vec3 + Send + Sync + 'static) -> Self {
+        if self.pending != ColorMatrix::IDENTITY {
+            self.steps.push(Step::Matrix(self.pending));
+        }
+        self.steps.push(Step::Function(Arc::new(f)));
+        Self {
+            pending: ColorMatrix::IDENTITY,
+            ..self
+        }
+    }
+
+    /// Add a non-linear step run in the profile connection space `pcs`, like
+    /// gamut mapping in Oklab.
+    ///
+    /// The conversions to and from linear connection spaces fuse with adjacent
+    /// matrices.
+    pub fn map_in(
+        self,
+        pcs: ConnectionSpace,
+        f: impl Fn(Vec3) -> Vec3 + Send + Sync + 'static,
+    ) -> Self
+    where
+        L: LinearConvertFromRaw<CieXYZ>,
+        CieXYZ: LinearConvertFromRaw<L>,
+    {
+        let to_xyz = ColorMatrix::conversion::<L, CieXYZ>();
+        let from_xyz = ColorMatrix::conversion::<CieXYZ, L>();
+        match pcs.matrices() {
+            Some((encode, decode)) => self
+                .matrix(to_xyz.then(encode))
+                .map(f)
+                .matrix(decode.then(from_xyz)),
+            None => self
+                .matrix(to_xyz)
+                .map(move |xyz| pcs.decode(f(pcs.encode(xyz))))
+                .matrix(from_xyz),
+        }
+    }
+
+    /// The number of steps each pixel goes through after fusion, not counting
+    /// decoding and encoding.
+    pub fn step_count(&self) -> usize {
+        self.steps.len() + usize::from(self.output() != ColorMatrix::IDENTITY)
+    }
+
+    /// Run the pipeline on one color.
+    #[inline]
+    pub fn convert(&self, color: Color<Src>) -> Color<Dst> {
+        self.run(&self.output(), color)
+    }
+
+    /// Run the pipeline on every pixel of `buffer`.
+    pub fn convert_buffer(&self, buffer: &ColorBuffer<Src>) -> ColorBuffer<Dst> {
+        let output = self.output();
+        buffer.map(|color| self.run(&output, color))
+    }
+
+    /// Run the pipeline on every pixel of `buffer` in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_convert_buffer(&self, buffer: &ColorBuffer<Src>) -> ColorBuffer<Dst>
+    where
+        Src::Repr: Send + Sync,
+        Dst::Repr: Send,
+    {
+        let output = self.output();
+        buffer.par_map(|color| self.run(&output, color))
+    }
+
+    /// Run the pipeline on `src`, writing the results to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    pub fn convert_slice(&self, src: &[Color<Src>], dst: &mut [Color<Dst>]) {
+        assert_eq!(src.len(), dst.len(), "the slices must have the same length");
+        let output = self.output();
+        dst.iter_mut()
+            .zip(src)
+            .for_each(|(dst, src)| *dst = self.run(&output, *src));
+    }
+
+    /// Run the pipeline on `src` in parallel, writing the results to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    #[cfg(feature = "rayon")]
+    pub fn par_convert_slice(&self, src: &[Color<Src>], dst: &mut [Color<Dst>])
+    where
+        Src::Repr: Send + Sync,
+        Dst::Repr: Send,
+    {
+        assert_eq!(src.len(), dst.len(), "the slices must have the same length");
+        let output = self.output();
+        dst.par_iter_mut()
+            .zip(src)
+            .for_each(|(dst, src)| *dst = self.run(&output, *src));
+    }
+
+    /// Run the pipeline on each of `rows`, passing the converted rows to
+    /// `f` in order.
+    ///
+    /// Only one converted row is held at a time, so the rows can come
+    /// straight from a decoder.
+    pub fn convert_rows<R>(
+        &self,
+        rows: impl IntoIterator<Item = R>,
+        mut f: impl FnMut(&[Color<Dst>]),
+    ) where
+        R: AsRef<[Color<Src>]>,
+    {
+        let output = self.output();
+        let mut converted = Vec::new();
+        rows.into_iter().for_each(|row| {
+            converted.clear();
+            converted.extend(row.as_ref().iter().map(|&color| self.run(&output, color)));
+            f(&converted);
+        });
+    }
+
+    /// Run the pipeline on each of `rows`, converting the pixels of each row
+    /// in parallel, as with [`Pipeline::convert_rows`].
+    #[cfg(feature = "rayon")]
+    pub fn par_convert_rows<R>(
+        &self,
+        rows: impl IntoIterator<Item = R>,
+        mut f: impl FnMut(&[Color<Dst>]),
+    ) where
+        R: AsRef<[Color<Src>]>,
+        Src::Repr: Send + Sync,
+        Dst::Repr: Send,
+    {
+        let output = self.output();
+        let mut converted = Vec::new();
+        rows.into_iter().for_each(|row| {
+            converted.clear();
+            converted.par_extend(
+                row.as_ref()
+                    .par_iter()
+                    .map(|&color| self.run(&output, color)),
+            );
+            f(&converted);
+        });
+    }
+
+    /// The matrices each pixel goes through after decoding, or `None` if the
+    /// pipeline has function steps.
+    pub(crate) fn matrices(&self) -> Option<Vec<ColorMatrix>> {
+        let mut matrices = self
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Matrix(matrix) => Some(*matrix),
+                Step::Function(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        matrices.push(self.output());
+        Some(matrices)
+    }
+
+    /// The pending matrices fused with the conversion to `Dst`.
+    fn output(&self) -> ColorMatrix {
+        self.pending
+            .then(ColorMatrix::conversion::<L, Dst::LinearSpace>())
+    }
+
+    fn run(&self, output: &ColorMatrix, color: Color<Src>) -> Color<Dst> {
+        let (raw, alpha) = Src::src_transform_raw(color.repr);
+        let raw = self.steps.iter().fold(raw, |raw, step| match step {
+            Step::Matrix(matrix) => matrix.matrix * raw + matrix.offset,
+            Step::Function(f) => f(raw),
+        });
+        Color::from_repr(Dst::dst_transform_raw(
+            output.matrix * raw + output.offset,
+            alpha,
+        ))
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<Src, Dst, L> Pipeline<Src, Dst, L>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    L: LinearColorSpace + LinearConvertFromRaw<Src::LinearSpace>,
+    Dst::LinearSpace: LinearConvertFromRaw<L>,
+    Src::Repr: bytemuck::Pod,
+    Dst::Repr: bytemuck::Pod,
+{
+    /// Run the pipeline on the raw pixels read from `reader`, laid out like
+    /// the repr of `Src`, writing the raw results laid out like the repr of
+    /// `Dst` to `writer`, `chunk_pixels` pixels at a time.
+    ///
+    /// Memory use is bounded by the chunk size, whatever the length of the
+    /// stream. Returns the number of pixels converted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if reading or writing fails, or if the stream ends within a
+    /// pixel, in which case the whole pixels before it are written.
+    pub fn convert_stream(
+        &self,
+        mut reader: impl Read,
+        mut writer: impl Write,
+        chunk_pixels: usize,
+    ) -> io::Result<u64> {
+        let chunk_pixels = chunk_pixels.max(1);
+        let pixel_size = core::mem::size_of::<Src::Repr>();
+        let mut bytes = Vec::with_capacity(chunk_pixels * pixel_size);
+        let mut src: Vec<Color<Src>> = vec![bytemuck::Zeroable::zeroed(); chunk_pixels];
+        let mut dst: Vec<Color<Dst>> = vec![bytemuck::Zeroable::zeroed(); chunk_pixels];
+
+        // Convert a chunk, returning the number of pixels converted.
+        let convert_chunk = || {
+            bytes.clear();
+            let filled = reader
+                .by_ref()
+                .take((chunk_pixels * pixel_size) as u64)
+                .read_to_end(&mut bytes)?;
+            let pixels = filled / pixel_size;
+            bytemuck::cast_slice_mut(&mut src[..pixels])
+                .copy_from_slice(&bytes[..pixels * pixel_size]);
+            self.convert_slice(&src[..pixels], &mut dst[..pixels]);
+            writer.write_all(bytemuck::cast_slice(&dst[..pixels]))?;
+
+            if filled.is_multiple_of(pixel_size) {
+                Ok(pixels)
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the stream ends within a pixel",
+                ))
+            }
+        };
+
+        // A chunk that isn't full ends the stream.
+        let converted =
+            core::iter::repeat_with(convert_chunk).try_fold(0, |count, chunk| match chunk {
+                Ok(pixels) if pixels == chunk_pixels => {
+                    ControlFlow::Continue(count + pixels as u64)
+                }
+                Ok(pixels) => ControlFlow::Break(Ok(count + pixels as u64)),
+                Err(error) => ControlFlow::Break(Err(error)),
+            });
+        match converted {
+            ControlFlow::Continue(count) => Ok(count),
+            ControlFlow::Break(result) => result,
+        }
+    }
+}
+
+impl<Src, Dst, L> Default for Pipeline<Src, Dst, L>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    L: LinearColorSpace + LinearConvertFromRaw<Src::LinearSpace>,
+    Dst::LinearSpace: LinearConvertFromRaw<L>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Src, Dst, L> Clone for Pipeline<Src, Dst, L> {
+    fn clone(&self) -> Self {
+        Self {
+            steps: self.steps.clone(),
+            pending: self.pending,
+            _encodings: PhantomData,
+        }
+    }
+}
+
+impl<Src: ColorEncoding, Dst: ColorEncoding, L> fmt::Debug for Pipeline<Src, Dst, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<&str> = self
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Matrix(_) => "matrix",
+                Step::Function(_) => "function",
+            })
+            .collect();
+        f.debug_struct("Pipeline")
+            .field("src", &Src::NAME)
+            .field("dst", &Dst::NAME)
+            .field("steps", &steps)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
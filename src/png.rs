@@ -0,0 +1,470 @@
+//! Helpers for the PNG color chunks `cICP`, `iCCP`, `sRGB`, `gAMA` and `cHRM`.
+//!
+//! [`PngColorInfo`] holds the decoded contents of these chunks. It can be built
+//! from an encoding when writing an image, or filled chunk by chunk when reading
+//! one, and is independent of any particular PNG library. The `*_chunk`
+//! methods return the raw chunk data, i.e. without length, type and CRC.
+//!
+//! With the `std` feature, encodings that `sRGB` can't express are also given
+//! an embedded ICC profile (`iCCP`) in matrix/TRC form, and read ones are
+//! parsed. The profile takes precedence over `sRGB`, `gAMA` and `cHRM` but not
+//! over `cICP`.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::png::PngColorInfo;
+//!
+//! // Writing.
+//! let info = PngColorInfo::for_encoding::<SrgbU8>();
+//! assert_eq!(info.cicp_chunk(), Some([1, 13, 0, 1]));
+//! assert_eq!(info.srgb_chunk(), Some([0]));
+//!
+//! // Reading.
+//! let mut decoded = PngColorInfo::default();
+//! decoded.read_chunk(*b"sRGB", &[0]);
+//! assert!(decoded.is::<SrgbU8>());
+//! ```
+
+use crate::cicp::{self, Cicp, CicpEncoding};
+use crate::custom::CustomColorSpace;
+use crate::quantize::round;
+#[cfg(feature = "std")]
+use crate::white::D50;
+#[cfg(feature = "std")]
+use crate::{
+    calibration::ColorMatrix,
+    display::{DisplayProfile, ToneCurve},
+    transfer::TransferFunction,
+};
+
+#[cfg(feature = "std")]
+use glam::{Mat3, Vec3};
+
+/// The `gAMA` value PNG uses as a fallback for the sRGB transfer function.
+const SRGB_GAMMA: u32 = 45455;
+/// The `gAMA` value of a linear transfer function.
+const LINEAR_GAMMA: u32 = 100_000;
+/// The keyword the `iCCP` chunk names generated profiles with.
+#[cfg(feature = "std")]
+const PROFILE_NAME: &[u8] = b"colstodian";
+
+/// The color information carried by the color chunks of a PNG file.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(not(feature = "std"), derive(Copy))]
+pub struct PngColorInfo {
+    /// The `cICP` triplet.
+    pub cicp: Option<Cicp>,
+    /// The rendering intent of the `sRGB` chunk.
+    pub srgb_intent: Option<u8>,
+    /// The `gAMA` value, i.e. the encoding gamma times `100000`.
+    pub gamma: Option<u32>,
+    /// The `cHRM` chromaticities of the white point followed by the red, green
+    /// and blue primaries.
+    pub chromaticities: Option<[[f32; 2]; 4]>,
+    /// The decompressed ICC profile of the `iCCP` chunk.
+    #[cfg(feature = "std")]
+    pub icc_profile: Option<Vec<u8>>,
+}
+
+impl PngColorInfo {
+    /// The chunks an image in the encoding `E` should be tagged with.
+    ///
+    /// `cICP` is always set. `sRGB`, `gAMA` and `cHRM` are added where they
+    /// can express the encoding, for decoders that do not support `cICP`, and
+    /// so is an ICC profile where `sRGB` can't.
+    pub fn for_encoding<E: CicpEncoding>() -> Self {
+        let cicp = E::CICP;
+        let srgb_intent = (cicp == Cicp::SRGB).then_some(0);
+        let gamma = match cicp.transfer_characteristics {
+            cicp::transfer::SRGB => Some(SRGB_GAMMA),
+            cicp::transfer::LINEAR => Some(LINEAR_GAMMA),
+            _ => None,
+        };
+        let chromaticities =
+            cicp::primaries_xy(cicp.color_primaries).map(|([r, g, b], w)| [w, r, g, b]);
+
+        Self {
+            cicp: Some(cicp),
+            srgb_intent,
+            gamma,
+            chromaticities,
+            #[cfg(feature = "std")]
+            icc_profile: srgb_intent.is_none().then(|| icc_profile(cicp)).flatten(),
+        }
+    }
+
+    /// Record the data of a chunk read from a PNG file.
+    ///
+    /// Returns `false` if the chunk is not a color chunk or its data is
+    /// malformed, in which case `self` is left unchanged.
+    pub fn read_chunk(&mut self, chunk_type: [u8; 4], data: &[u8]) -> bool {
+        match (&chunk_type, data) {
+            (b"cICP", &[primaries, transfer, matrix, full_range]) => {
+                self.cicp = Some(Cicp::new(primaries, transfer, matrix, full_range != 0));
+                true
+            }
+            (b"sRGB", &[intent]) => {
+                self.srgb_intent = Some(intent);
+                true
+            }
+            (b"gAMA", &[a, b, c, d]) => {
+                self.gamma = Some(u32::from_be_bytes([a, b, c, d]));
+                true
+            }
+            (b"cHRM", data) if data.len() == 32 => {
+                let value = |i: usize| {
+                    let bytes = [
+                        data[4 * i],
+                        data[4 * i + 1],
+                        data[4 * i + 2],
+                        data[4 * i + 3],
+                    ];
+                    u32::from_be_bytes(bytes) as f32 / 100_000.0
+                };
+                self.chromaticities =
+                    Some(core::array::from_fn(|i| [value(2 * i), value(2 * i + 1)]));
+                true
+            }
+            #[cfg(feature = "std")]
+            (b"iCCP", data) => read_iccp(data)
+                .map(|profile| self.icc_profile = Some(profile))
+                .is_some(),
+            _ => false,
+        }
+    }
+
+    /// The data of the `cICP` chunk.
+    pub fn cicp_chunk(&self) -> Option<[u8; 4]> {
+        self.cicp.map(|cicp| {
+            [
+                cicp.color_primaries,
+                cicp.transfer_characteristics,
+                cicp.matrix_coefficients,
+                cicp.full_range as u8,
+            ]
+        })
+    }
+
+    /// The data of the `sRGB` chunk.
+    pub fn srgb_chunk(&self) -> Option<[u8; 1]> {
+        self.srgb_intent.map(|intent| [intent])
+    }
+
+    /// The data of the `gAMA` chunk.
+    pub fn gama_chunk(&self) -> Option<[u8; 4]> {
+        self.gamma.map(u32::to_be_bytes)
+    }
+
+    /// The data of the `cHRM` chunk.
+    pub fn chrm_chunk(&self) -> Option<[u8; 32]> {
+        self.chromaticities.map(|xy| {
+            let mut data = [0; 32];
+            xy.as_flattened()
+                .iter()
+                .zip(data.chunks_exact_mut(4))
+                .for_each(|(value, out)| {
+                    out.copy_from_slice(&(round(value * 100_000.0) as u32).to_be_bytes());
+                });
+            data
+        })
+    }
+
+    /// The data of the `iCCP` chunk: the profile name, the compression method
+    /// and the zlib-compressed profile.
+    #[cfg(feature = "std")]
+    pub fn iccp_chunk(&self) -> Option<Vec<u8>> {
+        self.icc_profile.as_deref().map(|profile| {
+            [
+                PROFILE_NAME,
+                &[0, 0],
+                &miniz_oxide::deflate::compress_to_vec_zlib(profile, 10),
+            ]
+            .concat()
+        })
+    }
+
+    /// The CICP triplet the chunks describe, following the precedence rules of
+    /// the PNG specification.
+    ///
+    /// An ICC profile is recognized if it is in matrix/TRC form with the sRGB
+    /// or a linear transfer function. Without `cICP`, `iCCP` or `sRGB`, a
+    /// `gAMA` of `1/2.2` is taken to mean the sRGB transfer function and a
+    /// `gAMA` of `1.0` a linear one. Missing `cHRM` means BT.709 primaries.
+    pub fn effective_cicp(&self) -> Option<Cicp> {
+        self.cicp.or_else(|| match self.icc() {
+            Some(icc) => icc.and_then(|(space, transfer)| {
+                Some(Cicp::new(
+                    Cicp::color_primaries_of(&space)?,
+                    transfer?,
+                    cicp::matrix::IDENTITY,
+                    true,
+                ))
+            }),
+            None if self.srgb_intent.is_some() => Some(Cicp::SRGB),
+            None => self.legacy_cicp(),
+        })
+    }
+
+    /// Whether the chunks describe the encoding `E`.
+    #[inline]
+    pub fn is<E: CicpEncoding>(&self) -> bool {
+        self.effective_cicp() == Some(E::CICP)
+    }
+
+    /// The primaries and white point the chunks describe.
+    ///
+    /// Falls back to the ICC profile when `cICP` is absent, to `cHRM` when
+    /// `iCCP` and `sRGB` are absent as well, and to BT.709 primaries when no
+    /// chunk describes them.
+    pub fn to_custom_color_space(&self) -> Option<CustomColorSpace> {
+        self.cicp.map_or_else(
+            || match self.icc() {
+                Some(icc) => icc.map(|(space, _)| space),
+                None if self.srgb_intent.is_some() => Some(CustomColorSpace::default()),
+                None => Some(self.chromaticities_space().unwrap_or_default()),
+            },
+            Cicp::to_custom_color_space,
+        )
+    }
+
+    /// The CICP triplet of `gAMA` and `cHRM`.
+    fn legacy_cicp(&self) -> Option<Cicp> {
+        let transfer = match self.gamma? {
+            gamma if gamma.abs_diff(SRGB_GAMMA) <= 10 => Some(cicp::transfer::SRGB),
+            LINEAR_GAMMA => Some(cicp::transfer::LINEAR),
+            _ => None,
+        }?;
+        let primaries = self
+            .chromaticities_space()
+            .map_or(Some(cicp::primaries::BT709), |space| {
+                Cicp::color_primaries_of(&space)
+            })?;
+        Some(Cicp::new(primaries, transfer, cicp::matrix::IDENTITY, true))
+    }
+
+    fn chromaticities_space(&self) -> Option<CustomColorSpace> {
+        self.chromaticities.map(|[w, r, g, b]| {
+            CustomColorSpace::from_primaries_and_white_point(r, g, b, w[0], w[1])
+        })
+    }
+
+    /// The color space and CICP transfer characteristics of the ICC profile,
+    /// or `None` if there is no profile. The inner `None` marks a profile
+    /// that is not in matrix/TRC form.
+    #[cfg(feature = "std")]
+    fn icc(&self) -> Option<Option<(CustomColorSpace, Option<u8>)>> {
+        self.icc_profile.as_deref().map(|profile| {
+            DisplayProfile::from_icc(profile)
+                .map(|display| (space_of(display.matrix()), transfer_of(display.curves())))
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn icc(&self) -> Option<Option<(CustomColorSpace, Option<u8>)>> {
+        None
+    }
+}
+
+/// The profile of the `iCCP` chunk `data`, or `None` if it is malformed or
+/// not an ICC profile.
+#[cfg(feature = "std")]
+fn read_iccp(data: &[u8]) -> Option<Vec<u8>> {
+    // The name is 1 to 79 bytes long, followed by a zero and the compression
+    // method, which must be zlib.
+    let name_end = data.iter().take(80).position(|&byte| byte == 0)?;
+    (name_end > 0 && data.get(name_end + 1) == Some(&0)).then_some(())?;
+    miniz_oxide::inflate::decompress_to_vec_zlib(data.get(name_end + 2..)?)
+        .ok()
+        .filter(|profile| profile.get(36..40) == Some(b"acsp"))
+}
+
+/// An ICC v4 display profile in matrix/TRC form for `cicp`, or `None` if its
+/// primaries are unknown or its transfer function isn't the sRGB or a linear
+/// one.
+#[cfg(feature = "std")]
+fn icc_profile(cicp: Cicp) -> Option<Vec<u8>> {
+    let ([r, g, b], [w_x, w_y]) = cicp::primaries_xy(cicp.color_primaries)?;
+    let curve = match cicp.transfer_characteristics {
+        cicp::transfer::SRGB => Some(parametric_curve(
+            3,
+            &[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045],
+        )),
+        cicp::transfer::LINEAR => Some(parametric_curve(0, &[1.0])),
+        _ => None,
+    }?;
+
+    // Colorants are adapted to the D50 white of the connection space.
+    let space = CustomColorSpace::from_primaries_and_white_point(r, g, b, w_x, w_y);
+    let adaptation = ColorMatrix::bradford(space.to_xyz(Vec3::ONE), D50).matrix;
+    let [red, green, blue] =
+        [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| adaptation * space.to_xyz(axis));
+    let name = format!(
+        "CICP {}/{}/0",
+        cicp.color_primaries, cicp.transfer_characteristics
+    );
+
+    let tags = [
+        (b"desc", text(&name)),
+        (b"cprt", text("No copyright, use freely")),
+        (b"wtpt", xyz(D50)),
+        (
+            b"chad",
+            s15_fixed16_array(&adaptation.transpose().to_cols_array()),
+        ),
+        (b"rXYZ", xyz(red)),
+        (b"gXYZ", xyz(green)),
+        (b"bXYZ", xyz(blue)),
+        (b"rTRC", curve.clone()),
+        (b"gTRC", curve.clone()),
+        (b"bTRC", curve),
+    ];
+
+    // Tag data follows the header and the tag table, aligned to four bytes.
+    let offsets = tags
+        .iter()
+        .scan(132 + 12 * tags.len(), |offset, (_, data)| {
+            let start = *offset;
+            *offset += data.len().next_multiple_of(4);
+            Some(start)
+        })
+        .collect::<Vec<_>>();
+    let size = offsets[tags.len() - 1] + tags[tags.len() - 1].1.len();
+
+    let header: [&[u8]; 11] = [
+        &(size as u32).to_be_bytes(),
+        &[0; 4],
+        &0x0430_0000u32.to_be_bytes(),
+        b"mntr",
+        b"RGB ",
+        b"XYZ ",
+        &[0; 12],
+        b"acsp",
+        &[0; 28],
+        &xyz(D50)[8..],
+        &[0; 48],
+    ];
+    let table = tags
+        .iter()
+        .zip(&offsets)
+        .flat_map(|((signature, data), &offset)| {
+            [
+                **signature,
+                (offset as u32).to_be_bytes(),
+                (data.len() as u32).to_be_bytes(),
+            ]
+        })
+        .flatten();
+    let data = tags.iter().flat_map(|(_, data)| {
+        let padding = data.len().next_multiple_of(4) - data.len();
+        data.iter().copied().chain(core::iter::repeat_n(0, padding))
+    });
+
+    let profile = header
+        .into_iter()
+        .flatten()
+        .copied()
+        .chain((tags.len() as u32).to_be_bytes())
+        .chain(table)
+        .chain(data)
+        .take(size)
+        .collect();
+    Some(profile)
+}
+
+/// The ICC `s15Fixed16Number`s of `values`.
+#[cfg(feature = "std")]
+fn s15_fixed16(values: &[f32]) -> impl Iterator<Item = u8> + '_ {
+    values
+        .iter()
+        .flat_map(|&value| (round(value * 65536.0) as i32).to_be_bytes())
+}
+
+/// An ICC `s15Fixed16ArrayType` tag.
+#[cfg(feature = "std")]
+fn s15_fixed16_array(values: &[f32]) -> Vec<u8> {
+    b"sf32"
+        .iter()
+        .copied()
+        .chain([0; 4])
+        .chain(s15_fixed16(values))
+        .collect()
+}
+
+/// An ICC `XYZType` tag.
+#[cfg(feature = "std")]
+fn xyz(value: Vec3) -> Vec<u8> {
+    b"XYZ "
+        .iter()
+        .copied()
+        .chain([0; 4])
+        .chain(s15_fixed16(&value.to_array()))
+        .collect()
+}
+
+/// An ICC `parametricCurveType` tag.
+#[cfg(feature = "std")]
+fn parametric_curve(function: u16, parameters: &[f32]) -> Vec<u8> {
+    b"para"
+        .iter()
+        .copied()
+        .chain([0; 4])
+        .chain(function.to_be_bytes())
+        .chain([0; 2])
+        .chain(s15_fixed16(parameters))
+        .collect()
+}
+
+/// An ICC `multiLocalizedUnicodeType` tag with the English `text`.
+#[cfg(feature = "std")]
+fn text(text: &str) -> Vec<u8> {
+    let utf16 = text
+        .encode_utf16()
+        .flat_map(u16::to_be_bytes)
+        .collect::<Vec<_>>();
+    [
+        b"mluc".as_slice(),
+        &[0; 4],
+        &1u32.to_be_bytes(),
+        &12u32.to_be_bytes(),
+        b"enUS",
+        &(utf16.len() as u32).to_be_bytes(),
+        &28u32.to_be_bytes(),
+        &utf16,
+    ]
+    .concat()
+}
+
+/// The color space whose linear components `to_xyz` converts to CIE XYZ.
+#[cfg(feature = "std")]
+fn space_of(to_xyz: Mat3) -> CustomColorSpace {
+    let xy = |xyz: Vec3| [xyz.x / xyz.element_sum(), xyz.y / xyz.element_sum()];
+    let [w_x, w_y] = xy(to_xyz * Vec3::ONE);
+    CustomColorSpace::from_primaries_and_white_point(
+        xy(to_xyz.x_axis),
+        xy(to_xyz.y_axis),
+        xy(to_xyz.z_axis),
+        w_x,
+        w_y,
+    )
+}
+
+/// The CICP transfer characteristics of `curves`, if all three are the sRGB
+/// or the linear transfer function.
+#[cfg(feature = "std")]
+fn transfer_of(curves: &[ToneCurve; 3]) -> Option<u8> {
+    let matches = |transfer: TransferFunction| {
+        curves.iter().all(|curve| {
+            [0.01, 0.1, 0.5, 0.9]
+                .into_iter()
+                .all(|x| (curve.decode(x) - transfer.decode_f32(x)).abs() < 1e-3)
+        })
+    };
+    [
+        (TransferFunction::Srgb, cicp::transfer::SRGB),
+        (TransferFunction::Linear, cicp::transfer::LINEAR),
+    ]
+    .into_iter()
+    .find_map(|(transfer, code)| matches(transfer).then_some(code))
+}
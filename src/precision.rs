@@ -0,0 +1,153 @@
+//! Measuring the precision lost by conversion chains.
+//!
+//! Every quantized intermediate, like an 8-bit texture between two passes,
+//! rounds colors. Whether that is acceptable depends on how far the colors
+//! move, which a [`PrecisionReport`] measures over a grid of colors spanning
+//! the gamut of the source encoding: the largest and the mean color difference
+//! after a round trip, and the precision left in bits.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+//! use colstodian::details::encodings::{EncodedSrgbU16, Srgb};
+//! use colstodian::distance::DeltaE;
+//! use colstodian::precision;
+//!
+//! // Linear light through an 8-bit sRGB intermediate keeps about 8 bits.
+//! let u8 = precision::round_trip::<LinearSrgb, SrgbU8>(17, DeltaE::Cie2000).unwrap();
+//! assert!(u8.effective_bits > 7.0 && u8.effective_bits < 9.0);
+//! assert!(u8.max_delta_e < 1.0);
+//!
+//! // A 16-bit intermediate keeps about twice as many.
+//! let u16 = precision::round_trip::<Srgb, EncodedSrgbU16>(17, DeltaE::Cie2000).unwrap();
+//! assert!(u16.effective_bits > 15.0);
+//! assert!(u16.mean_delta_e < u8.mean_delta_e / 100.0);
+//! ```
+//!
+//! Any chain, like a pair of [pipelines](crate::pipeline), is measured with
+//! [`measure`].
+
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::distance::DeltaE;
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+/// The bits of precision of an `f32`, reported for chains without error.
+const F32_BITS: f32 = f32::MANTISSA_DIGITS as f32;
+
+/// The error a conversion chain introduces, returned by [`measure`] and
+/// [`round_trip`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionReport {
+    /// The number of colors measured.
+    pub samples: usize,
+    /// The largest color difference between a color and its round trip.
+    pub max_delta_e: f32,
+    /// The mean color difference between the colors and their round trips.
+    pub mean_delta_e: f32,
+    /// The linear components of the color with the largest difference.
+    pub worst: Vec3,
+    /// The largest difference of a linear component, relative to white at
+    /// `1.0`.
+    pub max_error: f32,
+    /// The precision of the linear components after the round trip in bits,
+    /// `-log2(max_error)`, up to the 24 bits of an `f32`.
+    pub effective_bits: f32,
+}
+
+/// The error of `f`, a chain of conversions from `Src` back to `Src`, over a
+/// grid of `samples_per_axis` values per linear component of `Src`, or `None`
+/// if `samples_per_axis` is `0`.
+///
+/// The colors of the grid are encoded in `Src`, so that only the error of the
+/// chain is measured, and compared by `metric`.
+///
+/// ## Example
+///
+/// ```
+/// use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+/// use colstodian::details::linear_spaces::Srgb;
+/// use colstodian::distance::DeltaE;
+/// use colstodian::pipeline::Pipeline;
+/// use colstodian::precision;
+///
+/// // A grade written to an 8-bit texture and undone in a later pass.
+/// let grade = Pipeline::<LinearSrgb, SrgbU8, Srgb>::new().exposure(-2.0);
+/// let undo = Pipeline::<SrgbU8, LinearSrgb, Srgb>::new().exposure(2.0);
+///
+/// let report = precision::measure(17, DeltaE::Ok, |color| undo.convert(grade.convert(color)))
+///     .unwrap();
+/// assert_eq!(report.samples, 17 * 17 * 17);
+///
+/// // Darkening before quantizing costs precision.
+/// let plain = precision::round_trip::<LinearSrgb, SrgbU8>(17, DeltaE::Ok).unwrap();
+/// assert!(report.effective_bits < plain.effective_bits - 0.5);
+/// ```
+pub fn measure<Src>(
+    samples_per_axis: usize,
+    metric: DeltaE,
+    mut f: impl FnMut(Color<Src>) -> Color<Src>,
+) -> Option<PrecisionReport>
+where
+    Src: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<Src::LinearSpace>,
+{
+    (samples_per_axis > 0).then_some(())?;
+    let step = 1.0 / (samples_per_axis - 1).max(1) as f32;
+    let grid = (0..samples_per_axis.pow(3)).map(|index| {
+        Vec3::new(
+            (index % samples_per_axis) as f32,
+            (index / samples_per_axis % samples_per_axis) as f32,
+            (index / samples_per_axis / samples_per_axis) as f32,
+        ) * step
+    });
+
+    let mut report = PrecisionReport {
+        samples: samples_per_axis.pow(3),
+        max_delta_e: 0.0,
+        mean_delta_e: 0.0,
+        worst: Vec3::ZERO,
+        max_error: 0.0,
+        effective_bits: F32_BITS,
+    };
+    grid.for_each(|linear| {
+        let color = Color::<Src>::from_repr(Src::dst_transform_raw(linear, 1.0));
+        let round_trip = f(color);
+
+        let delta_e = metric.delta_e(color, round_trip);
+        report.mean_delta_e += delta_e;
+        if delta_e > report.max_delta_e {
+            report.max_delta_e = delta_e;
+            report.worst = linear;
+        }
+
+        let (expected, _) = Src::src_transform_raw(color.repr);
+        let (actual, _) = Src::src_transform_raw(round_trip.repr);
+        report.max_error = report
+            .max_error
+            .max((actual - expected).abs().max_element());
+    });
+    report.mean_delta_e /= report.samples as f32;
+    if report.max_error > 0.0 {
+        report.effective_bits = (-report.max_error.log2()).min(F32_BITS);
+    }
+    Some(report)
+}
+
+/// The error of converting colors from `Src` to `Via` and back, as with
+/// [`measure`].
+pub fn round_trip<Src, Via>(samples_per_axis: usize, metric: DeltaE) -> Option<PrecisionReport>
+where
+    Src: ColorEncoding + ConvertFrom<Via>,
+    Via: ColorEncoding + ConvertFrom<Src>,
+    CieXYZ: LinearConvertFromRaw<Src::LinearSpace>,
+    Src::LinearSpace: LinearConvertFromRaw<Via::LinearSpace>,
+    Via::LinearSpace: LinearConvertFromRaw<Src::LinearSpace>,
+{
+    measure(samples_per_axis, metric, |color: Color<Src>| {
+        color.convert::<Via>().convert::<Src>()
+    })
+}
@@ -0,0 +1,124 @@
+//! Soft proofing, previewing on a display how colors print.
+//!
+//! A [`SoftProof`] simulates an output device, like a press with its paper
+//! and inks: colors are mapped to the device values of its ICC profile with
+//! a rendering intent, and back to the colors those values print, which are
+//! then shown in the encoding of the display. Colors the device can't
+//! reproduce show as what it prints instead.
+//!
+//! The printed colors are shown relative to the white of the paper, which
+//! appears as the white of the display. Simulating the paper white shows them
+//! with absolute colorimetry instead, so that the dull, tinted white of
+//! newsprint appears as such next to the white of the display.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::icc::{IccProfile, RenderingIntent};
+//! use colstodian::proof::SoftProof;
+//!
+//! /// How `color` prints on the paper of the profile `data`, shown on an sRGB
+//! /// display.
+//! fn preview(data: &[u8], color: Color<SrgbU8>) -> Option<Color<SrgbU8>> {
+//!     let profile = IccProfile::parse(data)?;
+//!     let proof = SoftProof::new(profile, RenderingIntent::Perceptual)?.with_paper_white(true);
+//!     Some(proof.proof(color))
+//! }
+//! ```
+
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::icc::{IccProfile, MAX_CHANNELS, RenderingIntent};
+use crate::{Color, ColorEncoding};
+
+/// A simulation of printing with an output profile, for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftProof {
+    profile: IccProfile,
+    intent: RenderingIntent,
+    paper_white: bool,
+}
+
+impl SoftProof {
+    /// A proof of printing with `profile` and the rendering intent `intent`,
+    /// or `None` if the profile has no `AToB` and `BToA` tables.
+    ///
+    /// The paper white is not simulated.
+    pub fn new(profile: IccProfile, intent: RenderingIntent) -> Option<Self> {
+        profile.has_luts().then_some(Self {
+            profile,
+            intent,
+            paper_white: false,
+        })
+    }
+
+    /// Set whether to show the white of the paper as it is, rather than as
+    /// the white of the display.
+    #[inline]
+    pub fn with_paper_white(self, paper_white: bool) -> Self {
+        Self {
+            paper_white,
+            ..self
+        }
+    }
+
+    /// The output profile.
+    #[inline]
+    pub fn profile(&self) -> &IccProfile {
+        &self.profile
+    }
+
+    /// The rendering intent colors are printed with.
+    #[inline]
+    pub fn intent(&self) -> RenderingIntent {
+        self.intent
+    }
+
+    /// Whether the white of the paper is simulated.
+    #[inline]
+    pub fn paper_white(&self) -> bool {
+        self.paper_white
+    }
+
+    /// The device values `color` prints with.
+    ///
+    /// The first [`IccProfile::channels`] values are used.
+    pub fn device_values<E>(&self, color: Color<E>) -> [f32; MAX_CHANNELS]
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let pcs = self.profile.color_to_pcs(color);
+        self.profile
+            .from_pcs(pcs, self.intent)
+            .expect("proofed profiles have tables")
+    }
+
+    /// How `color` looks printed, in the display encoding `D`.
+    ///
+    /// Alpha is kept.
+    pub fn proof<E, D>(&self, color: Color<E>) -> Color<D>
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+        D: ColorEncoding,
+        D::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+    {
+        let (_, alpha) = E::src_transform_raw(color.repr);
+        let device = self.device_values(color);
+        let intent = if self.paper_white {
+            RenderingIntent::AbsoluteColorimetric
+        } else {
+            RenderingIntent::RelativeColorimetric
+        };
+        let pcs = self
+            .profile
+            .to_pcs(&device[..self.profile.channels()], intent)
+            .expect("proofed profiles have tables");
+        let mut raw = self.profile.pcs_to_color(pcs).repr;
+        <D::LinearSpace as LinearConvertFromRaw<CieXYZ>>::linear_part_raw(&mut raw);
+        Color::from_repr(D::dst_transform_raw(raw, alpha))
+    }
+}
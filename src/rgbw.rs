@@ -0,0 +1,201 @@
+//! Splitting colors into the channels of RGBW and RGBWW lighting fixtures.
+//!
+//! Fixtures that add one white LED (RGBW) or a warm and a cool one (RGBWW) to
+//! red, green and blue can mix most colors in several ways. Mixing as much of
+//! a color as possible from the white LEDs makes it brighter and more
+//! efficient, and whites made of white LEDs render colors better than whites
+//! mixed from narrow-band red, green and blue.
+//!
+//! White LEDs aren't the white of the RGB LEDs, so the white channel can't be
+//! taken as the minimum of the components. [`RgbwFixture`] and
+//! [`RgbwwFixture`] take the color of each white LED, in [`CieXyz`] relative
+//! to the luminance of the RGB LEDs at full, and use as much white as fits
+//! under the color, with red, green and blue making up the rest.
+//!
+//! Colors and levels are linear, with the RGB LEDs taken to have the sRGB
+//! primaries. Levels are in `0.0..=1.0`, scale them to the range of the
+//! controller, like `0..=255` for DMX.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::rgbw::{RgbwFixture, white_led};
+//!
+//! // A 4000 K white LED as bright as the RGB LEDs together.
+//! let fixture = RgbwFixture::new(white_led(4000.0, 1.0));
+//!
+//! // A warm white comes mostly from the white LED.
+//! let [r, g, b, w] = fixture.split(Color::linear_srgb(1.0, 0.75, 0.5));
+//! assert!(w > 0.5 && w > r + g + b);
+//! // Saturated colors don't use it.
+//! assert_eq!(fixture.split(Color::linear_srgb(0.0, 0.0, 1.0)), [0.0, 0.0, 1.0, 0.0]);
+//! ```
+//!
+//! [`CieXyz`]: crate::details::encodings::CieXyz
+
+use crate::chromaticity::planckian_uv;
+use crate::details::encodings::{CieXyz, Srgb};
+use crate::details::linear_spaces;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use glam::{Vec2, Vec3};
+
+/// The luminance weights of linear sRGB.
+const SRGB_LUMINANCE: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+/// The color of a white LED at `kelvin` on the Planckian locus, with the
+/// luminance `luminance` relative to the RGB LEDs at full.
+///
+/// Use [`Color::from_xy_luminance`] for white LEDs off the locus.
+#[inline]
+pub fn white_led(kelvin: f32, luminance: f32) -> Color<CieXyz> {
+    let (u, v) = planckian_uv(kelvin);
+    Color::from_uv_prime_luminance(u, 1.5 * v, luminance)
+}
+
+/// A fixture with red, green, blue and white LEDs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbwFixture {
+    /// The white LED at full, in linear sRGB.
+    white: Vec3,
+}
+
+impl RgbwFixture {
+    /// A fixture whose white LED has the color `white` at full.
+    #[inline]
+    pub fn new(white: Color<CieXyz>) -> Self {
+        Self {
+            white: white.convert::<Srgb>().repr,
+        }
+    }
+
+    /// The red, green, blue and white levels that mix `color`, with as much
+    /// white as fits.
+    ///
+    /// Components outside of what the fixture can mix are clipped.
+    pub fn split<E>(&self, color: Color<E>) -> [f32; 4]
+    where
+        E: ColorEncoding,
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let color = linear(color);
+        // The white is limited by the first component it would exceed.
+        let white = Vec3::select(
+            self.white.cmpgt(Vec3::ZERO),
+            color / self.white,
+            Vec3::INFINITY,
+        )
+        .min_element()
+        .clamp(0.0, 1.0);
+        let [r, g, b] = residual(color, self.white * white);
+        [r, g, b, white]
+    }
+
+    /// The color mixed by the red, green, blue and white `levels`.
+    #[inline]
+    pub fn combine(&self, levels: [f32; 4]) -> Color<Srgb> {
+        let [r, g, b, w] = levels;
+        Color::from_repr(Vec3::new(r, g, b) + self.white * w)
+    }
+}
+
+/// A fixture with red, green, blue, warm white and cool white LEDs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbwwFixture {
+    /// The warm and cool white LEDs at full, in linear sRGB.
+    warm: Vec3,
+    cool: Vec3,
+}
+
+impl RgbwwFixture {
+    /// A fixture whose white LEDs have the colors `warm` and `cool` at full.
+    #[inline]
+    pub fn new(warm: Color<CieXyz>, cool: Color<CieXyz>) -> Self {
+        Self {
+            warm: warm.convert::<Srgb>().repr,
+            cool: cool.convert::<Srgb>().repr,
+        }
+    }
+
+    /// The red, green, blue, warm white and cool white levels that mix
+    /// `color`, with as much light from the white LEDs as fits.
+    ///
+    /// Components outside of what the fixture can mix are clipped.
+    pub fn split<E>(&self, color: Color<E>) -> [f32; 5]
+    where
+        E: ColorEncoding,
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let color = linear(color);
+        // A linear program in the two white levels: maximize their luminance
+        // with no component above that of `color`. The optimum is at a vertex
+        // of the feasible polygon, so try the intersections of all pairs of
+        // constraints, each a half-plane `normal · levels <= limit`.
+        let constraints = [
+            (Vec2::new(self.warm.x, self.cool.x), color.x),
+            (Vec2::new(self.warm.y, self.cool.y), color.y),
+            (Vec2::new(self.warm.z, self.cool.z), color.z),
+            (Vec2::NEG_X, 0.0),
+            (Vec2::NEG_Y, 0.0),
+            (Vec2::X, 1.0),
+            (Vec2::Y, 1.0),
+        ];
+        let objective = Vec2::new(self.warm.dot(SRGB_LUMINANCE), self.cool.dot(SRGB_LUMINANCE));
+        let feasible = |levels: Vec2| {
+            constraints
+                .iter()
+                .all(|&(normal, limit)| normal.dot(levels) <= limit + 1e-6)
+        };
+
+        let best = constraints
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(n1, d1))| {
+                constraints[i + 1..].iter().filter_map(move |&(n2, d2)| {
+                    let det = n1.perp_dot(n2);
+                    (det.abs() >= 1e-9)
+                        .then(|| Vec2::new(d1 * n2.y - d2 * n1.y, n1.x * d2 - n2.x * d1) / det)
+                })
+            })
+            .filter(|&levels| feasible(levels))
+            .fold(Vec2::ZERO, |best, levels| {
+                if objective.dot(levels) > objective.dot(best) {
+                    levels
+                } else {
+                    best
+                }
+            });
+
+        let best = best.clamp(Vec2::ZERO, Vec2::ONE);
+        let [r, g, b] = residual(color, self.warm * best.x + self.cool * best.y);
+        [r, g, b, best.x, best.y]
+    }
+
+    /// The color mixed by the red, green, blue, warm white and cool white
+    /// `levels`.
+    #[inline]
+    pub fn combine(&self, levels: [f32; 5]) -> Color<Srgb> {
+        let [r, g, b, warm, cool] = levels;
+        Color::from_repr(Vec3::new(r, g, b) + self.warm * warm + self.cool * cool)
+    }
+}
+
+/// The linear sRGB components of `color`, without negative ones.
+#[inline]
+fn linear<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, _) = E::src_transform_raw(color.repr);
+    <linear_spaces::Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    raw.max(Vec3::ZERO)
+}
+
+/// The red, green and blue levels that make up `color` beyond `white`.
+#[inline]
+fn residual(color: Vec3, white: Vec3) -> [f32; 3] {
+    (color - white).clamp(Vec3::ZERO, Vec3::ONE).to_array()
+}
@@ -0,0 +1,243 @@
+//! Vectorscope, waveform and zebra data for grading scopes and overlays.
+//!
+//! A vectorscope plots the chroma of every pixel, either as BT.709 Cb and Cr
+//! like broadcast scopes or as CIE 1976 u′v′ chromaticities. A waveform shows,
+//! for each column of an image, how many pixels have which luma. A
+//! [`ZebraMask`] flags the pixels that a monitor should warn about.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::buffer::ColorBuffer;
+//! use colstodian::scope;
+//!
+//! let buffer = ColorBuffer::from_fn(4, 2, |x, _| {
+//!     if x < 2 { Color::srgb_u8(255, 255, 255) } else { Color::srgb_u8(255, 0, 0) }
+//! });
+//!
+//! // Red points towards +Cr.
+//! let points = scope::vectorscope_cbcr(buffer.pixels().iter().copied());
+//! assert!(points[2].y > 0.4);
+//!
+//! // The left columns are white, the right ones at the luma of red.
+//! let waveform = scope::waveform(&buffer, 10);
+//! assert_eq!(waveform[0].counts()[9], 2);
+//! assert_eq!(waveform[3].counts()[2], 2);
+//! ```
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::buffer::ColorBuffer;
+//! use colstodian::scope::ZebraMask;
+//!
+//! let buffer = ColorBuffer::from_pixels(
+//!     3,
+//!     1,
+//!     vec![
+//!         Color::linear_srgb(0.5, 0.5, 0.5),
+//!         Color::linear_srgb(1.2, 0.9, 0.8),
+//!         Color::linear_srgb(-0.1, 0.5, 0.2),
+//!     ],
+//! )
+//! .unwrap();
+//!
+//! let clipped = ZebraMask::out_of_gamut::<SrgbU8, _>(&buffer);
+//! assert_eq!(clipped.flags(), &[false, true, true]);
+//!
+//! let hot = ZebraMask::over_luminance(&buffer, 0.9);
+//! assert_eq!(hot.flags(), &[false, true, false]);
+//! assert!((hot.fraction() - 1.0 / 3.0).abs() < 1e-6);
+//! ```
+
+use crate::buffer::ColorBuffer;
+use crate::chromaticity::xy_to_uv_prime;
+use crate::details::encodings::YCbCrBt709;
+use crate::details::linear_spaces::{CieXYZ, Srgb};
+use crate::details::traits::LinearConvertFromRaw;
+use crate::histogram::Histogram;
+use crate::{Color, ColorEncoding};
+
+use glam::{Vec2, Vec3};
+
+/// The BT.709 Cb and Cr of each of `colors`, each in `-0.5..=0.5`.
+pub fn vectorscope_cbcr<E>(colors: impl IntoIterator<Item = Color<E>>) -> Vec<Vec2>
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    colors
+        .into_iter()
+        .map(|color| {
+            let ycbcr = to_ycbcr(color);
+            Vec2::new(ycbcr.y, ycbcr.z)
+        })
+        .collect()
+}
+
+/// The CIE 1976 u′v′ chromaticity of each of `colors`.
+///
+/// Black pixels have no chromaticity and are left out.
+pub fn vectorscope_uv_prime<E>(colors: impl IntoIterator<Item = Color<E>>) -> Vec<Vec2>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    colors
+        .into_iter()
+        .filter_map(|color| {
+            let (mut xyz, _) = E::src_transform_raw(color.repr);
+            <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut xyz);
+            let sum = xyz.x + xyz.y + xyz.z;
+            (sum > 0.0).then(|| Vec2::from(xy_to_uv_prime(xyz.x / sum, xyz.y / sum)))
+        })
+        .collect()
+}
+
+/// A histogram of the BT.709 luma of each column of `buffer`, from left to
+/// right, with `levels` bins spanning `0.0..=1.0`.
+///
+/// Luma outside of the range, e.g. of superwhites, is counted in the first or
+/// last bin.
+///
+/// # Panics
+///
+/// Panics if `levels` is zero.
+pub fn waveform<E>(buffer: &ColorBuffer<E>, levels: usize) -> Vec<Histogram>
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let mut columns = vec![Histogram::new(levels, 0.0..=1.0); buffer.width()];
+    buffer.rows().for_each(|row| {
+        columns
+            .iter_mut()
+            .zip(row)
+            .for_each(|(column, color)| column.add(to_ycbcr(*color).x));
+    });
+    columns
+}
+
+/// A per-pixel warning mask over a buffer, as shown by zebra stripes on
+/// monitors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZebraMask {
+    width: usize,
+    height: usize,
+    flags: Vec<bool>,
+}
+
+impl ZebraMask {
+    /// Flag the pixels of `buffer` for which `predicate` returns `true`.
+    pub fn new<E: ColorEncoding>(
+        buffer: &ColorBuffer<E>,
+        predicate: impl FnMut(Color<E>) -> bool,
+    ) -> Self {
+        Self {
+            width: buffer.width(),
+            height: buffer.height(),
+            flags: buffer.pixels().iter().copied().map(predicate).collect(),
+        }
+    }
+
+    /// Flag the pixels of `buffer` that don't fit in the gamut of `Dst`, and
+    /// so would clip when converted to it.
+    ///
+    /// See [`Color::fits_in`].
+    pub fn out_of_gamut<Dst, E>(buffer: &ColorBuffer<E>) -> Self
+    where
+        Dst: ColorEncoding,
+        E: ColorEncoding,
+        Dst::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        Self::new(buffer, |color| !color.fits_in::<Dst>())
+    }
+
+    /// Flag the pixels of `buffer` with a relative luminance (CIE Y) above
+    /// `threshold`.
+    pub fn over_luminance<E>(buffer: &ColorBuffer<E>, threshold: f32) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        Self::new(buffer, |color| {
+            let (mut raw, _) = E::src_transform_raw(color.repr);
+            <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            raw.y > threshold
+        })
+    }
+
+    /// The pixels flagged by either mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the masks differ in size.
+    pub fn union(&self, other: &Self) -> Self {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "the masks must have the same size"
+        );
+        Self {
+            flags: self
+                .flags
+                .iter()
+                .zip(&other.flags)
+                .map(|(a, b)| *a || *b)
+                .collect(),
+            ..*self
+        }
+    }
+
+    /// The width of the mask.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the mask.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether each pixel is flagged, row by row.
+    #[inline]
+    pub fn flags(&self) -> &[bool] {
+        &self.flags
+    }
+
+    /// Whether the pixel at `(x, y)` is flagged, or `None` if it is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        (x < self.width && y < self.height).then(|| self.flags[y * self.width + x])
+    }
+
+    /// The number of flagged pixels.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.flags.iter().filter(|flag| **flag).count()
+    }
+
+    /// The fraction of flagged pixels, or `0.0` for an empty mask.
+    #[inline]
+    pub fn fraction(&self) -> f32 {
+        if self.flags.is_empty() {
+            0.0
+        } else {
+            self.count() as f32 / self.flags.len() as f32
+        }
+    }
+}
+
+fn to_ycbcr<E>(color: Color<E>) -> Vec3
+where
+    E: ColorEncoding,
+    Srgb: LinearConvertFromRaw<E::LinearSpace>,
+{
+    let (mut raw, alpha) = E::src_transform_raw(color.repr);
+    <Srgb as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+    YCbCrBt709::dst_transform_raw(raw, alpha)
+}
@@ -0,0 +1,168 @@
+//! CIE xy and brightness for smart lights.
+//!
+//! Philips Hue and other Zigbee lights take colors as a CIE 1931 xy
+//! chromaticity and a separate brightness. Each light can only mix the
+//! chromaticities in the triangle of its primaries, its gamut, and picks
+//! something else for chromaticities outside of it. [`Color::to_smart_light`]
+//! converts colors to the xy and brightness of a light, moving chromaticities
+//! outside of its [`LightGamut`] to the closest one on its edge, so that the
+//! light shows what was asked for as closely as it can.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::smart_light::LightGamut;
+//!
+//! let light = Color::srgb_u8(255, 80, 0).to_smart_light(&LightGamut::C);
+//! assert!((light.x - 0.596).abs() < 1e-3 && (light.y - 0.365).abs() < 1e-3);
+//! assert_eq!(light.hue_bri(), 69);
+//!
+//! // The green of Display P3 is outside of gamut B, the closest the light
+//! // gets is its own green.
+//! let green = Color::display_p3(0.0, 1.0, 0.0).to_smart_light(&LightGamut::B);
+//! assert_eq!((green.x, green.y), LightGamut::B.green);
+//! ```
+
+use crate::details::encodings::CieXyz;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use glam::Vec2;
+
+/// The triangle of chromaticities a light can mix, from the CIE 1931 xy
+/// chromaticities of its primaries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightGamut {
+    /// The red primary.
+    pub red: (f32, f32),
+    /// The green primary.
+    pub green: (f32, f32),
+    /// The blue primary.
+    pub blue: (f32, f32),
+}
+
+impl LightGamut {
+    /// Gamut A, of early Hue LightStrips and Living Colors lights.
+    pub const A: Self = Self::new((0.704, 0.296), (0.2151, 0.7106), (0.138, 0.08));
+    /// Gamut B, of early Hue bulbs.
+    pub const B: Self = Self::new((0.675, 0.322), (0.409, 0.518), (0.167, 0.04));
+    /// Gamut C, of current Hue bulbs and LightStrips.
+    pub const C: Self = Self::new((0.6915, 0.3083), (0.17, 0.7), (0.1532, 0.0475));
+
+    /// A gamut with the given primaries.
+    #[inline]
+    pub const fn new(red: (f32, f32), green: (f32, f32), blue: (f32, f32)) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Whether the light can mix the chromaticity `(x, y)`.
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let point = Vec2::new(x, y);
+        let sides = self
+            .edges()
+            .map(|(start, end)| (end - start).perp_dot(point - start));
+        sides.iter().all(|side| *side >= -1e-6) || sides.iter().all(|side| *side <= 1e-6)
+    }
+
+    /// The chromaticity `(x, y)` if the light can mix it, or the closest one
+    /// it can otherwise.
+    pub fn clamp(&self, x: f32, y: f32) -> (f32, f32) {
+        if self.contains(x, y) {
+            (x, y)
+        } else {
+            let point = Vec2::new(x, y);
+            self.edges()
+                .map(|(start, end)| {
+                    let edge = end - start;
+                    let t = (point - start).dot(edge) / edge.length_squared();
+                    if t >= 1.0 {
+                        end
+                    } else {
+                        start + edge * t.max(0.0)
+                    }
+                })
+                .into_iter()
+                .min_by(|a, b| {
+                    a.distance_squared(point)
+                        .total_cmp(&b.distance_squared(point))
+                })
+                .map(|closest| (closest.x, closest.y))
+                .expect("a triangle has edges")
+        }
+    }
+
+    /// The edges of the triangle, as start and end points.
+    fn edges(&self) -> [(Vec2, Vec2); 3] {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(Vec2::from);
+        [(red, green), (green, blue), (blue, red)]
+    }
+}
+
+/// A color as a CIE 1931 xy chromaticity and a brightness, as sent to smart
+/// lights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmartLightColor {
+    /// The x chromaticity coordinate.
+    pub x: f32,
+    /// The y chromaticity coordinate.
+    pub y: f32,
+    /// The brightness, the relative luminance in `0.0..=1.0`.
+    pub brightness: f32,
+}
+
+impl SmartLightColor {
+    /// The brightness in the `1..=254` range of the `bri` of the Hue API and
+    /// the Zigbee level control cluster.
+    ///
+    /// `1` is the lowest level the light can be dimmed to, not off.
+    #[inline]
+    pub fn hue_bri(&self) -> u8 {
+        1 + Vec2::splat(self.brightness.clamp(0.0, 1.0) * 253.0)
+            .round()
+            .x as u8
+    }
+
+    /// The chromaticity as the `CurrentX` and `CurrentY` attributes of the
+    /// Zigbee color control cluster, in units of `1/65536`.
+    #[inline]
+    pub fn zigbee_xy(&self) -> (u16, u16) {
+        let [x, y] = (Vec2::new(self.x, self.y) * 65536.0)
+            .round()
+            .clamp(Vec2::ZERO, Vec2::splat(65279.0))
+            .to_array();
+        (x as u16, y as u16)
+    }
+
+    /// The color the light shows, with the luminance of white at full
+    /// brightness being `1.0`.
+    #[inline]
+    pub fn to_color(&self) -> Color<CieXyz> {
+        Color::from_xy_luminance(self.x, self.y, self.brightness)
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+{
+    /// The chromaticity and brightness a smart light with the gamut `gamut`
+    /// shows this color with.
+    ///
+    /// Chromaticities outside of the gamut are moved to the closest one on
+    /// its edge. Luminance above that of white is clipped, black gets the
+    /// chromaticity of D65.
+    pub fn to_smart_light(self, gamut: &LightGamut) -> SmartLightColor {
+        let (mut raw, _) = E::src_transform_raw(self.repr);
+        <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+        let (x, y, luminance) = Color::<CieXyz>::from_repr(raw).to_xy_luminance();
+        let (x, y) = gamut.clamp(x, y);
+        SmartLightColor {
+            x,
+            y,
+            brightness: luminance.clamp(0.0, 1.0),
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! Reflectance spectra and their colors under different illuminants.
+//!
+//! The color of a surface depends on the light it is seen in. A [`Spectrum`]
+//! holds the reflectance of a surface from 380 nm to 780 nm, an
+//! [`Illuminant`] the power of a light source over the same wavelengths, and
+//! the color of the surface in that light follows from the CIE 1931 standard
+//! observer. Colors are given as seen by an observer adapted to the light, so
+//! that a white surface looks white in any of them.
+//!
+//! Surfaces with different spectra can match in one light and not in
+//! another, which is called metamerism. [`metamerism_index`] measures how
+//! much a pair that matches under a reference illuminant differs under a test
+//! illuminant. Where only the color of a surface is known,
+//! [`Spectrum::estimate_reflectance`] finds a smooth spectrum with that color
+//! in daylight.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::spectral::{self, Illuminant, Spectrum};
+//!
+//! let d65 = Illuminant::d65();
+//! let f11 = Illuminant::f11();
+//!
+//! // A surface reflecting narrow bands of blue and yellow light looks like the
+//! // smooth spectrum of the same color in daylight, but not under the spiky
+//! // spectrum of a fluorescent lamp.
+//! let bands = Spectrum::from_fn(|nm| {
+//!     if (440.0..=470.0).contains(&nm) || (560.0..=590.0).contains(&nm) {
+//!         0.8
+//!     } else {
+//!         0.2
+//!     }
+//! });
+//! let smooth = Spectrum::estimate_reflectance(bands.color_under(&d65));
+//! assert!(smooth.delta_e_under(&bands, &d65) < 0.1);
+//! assert!(spectral::metamerism_index(&bands, &smooth, &d65, &f11) > 2.0);
+//! ```
+
+use crate::calibration::ColorMatrix;
+use crate::chromaticity::{Whiteness, planckian_uv};
+use crate::colorchecker::{delta_e_2000, xyz_to_lab};
+use crate::details::encodings::CieXyz;
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::white;
+use crate::{Color, ColorEncoding};
+
+use core::ops::ControlFlow;
+
+use glam::{Mat2, Mat3, Vec2, Vec3};
+
+/// The number of samples of a [`Spectrum`].
+pub const SAMPLES: usize = 81;
+
+/// The wavelength of the first sample of a [`Spectrum`], in nanometers.
+pub const FIRST_WAVELENGTH: f32 = 380.0;
+
+/// The distance between the samples of a [`Spectrum`], in nanometers.
+pub const WAVELENGTH_STEP: f32 = 5.0;
+
+/// The CIE 1931 2° standard observer from 380 nm to 780 nm, every 10 nm.
+const CIE_1931: [Vec3; 41] = [
+    Vec3::new(0.001368, 0.000039, 0.00645),
+    Vec3::new(0.004243, 0.00012, 0.02005),
+    Vec3::new(0.01431, 0.000396, 0.06785),
+    Vec3::new(0.04351, 0.00121, 0.2074),
+    Vec3::new(0.13438, 0.004, 0.6456),
+    Vec3::new(0.2839, 0.0116, 1.3856),
+    Vec3::new(0.34828, 0.023, 1.74706),
+    Vec3::new(0.3362, 0.038, 1.77211),
+    Vec3::new(0.2908, 0.06, 1.6692),
+    Vec3::new(0.19536, 0.09098, 1.28764),
+    Vec3::new(0.09564, 0.13902, 0.81295),
+    Vec3::new(0.03201, 0.20802, 0.46518),
+    Vec3::new(0.0049, 0.323, 0.272),
+    Vec3::new(0.0093, 0.503, 0.1582),
+    Vec3::new(0.06327, 0.71, 0.07825),
+    Vec3::new(0.1655, 0.862, 0.04216),
+    Vec3::new(0.2904, 0.954, 0.0203),
+    Vec3::new(0.43345, 0.99495, 0.00875),
+    Vec3::new(0.5945, 0.995, 0.0039),
+    Vec3::new(0.7621, 0.952, 0.0021),
+    Vec3::new(0.9163, 0.87, 0.00165),
+    Vec3::new(1.0263, 0.757, 0.0011),
+    Vec3::new(1.0622, 0.631, 0.0008),
+    Vec3::new(1.0026, 0.503, 0.00034),
+    Vec3::new(0.85445, 0.381, 0.00019),
+    Vec3::new(0.6424, 0.265, 0.00005),
+    Vec3::new(0.4479, 0.175, 0.00002),
+    Vec3::new(0.2835, 0.107, 0.0),
+    Vec3::new(0.1649, 0.061, 0.0),
+    Vec3::new(0.0874, 0.032, 0.0),
+    Vec3::new(0.04677, 0.017, 0.0),
+    Vec3::new(0.0227, 0.00821, 0.0),
+    Vec3::new(0.011359, 0.004102, 0.0),
+    Vec3::new(0.00579, 0.002091, 0.0),
+    Vec3::new(0.002899, 0.001047, 0.0),
+    Vec3::new(0.00144, 0.00052, 0.0),
+    Vec3::new(0.00069, 0.000249, 0.0),
+    Vec3::new(0.000332, 0.00012, 0.0),
+    Vec3::new(0.000166, 0.00006, 0.0),
+    Vec3::new(0.000083, 0.00003, 0.0),
+    Vec3::new(0.000042, 0.000015, 0.0),
+];
+
+/// The CIE D65 illuminant from 380 nm to 780 nm, every 10 nm.
+const D65: [f32; 41] = [
+    49.9755, 54.6482, 82.7549, 91.486, 93.4318, 86.6823, 104.865, 117.008, 117.812, 114.861,
+    115.923, 108.811, 109.354, 107.802, 104.79, 107.689, 104.405, 104.046, 100.0, 96.3342, 95.788,
+    88.6856, 90.0062, 89.5991, 87.6987, 83.2886, 83.6992, 80.0268, 80.2146, 82.2778, 78.2842,
+    69.7213, 71.6091, 74.349, 61.604, 69.8856, 75.087, 63.5927, 46.4182, 66.8054, 63.3828,
+];
+
+/// The CIE F2 illuminant, a cool white fluorescent lamp, every 5 nm.
+const F2: [f32; SAMPLES] = [
+    1.18, 1.48, 1.84, 2.15, 3.44, 15.69, 3.85, 3.74, 4.19, 4.62, 5.06, 34.98, 11.81, 6.27, 6.63,
+    6.93, 7.19, 7.40, 7.54, 7.62, 7.65, 7.62, 7.62, 7.45, 7.28, 7.15, 7.05, 7.04, 7.16, 7.47, 8.04,
+    8.88, 10.01, 24.88, 16.64, 14.59, 16.16, 17.56, 18.62, 21.47, 22.79, 19.29, 18.66, 17.73,
+    16.54, 15.21, 13.80, 12.36, 10.95, 9.65, 8.40, 7.32, 6.31, 5.43, 4.68, 4.02, 3.45, 2.96, 2.55,
+    2.19, 1.89, 1.64, 1.53, 1.27, 1.10, 0.99, 0.88, 0.76, 0.68, 0.61, 0.56, 0.54, 0.51, 0.47, 0.47,
+    0.43, 0.46, 0.47, 0.40, 0.33, 0.27,
+];
+
+/// The CIE F11 illuminant, a narrow band fluorescent lamp, every 5 nm.
+const F11: [f32; SAMPLES] = [
+    0.91, 0.63, 0.46, 0.37, 1.29, 12.68, 1.59, 1.79, 2.46, 3.33, 4.49, 33.94, 12.13, 6.95, 7.19,
+    7.12, 6.72, 6.13, 5.46, 4.79, 5.66, 14.29, 14.96, 8.97, 4.72, 2.33, 1.47, 1.10, 0.89, 0.83,
+    1.18, 4.90, 39.59, 72.84, 32.61, 7.52, 2.83, 1.96, 1.67, 4.43, 11.28, 14.76, 12.73, 9.74, 7.33,
+    9.72, 55.27, 42.58, 13.18, 13.16, 12.26, 5.11, 2.07, 2.34, 3.58, 3.01, 2.48, 2.14, 1.54, 1.33,
+    1.46, 1.94, 2.00, 1.20, 1.35, 4.10, 5.58, 2.51, 0.57, 0.27, 0.23, 0.21, 0.24, 0.24, 0.20, 0.24,
+    0.32, 0.26, 0.16, 0.12, 0.09,
+];
+
+#[inline]
+fn exp(x: f32) -> f32 {
+    Vec2::new(x, 0.0).exp().x
+}
+
+/// The wavelength of the sample `index`, in nanometers.
+#[inline]
+fn wavelength(index: usize) -> f32 {
+    FIRST_WAVELENGTH + WAVELENGTH_STEP * index as f32
+}
+
+/// The standard observer at the sample `index` of a [`Spectrum`].
+#[inline]
+pub(crate) fn observer(index: usize) -> Vec3 {
+    let (low, high) = (CIE_1931[index / 2], CIE_1931[index.div_ceil(2)]);
+    (low + high) / 2.0
+}
+
+/// `samples` at the fractional index `position`, interpolated linearly and
+/// clamped to the ends.
+fn interpolate(samples: &[f32], position: f32) -> f32 {
+    let last = samples.len() - 1;
+    let position = position.clamp(0.0, last as f32);
+    let index = position as usize;
+    let t = position - index as f32;
+    samples[index] * (1.0 - t) + samples[(index + 1).min(last)] * t
+}
+
+/// The CIE XYZ tristimulus values of `spectrum`, not normalized.
+fn tristimulus(spectrum: &Spectrum) -> Vec3 {
+    (0..SAMPLES)
+        .map(|index| spectrum.values[index] * observer(index))
+        .sum()
+}
+
+/// A spectrum from 380 nm to 780 nm, sampled every 5 nm.
+///
+/// Reflectances are in `0.0..=1.0`, and the power of illuminants in any unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spectrum {
+    /// The values at [`FIRST_WAVELENGTH`] and every [`WAVELENGTH_STEP`] after
+    /// it.
+    pub values: [f32; SAMPLES],
+}
+
+impl Spectrum {
+    /// The same `value` at every wavelength.
+    #[inline]
+    pub const fn constant(value: f32) -> Self {
+        Self {
+            values: [value; SAMPLES],
+        }
+    }
+
+    /// The values of `f` at each sampled wavelength, in nanometers.
+    pub fn from_fn(mut f: impl FnMut(f32) -> f32) -> Self {
+        Self {
+            values: core::array::from_fn(|index| f(wavelength(index))),
+        }
+    }
+
+    /// Resample `samples` taken from the wavelength `first` every `step`
+    /// nanometers, interpolating linearly.
+    ///
+    /// Wavelengths outside of the samples get the nearest one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is empty.
+    pub fn from_samples(first: f32, step: f32, samples: &[f32]) -> Self {
+        Self::from_fn(|nm| interpolate(samples, (nm - first) / step))
+    }
+
+    /// The value at the wavelength `nm`, interpolated linearly.
+    #[inline]
+    pub fn at(&self, nm: f32) -> f32 {
+        interpolate(&self.values, (nm - FIRST_WAVELENGTH) / WAVELENGTH_STEP)
+    }
+
+    /// A smooth reflectance spectrum with the color `color` under D65.
+    ///
+    /// The spectrum is the sigmoid of a quadratic polynomial in the
+    /// wavelength, as by Jakob and Hanika, fitted to the CIELAB value of the
+    /// color. Colors lighter or more saturated than smooth reflectances can
+    /// get the closest spectrum found.
+    pub fn estimate_reflectance<E>(color: Color<E>) -> Self
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let (mut xyz, _) = E::src_transform_raw(color.repr);
+        <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut xyz);
+        let target = xyz_to_lab(xyz, white::D65);
+
+        let d65 = Illuminant::d65();
+        let residual =
+            |coefficients: Vec3| Self::sigmoid_polynomial(coefficients).lab_under(&d65) - target;
+
+        // Levenberg-Marquardt from a flat, mid gray spectrum.
+        const STEP: f32 = 1e-2;
+        let iterate = |(coefficients, error, damping): (Vec3, Vec3, f32), _| {
+            if error.length() < 1e-3 {
+                ControlFlow::Break((coefficients, error, damping))
+            } else {
+                let jacobian = Mat3::from_cols_array_2d(&[0, 1, 2].map(|axis| {
+                    let mut step = Vec3::ZERO;
+                    step[axis] = STEP;
+                    ((residual(coefficients + step) - error) / STEP).to_array()
+                }));
+                let normal = jacobian.transpose() * jacobian;
+                let gradient = jacobian.transpose() * error;
+                let damped = normal
+                    + Mat3::from_diagonal(
+                        damping * Vec3::new(normal.x_axis.x, normal.y_axis.y, normal.z_axis.z),
+                    );
+                let candidate = coefficients - damped.inverse() * gradient;
+                let candidate_error = residual(candidate);
+                ControlFlow::Continue(
+                    if candidate.is_finite() && candidate_error.length() < error.length() {
+                        (candidate, candidate_error, (damping / 10.0).max(1e-7))
+                    } else {
+                        (coefficients, error, damping * 10.0)
+                    },
+                )
+            }
+        };
+        let start = (Vec3::ZERO, residual(Vec3::ZERO), 1e-3);
+        let (ControlFlow::Break((coefficients, ..)) | ControlFlow::Continue((coefficients, ..))) =
+            (0..100).try_fold(start, iterate);
+        Self::sigmoid_polynomial(coefficients)
+    }
+
+    /// The spectrum `sigmoid(c0 t² + c1 t + c2)`, with `t` going from `-1.0`
+    /// to `1.0` over the sampled wavelengths.
+    fn sigmoid_polynomial(coefficients: Vec3) -> Self {
+        Self::from_fn(|nm| {
+            let t = (nm - 580.0) / 200.0;
+            let x = coefficients.dot(Vec3::new(t * t, t, 1.0));
+            0.5 + x / (2.0 * Vec2::new(x, 1.0).length())
+        })
+    }
+
+    /// The CIELAB value of the reflectance under `illuminant`, relative to its
+    /// white.
+    #[inline]
+    pub fn lab_under(&self, illuminant: &Illuminant) -> Vec3 {
+        xyz_to_lab(illuminant.tristimulus(self), illuminant.white)
+    }
+
+    /// The color of the reflectance under `illuminant`, adapted from its white
+    /// to D65 with the Bradford transform.
+    #[inline]
+    pub fn color_under(&self, illuminant: &Illuminant) -> Color<CieXyz> {
+        let adaptation = ColorMatrix::bradford(illuminant.white, white::D65).matrix;
+        Color::from_repr(adaptation * illuminant.tristimulus(self))
+    }
+
+    /// The CIE whiteness and tint of the reflectance under `illuminant`.
+    #[inline]
+    pub fn whiteness_under(&self, illuminant: &Illuminant) -> Whiteness {
+        let (x, y, _) = Color::<CieXyz>::from_repr(illuminant.white).to_xy_luminance();
+        Color::<CieXyz>::from_repr(illuminant.tristimulus(self)).whiteness((x, y))
+    }
+
+    /// The CIEDE2000 color difference of this and `other` under `illuminant`.
+    #[inline]
+    pub fn delta_e_under(&self, other: &Spectrum, illuminant: &Illuminant) -> f32 {
+        delta_e_2000(self.lab_under(illuminant), other.lab_under(illuminant))
+    }
+}
+
+/// A light source, given by its spectral power distribution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Illuminant {
+    spectrum: Spectrum,
+    /// The standard observer weighted by the spectrum, normalized to a white
+    /// luminance of `1.0`.
+    weights: [Vec3; SAMPLES],
+    white: Vec3,
+}
+
+impl Illuminant {
+    /// The illuminant with the spectral power distribution `spectrum`.
+    pub fn new(spectrum: Spectrum) -> Self {
+        let scale = tristimulus(&spectrum).y.recip();
+        let weights =
+            core::array::from_fn(|index| scale * spectrum.values[index] * observer(index));
+        Self {
+            spectrum,
+            weights,
+            white: weights.iter().sum(),
+        }
+    }
+
+    /// The CIE D65 illuminant, average daylight.
+    pub fn d65() -> Self {
+        Self::new(Spectrum::from_samples(FIRST_WAVELENGTH, 10.0, &D65))
+    }
+
+    /// The CIE A illuminant, an incandescent lamp at 2856 K.
+    pub fn a() -> Self {
+        const C2: f32 = 1.435e7;
+        let planck = |nm: f32| {
+            let ratio = 560.0 / nm;
+            ratio * ratio * ratio * ratio * ratio / (exp(C2 / (2848.0 * nm)) - 1.0)
+        };
+        let scale = 100.0 / planck(560.0);
+        Self::new(Spectrum::from_fn(|nm| scale * planck(nm)))
+    }
+
+    /// The CIE F2 illuminant, a cool white fluorescent lamp.
+    pub fn f2() -> Self {
+        Self::new(Spectrum { values: F2 })
+    }
+
+    /// The CIE F11 illuminant, a narrow band fluorescent lamp.
+    pub fn f11() -> Self {
+        Self::new(Spectrum { values: F11 })
+    }
+
+    /// A phosphor-converted white LED with the correlated color temperature
+    /// `kelvin`, on the Planckian locus.
+    ///
+    /// The spectrum is modeled as the peak of a blue LED at 450 nm and a broad
+    /// phosphor emission, and holds from 2500 K to 8000 K, which `kelvin` is
+    /// clamped to.
+    pub fn led(kelvin: f32) -> Self {
+        let (u, v) = planckian_uv(kelvin.clamp(2500.0, 8000.0));
+        let target = Vec2::new(u, 1.5 * v);
+
+        // The phosphor weight, as a logarithm, and peak wavelength.
+        let spectrum = |parameters: Vec2| {
+            let weight = exp(parameters.x);
+            Spectrum::from_fn(|nm| {
+                let blue = (nm - 450.0) / 10.0;
+                let phosphor = (nm - parameters.y) / 55.0;
+                exp(-0.5 * blue * blue) + weight * exp(-0.5 * phosphor * phosphor)
+            })
+        };
+        let residual = |parameters: Vec2| {
+            let xyz = tristimulus(&spectrum(parameters));
+            let denominator = xyz.dot(Vec3::new(1.0, 15.0, 3.0));
+            Vec2::new(4.0 * xyz.x, 9.0 * xyz.y) / denominator - target
+        };
+
+        // Newton's method, from an equal weight and a yellow phosphor.
+        const STEPS: Vec2 = Vec2::new(1e-2, 1e-1);
+        let parameters = (0..16).fold(Vec2::new(0.0, 570.0), |parameters, _| {
+            let error = residual(parameters);
+            let jacobian = Mat2::from_cols(
+                (residual(parameters + Vec2::X * STEPS) - error) / STEPS.x,
+                (residual(parameters + Vec2::Y * STEPS) - error) / STEPS.y,
+            );
+            parameters - jacobian.inverse() * error
+        });
+        Self::new(spectrum(parameters))
+    }
+
+    /// The spectral power distribution.
+    #[inline]
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// The white point in CIE XYZ, with a luminance of `1.0`.
+    #[inline]
+    pub fn white(&self) -> Vec3 {
+        self.white
+    }
+
+    /// The CIE XYZ values of `reflectance` under the illuminant.
+    fn tristimulus(&self, reflectance: &Spectrum) -> Vec3 {
+        reflectance
+            .values
+            .iter()
+            .zip(&self.weights)
+            .map(|(&value, &weight)| value * weight)
+            .sum()
+    }
+}
+
+/// The metamerism index of the reflectances `a` and `b` when the light
+/// changes from `reference` to `test`.
+///
+/// This is the CIEDE2000 color difference under `test`, after correcting
+/// `b` additively in CIELAB by how much it differs from `a` under
+/// `reference`, so that pairs that don't quite match to begin with are
+/// compared by how their difference changes.
+pub fn metamerism_index(
+    a: &Spectrum,
+    b: &Spectrum,
+    reference: &Illuminant,
+    test: &Illuminant,
+) -> f32 {
+    let correction = b.lab_under(reference) - a.lab_under(reference);
+    delta_e_2000(a.lab_under(test), b.lab_under(test) - correction)
+}
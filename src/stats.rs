@@ -0,0 +1,385 @@
+//! Statistics of color buffers.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::LinearSrgb;
+//! use colstodian::stats;
+//!
+//! let pixels = [Color::srgb_u8(255, 0, 0), Color::srgb_u8(0, 0, 255)];
+//!
+//! // Averaging light in a linear encoding gives a brighter purple than
+//! // averaging the encoded sRGB values would.
+//! let average = stats::average::<_, LinearSrgb>(pixels).unwrap();
+//! assert_eq!(average, Color::srgb_u8(188, 0, 188));
+//! ```
+
+use crate::details::color::to_oklab;
+use crate::details::encodings::{CieXyz, Oklab};
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::{Color, ColorEncoding};
+
+use core::ops::ControlFlow;
+
+use glam::Vec3;
+
+/// The mean of `colors`, averaged in the encoding `W`, or `None` if there are
+/// no colors.
+///
+/// Averaging in a linear encoding like
+/// [`LinearSrgb`](crate::basic_encodings::LinearSrgb) mixes light physically,
+/// as a blurred or downscaled image would. Averaging in
+/// [`Oklab`] gives the perceptual mean. Alpha is averaged separately.
+pub fn average<E, W>(colors: impl IntoIterator<Item = Color<E>>) -> Option<Color<E>>
+where
+    E: ColorEncoding,
+    W: ColorEncoding<Repr = Vec3>,
+    W::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<W::LinearSpace>,
+{
+    let (sum, alpha_sum, count) = colors.into_iter().fold(
+        (Vec3::ZERO, 0.0, 0usize),
+        |(sum, alpha_sum, count), color| {
+            let (mut raw, alpha) = E::src_transform_raw(color.repr);
+            <W::LinearSpace as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            (
+                sum + W::dst_transform_raw(raw, alpha),
+                alpha_sum + alpha,
+                count + 1,
+            )
+        },
+    );
+    (count > 0).then(|| {
+        let count = count as f32;
+        let alpha = alpha_sum / count;
+        let (mut raw, _) = W::src_transform_raw(sum / count);
+        <E::LinearSpace as LinearConvertFromRaw<W::LinearSpace>>::linear_part_raw(&mut raw);
+        Color::from_repr(E::dst_transform_raw(raw, alpha))
+    })
+}
+
+/// Up to `count` dominant colors of `colors`, with the fraction of colors each
+/// one stands for, most dominant first.
+///
+/// Colors are clustered with k-means in [`Oklab`], starting from the mean and
+/// repeatedly adding the color farthest from all cluster centers so far. The
+/// result is deterministic. Fewer than `count` colors are returned if `colors`
+/// has fewer distinct colors.
+pub fn dominant<E>(colors: impl IntoIterator<Item = Color<E>>, count: usize) -> Vec<(Color<E>, f32)>
+where
+    E: ColorEncoding,
+    CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    let points: Vec<(Vec3, f32)> = colors
+        .into_iter()
+        .map(|color| to_oklab::<E>(color.repr))
+        .collect();
+    let (assignment, clusters) = k_means(&points, count);
+    let (sums, counts) = cluster_sums(&points, &assignment, clusters);
+    let mut dominant: Vec<_> = sums
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|((sum, alpha_sum), count)| {
+            let color = from_oklab(sum / count as f32, alpha_sum / count as f32);
+            (color, count as f32 / points.len() as f32)
+        })
+        .collect();
+    dominant.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    dominant
+}
+
+/// The cluster of each of `points`, of up to `count` clusters, and the
+/// number of clusters.
+fn k_means(points: &[(Vec3, f32)], count: usize) -> (Vec<usize>, usize) {
+    const ITERATIONS: usize = 32;
+
+    if points.is_empty() || count == 0 {
+        (Vec::new(), 0)
+    } else {
+        let mut centers = initial_centers(points, count);
+        let assignment = vec![usize::MAX; points.len()];
+        let (ControlFlow::Break(assignment) | ControlFlow::Continue(assignment)) = (0..ITERATIONS)
+            .try_fold(assignment, |mut assignment, _| {
+                let mut changed = false;
+                points
+                    .iter()
+                    .zip(&mut assignment)
+                    .for_each(|((lab, _), cluster)| {
+                        let closest = nearest(&centers, *lab).0;
+                        changed |= *cluster != closest;
+                        *cluster = closest;
+                    });
+                if changed {
+                    let (sums, counts) = cluster_sums(points, &assignment, centers.len());
+                    centers
+                        .iter_mut()
+                        .zip(sums.iter().zip(&counts))
+                        .filter(|(_, (_, count))| **count > 0)
+                        .for_each(|(center, ((sum, _), count))| *center = *sum / *count as f32);
+                    ControlFlow::Continue(assignment)
+                } else {
+                    ControlFlow::Break(assignment)
+                }
+            });
+        (assignment, centers.len())
+    }
+}
+
+/// Up to `count` initial cluster centers of the non-empty `points`: their
+/// mean, followed by the point farthest from the centers so far, until every
+/// point is a center.
+fn initial_centers(points: &[(Vec3, f32)], count: usize) -> Vec<Vec3> {
+    let mean = points.iter().map(|(lab, _)| *lab).sum::<Vec3>() / points.len() as f32;
+    let mut centers = vec![mean];
+    (1..count).try_for_each(|_| {
+        let (farthest, distance) = points
+            .iter()
+            .map(|(lab, _)| (*lab, nearest(&centers, *lab).1))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        (distance > 0.0).then(|| centers.push(farthest))
+    });
+    centers
+}
+
+/// A method of estimating the illuminant of an image, for auto white
+/// balance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WhiteEstimation {
+    /// Assume the scene averages to gray, so the mean of each channel is the
+    /// color of the illuminant.
+    GrayWorld,
+    /// Assume the brightest surfaces are white, so the given percentile of
+    /// each channel is the color of the illuminant.
+    ///
+    /// A percentile below 100, like 99, ignores specular highlights and hot
+    /// pixels.
+    WhitePatch(f32),
+    /// The Minkowski mean of each channel with the given exponent, which
+    /// ranges from gray world at `1.0` to white patch as it grows. `6.0`
+    /// works well for most images.
+    ShadesOfGray(f32),
+}
+
+/// The white point of the illuminant of `colors`, estimated per channel of
+/// the linear working encoding `W`, with a luminance of one.
+///
+/// Returns `None` if there are no colors or the estimate has no luminance.
+/// Adapting from the estimate to the white point of the output, e.g. with
+/// [`ColorMatrix::bradford`](crate::calibration::ColorMatrix::bradford),
+/// white balances the image.
+pub fn estimate_white<E, W>(
+    colors: impl IntoIterator<Item = Color<E>>,
+    method: WhiteEstimation,
+) -> Option<Color<CieXyz>>
+where
+    E: ColorEncoding,
+    W: ColorEncoding<Repr = Vec3>,
+    W::LinearSpace: LinearConvertFromRaw<E::LinearSpace>,
+    CieXYZ: LinearConvertFromRaw<W::LinearSpace>,
+{
+    let channels: Vec<Vec3> = colors
+        .into_iter()
+        .map(|color| {
+            let (mut raw, alpha) = E::src_transform_raw(color.repr);
+            <W::LinearSpace as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+            W::dst_transform_raw(raw, alpha).max(Vec3::ZERO)
+        })
+        .filter(|channels| !channels.is_nan())
+        .collect();
+    (!channels.is_empty()).then_some(())?;
+    let count = channels.len() as f32;
+
+    let white = match method {
+        WhiteEstimation::GrayWorld => channels.iter().sum::<Vec3>() / count,
+        WhiteEstimation::WhitePatch(percentile) => {
+            let rank = ((percentile.clamp(0.0, 100.0) / 100.0 * (count - 1.0)).round()) as usize;
+            let channel = |index: usize| {
+                let mut values: Vec<f32> =
+                    channels.iter().map(|channels| channels[index]).collect();
+                values.sort_by(f32::total_cmp);
+                values[rank]
+            };
+            Vec3::new(channel(0), channel(1), channel(2))
+        }
+        WhiteEstimation::ShadesOfGray(exponent) => (channels
+            .iter()
+            .map(|channels| channels.powf(exponent))
+            .sum::<Vec3>()
+            / count)
+            .powf(1.0 / exponent),
+    };
+
+    let (mut raw, _) = W::src_transform_raw(white);
+    <CieXYZ as LinearConvertFromRaw<W::LinearSpace>>::linear_part_raw(&mut raw);
+    (raw.y > 0.0).then(|| Color::from_repr(raw / raw.y))
+}
+
+/// The index of and the squared distance to the center closest to `lab`.
+fn nearest(centers: &[Vec3], lab: Vec3) -> (usize, f32) {
+    centers
+        .iter()
+        .map(|center| center.distance_squared(lab))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap()
+}
+
+/// The sums of Oklab values and alphas, and the number of points, per cluster.
+fn cluster_sums(
+    points: &[(Vec3, f32)],
+    assignment: &[usize],
+    clusters: usize,
+) -> (Vec<(Vec3, f32)>, Vec<usize>) {
+    let mut sums = vec![(Vec3::ZERO, 0.0); clusters];
+    let mut counts = vec![0; clusters];
+    points
+        .iter()
+        .zip(assignment)
+        .for_each(|((lab, alpha), &cluster)| {
+            sums[cluster].0 += *lab;
+            sums[cluster].1 += alpha;
+            counts[cluster] += 1;
+        });
+    (sums, counts)
+}
+
+fn from_oklab<E>(lab: Vec3, alpha: f32) -> Color<E>
+where
+    E: ColorEncoding,
+    E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+{
+    let (mut raw, _) = Oklab::src_transform_raw(lab);
+    <E::LinearSpace as LinearConvertFromRaw<CieXYZ>>::linear_part_raw(&mut raw);
+    Color::from_repr(E::dst_transform_raw(raw, alpha))
+}
+
+/// Luminance statistics of a color buffer, as used to drive auto-exposure and
+/// tone mapping.
+///
+/// Luminance is relative luminance (CIE Y), where `1.0` is the luminance of
+/// the white point of the encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LuminanceStats {
+    sorted: Vec<f32>,
+    log_average: f32,
+}
+
+impl LuminanceStats {
+    /// The offset added to luminances before taking their logarithm, so that
+    /// black pixels don't pull the log-average to zero.
+    pub const LOG_DELTA: f32 = 1e-4;
+
+    /// The luminance of middle gray, an 18% reflectance.
+    pub const MIDDLE_GRAY: f32 = 0.18;
+
+    /// Compute the luminance statistics of `colors`, or `None` if there are
+    /// no colors with a luminance other than NaN.
+    pub fn new<E>(colors: impl IntoIterator<Item = Color<E>>) -> Option<Self>
+    where
+        E: ColorEncoding,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+    {
+        let mut sorted: Vec<f32> = colors
+            .into_iter()
+            .map(|color| {
+                let (mut raw, _) = E::src_transform_raw(color.repr);
+                <CieXYZ as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+                raw.y
+            })
+            .filter(|luminance| !luminance.is_nan())
+            .collect();
+        (!sorted.is_empty()).then_some(())?;
+        sorted.sort_by(f32::total_cmp);
+
+        let log_sum: f64 = sorted
+            .iter()
+            .map(|luminance| (luminance.max(0.0) + Self::LOG_DELTA).ln() as f64)
+            .sum();
+        let log_average = (log_sum / sorted.len() as f64).exp() as f32;
+        Some(Self {
+            sorted,
+            log_average,
+        })
+    }
+
+    /// The lowest luminance.
+    #[inline]
+    pub fn min(&self) -> f32 {
+        self.sorted[0]
+    }
+
+    /// The highest luminance.
+    #[inline]
+    pub fn max(&self) -> f32 {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    /// The luminance below which `percent` percent of the luminances lie,
+    /// interpolating linearly between neighboring luminances.
+    ///
+    /// `percent` is clamped to `0.0..=100.0`.
+    pub fn percentile(&self, percent: f32) -> f32 {
+        let rank = percent.clamp(0.0, 100.0) / 100.0 * (self.sorted.len() - 1) as f32;
+        let below = rank.floor() as usize;
+        let above = (below + 1).min(self.sorted.len() - 1);
+        let fraction = rank - below as f32;
+        self.sorted[below] + (self.sorted[above] - self.sorted[below]) * fraction
+    }
+
+    /// The median luminance.
+    #[inline]
+    pub fn median(&self) -> f32 {
+        self.percentile(50.0)
+    }
+
+    /// The log-average (geometric mean) luminance, the key value of
+    /// Reinhard-style tone mapping.
+    ///
+    /// [`Self::LOG_DELTA`] is added to each luminance before taking its
+    /// logarithm.
+    #[inline]
+    pub fn log_average(&self) -> f32 {
+        self.log_average
+    }
+
+    /// The number of luminances the statistics cover.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// The exposure adjustment in EV (stops) that brings the luminance
+    /// measured by `metering` to `target`.
+    ///
+    /// Scaling colors by `2^EV`, e.g. with [`exposure_scale`], applies the
+    /// adjustment. A `target` of [`Self::MIDDLE_GRAY`] is the usual choice
+    /// before tone mapping. The measured luminance is clamped to at least
+    /// [`Self::LOG_DELTA`], so that black images get a finite adjustment.
+    pub fn exposure_compensation(&self, metering: Metering, target: f32) -> f32 {
+        let measured = match metering {
+            Metering::LogAverage => self.log_average,
+            Metering::Percentile(percent) => self.percentile(percent),
+        };
+        (target / measured.max(Self::LOG_DELTA)).log2()
+    }
+}
+
+/// Which luminance of a buffer auto-exposure brings to its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metering {
+    /// The log-average luminance, which weighs the whole image.
+    LogAverage,
+    /// The luminance at the given percentile, e.g. `50.0` for the median, or
+    /// a high percentile to expose for the highlights.
+    Percentile(f32),
+}
+
+/// The factor that scales luminance by `ev` stops.
+#[inline]
+pub fn exposure_scale(ev: f32) -> f32 {
+    ev.exp2()
+}
@@ -0,0 +1,118 @@
+//! Compensation for the surround a display is viewed in.
+//!
+//! The darker the room around a display, the flatter the same image looks.
+//! Rec.709 television, viewed in a dim room, and cinema, viewed in the dark,
+//! make up for this with a system gamma above `1.0` from the scene to the
+//! display. This is a matter of taste, so conversions don't apply it unless
+//! asked to with [`Color::convert_for_surround`] or
+//! [`Color::convert_between_surrounds`].
+//!
+//! The gamma is applied to luminance, with the components scaled alike, so
+//! that chromaticities are kept, as in the HLG OOTF of ITU-R BT.2100.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::surround::Surround;
+//!
+//! let gray = Color::linear_srgb(0.18, 0.18, 0.18);
+//!
+//! assert_eq!(gray.convert_for_surround::<SrgbU8>(Surround::Average), gray.convert());
+//! // Midtones are darker for a dark room, white stays white.
+//! assert_eq!(
+//!     gray.convert_for_surround::<SrgbU8>(Surround::Dark),
+//!     Color::srgb_u8(100, 100, 100)
+//! );
+//! assert_eq!(
+//!     Color::linear_srgb(1.0, 1.0, 1.0).convert_for_surround::<SrgbU8>(Surround::Dark),
+//!     Color::srgb_u8(255, 255, 255)
+//! );
+//! ```
+
+use crate::details::linear_spaces::CieXYZ;
+use crate::details::traits::{ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+/// The lighting around a display.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Surround {
+    /// A room about as bright as the display, like an office.
+    #[default]
+    Average,
+    /// A dim room, as assumed for Rec.709 television.
+    Dim,
+    /// A dark room, as in a cinema.
+    Dark,
+}
+
+impl Surround {
+    /// The system gamma that compensates for this surround, relative to an
+    /// average one.
+    #[inline]
+    pub fn system_gamma(self) -> f32 {
+        match self {
+            Surround::Average => 1.0,
+            Surround::Dim => 1.1,
+            Surround::Dark => 1.2,
+        }
+    }
+
+    /// The gamma that adapts content mastered for `mastered` to this surround.
+    ///
+    /// It is below `1.0` when viewing in a brighter surround than the one the
+    /// content was mastered for.
+    #[inline]
+    pub fn gamma_from(self, mastered: Surround) -> f32 {
+        self.system_gamma() / mastered.system_gamma()
+    }
+}
+
+impl<SrcEnc: ColorEncoding> Color<SrcEnc> {
+    /// Convert `self`, meant for an average surround, to the display encoding
+    /// `DstEnc` with the system gamma of `surround` applied.
+    ///
+    /// The gamma is applied to luminance relative to the white of `DstEnc`.
+    /// Alpha is kept.
+    #[inline]
+    pub fn convert_for_surround<DstEnc>(self, surround: Surround) -> Color<DstEnc>
+    where
+        DstEnc: ColorEncoding + ConvertFrom<SrcEnc>,
+        DstEnc::LinearSpace: LinearConvertFromRaw<SrcEnc::LinearSpace>,
+        CieXYZ: LinearConvertFromRaw<DstEnc::LinearSpace>,
+    {
+        self.convert_between_surrounds(Surround::Average, surround)
+    }
+
+    /// Convert `self`, mastered for the surround `mastered`, to the display
+    /// encoding `DstEnc` for viewing in the surround `viewed`.
+    ///
+    /// See [`Surround::gamma_from`].
+    pub fn convert_between_surrounds<DstEnc>(
+        self,
+        mastered: Surround,
+        viewed: Surround,
+    ) -> Color<DstEnc>
+    where
+        DstEnc: ColorEncoding + ConvertFrom<SrcEnc>,
+        DstEnc::LinearSpace: LinearConvertFromRaw<SrcEnc::LinearSpace>,
+        CieXYZ: LinearConvertFromRaw<DstEnc::LinearSpace>,
+    {
+        let mut repr = self.repr;
+        <DstEnc as ConvertFrom<SrcEnc>>::map_src(&mut repr);
+        let (mut raw, alpha) = SrcEnc::src_transform_raw(repr);
+        <DstEnc::LinearSpace as LinearConvertFromRaw<SrcEnc::LinearSpace>>::linear_part_raw(
+            &mut raw,
+        );
+
+        let mut xyz = raw;
+        <CieXYZ as LinearConvertFromRaw<DstEnc::LinearSpace>>::linear_part_raw(&mut xyz);
+        if xyz.y > 0.0 {
+            raw *= Vec3::splat(xyz.y).powf(viewed.gamma_from(mastered) - 1.0);
+        }
+        Color::from_repr(DstEnc::dst_transform_raw(raw, alpha))
+    }
+}
@@ -0,0 +1,199 @@
+//! Tone mapping of scene-referred colors to a target display.
+//!
+//! A [`TargetDisplay`] describes the display the colors are mapped for by its
+//! peak luminance, black level and the level of SDR (diffuse) white. A
+//! [`ToneMapper`] maps scene-referred linear colors, where `1.0` is diffuse
+//! white, to that display with the EETF of ITU-R BT.2390: midtones are kept,
+//! diffuse white lands at or just below the SDR white of the display, and
+//! highlights up to the source peak roll off smoothly into the headroom above
+//! it. The same data thus maps consistently to an SDR display, a 300-nit
+//! laptop or a 1000-nit TV.
+//!
+//! The curve is applied to the intensity I of [`ICtCpPq`], so hues are kept,
+//! and chroma is reduced with the intensity as BT.2390 recommends. Mapped
+//! colors are relative to the SDR white of the display: they fit `0.0..=1.0`
+//! on an SDR display and reach [`TargetDisplay::headroom`] on an HDR one. For
+//! [`EncodedHdr10`], whose `1.0` is
+//! [`HDR_REFERENCE_WHITE`](crate::details::encodings::HDR_REFERENCE_WHITE),
+//! use a target with that SDR white.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::tone_map::{TargetDisplay, ToneMapper};
+//!
+//! let gray = Color::bt2020(0.18, 0.18, 0.18);
+//! let highlight = Color::bt2020(20.0, 20.0, 20.0);
+//!
+//! for target in [
+//!     TargetDisplay::SDR,
+//!     TargetDisplay::new(300.0, 0.2, 203.0),
+//!     TargetDisplay::HDR_1000,
+//! ] {
+//!     let mapper = ToneMapper::new(target);
+//!     // Middle gray looks alike everywhere, highlights use the headroom.
+//!     assert!((gray.tone_mapped(&mapper).g - 0.18).abs() < 0.01);
+//!     assert!((highlight.tone_mapped(&mapper).g / target.headroom() - 1.0).abs() < 0.01);
+//! }
+//! ```
+//!
+//! [`EncodedHdr10`]: crate::details::encodings::EncodedHdr10
+
+use crate::details::encodings::{HDR_REFERENCE_WHITE, ICtCpPq};
+use crate::details::linear_spaces::Bt2020;
+use crate::details::traits::LinearConvertFromRaw;
+use crate::transfer::{pq_eotf, pq_inverse_eotf};
+use crate::{Color, ColorEncoding};
+
+use glam::Vec3;
+
+/// A display that colors are tone mapped for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetDisplay {
+    /// The peak luminance in nits.
+    pub peak_nits: f32,
+    /// The black level in nits.
+    pub black_nits: f32,
+    /// The luminance of SDR (diffuse) white in nits.
+    pub sdr_white_nits: f32,
+}
+
+impl TargetDisplay {
+    /// An SDR display, with white at its peak of 100 nits.
+    pub const SDR: Self = Self::new(100.0, 0.0, 100.0);
+    /// An HDR display with a peak of 1000 nits and white at the HDR reference
+    /// white of 203 nits.
+    pub const HDR_1000: Self = Self::new(1000.0, 0.005, HDR_REFERENCE_WHITE);
+
+    /// A display with the given peak, black and SDR white levels in nits.
+    #[inline]
+    pub const fn new(peak_nits: f32, black_nits: f32, sdr_white_nits: f32) -> Self {
+        Self {
+            peak_nits,
+            black_nits,
+            sdr_white_nits,
+        }
+    }
+
+    /// How many times brighter than SDR white the display gets.
+    #[inline]
+    pub fn headroom(&self) -> f32 {
+        self.peak_nits / self.sdr_white_nits
+    }
+}
+
+/// The BT.2390 EETF mapping scene-referred colors to a [`TargetDisplay`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneMapper {
+    target: TargetDisplay,
+    source_peak: f32,
+    /// The PQ signal of the source peak.
+    source_pq: f32,
+    /// The normalized PQ signals of the target black and peak.
+    min_lum: f32,
+    max_lum: f32,
+}
+
+impl ToneMapper {
+    /// The default source peak, relative to diffuse white: the 1000 nits of a
+    /// common mastering display with diffuse white at the HDR reference white.
+    pub const DEFAULT_SOURCE_PEAK: f32 = 1000.0 / HDR_REFERENCE_WHITE;
+
+    /// A tone mapper for `target` with the [`Self::DEFAULT_SOURCE_PEAK`].
+    #[inline]
+    pub fn new(target: TargetDisplay) -> Self {
+        Self::with_source_peak(target, Self::DEFAULT_SOURCE_PEAK)
+    }
+
+    /// A tone mapper for `target` where `source_peak`, relative to diffuse
+    /// white, is the brightest level of the colors.
+    ///
+    /// Levels above it are mapped to the peak of the display. A lower source
+    /// peak leaves more of the range of the display to the levels below it.
+    pub fn with_source_peak(target: TargetDisplay, source_peak: f32) -> Self {
+        let source_pq = pq(source_peak * target.sdr_white_nits);
+        Self {
+            target,
+            source_peak,
+            source_pq,
+            min_lum: pq(target.black_nits) / source_pq,
+            max_lum: pq(target.peak_nits) / source_pq,
+        }
+    }
+
+    /// The display this maps colors to.
+    #[inline]
+    pub fn target(&self) -> &TargetDisplay {
+        &self.target
+    }
+
+    /// The source peak, relative to diffuse white.
+    #[inline]
+    pub fn source_peak(&self) -> f32 {
+        self.source_peak
+    }
+
+    /// Map a scene luminance in nits, with diffuse white at the SDR white of
+    /// the target, to the luminance the display shows in nits.
+    pub fn map_luminance(&self, nits: f32) -> f32 {
+        let e = pq(nits) / self.source_pq;
+        pq_eotf(Vec3::splat(self.eetf(e) * self.source_pq)).x
+    }
+
+    /// The EETF on PQ signals normalized to the source range.
+    fn eetf(&self, e: f32) -> f32 {
+        let e = e.clamp(0.0, 1.0);
+        // The knee where the roll-off towards the peak starts.
+        let knee = 1.5 * self.max_lum - 0.5;
+        let rolled_off = if e < knee || knee >= 1.0 {
+            e
+        } else {
+            let t = (e - knee) / (1.0 - knee);
+            let (t2, t3) = (t * t, t * t * t);
+            (2.0 * t3 - 3.0 * t2 + 1.0) * knee
+                + (t3 - 2.0 * t2 + t) * (1.0 - knee)
+                + (-2.0 * t3 + 3.0 * t2) * self.max_lum
+        };
+        let toe = (1.0 - rolled_off) * (1.0 - rolled_off);
+        (rolled_off + self.min_lum * toe * toe).min(self.max_lum)
+    }
+}
+
+impl<E> Color<E>
+where
+    E: ColorEncoding,
+    Bt2020: LinearConvertFromRaw<E::LinearSpace>,
+    E::LinearSpace: LinearConvertFromRaw<Bt2020>,
+{
+    /// Tone map this scene-referred color, where `1.0` is diffuse white, for
+    /// the display of `mapper`, keeping its hue.
+    ///
+    /// The result is relative to the SDR white of the display. Alpha is kept.
+    pub fn tone_mapped(self, mapper: &ToneMapper) -> Self {
+        // ICtCp is relative to the HDR reference white, nits are relative to
+        // the SDR white of the display.
+        let scale = mapper.target.sdr_white_nits / HDR_REFERENCE_WHITE;
+        let (mut raw, alpha) = E::src_transform_raw(self.repr);
+        <Bt2020 as LinearConvertFromRaw<E::LinearSpace>>::linear_part_raw(&mut raw);
+
+        let ictcp = ICtCpPq::dst_transform_raw(raw * scale, alpha);
+        let intensity = mapper.eetf(ictcp.x / mapper.source_pq) * mapper.source_pq;
+        let chroma = if ictcp.x > 0.0 && intensity > 0.0 {
+            (ictcp.x / intensity).min(intensity / ictcp.x)
+        } else {
+            0.0
+        };
+        let ictcp = Vec3::new(intensity, ictcp.y * chroma, ictcp.z * chroma);
+
+        let mut raw = ICtCpPq::src_transform_raw(ictcp).0 / scale;
+        <E::LinearSpace as LinearConvertFromRaw<Bt2020>>::linear_part_raw(&mut raw);
+        Color::from_repr(E::dst_transform_raw(raw, alpha))
+    }
+}
+
+/// The PQ signal of `nits`.
+#[inline(always)]
+fn pq(nits: f32) -> f32 {
+    pq_inverse_eotf(Vec3::splat(nits)).x
+}
@@ -0,0 +1,204 @@
+//! Keyframe color animation.
+//!
+//! # Examples
+//!
+//! A day/night cycle over 24 hours, mixed in [`Oklab`]:
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::SrgbaU8;
+//! use colstodian::gradient::InterpolationSpace;
+//! use colstodian::track::{ColorTrack, Repeat};
+//!
+//! let night = Color::<SrgbaU8>::srgba_u8(10, 10, 40, 255);
+//! let noon = Color::srgba_u8(255, 250, 230, 255);
+//! let sky = ColorTrack::new([(0.0, night), (12.0, noon), (24.0, night)])
+//!     .with_space(InterpolationSpace::Oklab)
+//!     .with_repeat(Repeat::Loop);
+//!
+//! assert_eq!(sky.duration(), 24.0);
+//! assert_eq!(sky.sample_mixed(36.0), noon);
+//! ```
+//!
+//! [`Oklab`]: crate::details::encodings::Oklab
+
+use crate::details::linear_spaces::{self, CieXYZ};
+use crate::details::traits::{LinearConvertFromRaw, LinearInterpolate};
+use crate::gradient::{Easing, Gradient, InterpolationSpace};
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+
+/// What a [`ColorTrack`] does outside of its keyframes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repeat {
+    /// Hold the color of the first or last keyframe.
+    #[default]
+    Once,
+    /// Start over from the first keyframe after the last one.
+    Loop,
+    /// Play backwards after the last keyframe, then forwards again.
+    PingPong,
+}
+
+/// Colors at points in time, sampleable at any time in between.
+///
+/// A track is a [`Gradient`] over time: keyframes are its stops, and the
+/// segments between them are eased and interpolated the same way.
+pub struct ColorTrack<E: ColorEncoding> {
+    keyframes: Gradient<E>,
+    repeat: Repeat,
+}
+
+impl<E: ColorEncoding> ColorTrack<E> {
+    /// Create a track from `(time, color)` keyframes.
+    ///
+    /// Keyframes are sorted by time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no keyframes or a time is NaN.
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, Color<E>)>) -> Self {
+        Self::from_gradient(Gradient::new(keyframes))
+    }
+
+    /// Create a track with the stops of `gradient` as keyframes, taking stop
+    /// positions as times.
+    #[inline]
+    pub fn from_gradient(gradient: Gradient<E>) -> Self {
+        Self {
+            keyframes: gradient,
+            repeat: Repeat::Once,
+        }
+    }
+
+    /// Set what this track does outside of its keyframes.
+    #[inline]
+    pub fn with_repeat(self, repeat: Repeat) -> Self {
+        Self { repeat, ..self }
+    }
+
+    /// Ease every segment between two keyframes with `easing`.
+    pub fn with_easing(self, easing: Easing) -> Self {
+        Self {
+            keyframes: self.keyframes.with_easing(easing),
+            ..self
+        }
+    }
+
+    /// Ease the segment between keyframe `segment` and the next keyframe with
+    /// `easing`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no keyframe after keyframe `segment`.
+    pub fn with_segment_easing(self, segment: usize, easing: Easing) -> Self {
+        Self {
+            keyframes: self.keyframes.with_segment_easing(segment, easing),
+            ..self
+        }
+    }
+
+    /// Interpolate in `space` when sampling.
+    pub fn with_space(self, space: InterpolationSpace) -> Self
+    where
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+        CieXYZ: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<CieXYZ>,
+    {
+        Self {
+            keyframes: self.keyframes.with_space(space),
+            ..self
+        }
+    }
+
+    /// The keyframes of this track, sorted by time.
+    #[inline]
+    pub fn keyframes(&self) -> &[(f32, Color<E>)] {
+        self.keyframes.stops()
+    }
+
+    /// The keyframes as a gradient over time.
+    #[inline]
+    pub fn gradient(&self) -> &Gradient<E> {
+        &self.keyframes
+    }
+
+    /// What this track does outside of its keyframes.
+    #[inline]
+    pub fn repeat(&self) -> Repeat {
+        self.repeat
+    }
+
+    /// The time of the first keyframe.
+    #[inline]
+    pub fn start(&self) -> f32 {
+        self.keyframes()[0].0
+    }
+
+    /// The time of the last keyframe.
+    #[inline]
+    pub fn end(&self) -> f32 {
+        self.keyframes()[self.keyframes().len() - 1].0
+    }
+
+    /// The time from the first to the last keyframe.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.end() - self.start()
+    }
+
+    /// The color at time `t`, interpolated as with
+    /// [`Gradient::sample_mixed`].
+    pub fn sample_mixed(&self, t: f32) -> Color<E>
+    where
+        linear_spaces::Srgb: LinearConvertFromRaw<E::LinearSpace>,
+        E::LinearSpace: LinearConvertFromRaw<linear_spaces::Srgb>,
+    {
+        self.keyframes.sample_mixed(self.local_time(t))
+    }
+
+    /// The time within the keyframes that `t` repeats to.
+    fn local_time(&self, t: f32) -> f32 {
+        let (start, duration) = (self.start(), self.duration());
+        match self.repeat {
+            _ if duration <= 0.0 => t,
+            Repeat::Once => t,
+            Repeat::Loop => start + (t - start).rem_euclid(duration),
+            Repeat::PingPong => {
+                let t = (t - start).rem_euclid(2.0 * duration);
+                start + duration - (t - duration).abs()
+            }
+        }
+    }
+}
+
+impl<E: ColorEncoding + LinearInterpolate> ColorTrack<E> {
+    /// The color at time `t`, interpolated as with [`Gradient::sample`].
+    pub fn sample(&self, t: f32) -> Color<E> {
+        self.keyframes.sample(self.local_time(t))
+    }
+}
+
+impl<E: ColorEncoding> Clone for ColorTrack<E> {
+    fn clone(&self) -> Self {
+        Self {
+            keyframes: self.keyframes.clone(),
+            repeat: self.repeat,
+        }
+    }
+}
+
+impl<E> fmt::Debug for ColorTrack<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColorTrack")
+            .field("keyframes", &self.keyframes)
+            .field("repeat", &self.repeat)
+            .finish()
+    }
+}
@@ -0,0 +1,400 @@
+//! Transfer functions on bare values.
+//!
+//! An OETF (opto-electronic transfer function) encodes linear light into the
+//! non-linear values stored in images and sent to displays, and an EOTF
+//! (electro-optical transfer function) decodes them back into linear light.
+//!
+//! The curves here are the ones the encodings of this crate use, operating
+//! directly on `f32` and [`Vec3`] so that they can be ported to shaders or
+//! baked into lookup tables without constructing [`Color`](crate::Color)s.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::transfer::{self, TransferFunction};
+//! use glam::Vec3;
+//!
+//! let encoded = transfer::srgb_oetf(Vec3::splat(0.18));
+//! assert!((encoded.x - 0.4613).abs() < 1e-4);
+//!
+//! // A 1D lookup table of the sRGB EOTF.
+//! let lut: Vec<f32> = (0..=255)
+//!     .map(|i| TransferFunction::Srgb.decode_f32(i as f32 / 255.0))
+//!     .collect();
+//! assert_eq!(lut[255], 1.0);
+//! ```
+
+use glam::Vec3;
+use kolor::details::color::WhitePoint;
+use kolor::details::transform;
+
+/// A transfer function, to pick a curve at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferFunction {
+    /// No transfer function, values are linear.
+    Linear,
+    /// The piecewise sRGB curve of IEC 61966-2-1.
+    Srgb,
+    /// The pure gamma of 563/256 of Adobe RGB (1998).
+    AdobeRgb,
+    /// The curve of ProPhoto RGB (ROMM RGB), a gamma of 1.8 with a linear
+    /// segment.
+    ProPhotoRgb,
+    /// The SMPTE ST 2084 perceptual quantizer of HDR10, where linear values
+    /// are in nits.
+    Pq,
+    /// The Kodak Cineon printing density log curve of film scans, on code
+    /// values normalized from `0..=1023`.
+    Cineon,
+}
+
+impl TransferFunction {
+    /// Encode linear `values` with the OETF, or the inverse EOTF for
+    /// [`TransferFunction::Pq`].
+    #[inline]
+    pub fn encode(self, values: Vec3) -> Vec3 {
+        match self {
+            TransferFunction::Linear => values,
+            TransferFunction::Srgb => srgb_oetf(values),
+            TransferFunction::AdobeRgb => adobe_rgb_oetf(values),
+            TransferFunction::ProPhotoRgb => prophoto_rgb_oetf(values),
+            TransferFunction::Pq => pq_inverse_eotf(values),
+            TransferFunction::Cineon => cineon_oetf(values),
+        }
+    }
+
+    /// Decode encoded `values` into linear values with the EOTF.
+    #[inline]
+    pub fn decode(self, values: Vec3) -> Vec3 {
+        match self {
+            TransferFunction::Linear => values,
+            TransferFunction::Srgb => srgb_eotf(values),
+            TransferFunction::AdobeRgb => adobe_rgb_eotf(values),
+            TransferFunction::ProPhotoRgb => prophoto_rgb_eotf(values),
+            TransferFunction::Pq => pq_eotf(values),
+            TransferFunction::Cineon => cineon_eotf(values),
+        }
+    }
+
+    /// The parameters of the EOTF as a parametric curve, or `None` for
+    /// [`TransferFunction::Pq`] and [`TransferFunction::Cineon`], which
+    /// aren't one; use the `PQ_*` and `CINEON_*` constants for them instead.
+    pub fn parameters(self) -> Option<TransferParameters> {
+        match self {
+            TransferFunction::Linear => Some(TransferParameters::gamma(1.0)),
+            TransferFunction::Srgb => Some(TransferParameters::new(
+                2.4,
+                1.0 / 1.055,
+                0.055 / 1.055,
+                1.0 / 12.92,
+                0.04045,
+                0.0,
+                0.0,
+            )),
+            TransferFunction::AdobeRgb => Some(TransferParameters::gamma(563.0 / 256.0)),
+            TransferFunction::ProPhotoRgb => Some(TransferParameters::new(
+                1.8,
+                1.0,
+                0.0,
+                1.0 / 16.0,
+                16.0 / 512.0,
+                0.0,
+                0.0,
+            )),
+            TransferFunction::Pq | TransferFunction::Cineon => None,
+        }
+    }
+
+    /// Encode a single linear `value`, as with [`TransferFunction::encode`].
+    #[inline]
+    pub fn encode_f32(self, value: f32) -> f32 {
+        self.encode(Vec3::splat(value)).x
+    }
+
+    /// Decode a single encoded `value`, as with [`TransferFunction::decode`].
+    #[inline]
+    pub fn decode_f32(self, value: f32) -> f32 {
+        self.decode(Vec3::splat(value)).x
+    }
+}
+
+/// The sRGB OETF, from linear to encoded values.
+#[inline(always)]
+pub fn srgb_oetf(linear: Vec3) -> Vec3 {
+    transform::srgb_oetf(linear, WhitePoint::D65)
+}
+
+/// The sRGB EOTF, from encoded to linear values.
+#[inline(always)]
+pub fn srgb_eotf(encoded: Vec3) -> Vec3 {
+    transform::srgb_eotf(encoded, WhitePoint::D65)
+}
+
+/// The Adobe RGB OETF, a gamma of 256/563.
+#[inline(always)]
+pub fn adobe_rgb_oetf(linear: Vec3) -> Vec3 {
+    const GAMMA: f32 = 256.0 / 563.0; // 1/2.19921875
+    linear.powf(GAMMA)
+}
+
+/// The Adobe RGB EOTF, a gamma of 563/256.
+#[inline(always)]
+pub fn adobe_rgb_eotf(encoded: Vec3) -> Vec3 {
+    const GAMMA: f32 = 563.0 / 256.0; // 2.19921875
+    encoded.powf(GAMMA)
+}
+
+/// The ProPhoto RGB OETF, a gamma of 1/1.8 with a linear segment below
+/// `1/512`.
+#[inline(always)]
+pub fn prophoto_rgb_oetf(linear: Vec3) -> Vec3 {
+    const ET: f32 = 1.0 / 512.0; // Threshold
+    let lower = linear * 16.0;
+    let higher = linear.powf(1.0 / 1.8);
+    Vec3::select(linear.cmple(Vec3::splat(ET)), lower, higher)
+}
+
+/// The ProPhoto RGB EOTF, the inverse of [`prophoto_rgb_oetf`].
+#[inline(always)]
+pub fn prophoto_rgb_eotf(encoded: Vec3) -> Vec3 {
+    const ET2: f32 = 16.0 / 512.0; // Encoded threshold
+    let lower = encoded / 16.0;
+    let higher = encoded.powf(1.8);
+    Vec3::select(encoded.cmple(Vec3::splat(ET2)), lower, higher)
+}
+
+/// The PQ exponent `m1`.
+pub const PQ_M1: f32 = 2610.0 / 16384.0;
+/// The PQ exponent `m2`.
+pub const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+/// The PQ constant `c1`.
+pub const PQ_C1: f32 = 3424.0 / 4096.0;
+/// The PQ constant `c2`.
+pub const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+/// The PQ constant `c3`.
+pub const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+/// The SMPTE ST 2084 (PQ) EOTF, from encoded values to nits.
+///
+/// Encoded values are clamped to `0.0..=1.0`.
+pub fn pq_eotf(encoded: Vec3) -> Vec3 {
+    let e = encoded.clamp(Vec3::ZERO, Vec3::ONE).powf(1.0 / PQ_M2);
+    let linear = ((e - PQ_C1).max(Vec3::ZERO) / (PQ_C2 - PQ_C3 * e)).powf(1.0 / PQ_M1);
+    linear * 10_000.0
+}
+
+/// The inverse of the SMPTE ST 2084 (PQ) EOTF, from nits to encoded values.
+///
+/// Levels are clamped to `0.0..=10_000.0` nits.
+pub fn pq_inverse_eotf(nits: Vec3) -> Vec3 {
+    let y = (nits / 10_000.0).clamp(Vec3::ZERO, Vec3::ONE).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/// The 10-bit Cineon code value of reference black.
+pub const CINEON_BLACK: f32 = 95.0;
+/// The 10-bit Cineon code value of reference white.
+pub const CINEON_WHITE: f32 = 685.0;
+/// The printing density of one Cineon code value.
+pub const CINEON_DENSITY_PER_CODE: f32 = 0.002;
+/// The negative film gamma assumed by the Cineon curve.
+pub const CINEON_GAMMA: f32 = 0.6;
+
+/// The linear value the Cineon curve maps to zero before rescaling black,
+/// `10^((CINEON_BLACK - CINEON_WHITE) * CINEON_DENSITY_PER_CODE / CINEON_GAMMA)`.
+const CINEON_OFFSET: f32 = 0.010_797_752;
+
+/// The Cineon log encoding, from linear values to code values normalized from
+/// `0..=1023`.
+///
+/// Reference white `1.0` maps to code value [`CINEON_WHITE`] and black to
+/// [`CINEON_BLACK`]; values above white use the headroom up to code value
+/// 1023.
+pub fn cineon_oetf(linear: Vec3) -> Vec3 {
+    let exposure = (linear * (1.0 - CINEON_OFFSET) + CINEON_OFFSET).max(Vec3::splat(1e-10));
+    (CINEON_WHITE + log10(exposure) * CINEON_GAMMA / CINEON_DENSITY_PER_CODE) / 1023.0
+}
+
+/// The Cineon log decoding, the inverse of [`cineon_oetf`].
+pub fn cineon_eotf(encoded: Vec3) -> Vec3 {
+    let density = (encoded * 1023.0 - CINEON_WHITE) * CINEON_DENSITY_PER_CODE / CINEON_GAMMA;
+    (exp10(density) - CINEON_OFFSET) / (1.0 - CINEON_OFFSET)
+}
+
+/// The V-Log constant `b`.
+const V_LOG_B: f32 = 0.00873;
+/// The V-Log constant `c`.
+const V_LOG_C: f32 = 0.241514;
+/// The V-Log constant `d`.
+const V_LOG_D: f32 = 0.598206;
+
+/// The Panasonic V-Log encoding, from scene-linear reflectance to normalized
+/// code values.
+///
+/// 18% gray maps to `0.423`, and values below `0.01` are encoded linearly.
+pub fn v_log_oetf(linear: Vec3) -> Vec3 {
+    let low = linear * 5.6 + 0.125;
+    let high = log10((linear + V_LOG_B).max(Vec3::splat(1e-10))) * V_LOG_C + V_LOG_D;
+    Vec3::select(linear.cmplt(Vec3::splat(0.01)), low, high)
+}
+
+/// The Panasonic V-Log decoding, the inverse of [`v_log_oetf`].
+pub fn v_log_eotf(encoded: Vec3) -> Vec3 {
+    let low = (encoded - 0.125) / 5.6;
+    let high = exp10((encoded - V_LOG_D) / V_LOG_C) - V_LOG_B;
+    Vec3::select(encoded.cmplt(Vec3::splat(0.181)), low, high)
+}
+
+/// The Canon Log 2 encoding, from scene-linear reflectance to legal range
+/// normalized 10-bit code values.
+///
+/// 18% gray maps to `0.398`. The curve is mirrored for negative values.
+pub fn canon_log2_oetf(linear: Vec3) -> Vec3 {
+    let x = linear / 0.9;
+    let log = log10(x.abs() * 87.099_38 + 1.0) * 0.281_863_1;
+    full_to_legal(x.signum() * log + 0.035_388_13)
+}
+
+/// The Canon Log 2 decoding, the inverse of [`canon_log2_oetf`].
+pub fn canon_log2_eotf(encoded: Vec3) -> Vec3 {
+    let clog = legal_to_full(encoded) - 0.035_388_13;
+    let x = (exp10(clog.abs() / 0.281_863_1) - 1.0) / 87.099_38;
+    clog.signum() * x * 0.9
+}
+
+/// The Canon Log 3 encoding, from scene-linear reflectance to legal range
+/// normalized 10-bit code values.
+///
+/// 18% gray maps to `0.343`. Values near zero are encoded linearly and the
+/// curve is mirrored for negative values.
+pub fn canon_log3_oetf(linear: Vec3) -> Vec3 {
+    let x = linear / 0.9;
+    let log = log10(x.abs() * 14.98325 + 1.0) * 0.428_899_1;
+    let low = Vec3::splat(0.076_232_09) - log;
+    let middle = x * 2.306_981_5 + 0.073_059_36;
+    let high = log + 0.069_886_63;
+    let clog = Vec3::select(
+        x.cmplt(Vec3::splat(-CANON_LOG3_CUT)),
+        low,
+        Vec3::select(x.cmple(Vec3::splat(CANON_LOG3_CUT)), middle, high),
+    );
+    full_to_legal(clog)
+}
+
+/// The Canon Log 3 decoding, the inverse of [`canon_log3_oetf`].
+pub fn canon_log3_eotf(encoded: Vec3) -> Vec3 {
+    let clog = legal_to_full(encoded);
+    let low = -(exp10((Vec3::splat(0.076_232_09) - clog) / 0.428_899_1) - 1.0) / 14.98325;
+    let middle = (clog - 0.073_059_36) / 2.306_981_5;
+    let high = (exp10((clog - 0.069_886_63) / 0.428_899_1) - 1.0) / 14.98325;
+    let x = Vec3::select(
+        clog.cmplt(Vec3::splat(0.040_761_62)),
+        low,
+        Vec3::select(clog.cmple(Vec3::splat(0.105_357_1)), middle, high),
+    );
+    x * 0.9
+}
+
+/// The reflectance, divided by 0.9, below which Canon Log 3 is linear.
+const CANON_LOG3_CUT: f32 = 0.014;
+
+/// Full range normalized values to the legal range of 10-bit video, code
+/// values `64..=940`.
+fn full_to_legal(full: Vec3) -> Vec3 {
+    (full * 876.0 + 64.0) / 1023.0
+}
+
+/// The inverse of [`full_to_legal`].
+fn legal_to_full(legal: Vec3) -> Vec3 {
+    (legal * 1023.0 - 64.0) / 876.0
+}
+
+/// The base 10 logarithm.
+fn log10(x: Vec3) -> Vec3 {
+    x.log2() / core::f32::consts::LOG2_10
+}
+
+/// 10 to the power of `x`.
+fn exp10(x: Vec3) -> Vec3 {
+    (x * core::f32::consts::LOG2_10).exp2()
+}
+
+/// An EOTF as the parametric curve of ICC profiles (`parametricCurveType`
+/// with all seven parameters), for uploading to shaders.
+///
+/// An encoded value `x` decodes to `(a * x + b)^g + e` where `x >= d`, and to
+/// `c * x + f` below. The fields are laid out as eight `f32`s, the last one
+/// padding, so the struct has the same layout in `repr(C)`, std140 and
+/// WGSL uniform buffers.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferParameters {
+    /// The exponent.
+    pub g: f32,
+    /// The scale before the exponent.
+    pub a: f32,
+    /// The offset before the exponent.
+    pub b: f32,
+    /// The slope of the linear segment.
+    pub c: f32,
+    /// The encoded value where the curve switches from the linear segment to
+    /// the power segment.
+    pub d: f32,
+    /// The offset after the exponent.
+    pub e: f32,
+    /// The offset of the linear segment.
+    pub f: f32,
+    _padding: f32,
+}
+
+impl TransferParameters {
+    /// A parametric curve from its seven parameters.
+    #[inline]
+    pub const fn new(g: f32, a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self {
+            g,
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            _padding: 0.0,
+        }
+    }
+
+    /// A pure power curve with exponent `g`.
+    #[inline]
+    pub const fn gamma(g: f32) -> Self {
+        Self::new(g, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Decode encoded `values` with the curve.
+    pub fn decode(&self, values: Vec3) -> Vec3 {
+        let power = (values * self.a + self.b).max(Vec3::ZERO).powf(self.g) + self.e;
+        let linear = values * self.c + self.f;
+        Vec3::select(values.cmpge(Vec3::splat(self.d)), power, linear)
+    }
+
+    /// Encode linear `values` with the inverse of the curve, clamped to
+    /// `0.0..=1.0`.
+    ///
+    /// Linear values below the linear segment, where it is flat or missing,
+    /// encode to `d`.
+    pub fn encode(&self, values: Vec3) -> Vec3 {
+        let threshold = self.decode(Vec3::splat(self.d));
+        let power = ((values - self.e).max(Vec3::ZERO).powf(1.0 / self.g) - self.b) / self.a;
+        let linear = if self.c > 0.0 {
+            (values - self.f) / self.c
+        } else {
+            Vec3::splat(self.d)
+        };
+        Vec3::select(values.cmpge(threshold), power, linear).clamp(Vec3::ZERO, Vec3::ONE)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for TransferParameters {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for TransferParameters {}
@@ -0,0 +1,99 @@
+//! Conversion constants as plain data for hand-written shaders.
+//!
+//! Matrices are returned column-major, as `matrix[column][row]`, which is the
+//! order WGSL, GLSL and HLSL with `column_major` expect, and what
+//! [`glam::Mat3::from_cols_array_2d`] takes. Multiplying a column vector of
+//! linear components by a matrix converts them.
+//!
+//! [`ConversionConstants`] bundles a matrix and the transfer functions of
+//! both encodings in a layout that can be copied into uniform buffers as is.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::basic_encodings::SrgbU8;
+//! use colstodian::details::encodings::Bt2020;
+//! use colstodian::details::linear_spaces;
+//! use colstodian::uniforms;
+//!
+//! let matrix = uniforms::matrix::<linear_spaces::Srgb, linear_spaces::Bt2020>();
+//! // The first column is where linear sRGB red ends up.
+//! assert!((matrix[0][0] - 0.6274).abs() < 1e-4);
+//!
+//! let constants = uniforms::conversion::<SrgbU8, Bt2020>().unwrap();
+//! assert_eq!(constants.decode.g, 2.4);
+//! assert_eq!(core::mem::size_of_val(&constants), 112);
+//! ```
+//!
+//! A WGSL declaration matching [`ConversionConstants`]:
+//!
+//! ```wgsl
+//! struct TransferParameters { g: f32, a: f32, b: f32, c: f32, d: f32, e: f32, f: f32, padding: f32 }
+//! struct ConversionConstants { matrix: mat3x3<f32>, decode: TransferParameters, encode: TransferParameters }
+//! ```
+
+use crate::ColorEncoding;
+use crate::calibration::ColorMatrix;
+use crate::details::traits::{LinearColorSpace, LinearConvertFromRaw};
+use crate::transfer::TransferParameters;
+
+/// The column-major matrix converting linear components of the color space
+/// `Src` to those of `Dst`.
+pub fn matrix<Src, Dst>() -> [[f32; 3]; 3]
+where
+    Src: LinearColorSpace,
+    Dst: LinearConvertFromRaw<Src>,
+{
+    ColorMatrix::conversion::<Src, Dst>()
+        .matrix
+        .to_cols_array_2d()
+}
+
+/// The column-major matrix converting linear components of the color space
+/// `Src` to those of `Dst`, with each column padded to four components as
+/// `mat3x3` is laid out in std140 and WGSL uniform buffers.
+pub fn matrix_padded<Src, Dst>() -> [[f32; 4]; 3]
+where
+    Src: LinearColorSpace,
+    Dst: LinearConvertFromRaw<Src>,
+{
+    matrix::<Src, Dst>().map(|[x, y, z]| [x, y, z, 0.0])
+}
+
+/// The constants of a conversion between two encodings, laid out for uniform
+/// buffers.
+///
+/// A shader converts by decoding with `decode`, multiplying by `matrix` and
+/// encoding with the inverse of `encode`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConversionConstants {
+    /// The column-major matrix between the linear color spaces, padded as in
+    /// [`matrix_padded`].
+    pub matrix: [[f32; 4]; 3],
+    /// The EOTF of the source encoding.
+    pub decode: TransferParameters,
+    /// The EOTF of the destination encoding, whose inverse encodes.
+    pub encode: TransferParameters,
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for ConversionConstants {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for ConversionConstants {}
+
+/// The constants converting from `Src` to `Dst`, or `None` if either has no
+/// parametric RGB transfer function, see [`ColorEncoding::TRANSFER`].
+pub fn conversion<Src, Dst>() -> Option<ConversionConstants>
+where
+    Src: ColorEncoding,
+    Dst: ColorEncoding,
+    Dst::LinearSpace: LinearConvertFromRaw<Src::LinearSpace>,
+{
+    Some(ConversionConstants {
+        matrix: matrix_padded::<Src::LinearSpace, Dst::LinearSpace>(),
+        decode: Src::TRANSFER?.parameters()?,
+        encode: Dst::TRANSFER?.parameters()?,
+    })
+}
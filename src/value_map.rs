@@ -0,0 +1,235 @@
+//! Mapping scalar values to colors, as in heatmaps.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::details::encodings::Oklab;
+//! use colstodian::gradient::Gradient;
+//! use colstodian::value_map::{Normalization, ValueMap};
+//!
+//! let gradient = Gradient::evenly_spaced([
+//!     Color::srgb_u8(0, 0, 255).convert::<Oklab>(),
+//!     Color::srgb_u8(255, 0, 0).convert::<Oklab>(),
+//! ]);
+//! let map = ValueMap::new(gradient, Normalization::log(1.0, 1000.0))
+//!     .with_nan_color(Color::oklab(0.5, 0.0, 0.0));
+//!
+//! assert_eq!(map.map(1.0), map.gradient().sample(0.0));
+//! assert_eq!(map.map(f32::NAN), Color::oklab(0.5, 0.0, 0.0));
+//! ```
+
+use crate::details::traits::LinearInterpolate;
+use crate::gradient::Gradient;
+use crate::{Color, ColorEncoding};
+
+use core::fmt;
+
+/// How values are normalized to gradient positions in `0.0..=1.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Normalization {
+    /// Linear from `min` to `max`.
+    Linear { min: f32, max: f32 },
+    /// Logarithmic from `min` to `max`. Values that aren't positive are below
+    /// the range.
+    Log { min: f32, max: f32 },
+    /// By rank within a sorted sample of the data, so that every color of the
+    /// gradient is used equally often. If all the data is the same value, it
+    /// maps to `0.0`.
+    Percentile { sorted: Vec<f32> },
+}
+
+impl Normalization {
+    /// Linear normalization from `min` to `max`.
+    #[inline]
+    pub fn linear(min: f32, max: f32) -> Self {
+        Self::Linear { min, max }
+    }
+
+    /// Logarithmic normalization from `min` to `max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not positive.
+    #[inline]
+    pub fn log(min: f32, max: f32) -> Self {
+        assert!(
+            min > 0.0,
+            "logarithmic normalization needs a positive `min`"
+        );
+        Self::Log { min, max }
+    }
+
+    /// Percentile normalization over `data`.
+    ///
+    /// NaN values in `data` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` contains no values other than NaN.
+    pub fn percentile(data: &[f32]) -> Self {
+        let mut sorted: Vec<_> = data.iter().copied().filter(|v| !v.is_nan()).collect();
+        assert!(!sorted.is_empty(), "percentile normalization needs data");
+        sorted.sort_by(f32::total_cmp);
+        Self::Percentile { sorted }
+    }
+
+    /// The gradient position of `value`.
+    ///
+    /// Values outside the range give positions outside `0.0..=1.0`.
+    pub fn normalize(&self, value: f32) -> f32 {
+        match self {
+            Self::Linear { min, max } => (value - min) / (max - min),
+            Self::Log { .. } if value <= 0.0 => f32::NEG_INFINITY,
+            Self::Log { min, max } => (value / min).ln() / (max / min).ln(),
+            Self::Percentile { sorted } => {
+                let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+                if value < first {
+                    -1.0
+                } else if value > last {
+                    2.0
+                } else if first == last {
+                    0.0
+                } else {
+                    let above = sorted
+                        .partition_point(|v| *v <= value)
+                        .min(sorted.len() - 1);
+                    let (low, high) = (sorted[above - 1], sorted[above]);
+                    let fraction = if high > low {
+                        (value - low) / (high - low)
+                    } else {
+                        0.0
+                    };
+                    ((above - 1) as f32 + fraction) / (sorted.len() - 1) as f32
+                }
+            }
+        }
+    }
+}
+
+/// Maps scalar values through a [`Gradient`].
+///
+/// NaN values and values outside the normalization range can be given
+/// dedicated colors. By default, NaN maps to [`Color::default`], and
+/// out-of-range values clamp to the ends of the gradient.
+pub struct ValueMap<E: ColorEncoding> {
+    gradient: Gradient<E>,
+    normalization: Normalization,
+    nan_color: Color<E>,
+    under_color: Option<Color<E>>,
+    over_color: Option<Color<E>>,
+}
+
+impl<E> ValueMap<E>
+where
+    E: ColorEncoding + LinearInterpolate,
+    E::Repr: Default,
+{
+    /// Create a map normalizing values with `normalization` and coloring them
+    /// with `gradient`.
+    ///
+    /// The gradient is sampled from `0.0` to `1.0`.
+    pub fn new(gradient: Gradient<E>, normalization: Normalization) -> Self {
+        Self {
+            gradient,
+            normalization,
+            nan_color: Color::default(),
+            under_color: None,
+            over_color: None,
+        }
+    }
+}
+
+impl<E: ColorEncoding + LinearInterpolate> ValueMap<E> {
+    /// Set the color of NaN values.
+    #[inline]
+    pub fn with_nan_color(self, color: Color<E>) -> Self {
+        Self {
+            nan_color: color,
+            ..self
+        }
+    }
+
+    /// Set the color of values below the normalization range.
+    #[inline]
+    pub fn with_under_color(self, color: Color<E>) -> Self {
+        Self {
+            under_color: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the color of values above the normalization range.
+    #[inline]
+    pub fn with_over_color(self, color: Color<E>) -> Self {
+        Self {
+            over_color: Some(color),
+            ..self
+        }
+    }
+
+    /// The gradient values are mapped through.
+    #[inline]
+    pub fn gradient(&self) -> &Gradient<E> {
+        &self.gradient
+    }
+
+    /// The normalization of values to gradient positions.
+    #[inline]
+    pub fn normalization(&self) -> &Normalization {
+        &self.normalization
+    }
+
+    /// The color of `value`.
+    pub fn map(&self, value: f32) -> Color<E> {
+        let position = self.normalization.normalize(value);
+        match (self.under_color, self.over_color) {
+            _ if value.is_nan() => self.nan_color,
+            (Some(under), _) if position < 0.0 => under,
+            (_, Some(over)) if position > 1.0 => over,
+            _ => self.gradient.sample(position.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+impl<E: ColorEncoding> Clone for ValueMap<E> {
+    fn clone(&self) -> Self {
+        Self {
+            gradient: self.gradient.clone(),
+            normalization: self.normalization.clone(),
+            nan_color: self.nan_color,
+            under_color: self.under_color,
+            over_color: self.over_color,
+        }
+    }
+}
+
+impl<E> PartialEq for ValueMap<E>
+where
+    E: ColorEncoding,
+    E::Repr: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.gradient == other.gradient
+            && self.normalization == other.normalization
+            && self.nan_color == other.nan_color
+            && self.under_color == other.under_color
+            && self.over_color == other.over_color
+    }
+}
+
+impl<E> fmt::Debug for ValueMap<E>
+where
+    E: ColorEncoding,
+    E::ComponentStruct: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueMap")
+            .field("gradient", &self.gradient)
+            .field("normalization", &self.normalization)
+            .field("nan_color", &self.nan_color)
+            .field("under_color", &self.under_color)
+            .field("over_color", &self.over_color)
+            .finish()
+    }
+}
@@ -0,0 +1,300 @@
+//! Views of colors in external memory.
+//!
+//! A [`StridedView`] reads typed colors from raw bytes laid out according to a
+//! [`PixelLayout`]: rows and pixels can be padded, and the components of a
+//! pixel can be stored in any order. Nothing is copied until a pixel is read,
+//! so frames from video decoders or mapped GPU memory can be processed in
+//! place.
+//!
+//! # Examples
+//!
+//! ```
+//! use colstodian::Color;
+//! use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+//! use colstodian::view::{PixelLayout, StridedView};
+//!
+//! // A 2×2 BGRX frame with rows padded to 12 bytes.
+//! let frame = [
+//!     0, 0, 255, 0, 0, 255, 0, 0, 0, 0, 0, 0, //
+//!     255, 0, 0, 0, 255, 255, 255, 0, 0, 0, 0, 0,
+//! ];
+//! let layout = PixelLayout::packed::<SrgbU8>(2, 2)
+//!     .with_pixel_stride(4)
+//!     .with_row_stride(12)
+//!     .with_channel_order([2, 1, 0, 3]);
+//! let view = StridedView::<SrgbU8>::new(&frame, layout).unwrap();
+//!
+//! assert_eq!(view.get(0, 0), Some(Color::srgb_u8(255, 0, 0)));
+//! assert_eq!(view.get(0, 1), Some(Color::srgb_u8(0, 0, 255)));
+//!
+//! let linear: Vec<_> = view.pixels().map(|c| c.convert::<LinearSrgb>()).collect();
+//! assert_eq!(linear[3], Color::linear_srgb(1.0, 1.0, 1.0));
+//! ```
+
+use crate::details::traits::{ColorRepr, ConvertFrom, LinearConvertFromRaw};
+use crate::{Color, ColorEncoding};
+
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// How the pixels of an image are laid out in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PixelLayout {
+    /// The width in pixels.
+    pub width: usize,
+    /// The height in pixels.
+    pub height: usize,
+    /// The distance in bytes between the starts of consecutive rows.
+    pub row_stride: usize,
+    /// The distance in bytes between the starts of consecutive pixels in a
+    /// row.
+    pub pixel_stride: usize,
+    /// `channel_order[i]` is the position within a pixel, in elements, of
+    /// component `i` of the repr. Entries past the number of components are
+    /// ignored.
+    pub channel_order: [usize; 4],
+}
+
+impl PixelLayout {
+    /// The layout of tightly packed pixels of the repr of `E`, without padding
+    /// and in component order.
+    pub fn packed<E: ColorEncoding>(width: usize, height: usize) -> Self {
+        let pixel_stride = size_of::<E::Repr>();
+        Self {
+            width,
+            height,
+            row_stride: width * pixel_stride,
+            pixel_stride,
+            channel_order: [0, 1, 2, 3],
+        }
+    }
+
+    /// Set the distance in bytes between the starts of consecutive rows.
+    #[inline]
+    pub fn with_row_stride(self, row_stride: usize) -> Self {
+        Self { row_stride, ..self }
+    }
+
+    /// Set the distance in bytes between the starts of consecutive pixels.
+    ///
+    /// This does not change the row stride.
+    #[inline]
+    pub fn with_pixel_stride(self, pixel_stride: usize) -> Self {
+        Self {
+            pixel_stride,
+            ..self
+        }
+    }
+
+    /// Set the position of each component within a pixel, e.g. `[2, 1, 0, 3]`
+    /// to read BGRA memory as RGBA.
+    #[inline]
+    pub fn with_channel_order(self, channel_order: [usize; 4]) -> Self {
+        Self {
+            channel_order,
+            ..self
+        }
+    }
+
+    /// The number of bytes needed to hold an image with this layout, or `None`
+    /// if it overflows.
+    fn required_len<E: ColorEncoding>(&self) -> Option<usize> {
+        if self.width == 0 || self.height == 0 {
+            Some(0)
+        } else {
+            let element = size_of::<<E::Repr as ColorRepr>::Element>();
+            let components = size_of::<E::Repr>() / element;
+            let pixel_extent = (self.channel_order[..components].iter().max()? + 1) * element;
+            (self.height - 1)
+                .checked_mul(self.row_stride)?
+                .checked_add((self.width - 1).checked_mul(self.pixel_stride)?)?
+                .checked_add(pixel_extent)
+        }
+    }
+
+    /// The byte offset of the pixel at `(x, y)`.
+    #[inline(always)]
+    fn offset(&self, x: usize, y: usize) -> usize {
+        y * self.row_stride + x * self.pixel_stride
+    }
+}
+
+/// A read-only view of colors in external memory.
+pub struct StridedView<'a, E: ColorEncoding> {
+    data: &'a [u8],
+    layout: PixelLayout,
+    _encoding: PhantomData<E>,
+}
+
+impl<'a, E> StridedView<'a, E>
+where
+    E: ColorEncoding,
+    E::Repr: bytemuck::Pod,
+{
+    /// Create a view of `data` with `layout`, or `None` if `data` is too short
+    /// for the layout.
+    pub fn new(data: &'a [u8], layout: PixelLayout) -> Option<Self> {
+        (layout.required_len::<E>()? <= data.len()).then_some(Self {
+            data,
+            layout,
+            _encoding: PhantomData,
+        })
+    }
+
+    /// The layout of the viewed memory.
+    #[inline]
+    pub fn layout(&self) -> &PixelLayout {
+        &self.layout
+    }
+
+    /// The width in pixels.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.layout.width
+    }
+
+    /// The height in pixels.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.layout.height
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<Color<E>> {
+        (x < self.layout.width && y < self.layout.height).then(|| self.read(x, y))
+    }
+
+    /// An iterator over the pixels of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> impl ExactSizeIterator<Item = Color<E>> + '_ {
+        assert!(y < self.layout.height, "row out of bounds");
+        (0..self.layout.width).map(move |x| self.read(x, y))
+    }
+
+    /// An iterator over all pixels, row by row.
+    pub fn pixels(&self) -> impl Iterator<Item = Color<E>> + '_ {
+        (0..self.layout.height).flat_map(move |y| self.row(y))
+    }
+
+    fn read(&self, x: usize, y: usize) -> Color<E> {
+        read_pixel(self.data, &self.layout, self.layout.offset(x, y))
+    }
+}
+
+/// A mutable view of colors in external memory.
+pub struct StridedViewMut<'a, E: ColorEncoding> {
+    data: &'a mut [u8],
+    layout: PixelLayout,
+    _encoding: PhantomData<E>,
+}
+
+impl<'a, E> StridedViewMut<'a, E>
+where
+    E: ColorEncoding,
+    E::Repr: bytemuck::Pod,
+{
+    /// Create a mutable view of `data` with `layout`, or `None` if `data` is
+    /// too short for the layout.
+    ///
+    /// Bytes of padding between pixels and rows are never written.
+    pub fn new(data: &'a mut [u8], layout: PixelLayout) -> Option<Self> {
+        (layout.required_len::<E>()? <= data.len()).then_some(Self {
+            data,
+            layout,
+            _encoding: PhantomData,
+        })
+    }
+
+    /// A read-only view of the same memory.
+    #[inline]
+    pub fn as_view(&self) -> StridedView<'_, E> {
+        StridedView {
+            data: self.data,
+            layout: self.layout,
+            _encoding: PhantomData,
+        }
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<Color<E>> {
+        self.as_view().get(x, y)
+    }
+
+    /// Set the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, color: Color<E>) {
+        assert!(
+            x < self.layout.width && y < self.layout.height,
+            "pixel out of bounds"
+        );
+        let element = size_of::<<E::Repr as ColorRepr>::Element>();
+        let base = self.layout.offset(x, y);
+        bytemuck::bytes_of(&color.repr)
+            .chunks_exact(element)
+            .zip(self.layout.channel_order)
+            .for_each(|(component, position)| {
+                let start = base + position * element;
+                self.data[start..start + element].copy_from_slice(component);
+            });
+    }
+
+    /// Replace every pixel with `f(color)`.
+    pub fn map_in_place(&mut self, mut f: impl FnMut(Color<E>) -> Color<E>) {
+        (0..self.layout.height).for_each(|y| {
+            (0..self.layout.width).for_each(|x| {
+                let color = read_pixel(self.data, &self.layout, self.layout.offset(x, y));
+                self.set(x, y, f(color));
+            })
+        });
+    }
+
+    /// Overwrite every pixel with the matching pixel of `src`, converted to
+    /// `E`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has a different width or height.
+    pub fn convert_from<S>(&mut self, src: &StridedView<'_, S>)
+    where
+        S: ColorEncoding,
+        S::Repr: bytemuck::Pod,
+        E: ConvertFrom<S>,
+        E::LinearSpace: LinearConvertFromRaw<S::LinearSpace>,
+    {
+        assert!(
+            src.width() == self.layout.width && src.height() == self.layout.height,
+            "views must have the same size"
+        );
+        (0..self.layout.height).for_each(|y| {
+            src.row(y)
+                .enumerate()
+                .for_each(|(x, color)| self.set(x, y, color.convert()));
+        });
+    }
+}
+
+/// Gather the components of the pixel starting at `base`.
+fn read_pixel<E>(data: &[u8], layout: &PixelLayout, base: usize) -> Color<E>
+where
+    E: ColorEncoding,
+    E::Repr: bytemuck::Pod,
+{
+    let element = size_of::<<E::Repr as ColorRepr>::Element>();
+    let mut repr: E::Repr = bytemuck::Zeroable::zeroed();
+    bytemuck::bytes_of_mut(&mut repr)
+        .chunks_exact_mut(element)
+        .zip(layout.channel_order)
+        .for_each(|(component, position)| {
+            let start = base + position * element;
+            component.copy_from_slice(&data[start..start + element]);
+        });
+    Color::from_repr(repr)
+}
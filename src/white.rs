@@ -0,0 +1,13 @@
+//! The white points shared by the profile, camera and spectral modules.
+
+use glam::Vec3;
+
+/// The CIE xy chromaticity of the D65 white point.
+pub(crate) const D65_XY: [f32; 2] = [0.3127, 0.3290];
+
+/// The D65 white point in CIE XYZ.
+pub(crate) const D65: Vec3 = Vec3::new(0.950_47, 1.0, 1.088_83);
+
+/// The D50 white point in CIE XYZ, the white of the ICC and DNG profile
+/// connection spaces.
+pub(crate) const D50: Vec3 = Vec3::new(0.964_22, 1.0, 0.825_21);
@@ -0,0 +1,80 @@
+//! Tests for ANSI terminal color output.
+
+use colstodian::Color;
+use colstodian::ansi::{Ansi16, Swatches, ansi256_to_srgb};
+use colstodian::basic_encodings::LinearSrgb;
+
+#[test]
+fn truecolor_sequences() {
+    let color = Color::srgb_u8(12, 34, 56);
+
+    assert_eq!(color.to_ansi_truecolor().to_string(), "\x1b[38;2;12;34;56m");
+    assert_eq!(
+        color.to_ansi_truecolor().background().to_string(),
+        "\x1b[48;2;12;34;56m"
+    );
+    assert_eq!(
+        Color::<LinearSrgb>::WHITE.to_ansi_truecolor().rgb,
+        [255, 255, 255]
+    );
+}
+
+#[test]
+fn palette_entries_map_to_themselves() {
+    (16..=255u8).for_each(|index| {
+        assert_eq!(ansi256_to_srgb(index).nearest_ansi256().index, index);
+    });
+    (0..16u8).for_each(|index| {
+        assert_eq!(ansi256_to_srgb(index).nearest_ansi16().index, index);
+    });
+}
+
+#[test]
+fn palette_layout() {
+    assert_eq!(ansi256_to_srgb(16).repr, [0, 0, 0]);
+    assert_eq!(ansi256_to_srgb(196).repr, [255, 0, 0]);
+    assert_eq!(ansi256_to_srgb(231).repr, [255, 255, 255]);
+    assert_eq!(ansi256_to_srgb(244).repr, [128, 128, 128]);
+}
+
+#[test]
+fn ansi16_sequences() {
+    let seq = |index, background| Ansi16 { index, background }.to_string();
+
+    assert_eq!(seq(1, false), "\x1b[31m");
+    assert_eq!(seq(1, true), "\x1b[41m");
+    assert_eq!(seq(9, false), "\x1b[91m");
+    assert_eq!(seq(15, true), "\x1b[107m");
+}
+
+#[test]
+fn nearest_is_perceptual() {
+    assert_eq!(Color::srgb_u8(250, 250, 250).nearest_ansi256().index, 231);
+    assert_eq!(Color::srgb_u8(120, 120, 122).nearest_ansi256().index, 243);
+    assert_eq!(Color::srgb_u8(200, 10, 10).nearest_ansi16().index, 1);
+}
+
+#[test]
+fn swatches() {
+    let palette = [Color::srgb_u8(255, 0, 0), Color::srgb_u8(0, 0, 255)];
+
+    assert_eq!(
+        Swatches::new(&palette).to_string(),
+        "\x1b[48;2;255;0;0m  \x1b[48;2;0;0;255m  \x1b[0m"
+    );
+    assert_eq!(
+        Swatches::new(&palette).with_hex().to_string(),
+        "\x1b[48;2;255;0;0m        \x1b[48;2;0;0;255m        \x1b[0m\n#ff0000 #0000ff "
+    );
+    assert_eq!(
+        Swatches::new(&palette)
+            .block_width(4)
+            .with_labels(&["red"])
+            .to_string(),
+        "\x1b[48;2;255;0;0m    \x1b[48;2;0;0;255m    \x1b[0m\nred     "
+    );
+    let swatches = Swatches::new(&palette).with_hex();
+    assert_eq!(swatches.clone(), swatches);
+    assert_ne!(swatches, Swatches::new(&palette));
+    assert!(format!("{swatches:?}").starts_with("Swatches"));
+}
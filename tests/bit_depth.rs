@@ -0,0 +1,63 @@
+use colstodian::basic_encodings::*;
+use colstodian::bit_depth::{self, max_value, normalize, rescale};
+use colstodian::details::encodings::{
+    EncodedSrgbU10, EncodedSrgbU12, EncodedSrgbU14, EncodedSrgbU16, EncodedSrgbaU16,
+};
+use colstodian::*;
+
+#[test]
+fn rescaling() {
+    assert_eq!(max_value(10), 1023);
+    assert_eq!(max_value(16), u16::MAX);
+
+    (0..=255u16).for_each(|v| {
+        assert_eq!(rescale(v, 8, 16), v * 257);
+        assert_eq!(bit_depth::u8_to_u16(v as u8), v * 257);
+        assert_eq!(rescale(rescale(v, 8, 10), 10, 8), v);
+        assert_eq!(rescale(rescale(v, 8, 12), 12, 8), v);
+        assert_eq!(bit_depth::u16_to_u8(v * 257), v as u8);
+    });
+
+    // Out of range values are clamped.
+    assert_eq!(rescale(2000, 10, 8), 255);
+    assert_eq!(normalize(1023, 10), 1.0);
+}
+
+#[test]
+#[should_panic(expected = "bit depth")]
+fn invalid_bit_depth_panics() {
+    rescale(0, 8, 17);
+}
+
+#[test]
+fn encodings_agree_with_rescaling() {
+    (0..=255u8).for_each(|v| {
+        let color = Color::srgb_u8(v, 0, 255);
+        let u16 = color.convert::<EncodedSrgbU16>();
+        assert_eq!(u16.repr, [v as u16 * 257, 0, u16::MAX]);
+        assert_eq!(u16.convert::<SrgbU8>(), color);
+
+        let u10 = color.convert::<EncodedSrgbU10>();
+        assert_eq!(u10.r, rescale(v as u16, 8, 10));
+        assert_eq!(u10.convert::<SrgbU8>(), color);
+
+        let u12 = color.convert::<EncodedSrgbU12>();
+        assert_eq!(u12.r, rescale(v as u16, 8, 12));
+        assert_eq!(u12.convert::<SrgbU8>(), color);
+
+        let u14 = color.convert::<EncodedSrgbU14>();
+        assert_eq!(u14.r, rescale(v as u16, 8, 14));
+        assert_eq!(u14.convert::<SrgbU8>(), color);
+    });
+
+    // Full scale is white, not a dim gray as if read as 16-bit.
+    let white = Color::encoded_srgb_u12(4095, 4095, 4095).convert::<LinearSrgb>();
+    assert_eq!(white.repr, glam::Vec3::ONE);
+    assert_eq!(
+        Color::encoded_srgb_u14(16383, 0, 0).convert::<SrgbU8>(),
+        Color::srgb_u8(255, 0, 0)
+    );
+
+    let translucent = Color::srgba_u8(10, 20, 30, 128).convert::<EncodedSrgbaU16>();
+    assert_eq!(translucent.repr, [2570, 5140, 7710, 32896]);
+}
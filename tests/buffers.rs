@@ -0,0 +1,72 @@
+//! Tests for typed image buffers.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::buffer::ColorBuffer;
+
+#[test]
+fn pixel_and_row_access() {
+    let mut buffer = ColorBuffer::<SrgbU8>::new(3, 2);
+    buffer[(2, 1)] = Color::srgb_u8(1, 2, 3);
+    *buffer.get_mut(0, 0).unwrap() = Color::srgb_u8(4, 5, 6);
+
+    assert_eq!(buffer.get(3, 0), None);
+    assert_eq!(buffer.row(1)[2], Color::srgb_u8(1, 2, 3));
+    assert_eq!(buffer.rows().len(), 2);
+    assert_eq!(buffer.pixels()[0], Color::srgb_u8(4, 5, 6));
+
+    buffer.row_mut(0).fill(Color::srgb_u8(7, 7, 7));
+    assert!(buffer.row(0).iter().all(|&p| p == Color::srgb_u8(7, 7, 7)));
+}
+
+#[test]
+fn from_pixels_checks_size() {
+    let pixels = vec![Color::srgb_u8(0, 0, 0); 6];
+    assert!(ColorBuffer::from_pixels(2, 3, pixels.clone()).is_some());
+    assert!(ColorBuffer::from_pixels(4, 2, pixels).is_none());
+}
+
+#[test]
+fn whole_buffer_conversion() {
+    let buffer = ColorBuffer::from_fn(2, 2, |x, y| Color::srgb_u8(x as u8 * 255, y as u8 * 255, 0));
+    let linear = buffer.convert::<LinearSrgb>();
+
+    assert_eq!(linear.width(), 2);
+    assert_eq!(linear[(1, 0)], Color::linear_srgb(1.0, 0.0, 0.0));
+    assert_eq!(linear.convert::<SrgbU8>(), buffer);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_map() {
+    let buffer = ColorBuffer::from_fn(64, 48, |x, y| Color::srgb_u8(x as u8, y as u8, 7));
+    let invert = |color: Color<SrgbU8>| Color::srgb_u8(255 - color.r, 255 - color.g, 255 - color.b);
+    assert_eq!(buffer.par_map(invert), buffer.map(invert));
+    assert_eq!(
+        buffer.par_convert::<LinearSrgb>(),
+        buffer.convert::<LinearSrgb>()
+    );
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn byte_interop() {
+    let bytes = [255u8, 0, 0, 0, 255, 0];
+    let mut buffer = ColorBuffer::<SrgbU8>::from_bytes(2, 1, &bytes).unwrap();
+    assert_eq!(buffer[(1, 0)], Color::srgb_u8(0, 255, 0));
+    assert!(ColorBuffer::<SrgbU8>::from_bytes(2, 2, &bytes).is_none());
+
+    buffer.as_bytes_mut()[2] = 9;
+    assert_eq!(buffer[(0, 0)], Color::srgb_u8(255, 0, 9));
+    assert_eq!(buffer.as_bytes(), &[255, 0, 9, 0, 255, 0]);
+
+    // Unaligned float data is copied.
+    let floats: Vec<u8> = [0.5f32, 0.25, 1.0]
+        .iter()
+        .flat_map(|v| v.to_ne_bytes())
+        .collect();
+    let linear = ColorBuffer::<LinearSrgb>::from_bytes(1, 1, &floats).unwrap();
+    assert_eq!(linear[(0, 0)], Color::linear_srgb(0.5, 0.25, 1.0));
+}
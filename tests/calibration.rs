@@ -0,0 +1,64 @@
+//! Tests for color correction matrices.
+
+use colstodian::Color;
+use colstodian::basic_encodings::LinearSrgb;
+use colstodian::calibration::ColorMatrix;
+use colstodian::colorchecker::PATCHES;
+use glam::{Mat3, Vec3};
+
+fn chart() -> [Color<LinearSrgb>; 24] {
+    PATCHES.map(|patch| patch.srgb.convert())
+}
+
+#[test]
+fn fit_recovers_matrix() {
+    let camera = ColorMatrix::new(Mat3::from_cols(
+        Vec3::new(0.7, 0.2, 0.05),
+        Vec3::new(0.25, 0.9, 0.1),
+        Vec3::new(0.05, -0.1, 0.85),
+    ));
+    let pairs = chart().map(|reference| (camera.transform(reference), reference));
+    let correction = ColorMatrix::fit(pairs).unwrap();
+    assert!(
+        correction
+            .matrix
+            .abs_diff_eq(camera.inverse().unwrap().matrix, 1e-4)
+    );
+    assert_eq!(correction.offset, Vec3::ZERO);
+    assert!(
+        camera
+            .then(correction)
+            .matrix
+            .abs_diff_eq(Mat3::IDENTITY, 1e-4)
+    );
+}
+
+#[test]
+fn fit_affine_recovers_offset() {
+    let camera = ColorMatrix::with_offset(
+        Mat3::from_diagonal(Vec3::new(0.9, 1.1, 0.8)),
+        Vec3::new(0.02, 0.01, 0.03),
+    );
+    let pairs = chart().map(|reference| (camera.transform(reference), reference));
+    let correction = ColorMatrix::fit_affine(pairs).unwrap();
+    let expected = camera.inverse().unwrap();
+    assert!(correction.matrix.abs_diff_eq(expected.matrix, 1e-4));
+    assert!(correction.offset.abs_diff_eq(expected.offset, 1e-4));
+
+    // Without the offset, the fit can only approximate the camera.
+    let linear = ColorMatrix::fit(pairs).unwrap();
+    assert!(!linear.matrix.abs_diff_eq(expected.matrix, 1e-3));
+}
+
+#[test]
+fn fit_needs_spanning_colors() {
+    let gray = |v| Color::linear_srgb(v, v, v);
+    let grays = [0.1, 0.5, 0.9].map(|v| (gray(v), gray(v)));
+    assert_eq!(ColorMatrix::fit(grays), None);
+    assert_eq!(
+        ColorMatrix::fit_affine(chart()[..3].iter().map(|&c| (c, c))),
+        None
+    );
+    assert_eq!(ColorMatrix::fit::<LinearSrgb>([]), None);
+    assert_eq!(ColorMatrix::new(Mat3::ZERO).inverse(), None);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use approx::assert_relative_eq;
+use colstodian::Color;
+use colstodian::basic_encodings::*;
+use colstodian::buffer::ColorBuffer;
+use colstodian::chroma::{ChromaFilter, ChromaSiting, SubsampledBuffer, Subsampling};
+use colstodian::details::encodings::YCbCrBt709;
+
+/// A frame whose first chroma component is the x coordinate.
+fn ramp(width: usize, height: usize) -> ColorBuffer<YCbCrBt709> {
+    ColorBuffer::from_fn(width, height, |x, y| {
+        Color::ycbcr_bt709(y as f32, x as f32, 0.0)
+    })
+}
+
+#[test]
+fn ycbcr_bt709() {
+    let white = Color::srgb_u8(255, 255, 255).convert::<YCbCrBt709>();
+    assert_relative_eq!(white.y, 1.0, epsilon = 1e-5);
+    assert_relative_eq!(white.cb, 0.0, epsilon = 1e-5);
+    assert_relative_eq!(white.cr, 0.0, epsilon = 1e-5);
+
+    let red = Color::srgb_u8(255, 0, 0).convert::<YCbCrBt709>();
+    assert_relative_eq!(red.y, 0.2126, epsilon = 1e-5);
+    assert_relative_eq!(red.cr, 0.5, epsilon = 1e-5);
+
+    let color = Color::srgb_u8(12, 200, 99);
+    assert_eq!(color.convert::<YCbCrBt709>().convert::<SrgbU8>(), color);
+}
+
+#[test]
+fn box_filter_averages_blocks() {
+    let subsampled = SubsampledBuffer::new(
+        &ramp(5, 4),
+        Subsampling::Yuv420,
+        ChromaSiting::Center,
+        ChromaFilter::Box,
+    );
+    assert_eq!(subsampled.chroma_size(), (3, 2));
+    assert_eq!(subsampled.luma().len(), 5 * 4);
+
+    let cb: Vec<f32> = subsampled.chroma().iter().map(|c| c.x).collect();
+    assert_eq!(cb, [0.5, 2.5, 4.0, 0.5, 2.5, 4.0]);
+}
+
+#[test]
+fn bilinear_filter_respects_siting() {
+    let frame = ramp(8, 2);
+    let cb = |siting| {
+        let subsampled =
+            SubsampledBuffer::new(&frame, Subsampling::Yuv422, siting, ChromaFilter::Bilinear);
+        subsampled.chroma()[1].x
+    };
+    // The second chroma sample sits at x = 2 when co-sited and x = 2.5 when
+    // centered.
+    assert_relative_eq!(cb(ChromaSiting::Left), 2.0);
+    assert_relative_eq!(cb(ChromaSiting::Center), 2.5);
+
+    [ChromaSiting::Left, ChromaSiting::Center]
+        .into_iter()
+        .for_each(|siting| {
+            let subsampled =
+                SubsampledBuffer::new(&frame, Subsampling::Yuv422, siting, ChromaFilter::Bilinear);
+            let reconstructed = subsampled.reconstruct(ChromaFilter::Bilinear);
+            // Away from the edges, which are clamped, the ramp is reconstructed.
+            (3..5).for_each(|x| assert_relative_eq!(reconstructed[(x, 1)].cb, x as f32));
+            assert_eq!(reconstructed[(3, 1)].y, 1.0);
+        });
+}
+
+#[test]
+fn full_resolution_chroma_is_lossless() {
+    let frame = ramp(3, 3);
+    [ChromaFilter::Box, ChromaFilter::Bilinear]
+        .into_iter()
+        .for_each(|filter| {
+            let subsampled =
+                SubsampledBuffer::new(&frame, Subsampling::Yuv444, ChromaSiting::Left, filter);
+            assert_eq!(subsampled.reconstruct(filter), frame);
+        });
+}
@@ -0,0 +1,71 @@
+//! Tests for chromaticity coordinates, CCT, Duv and whiteness.
+
+use colstodian::Color;
+use colstodian::chromaticity::{self, cct_duv, planckian_uv, uv_prime_to_xy, xy_to_uv_prime};
+use colstodian::details::encodings::CieXyz;
+
+#[test]
+fn uv_prime_round_trip() {
+    let (u, v) = xy_to_uv_prime(0.3127, 0.3290);
+    assert!((u - 0.1978).abs() < 1e-4 && (v - 0.4683).abs() < 1e-4);
+    let (x, y) = uv_prime_to_xy(u, v);
+    assert!((x - 0.3127).abs() < 1e-6 && (y - 0.3290).abs() < 1e-6);
+
+    let color = Color::from_uv_prime_luminance(u, v, 0.5);
+    let expected = Color::from_xy_luminance(0.3127, 0.3290, 0.5);
+    assert!(color.repr.abs_diff_eq(expected.repr, 1e-6));
+}
+
+#[test]
+fn cct_and_duv() {
+    // Points on the locus have a Duv of zero.
+    [2700.0, 4000.0, 6500.0, 10_000.0]
+        .into_iter()
+        .for_each(|kelvin| {
+            let (u, v) = planckian_uv(kelvin);
+            let (cct, duv) = cct_duv(u, v * 1.5);
+            assert!((cct - kelvin).abs() / kelvin < 1e-3, "{kelvin} {cct}");
+            assert!(duv.abs() < 1e-4);
+        });
+
+    // Illuminant A is a black body at 2856 K.
+    let (u, v) = xy_to_uv_prime(0.447_58, 0.407_45);
+    let (cct, duv) = cct_duv(u, v);
+    assert!((cct - 2856.0).abs() < 5.0);
+    assert!(duv.abs() < 1e-3);
+
+    // Moving off the locus towards green or magenta gives the sign of Duv.
+    let (u, v) = planckian_uv(5000.0);
+    assert!(chromaticity::cct_duv(u, (v + 0.005) * 1.5).1 > 0.002);
+    assert!(chromaticity::cct_duv(u, (v - 0.005) * 1.5).1 < -0.002);
+
+    let (cct, duv) = Color::<CieXyz>::from_xy_luminance(0.3127, 0.3290, 1.0).cct_duv();
+    assert!((cct - 6504.0).abs() < 10.0);
+    assert!((duv - 0.0032).abs() < 2e-4);
+}
+
+#[test]
+fn whiteness_and_tint() {
+    const D65: (f32, f32) = (0.3127, 0.3290);
+
+    // The perfect reflecting diffuser.
+    let white = chromaticity::whiteness(0.3127, 0.3290, 1.0, D65);
+    assert!((white.whiteness - 100.0).abs() < 1e-3 && white.tint.abs() < 1e-3);
+    assert!(white.in_range(1.0));
+
+    // A slightly blue, darker white, as with optical brighteners.
+    let color = Color::<CieXyz>::from_xy_luminance(0.3100, 0.3200, 0.9);
+    let bluish = color.whiteness(D65);
+    assert!((bluish.whiteness - 107.46).abs() < 0.01);
+    assert!((bluish.tint + 3.15).abs() < 0.01);
+    assert!(bluish.in_range(0.9));
+
+    // Reddish and yellowish whites fall outside of the formulas.
+    assert!(!chromaticity::whiteness(0.3200, 0.3290, 0.9, D65).in_range(0.9));
+    assert!(!chromaticity::whiteness(0.3300, 0.3450, 0.9, D65).in_range(0.9));
+
+    // Relative to illuminant A, its own white is neutral.
+    let a = (0.447_58, 0.407_45);
+    let white = Color::<CieXyz>::from_xy_luminance(a.0, a.1, 1.0).whiteness(a);
+    assert!((white.whiteness - 100.0).abs() < 1e-3 && white.tint.abs() < 1e-3);
+}
@@ -0,0 +1,62 @@
+//! Tests for CICP code point mapping.
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8, SrgbaU8};
+use colstodian::cicp::{Cicp, CicpEncoding, primaries, transfer};
+use colstodian::custom::CustomColorSpace;
+use colstodian::details::encodings::{Bt2020, DisplayP3};
+use glam::Vec3;
+
+#[test]
+fn encodings_map_to_triplets() {
+    assert_eq!(SrgbU8::CICP, Cicp::new(1, 13, 0, true));
+    assert_eq!(SrgbaU8::CICP, SrgbU8::CICP);
+    assert_eq!(LinearSrgb::CICP, Cicp::new(1, 8, 0, true));
+    assert_eq!(DisplayP3::CICP.color_primaries, primaries::SMPTE432);
+    assert_eq!(Bt2020::CICP.transfer_characteristics, transfer::LINEAR);
+}
+
+#[test]
+fn triplets_identify_encodings() {
+    let parsed = Cicp::new(1, 13, 0, true);
+
+    assert!(parsed.is::<SrgbU8>());
+    assert!(!parsed.is::<LinearSrgb>());
+    assert!(
+        !Cicp {
+            full_range: false,
+            ..parsed
+        }
+        .is::<SrgbU8>()
+    );
+}
+
+#[test]
+fn primaries_round_trip() {
+    let bt709 = Cicp::SRGB.to_custom_color_space().unwrap();
+    let white = bt709.to_linear_srgb(Vec3::ONE);
+    assert!((white - Vec3::ONE).abs().max_element() < 1e-4);
+
+    assert_eq!(
+        Cicp::color_primaries_of(&CustomColorSpace::default()),
+        Some(primaries::BT709)
+    );
+    let p3 = Cicp::DISPLAY_P3.to_custom_color_space().unwrap();
+    assert_eq!(Cicp::color_primaries_of(&p3), Some(primaries::SMPTE432));
+    let bt2020 = Cicp::BT2100_PQ.to_custom_color_space().unwrap();
+    assert_eq!(Cicp::color_primaries_of(&bt2020), Some(primaries::BT2020));
+
+    assert_eq!(
+        Cicp::new(primaries::UNSPECIFIED, 2, 2, true).to_custom_color_space(),
+        None
+    );
+}
+
+#[test]
+fn xyz_primaries_are_identity() {
+    let xyz = Cicp::new(primaries::XYZ, transfer::LINEAR, 0, true)
+        .to_custom_color_space()
+        .unwrap();
+    let v = Vec3::new(0.2, 0.5, 0.7);
+
+    assert!((xyz.to_xyz(v) - v).abs().max_element() < 1e-6);
+}
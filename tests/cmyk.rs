@@ -0,0 +1,277 @@
+//! Tests for CMYK separation and ICC profile tables.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::cmyk::{BlackGeneration, Cmyk, CmykProfile, Separation};
+use colstodian::colorchecker::delta_e_2000;
+use colstodian::details::encodings::{CieXyz, EncodedSrgbU8};
+use colstodian::icc::{IccProfile, Pcs, RenderingIntent};
+use glam::Vec3;
+
+mod common;
+
+use common::icc::{self, encode_u16, push_u16, push_u32};
+
+/// Sample `function` on a grid of `points` per input, the first input
+/// varying slowest.
+fn grid(inputs: usize, points: usize, function: impl Fn(&[f32]) -> Vec<f32>) -> Vec<f32> {
+    let mut values = Vec::new();
+    for index in 0..points.pow(inputs as u32) {
+        let input: Vec<f32> = (0..inputs)
+            .map(|dimension| {
+                let position = index / points.pow((inputs - 1 - dimension) as u32) % points;
+                position as f32 / (points - 1) as f32
+            })
+            .collect();
+        values.extend(function(&input));
+    }
+    values
+}
+
+/// A `lutBToAType` tag with identity `curv` B curves, a CLUT and identity
+/// `para` A curves.
+fn mba(inputs: u8, outputs: u8, points: u8, clut: &[f32]) -> Vec<u8> {
+    let b = 32;
+    let clut_at = b + 12 * inputs as usize;
+    let a = clut_at + 20 + 2 * clut.len();
+    let a = a.next_multiple_of(4);
+
+    let mut tag = b"mBA ".to_vec();
+    push_u32(&mut tag, 0);
+    tag.extend_from_slice(&[inputs, outputs, 0, 0]);
+    for offset in [b, 0, 0, clut_at, a] {
+        push_u32(&mut tag, offset as u32);
+    }
+    for _ in 0..inputs {
+        tag.extend_from_slice(b"curv");
+        push_u32(&mut tag, 0);
+        push_u32(&mut tag, 0);
+    }
+    let mut points_per_input = [0; 16];
+    points_per_input[..inputs as usize].fill(points);
+    tag.extend_from_slice(&points_per_input);
+    tag.extend_from_slice(&[2, 0, 0, 0]);
+    clut.iter()
+        .for_each(|&value| push_u16(&mut tag, encode_u16(value)));
+    tag.resize(a, 0);
+    for _ in 0..outputs {
+        tag.extend_from_slice(b"para");
+        push_u32(&mut tag, 0);
+        push_u16(&mut tag, 0);
+        push_u16(&mut tag, 0);
+        push_u32(&mut tag, 0x10000);
+    }
+    tag
+}
+
+/// A CMYK profile of device CMYK, with a legacy `lut16Type` `AToB0` table
+/// and a `lutBToAType` `BToA0` table separating with `separation`.
+fn device_profile(separation: &Separation) -> Vec<u8> {
+    let pcs = IccProfile::parse(&icc::profile(b"CMYK", b"Lab ", &[])).unwrap();
+    let a_to_b = grid(4, 9, |cmyk| {
+        let color = Cmyk::new(cmyk[0], cmyk[1], cmyk[2], cmyk[3]).to_device_color();
+        let lab = pcs.color_to_pcs(color);
+        let encoded = (lab + Vec3::new(0.0, 128.0, 128.0)) / Vec3::new(100.0, 255.0, 255.0);
+        (encoded * (65280.0 / 65535.0)).to_array().to_vec()
+    });
+    let b_to_a = grid(3, 17, |lab| {
+        let lab =
+            Vec3::new(100.0, 255.0, 255.0) * Vec3::from_slice(lab) - Vec3::new(0.0, 128.0, 128.0);
+        let cmyk = pcs.pcs_to_color(lab).to_device_cmyk(separation);
+        vec![cmyk.c, cmyk.m, cmyk.y, cmyk.k]
+    });
+    icc::profile(
+        b"CMYK",
+        b"Lab ",
+        &[
+            (b"desc", vec![0; 12]),
+            (b"A2B0", icc::mft2(4, 3, 9, &a_to_b)),
+            (b"B2A0", mba(3, 4, 17, &b_to_a)),
+        ],
+    )
+}
+
+fn delta_e(a: Color<CieXyz>, b: Color<CieXyz>) -> f32 {
+    let lab = colstodian::colorchecker::lab_d50;
+    delta_e_2000(lab(a), lab(b))
+}
+
+fn colors() -> [Color<EncodedSrgbU8>; 6] {
+    [
+        Color::srgb_u8(255, 255, 255),
+        Color::srgb_u8(128, 128, 128),
+        Color::srgb_u8(20, 20, 20),
+        Color::srgb_u8(102, 77, 51),
+        Color::srgb_u8(200, 40, 60),
+        Color::srgb_u8(30, 140, 200),
+    ]
+}
+
+#[test]
+fn device_black_generation() {
+    let gray = Color::srgb_u8(128, 128, 128);
+    let red = Color::srgb_u8(200, 40, 60);
+    let gcr = Separation {
+        black_generation: BlackGeneration::Gcr {
+            amount: 1.0,
+            start: 0.0,
+        },
+        ..Separation::default()
+    };
+    let ucr = Separation {
+        black_generation: BlackGeneration::Ucr {
+            amount: 1.0,
+            start: 0.0,
+        },
+        ..Separation::default()
+    };
+
+    // A gray is all black with either.
+    for separation in [gcr, ucr] {
+        let cmyk = gray.to_device_cmyk(&separation);
+        assert!(cmyk.c.max(cmyk.m).max(cmyk.y) < 1e-6);
+        assert!((cmyk.k - 0.498).abs() < 1e-3);
+    }
+    // A saturated color gets less black with UCR, and the same color.
+    let with_gcr = red.to_device_cmyk(&gcr);
+    let with_ucr = red.to_device_cmyk(&ucr);
+    assert!(with_ucr.k < 0.5 * with_gcr.k);
+    for cmyk in [with_gcr, with_ucr] {
+        assert_eq!(cmyk.to_device_color().convert::<EncodedSrgbU8>(), red);
+    }
+
+    // Black starts at the gray component `start`.
+    let late = Separation {
+        black_generation: BlackGeneration::Gcr {
+            amount: 1.0,
+            start: 0.6,
+        },
+        ..Separation::default()
+    };
+    assert_eq!(gray.to_device_cmyk(&late).k, 0.0);
+    assert!(Color::srgb_u8(20, 20, 20).to_device_cmyk(&late).k > 0.5);
+}
+
+#[test]
+fn device_ink_limits() {
+    let dark = Color::srgb_u8(30, 10, 20);
+    let separation = Separation {
+        black_generation: BlackGeneration::NONE,
+        max_black: 1.0,
+        max_ink: 2.5,
+    };
+    let cmyk = dark.to_device_cmyk(&separation);
+    assert!((cmyk.total_ink() - 2.5).abs() < 1e-5);
+
+    let separation = Separation {
+        black_generation: BlackGeneration::Gcr {
+            amount: 1.0,
+            start: 0.0,
+        },
+        max_black: 0.7,
+        max_ink: 4.0,
+    };
+    let cmyk = Color::srgb_u8(0, 0, 0).to_device_cmyk(&separation);
+    assert_eq!(cmyk.k, 0.7);
+    assert_eq!((cmyk.c, cmyk.m, cmyk.y), (1.0, 1.0, 1.0));
+}
+
+#[test]
+fn parse_profiles() {
+    let data = device_profile(&Separation::default());
+    let profile = IccProfile::parse(&data).unwrap();
+    assert_eq!(profile.color_space(), *b"CMYK");
+    assert_eq!(profile.channels(), 4);
+    assert_eq!(profile.pcs(), Pcs::Lab);
+    assert!(profile.has_luts());
+    assert!(CmykProfile::new(profile).is_some());
+
+    // Not a profile, or truncated.
+    assert_eq!(IccProfile::parse(&data[..100]), None);
+    assert_eq!(IccProfile::parse(&data[..data.len() - 200]), None);
+    let mut bad = data.clone();
+    bad[36] = b'x';
+    assert_eq!(IccProfile::parse(&bad), None);
+
+    // Profiles without tables, or of other color spaces, can't separate.
+    assert!(CmykProfile::parse(&icc::profile(b"CMYK", b"Lab ", &[])).is_none());
+    assert!(CmykProfile::parse(&icc::profile(b"RGB ", b"XYZ ", &[])).is_none());
+}
+
+#[test]
+fn profile_tables() {
+    let profile = CmykProfile::parse(&device_profile(&Separation::default())).unwrap();
+    let intent = RenderingIntent::Perceptual;
+
+    // The AToB table reproduces device CMYK.
+    for cmyk in [
+        Cmyk::new(0.0, 0.0, 0.0, 0.0),
+        Cmyk::new(0.3, 0.6, 0.1, 0.2),
+        Cmyk::new(1.0, 0.0, 0.5, 0.0),
+    ] {
+        let expected = cmyk.to_device_color().convert::<CieXyz>();
+        assert!(delta_e(profile.to_color(cmyk, intent), expected) < 1.5);
+    }
+
+    // Separated colors print as themselves, and missing intents fall back to
+    // the perceptual tables.
+    for color in colors() {
+        let xyz = color.convert::<CieXyz>();
+        let cmyk = profile.separate(color, intent);
+        assert!(delta_e(profile.to_color(cmyk, intent), xyz) < 2.0);
+        assert_eq!(
+            profile.separate(color, RenderingIntent::RelativeColorimetric),
+            cmyk
+        );
+    }
+}
+
+#[test]
+fn profile_black_generation() {
+    // A profile with light GCR, re-separated with other black generation.
+    let light = Separation {
+        black_generation: BlackGeneration::Gcr {
+            amount: 0.3,
+            start: 0.5,
+        },
+        ..Separation::default()
+    };
+    let profile = CmykProfile::parse(&device_profile(&light)).unwrap();
+    let intent = RenderingIntent::Perceptual;
+    let max = Separation {
+        black_generation: BlackGeneration::Gcr {
+            amount: 1.0,
+            start: 0.0,
+        },
+        max_black: 1.0,
+        max_ink: 4.0,
+    };
+    let none = Separation {
+        black_generation: BlackGeneration::NONE,
+        ..max
+    };
+
+    for color in colors() {
+        let original = profile.to_color(profile.separate(color, intent), intent);
+        for separation in [max, none] {
+            let cmyk = profile.separate_with(color, intent, &separation);
+            assert!(delta_e(profile.to_color(cmyk, intent), original) < 1.0);
+        }
+    }
+
+    let gray = Color::srgb_u8(128, 128, 128);
+    assert!(profile.separate(gray, intent).k < 0.1);
+    let cmyk = profile.separate_with(gray, intent, &max);
+    assert!((cmyk.k - 0.498).abs() < 1e-3);
+    assert!(cmyk.c.max(cmyk.m).max(cmyk.y) < 0.05);
+    assert_eq!(profile.separate_with(gray, intent, &none).k, 0.0);
+
+    // The ink limit removes cyan, magenta and yellow.
+    let limited = Separation {
+        max_ink: 2.0,
+        ..none
+    };
+    let cmyk = profile.separate_with(Color::srgb_u8(20, 20, 20), intent, &limited);
+    assert!(cmyk.total_ink() <= 2.0 + 1e-5);
+}
@@ -0,0 +1,119 @@
+//! Tests for shader code generation and constants.
+
+#![cfg(feature = "std")]
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::calibration::ColorMatrix;
+use colstodian::codegen::{self, ShaderLanguage};
+use colstodian::details::encodings::{
+    Bt2020, DisplayP3, EncodedProPhotoRgbU8, Oklab, Srgba, SrgbaPremultiplied, YCbCrBt709,
+};
+use colstodian::details::linear_spaces;
+use colstodian::details::traits::LinearConvertFromRaw;
+use colstodian::pipeline::Pipeline;
+use colstodian::transfer::TransferFunction;
+use colstodian::uniforms;
+use glam::{Mat3, Vec3};
+
+#[test]
+fn function_names() {
+    let wgsl = codegen::conversion::<SrgbU8, Bt2020>(ShaderLanguage::Wgsl).unwrap();
+    assert!(wgsl.contains("fn encoded_srgb_u8_decode(c: vec3<f32>) -> vec3<f32> {"));
+    assert!(wgsl.contains("fn bt2020_encode(c: vec3<f32>) -> vec3<f32> {"));
+    assert!(wgsl.contains("return bt2020_encode(mat3x3<f32>(vec3<f32>(0.6274"));
+    assert!(wgsl.contains("* encoded_srgb_u8_decode(c));"));
+
+    let glsl = codegen::encode::<EncodedProPhotoRgbU8>(ShaderLanguage::Glsl).unwrap();
+    assert!(glsl.starts_with("vec3 encoded_pro_photo_rgb_u8_encode(vec3 c) {\n"));
+    assert!(glsl.contains("lessThanEqual(c, vec3(0.001953125))"));
+
+    assert!(
+        codegen::decode::<YCbCrBt709>(ShaderLanguage::Wgsl).is_none(),
+        "YCbCr has no RGB transfer function"
+    );
+    assert!(codegen::conversion::<Oklab, SrgbU8>(ShaderLanguage::Glsl).is_none());
+}
+
+#[test]
+fn same_space_has_no_matrix() {
+    let glsl = codegen::conversion::<SrgbU8, LinearSrgb>(ShaderLanguage::Glsl).unwrap();
+    assert!(glsl.contains("return srgb_encode(encoded_srgb_u8_decode(c));"));
+    assert!(!glsl.contains("mat3"));
+    assert!(glsl.contains("vec3 srgb_encode(vec3 c) {\n    return c;\n}"));
+}
+
+#[test]
+fn transfer_functions() {
+    let pq = codegen::transfer_decode(TransferFunction::Pq, ShaderLanguage::Wgsl, "pq_eotf");
+    assert!(pq.contains("let e = pow(clamp(c, vec3<f32>(0.0), vec3<f32>(1.0))"));
+    assert!(pq.contains("return linear * 10000.0;"));
+
+    let srgb = codegen::transfer_encode(TransferFunction::Srgb, ShaderLanguage::Wgsl, "oetf");
+    assert_eq!(
+        srgb,
+        "fn oetf(c: vec3<f32>) -> vec3<f32> {\n    return select(1.055 * pow(max(c, vec3<f32>(0.0)), \
+         vec3<f32>(0.41666666)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));\n}\n"
+    );
+}
+
+#[test]
+fn conversion_constants() {
+    let matrix = Mat3::from_cols_array_2d(&uniforms::matrix::<
+        linear_spaces::Srgb,
+        linear_spaces::Bt2020,
+    >());
+    let red = Vec3::new(0.8, 0.1, 0.2);
+    let mut expected = red;
+    <linear_spaces::Bt2020 as LinearConvertFromRaw<linear_spaces::Srgb>>::linear_part_raw(
+        &mut expected,
+    );
+    assert!((matrix * red).abs_diff_eq(expected, 1e-6));
+
+    let constants = uniforms::conversion::<SrgbU8, DisplayP3>().unwrap();
+    assert_eq!(Some(constants.decode), TransferFunction::Srgb.parameters());
+    assert_eq!(
+        Some(constants.encode),
+        TransferFunction::Linear.parameters()
+    );
+    assert_eq!(constants.matrix[0][3], 0.0);
+    assert_eq!(
+        constants.matrix.map(|[x, y, z, _]| [x, y, z]),
+        uniforms::matrix::<linear_spaces::Srgb, linear_spaces::DisplayP3>()
+    );
+    assert!(uniforms::conversion::<Oklab, SrgbU8>().is_none());
+}
+
+#[test]
+fn pipeline_compute_shader() {
+    let pipeline = Pipeline::<SrgbU8, Bt2020, linear_spaces::Srgb>::new().exposure(1.0);
+    let wgsl = codegen::compute_shader(&pipeline, "rgba16float").unwrap();
+    assert!(wgsl.contains("fn encoded_srgb_u8_decode(c: vec3<f32>) -> vec3<f32> {"));
+    assert!(wgsl.contains("fn bt2020_encode(c: vec3<f32>) -> vec3<f32> {"));
+    assert!(wgsl.contains("var output: texture_storage_2d<rgba16float, write>;"));
+    assert!(wgsl.contains("@compute @workgroup_size(8, 8)\nfn main("));
+    assert!(wgsl.contains("    var c = encoded_srgb_u8_decode(pixel.rgb);\n"));
+    assert!(wgsl.contains("textureStore(output, id.xy, vec4<f32>(bt2020_encode(c), pixel.a));"));
+
+    // The exposure fuses with the conversion to BT.2020 into one matrix, the
+    // first column of which is that of the conversion, doubled.
+    assert_eq!(wgsl.matches("mat3x3<f32>").count(), 1);
+    assert!(wgsl.contains("c = mat3x3<f32>(vec3<f32>(1.2548"));
+
+    // Identity matrices are skipped, and offsets kept.
+    let plain = Pipeline::<SrgbU8, LinearSrgb, linear_spaces::Srgb>::new();
+    let wgsl = codegen::compute_shader(&plain, "rgba8unorm").unwrap();
+    assert!(!wgsl.contains("mat3x3"));
+    let lifted = plain
+        .clone()
+        .matrix(ColorMatrix::with_offset(Mat3::IDENTITY, Vec3::splat(0.25)));
+    let wgsl = codegen::compute_shader(&lifted, "rgba8unorm").unwrap();
+    assert!(wgsl.contains(" * c + vec3<f32>(0.25, 0.25, 0.25);"));
+
+    // Functions can't run in shaders.
+    let mapped = plain.map(|rgb| rgb * rgb);
+    assert!(codegen::compute_shader(&mapped, "rgba8unorm").is_none());
+    let oklab = Pipeline::<Oklab, SrgbU8, linear_spaces::Srgb>::new();
+    assert!(codegen::compute_shader(&oklab, "rgba8unorm").is_none());
+    let premultiplied = Pipeline::<SrgbaPremultiplied, Srgba, linear_spaces::Srgb>::new();
+    assert!(codegen::compute_shader(&premultiplied, "rgba32float").is_none());
+}
@@ -0,0 +1,72 @@
+//! Tests for the ColorChecker chart.
+
+use colstodian::Color;
+use colstodian::colorchecker::{self, PATCHES, delta_e_2000, lab_d50};
+use glam::Vec3;
+
+#[test]
+fn ciede2000_reference_pairs() {
+    // Pairs from Sharma, Wu and Dalal's CIEDE2000 test data.
+    [
+        (
+            Vec3::new(50.0, 2.6772, -79.7751),
+            Vec3::new(50.0, 0.0, -82.7485),
+            2.0425,
+        ),
+        (
+            Vec3::new(50.0, 2.5, 0.0),
+            Vec3::new(50.0, 0.0, -2.5),
+            4.3065,
+        ),
+        (
+            Vec3::new(50.0, 2.5, 0.0),
+            Vec3::new(56.0, -27.0, -3.0),
+            31.9030,
+        ),
+        (
+            Vec3::new(60.2574, -34.0099, 36.2677),
+            Vec3::new(60.4626, -34.1751, 39.4387),
+            1.2644,
+        ),
+        (
+            Vec3::new(22.7233, 20.0904, -46.694),
+            Vec3::new(23.0331, 14.973, -42.5619),
+            2.0373,
+        ),
+    ]
+    .into_iter()
+    .for_each(|(lab1, lab2, expected)| {
+        assert!((delta_e_2000(lab1, lab2) - expected).abs() < 1e-3);
+        assert!((delta_e_2000(lab2, lab1) - expected).abs() < 1e-3);
+    });
+    assert_eq!(
+        delta_e_2000(Vec3::new(50.0, 0.0, 0.0), Vec3::new(50.0, 0.0, 0.0)),
+        0.0
+    );
+}
+
+#[test]
+fn lab_of_white_and_black() {
+    let white = lab_d50(Color::srgb_u8(255, 255, 255));
+    assert!(white.abs_diff_eq(Vec3::new(100.0, 0.0, 0.0), 0.05));
+    assert_eq!(lab_d50(Color::srgb_u8(0, 0, 0)), Vec3::ZERO);
+}
+
+#[test]
+fn evaluate_chart() {
+    // The sRGB references only differ from the CIELAB ones by quantization,
+    // apart from the out of gamut cyan.
+    let report = colorchecker::evaluate(&PATCHES.map(|patch| patch.srgb));
+    PATCHES
+        .iter()
+        .zip(report.delta_e)
+        .filter(|(patch, _)| patch.name != "cyan")
+        .for_each(|(patch, delta_e)| assert!(delta_e < 0.5, "{}: {delta_e}", patch.name));
+    assert_eq!(report.max, report.delta_e[report.worst]);
+
+    // A gray capture is far off on the colorful patches.
+    let gray = PATCHES.map(|_| Color::srgb_u8(121, 121, 121));
+    let report = colorchecker::evaluate(&gray);
+    assert!(report.average > 20.0);
+    assert!(report.delta_e[21] < 0.5);
+}
@@ -0,0 +1,84 @@
+//! Builders of ICC profiles and their tags.
+
+#![allow(dead_code)]
+
+use glam::Vec3;
+
+pub fn push_u16(data: &mut Vec<u8>, value: u16) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+/// The `s15Fixed16Number` of `value`.
+pub fn s15_fixed16(value: f32) -> [u8; 4] {
+    ((value * 65536.0).round() as i32).to_be_bytes()
+}
+
+/// The 16-bit table value of `value`, in `0.0..=1.0`.
+pub fn encode_u16(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * 65535.0).round() as u16
+}
+
+/// An ICC profile with the given header fields and tags.
+pub fn profile(color_space: &[u8; 4], pcs: &[u8; 4], tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut data = vec![0; 128];
+    data[8..12].copy_from_slice(&0x0420_0000u32.to_be_bytes());
+    data[12..16].copy_from_slice(b"prtr");
+    data[16..20].copy_from_slice(color_space);
+    data[20..24].copy_from_slice(pcs);
+    data[36..40].copy_from_slice(b"acsp");
+
+    push_u32(&mut data, tags.len() as u32);
+    let mut offset = 132 + 12 * tags.len();
+    for (signature, tag) in tags {
+        data.extend_from_slice(*signature);
+        push_u32(&mut data, offset as u32);
+        push_u32(&mut data, tag.len() as u32);
+        offset += tag.len().next_multiple_of(4);
+    }
+    for (_, tag) in tags {
+        data.extend_from_slice(tag);
+        data.resize(data.len().next_multiple_of(4), 0);
+    }
+    let size = data.len() as u32;
+    data[..4].copy_from_slice(&size.to_be_bytes());
+    data
+}
+
+/// An `XYZType` tag of `xyz`.
+pub fn xyz(xyz: Vec3) -> Vec<u8> {
+    let mut tag = b"XYZ ".to_vec();
+    push_u32(&mut tag, 0);
+    xyz.to_array()
+        .into_iter()
+        .for_each(|value| tag.extend_from_slice(&s15_fixed16(value)));
+    tag
+}
+
+/// A `lut16Type` tag with identity curves and the CLUT `clut`, in
+/// `0.0..=1.0`.
+pub fn mft2(inputs: u8, outputs: u8, points: u8, clut: &[f32]) -> Vec<u8> {
+    let mut tag = b"mft2".to_vec();
+    push_u32(&mut tag, 0);
+    tag.extend_from_slice(&[inputs, outputs, points, 0]);
+    for i in 0..9 {
+        push_u32(&mut tag, if i % 4 == 0 { 0x10000 } else { 0 });
+    }
+    push_u16(&mut tag, 2);
+    push_u16(&mut tag, 2);
+    for _ in 0..inputs {
+        push_u16(&mut tag, 0);
+        push_u16(&mut tag, 65535);
+    }
+    for &value in clut {
+        push_u16(&mut tag, encode_u16(value));
+    }
+    for _ in 0..outputs {
+        push_u16(&mut tag, 0);
+        push_u16(&mut tag, 65535);
+    }
+    tag
+}
@@ -0,0 +1,66 @@
+//! Tests for contrast metrics and palette adjustment.
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::contrast::{ContrastTarget, adjust_palette};
+use colstodian::details::encodings::Oklch;
+
+#[test]
+fn wcag_contrast_ratio() {
+    let (black, white) = (Color::<SrgbU8>::BLACK, Color::<SrgbU8>::WHITE);
+
+    assert!((black.contrast_ratio(white) - 21.0).abs() < 1e-4);
+    assert_eq!(white.contrast_ratio(white), 1.0);
+    assert_eq!(white.contrast_ratio(black), black.contrast_ratio(white));
+
+    // The reference value of #777777 on white.
+    let gray = Color::srgb_u8(0x77, 0x77, 0x77);
+    assert!((gray.contrast_ratio(white) - 4.48).abs() < 0.01);
+}
+
+#[test]
+fn apca_contrast() {
+    let (black, white) = (Color::<SrgbU8>::BLACK, Color::<SrgbU8>::WHITE);
+
+    // Reference values of the APCA 0.0.98G test suite.
+    assert!((black.apca_contrast(white) - 106.04).abs() < 0.01);
+    assert!((white.apca_contrast(black) + 107.88).abs() < 0.01);
+    let gray = Color::srgb_u8(0x88, 0x88, 0x88);
+    assert!((gray.apca_contrast(white) - 63.06).abs() < 0.01);
+    assert_eq!(gray.apca_contrast(gray), 0.0);
+}
+
+#[test]
+fn palette_adjustment() {
+    let background = Color::srgb_u8(250, 250, 250);
+    let palette = [
+        Color::srgb_u8(255, 200, 0),
+        Color::srgb_u8(30, 30, 30),
+        Color::srgb_u8(120, 180, 255),
+    ];
+
+    for target in [ContrastTarget::WCAG_AA, ContrastTarget::APCA_BODY] {
+        let adjusted = adjust_palette(palette, background, target);
+
+        assert!(adjusted.iter().all(|adjustment| adjustment.met));
+        assert!(adjusted.iter().all(|a| target.is_met(a.color, background)));
+        assert_eq!(adjusted[1].color, palette[1]);
+        assert_eq!(adjusted[1].lightness_change, 0.0);
+
+        // Light colors on a light background get darker, keeping their hue.
+        for (adjustment, original) in adjusted.iter().zip(palette).skip(2) {
+            assert!(adjustment.lightness_change < 0.0);
+            let (before, after) = (
+                original.convert::<Oklch>(),
+                adjustment.color.convert::<Oklch>(),
+            );
+            assert!((before.h - after.h).abs() < 5.0);
+        }
+    }
+
+    // Nothing reaches a contrast ratio of 25.
+    let impossible =
+        Color::srgb_u8(128, 128, 128).adjust_contrast(background, ContrastTarget::Wcag(25.0));
+    assert!(!impossible.met);
+    assert_eq!(impossible.color, Color::srgb_u8(0, 0, 0));
+}
@@ -1,6 +1,9 @@
 use approx::assert_relative_eq;
-use colstodian::details::encodings::EncodedSrgbF32;
-use colstodian::{Color, basic_encodings::*};
+use colstodian::details::encodings::{
+    CieXyz, CieXyzF64, EncodedBgrU8, EncodedBgraU8, EncodedGrayU8, EncodedSrgbF32, Oklab, OklabF64,
+    Oklch, SrgbF64, SrgbHsi,
+};
+use colstodian::{Color, ColorInto, ParseHexError, basic_encodings::*};
 
 #[test]
 fn srgb_u8_to_f32_conversion() {
@@ -69,3 +72,248 @@ fn extremes_conversion() {
     assert_relative_eq!(white_linear.g, 1.0, epsilon = 0.001);
     assert_relative_eq!(white_linear.b, 1.0, epsilon = 0.001);
 }
+
+#[test]
+fn bgr_channel_order() {
+    let bgr = Color::encoded_bgr_u8(255, 128, 0);
+    assert_eq!(bgr.repr, [0, 128, 255]);
+    assert_eq!((bgr.r, bgr.g, bgr.b), (255, 128, 0));
+    assert_eq!(bgr.convert::<SrgbU8>(), Color::srgb_u8(255, 128, 0));
+    assert_eq!(
+        Color::srgb_u8(1, 2, 3).convert::<EncodedBgrU8>().repr,
+        [3, 2, 1]
+    );
+
+    let bgra = Color::encoded_bgra_u8(10, 20, 30, 40);
+    assert_eq!(bgra.repr, [30, 20, 10, 40]);
+    assert_eq!(bgra.convert::<SrgbaU8>(), Color::srgba_u8(10, 20, 30, 40));
+    assert_eq!(
+        Color::srgba_u8(10, 20, 30, 40).convert::<EncodedBgraU8>(),
+        bgra
+    );
+
+    let linear = bgra.convert::<LinearSrgba>();
+    assert_relative_eq!(linear.a, 40.0 / 255.0, epsilon = 1e-6);
+    assert_eq!(linear.convert::<EncodedBgraU8>(), bgra);
+}
+
+#[test]
+fn channel_swizzles() {
+    use colstodian::details::component_structs::Channel;
+
+    let rgba = Color::srgba_u8(1, 2, 3, 4);
+    assert_eq!(rgba.bgr(), Color::srgba_u8(3, 2, 1, 4));
+    assert_eq!(rgba.rgba_to_argb().repr, [4, 1, 2, 3]);
+    assert_eq!(rgba.rgba_to_argb().argb_to_rgba(), rgba);
+    assert_eq!(
+        rgba.with_swapped(Channel::G, Channel::A),
+        Color::srgba_u8(1, 4, 3, 2)
+    );
+
+    // Channels are named, not positional.
+    let bgr = Color::encoded_bgr_u8(1, 2, 3).with_swapped(Channel::R, Channel::G);
+    assert_eq!(bgr, Color::encoded_bgr_u8(2, 1, 3));
+    assert_eq!(
+        Color::linear_srgb(0.1, 0.2, 0.3).bgr(),
+        Color::linear_srgb(0.3, 0.2, 0.1)
+    );
+}
+
+#[test]
+#[should_panic]
+fn swizzling_missing_alpha_panics() {
+    Color::srgb_u8(1, 2, 3).rgba_to_argb();
+}
+
+#[test]
+fn hex_parsing() {
+    assert_eq!(
+        Color::<SrgbU8>::from_hex("#ff0080"),
+        Some(Color::srgb_u8(255, 0, 128))
+    );
+    assert_eq!(
+        Color::<SrgbU8>::from_hex("F08"),
+        Some(Color::srgb_u8(255, 0, 136))
+    );
+    assert_eq!(Color::<SrgbU8>::from_hex("#ff008080"), None);
+    assert_eq!(
+        Color::<SrgbaU8>::from_hex("#ff008080"),
+        Some(Color::srgba_u8(255, 0, 128, 128))
+    );
+    assert_eq!(
+        Color::<SrgbaU8>::from_hex("#f08c"),
+        Some(Color::srgba_u8(255, 0, 136, 204))
+    );
+    assert_eq!(
+        Color::<SrgbaU8>::from_hex("abc"),
+        Some(Color::srgba_u8(170, 187, 204, 255))
+    );
+    ["", "#", "#12345", "#ggg", "#ff00800", "ff 080"]
+        .into_iter()
+        .for_each(|hex| assert_eq!(Color::<SrgbaU8>::from_hex(hex), None, "{hex}"));
+}
+
+#[test]
+fn plain_values_into_colors() {
+    fn linear(color: impl ColorInto<Color<LinearSrgb>>) -> Color<LinearSrgb> {
+        color.color_into()
+    }
+    fn encoded(color: impl ColorInto<Color<SrgbaU8>>) -> Color<SrgbaU8> {
+        color.color_into()
+    }
+
+    assert_eq!(linear((255, 0, 0)), Color::linear_srgb(1.0, 0.0, 0.0));
+    assert_eq!(linear([255u8, 255, 255]), Color::linear_srgb(1.0, 1.0, 1.0));
+    assert_eq!(linear([0.2, 0.4, 0.6]), Color::linear_srgb(0.2, 0.4, 0.6));
+    assert_eq!(linear((0.5, 0.5, 0.5)), Color::linear_srgb(0.5, 0.5, 0.5));
+
+    assert_eq!(encoded((1, 2, 3, 4)), Color::srgba_u8(1, 2, 3, 4));
+    assert_eq!(
+        encoded([1.0, 1.0, 1.0, 0.0]),
+        Color::srgba_u8(255, 255, 255, 0)
+    );
+}
+
+#[test]
+fn hex_from_str() {
+    assert_eq!("#ff0000".parse(), Ok(Color::srgb_u8(255, 0, 0)));
+    assert_eq!("#ff008080".parse(), Ok(Color::srgba_u8(255, 0, 128, 128)));
+    assert_eq!("#ff008080".parse::<Color<SrgbU8>>(), Err(ParseHexError));
+    assert_eq!("#nope".parse::<Color<SrgbaU8>>(), Err(ParseHexError));
+    assert_eq!(ParseHexError.to_string(), "not a CSS hex color");
+}
+
+#[test]
+fn from_primitives() {
+    assert_eq!(Color::from([1u8, 2, 3]), Color::srgb_u8(1, 2, 3));
+    assert_eq!(Color::from((1u8, 2, 3)), Color::srgb_u8(1, 2, 3));
+    assert_eq!(Color::from([1u8, 2, 3, 4]), Color::srgba_u8(1, 2, 3, 4));
+    assert_eq!(Color::from((1u8, 2, 3, 4)), Color::srgba_u8(1, 2, 3, 4));
+
+    // Floats are linear.
+    let linear: Color<LinearSrgb> = [0.5, 0.25, 1.0].into();
+    assert_eq!(linear, Color::linear_srgb(0.5, 0.25, 1.0));
+    assert_eq!(
+        Color::from((0.5f32, 0.25, 1.0)),
+        Color::linear_srgb(0.5, 0.25, 1.0)
+    );
+    assert_eq!(
+        Color::from([0.5f32, 0.25, 1.0, 0.0]),
+        Color::linear_srgba(0.5, 0.25, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn double_precision_encodings() {
+    let white = Color::srgb_u8(255, 255, 255).convert::<CieXyzF64>();
+    assert_relative_eq!(white.x, 0.9505, epsilon = 0.001);
+    assert_relative_eq!(white.y, 1.0, epsilon = 0.001);
+    assert_relative_eq!(white.z, 1.089, epsilon = 0.001);
+
+    let red = Color::srgb_u8(255, 0, 0);
+    assert_eq!(red.convert::<OklabF64>().convert::<SrgbU8>(), red);
+    assert_eq!(red.convert::<SrgbF64>(), Color::srgb_f64(1.0, 0.0, 0.0));
+
+    // Accumulating many small values keeps full precision in f64.
+    let step = Color::<SrgbF64>::gray(1e-9);
+    let sum = (0..1_000_000).fold(Color::<SrgbF64>::BLACK, |sum, _| sum + step);
+    assert_relative_eq!(sum.r, 1e-3, epsilon = 1e-12);
+}
+
+#[test]
+fn hsi() {
+    let hsi = |r, g, b| Color::srgb_u8(r, g, b).convert::<SrgbHsi>();
+
+    let red = hsi(255, 0, 0);
+    assert_relative_eq!(red.h, 0.0, epsilon = 1e-3);
+    assert_relative_eq!(red.s, 1.0, epsilon = 1e-5);
+    assert_relative_eq!(red.i, 1.0 / 3.0, epsilon = 1e-5);
+    assert_relative_eq!(hsi(0, 255, 0).h, 120.0, epsilon = 1e-3);
+    assert_relative_eq!(hsi(0, 0, 255).h, 240.0, epsilon = 1e-3);
+    assert_relative_eq!(hsi(255, 255, 0).h, 60.0, epsilon = 1e-3);
+
+    let gray = hsi(128, 128, 128);
+    assert_eq!((gray.h, gray.s), (0.0, 0.0));
+    assert_eq!(hsi(0, 0, 0).s, 0.0);
+
+    [(12, 200, 99), (250, 10, 180), (90, 60, 30), (128, 128, 128)]
+        .into_iter()
+        .for_each(|(r, g, b)| {
+            let color = Color::srgb_u8(r, g, b);
+            assert_eq!(color.convert::<SrgbHsi>().convert::<SrgbU8>(), color);
+        });
+}
+
+#[test]
+fn gray_u8() {
+    // Every gray level survives a round trip through RGB.
+    for v in 0..=255 {
+        let gray = Color::encoded_gray_u8(v);
+        let rgb = gray.convert::<SrgbU8>();
+        assert_eq!(rgb, Color::srgb_u8(v, v, v));
+        assert_eq!(rgb.convert::<EncodedGrayU8>(), gray);
+    }
+
+    // Colors become the gray of their relative luminance.
+    let color = Color::srgb_u8(40, 180, 90);
+    let luminance = color.convert::<CieXyz>().y;
+    let gray = color.convert::<EncodedGrayU8>();
+    assert_relative_eq!(gray.convert::<CieXyz>().y, luminance, epsilon = 2e-3);
+    assert_eq!(Color::srgb_u8(0, 0, 255).convert::<EncodedGrayU8>().v, 76);
+    assert_eq!(
+        Color::srgba_u8(255, 255, 255, 0).convert::<EncodedGrayU8>(),
+        Color::<EncodedGrayU8>::WHITE
+    );
+    assert_eq!(format!("{:?}", *Color::encoded_gray_u8(7)), "V: 7");
+}
+
+#[test]
+fn oklch() {
+    let color = Color::srgb_u8(40, 110, 200);
+    let lab = color.convert::<Oklab>();
+    let mut lch = color.convert::<Oklch>();
+    assert_relative_eq!(lch.l, lab.l, epsilon = 1e-6);
+    assert_relative_eq!(
+        lch.c,
+        (lab.a * lab.a + lab.b * lab.b).sqrt(),
+        epsilon = 1e-6
+    );
+    assert!((0.0..360.0).contains(&lch.h));
+    assert_eq!(lch.convert::<SrgbU8>(), color);
+
+    // Rotating the hue with dot syntax matches `rotate_hue`.
+    lch.h += 30.0;
+    assert_eq!(lch.convert::<SrgbU8>(), color.rotate_hue(30.0));
+
+    let gray = Color::srgb_u8(128, 128, 128).convert::<Oklch>();
+    assert!(gray.c < 1e-4);
+}
+
+#[test]
+fn xy_luminance() {
+    let white = Color::from_xy_luminance(0.3127, 0.3290, 1.0);
+    let srgb = white.convert::<Srgb>();
+    assert!(srgb.repr.abs_diff_eq(glam::Vec3::ONE, 1e-3));
+
+    let (x, y, luminance) = Color::linear_srgb(0.0, 0.0, 1.0)
+        .convert::<CieXyz>()
+        .to_xy_luminance();
+    assert!((x - 0.15).abs() < 1e-3 && (y - 0.06).abs() < 1e-3);
+    assert!((luminance - 0.0722).abs() < 1e-3);
+
+    let color = Color::cie_xyz(0.2, 0.3, 0.4);
+    let (x, y, luminance) = color.to_xy_luminance();
+    assert!(
+        Color::from_xy_luminance(x, y, luminance)
+            .repr
+            .abs_diff_eq(color.repr, 1e-6)
+    );
+    assert_eq!(
+        Color::from_xy_luminance(0.3, 0.0, 1.0),
+        Color::cie_xyz(0.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        Color::cie_xyz(0.0, 0.0, 0.0).to_xy_luminance(),
+        (0.3127, 0.3290, 0.0)
+    );
+}
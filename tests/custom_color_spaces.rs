@@ -86,9 +86,9 @@ fn test_color_to_custom_rgb() {
     let (r, g, b) = srgb.to_custom_rgb(&space);
 
     // Values should be roughly preserved since we're using sRGB primaries.
-    assert!(r >= 0.0 && r <= 1.0);
-    assert!(g >= 0.0 && g <= 1.0);
-    assert!(b >= 0.0 && b <= 1.0);
+    assert!((0.0..=1.0).contains(&r));
+    assert!((0.0..=1.0).contains(&g));
+    assert!((0.0..=1.0).contains(&b));
 }
 
 #[test]
@@ -111,3 +111,118 @@ fn test_xyz_intermediate_preserves_wide_gamut() {
     // The green channel will be mapped but should still be high.
     assert!(linear.repr.y > 0.5); // Should have significant green component.
 }
+
+#[test]
+fn dynamic_color_math() {
+    let space = CustomColorSpace::from_primaries_d65([0.64, 0.33], [0.21, 0.71], [0.15, 0.06]);
+
+    let a = DynamicColor::new(0.2, 0.4, 0.6, space);
+    let b = DynamicColor::new(0.1, 0.1, 0.1, space);
+
+    assert_eq!((a + b).value, a.value + b.value);
+    assert_eq!((a - b).value, a.value - b.value);
+    assert_eq!((a * 2.0).value, a.value * 2.0);
+    assert_eq!((2.0 * a).value, (a * 2.0).value);
+    assert_eq!((a / 2.0).value, a.value / 2.0);
+    assert_eq!((a + b).space, space);
+
+    let mid = a.lerp(b, 0.5);
+    assert!(
+        (mid.value - Vec3::new(0.15, 0.25, 0.35))
+            .abs()
+            .max_element()
+            < 1e-6
+    );
+}
+
+#[test]
+#[should_panic(expected = "same `CustomColorSpace`")]
+fn dynamic_color_math_rejects_mixed_spaces() {
+    let a = DynamicColor::new(0.2, 0.4, 0.6, CustomColorSpace::default());
+    let b = DynamicColor::new(
+        0.1,
+        0.1,
+        0.1,
+        CustomColorSpace::from_primaries_d65([0.64, 0.33], [0.21, 0.71], [0.15, 0.06]),
+    );
+
+    let _ = a + b;
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn custom_space_serde_round_trip() {
+    let space = CustomColorSpace {
+        primaries: RgbPrimaries::from_rgb_xy([0.64, 0.33], [0.21, 0.71], [0.15, 0.06]),
+        white_point: WhitePoint::D60,
+    };
+    let color = DynamicColor::new(0.2, 0.4, 0.6, space);
+
+    let json = serde_json::to_string(&color).unwrap();
+    let restored: DynamicColor = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, color);
+    assert_eq!(
+        serde_json::from_str::<CustomColorSpace>(&serde_json::to_string(&space).unwrap()).unwrap(),
+        space
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn measured_transfer() {
+    use colstodian::custom::{CustomEncoding, MeasuredTransfer};
+
+    assert!(MeasuredTransfer::neutral(vec![1.0]).is_none());
+    assert!(MeasuredTransfer::neutral(vec![0.0, 0.6, 0.5, 1.0]).is_none());
+
+    let transfer = MeasuredTransfer::neutral(vec![0.0, 0.1, 0.1, 0.4, 1.0]).unwrap();
+    let encoded = Vec3::new(0.125, 0.625, 0.9);
+    let linear = transfer.decode(encoded);
+    assert!(linear.abs_diff_eq(Vec3::new(0.05, 0.25, 0.76), 1e-6));
+    assert!(transfer.encode(linear).abs_diff_eq(encoded, 1e-6));
+
+    // The flat stretch encodes to its start, out of range values clamp.
+    assert_eq!(
+        transfer.encode(Vec3::new(0.1, -0.5, 2.0)),
+        Vec3::new(0.25, 0.0, 1.0)
+    );
+    assert_eq!(
+        transfer.decode(Vec3::new(-1.0, 1.5, 1.0)),
+        Vec3::new(0.0, 1.0, 1.0)
+    );
+
+    // Colors are converted to the space of the encoding.
+    let wide = CustomColorSpace::from_primaries_d65([0.64, 0.33], [0.21, 0.71], [0.15, 0.06]);
+    let display = CustomEncoding::new(wide, transfer.clone());
+    let color = Color::srgb(0.3, 0.6, 0.2);
+    let encoded = display.encode_color(color);
+    let decoded: Color<LinearSrgb> = DynamicColor::from_encoded(encoded, &display).to_color();
+    assert!(decoded.repr.abs_diff_eq(color.repr, 1e-5));
+    assert_eq!(display.decode(encoded).to_encoded(&display), encoded);
+
+    // Converting between two measured encodings goes through linear values.
+    let srgb_primaries = CustomEncoding::new(CustomColorSpace::default(), transfer);
+    let converted = display.convert(encoded, &srgb_primaries);
+    let expected = srgb_primaries.encode_color(color);
+    assert!(converted.abs_diff_eq(expected, 1e-5));
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn measured_transfer_serde() {
+    use colstodian::custom::{CustomEncoding, MeasuredTransfer};
+
+    let transfer = MeasuredTransfer::new(vec![0.0, 1.0], vec![0.0, 0.5, 1.0], vec![0.0, 1.0]);
+    let encoding = CustomEncoding::new(CustomColorSpace::default(), transfer.unwrap());
+    let json = serde_json::to_string(&encoding).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CustomEncoding>(&json).unwrap(),
+        encoding
+    );
+
+    assert!(
+        serde_json::from_str::<MeasuredTransfer>("[[0.0, 1.0], [1.0, 0.0], [0.0, 1.0]]").is_err()
+    );
+    assert!(serde_json::from_str::<MeasuredTransfer>("[[0.0], [0.0, 1.0], [0.0, 1.0]]").is_err());
+}
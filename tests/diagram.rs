@@ -0,0 +1,56 @@
+//! Tests for chromaticity diagram data.
+
+use colstodian::chromaticity;
+use colstodian::details::linear_spaces::{Bt2020, Srgb};
+use colstodian::diagram::{self, Diagram};
+
+#[test]
+fn spectral_locus() {
+    let locus = diagram::spectral_locus(Diagram::Xy);
+    assert_eq!(locus.len(), 81);
+
+    // Monochromatic light at 520 nm is the greenest, and the locus ends in
+    // deep red.
+    let green = locus[28];
+    assert!((green.0 - 0.0743).abs() < 1e-3 && (green.1 - 0.8338).abs() < 1e-3);
+    assert!(locus.iter().all(|point| point.1 <= green.1));
+    let red = locus[80];
+    assert!((red.0 - 0.7347).abs() < 3e-3 && (red.0 + red.1 - 1.0).abs() < 1e-3);
+
+    let locus_uv = diagram::spectral_locus(Diagram::UvPrime);
+    let (u, v) = chromaticity::xy_to_uv_prime(green.0, green.1);
+    assert!((locus_uv[28].0 - u).abs() < 1e-6 && (locus_uv[28].1 - v).abs() < 1e-6);
+}
+
+#[test]
+fn planckian_locus() {
+    let locus: [(f32, f32); 3] = diagram::planckian_locus(Diagram::UvPrime, 2000.0, 10_000.0);
+    let (u, v) = chromaticity::planckian_uv(2000.0);
+    assert_eq!(locus[0], (u, 1.5 * v));
+    // Evenly spaced in mireds, the middle of 500 and 100 mireds is 300.
+    let (cct, duv) = chromaticity::cct_duv(locus[1].0, locus[1].1);
+    assert!((cct - 1e6 / 300.0).abs() < 5.0 && duv.abs() < 1e-4);
+    let (cct, _) = chromaticity::cct_duv(locus[2].0, locus[2].1);
+    assert!((cct - 10_000.0).abs() < 20.0);
+
+    // The xy locus passes close to D65.
+    let locus: [(f32, f32); 1] = diagram::planckian_locus(Diagram::Xy, 6504.0, 6504.0);
+    assert!((locus[0].0 - 0.3135).abs() < 1e-3 && (locus[0].1 - 0.3237).abs() < 1e-3);
+}
+
+#[test]
+fn gamut_triangles() {
+    let srgb = diagram::gamut_triangle::<Srgb>(Diagram::Xy);
+    let expected = [(0.64, 0.33), (0.30, 0.60), (0.15, 0.06), (0.64, 0.33)];
+    srgb.iter().zip(expected).for_each(|(point, expected)| {
+        assert!((point.0 - expected.0).abs() < 1e-4 && (point.1 - expected.1).abs() < 1e-4);
+    });
+    let white = diagram::white_point::<Srgb>(Diagram::Xy);
+    assert!((white.0 - 0.3127).abs() < 1e-4 && (white.1 - 0.3290).abs() < 1e-4);
+
+    let bt2020 = diagram::gamut_triangle::<Bt2020>(Diagram::UvPrime);
+    let (u, v) = chromaticity::xy_to_uv_prime(0.170, 0.797);
+    assert!((bt2020[1].0 - u).abs() < 1e-4 && (bt2020[1].1 - v).abs() < 1e-4);
+    let white = diagram::white_point::<Bt2020>(Diagram::UvPrime);
+    assert!((white.0 - 0.1978).abs() < 1e-4 && (white.1 - 0.4683).abs() < 1e-4);
+}
@@ -0,0 +1,140 @@
+//! Tests for display profiles.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::calibration::ColorMatrix;
+use colstodian::details::encodings::CieXyz;
+use colstodian::details::linear_spaces::{CieXYZ, Srgb};
+use colstodian::display::{DisplayProfile, ToneCurve};
+use colstodian::transfer::{TransferFunction, TransferParameters};
+use glam::Vec3;
+
+mod common;
+
+use common::icc::{self, s15_fixed16};
+
+/// A matrix/TRC ICC profile of sRGB with the red curve as a `para` tag, green
+/// as a `curv` table and blue as a `curv` gamma.
+fn srgb_icc() -> Vec<u8> {
+    let para = [
+        b"para".as_slice(),
+        &[0; 4],
+        &3u16.to_be_bytes(),
+        &[0; 2],
+        &s15_fixed16(2.4),
+        &s15_fixed16(1.0 / 1.055),
+        &s15_fixed16(0.055 / 1.055),
+        &s15_fixed16(1.0 / 12.92),
+        &s15_fixed16(0.04045),
+    ]
+    .concat();
+    let table = (0..1024)
+        .flat_map(|i| {
+            let linear = TransferFunction::Srgb.decode_f32(i as f32 / 1023.0);
+            ((linear * 65535.0).round() as u16).to_be_bytes()
+        })
+        .collect::<Vec<_>>();
+    let curv = [b"curv".as_slice(), &[0; 4], &1024u32.to_be_bytes(), &table].concat();
+    let gamma = [
+        b"curv".as_slice(),
+        &[0; 4],
+        &1u32.to_be_bytes(),
+        &563u16.to_be_bytes(),
+    ]
+    .concat();
+
+    let tags: [(&[u8; 4], Vec<u8>); 6] = [
+        (b"rXYZ", icc::xyz(Vec3::new(0.4360, 0.2225, 0.0139))),
+        (b"gXYZ", icc::xyz(Vec3::new(0.3851, 0.7169, 0.0971))),
+        (b"bXYZ", icc::xyz(Vec3::new(0.1431, 0.0606, 0.7141))),
+        (b"rTRC", para),
+        (b"gTRC", curv),
+        (b"bTRC", gamma),
+    ];
+    icc::profile(b"RGB ", b"XYZ ", &tags)
+}
+
+#[test]
+fn icc_profile() {
+    let profile = DisplayProfile::from_icc(&srgb_icc()).unwrap();
+
+    let srgb = ColorMatrix::conversion::<Srgb, CieXYZ>().matrix;
+    assert!(profile.matrix().abs_diff_eq(srgb, 2e-3));
+
+    let [red, green, blue] = profile.curves();
+    assert!(matches!(red, ToneCurve::Parametric(_)));
+    assert!(matches!(green, ToneCurve::Table(table) if table.len() == 1024));
+    assert_eq!(
+        blue,
+        &ToneCurve::Parametric(TransferParameters::gamma(563.0 / 256.0))
+    );
+    [0.02, 0.2, 0.7].into_iter().for_each(|encoded| {
+        let expected = TransferFunction::Srgb.decode_f32(encoded);
+        assert!((red.decode(encoded) - expected).abs() < 1e-4);
+        assert!((green.decode(encoded) - expected).abs() < 1e-4);
+        assert!((red.encode(expected) - encoded).abs() < 1e-4);
+        assert!((green.encode(expected) - encoded).abs() < 1e-4);
+    });
+
+    // Red and green follow sRGB and need no correction, blue doesn't.
+    let color = Color::srgb_u8(200, 90, 30);
+    let device = profile.device_values(color);
+    assert!((device.x - 200.0 / 255.0).abs() < 2e-3);
+    assert!((device.y - 90.0 / 255.0).abs() < 2e-3);
+    let blue =
+        TransferFunction::AdobeRgb.encode_f32(TransferFunction::Srgb.decode_f32(30.0 / 255.0));
+    assert!((device.z - blue).abs() < 2e-3);
+
+    assert!(DisplayProfile::from_icc(&srgb_icc()[..200]).is_none());
+    assert!(DisplayProfile::from_icc(&[0; 64]).is_none());
+}
+
+#[test]
+fn measured_display() {
+    // A wide gamut display with a D50 white and a table curve.
+    let curve = ToneCurve::Table((0..=16).map(|i| (i as f32 / 16.0).powf(2.2)).collect());
+    let profile = DisplayProfile::from_measurements(
+        [
+            Vec3::new(0.6097, 0.3111, 0.0195),
+            Vec3::new(0.2053, 0.6257, 0.0609),
+            Vec3::new(0.1492, 0.0632, 0.7446),
+        ]
+        .map(|primary| primary * 120.0),
+        [curve.clone(), curve.clone(), curve],
+    )
+    .unwrap();
+
+    // The white of the display shows D65 white.
+    let white = profile.displayed(Vec3::ONE);
+    assert!(white.repr.abs_diff_eq(
+        Color::<CieXyz>::from_xy_luminance(0.3127, 0.3290, 1.0).repr,
+        1e-3
+    ));
+    assert!(
+        profile
+            .device_values(Color::srgb(1.0, 1.0, 1.0))
+            .abs_diff_eq(Vec3::ONE, 1e-3)
+    );
+
+    let color = Color::srgb(0.4, 0.2, 0.1);
+    let shown = profile.displayed(profile.device_values(color));
+    assert!(
+        shown
+            .convert::<colstodian::details::encodings::Srgb>()
+            .repr
+            .abs_diff_eq(color.repr, 1e-4)
+    );
+
+    // Alpha survives the correction.
+    let corrected = profile.correct(Color::srgba_u8(128, 128, 128, 77));
+    assert_eq!(corrected.repr[3], 77);
+
+    assert!(
+        DisplayProfile::new(
+            glam::Mat3::ZERO,
+            [0, 1, 2].map(|_| ToneCurve::Table(vec![0.0, 1.0]))
+        )
+        .is_none()
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests for pairwise color differences.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::colorchecker::{delta_e_2000, lab_d50};
+use colstodian::distance::{self, DeltaE, DistanceMatrix};
+
+fn colors() -> Vec<Color<SrgbU8>> {
+    (0..20u8)
+        .map(|i| Color::srgb_u8(i * 13, 255 - i * 7, ((i as u16 * 37) % 255) as u8))
+        .collect()
+}
+
+#[test]
+fn matrix_matches_pairwise_differences() {
+    let colors = colors();
+    for metric in [DeltaE::Ok, DeltaE::Cie76, DeltaE::Cie2000] {
+        let matrix = DistanceMatrix::new(&colors, metric);
+        assert_eq!(matrix.len(), 20);
+        assert_eq!(matrix.as_slice().len(), 400);
+        for (i, &a) in colors.iter().enumerate() {
+            assert_eq!(matrix.get(i, i), 0.0);
+            for (j, &b) in colors.iter().enumerate() {
+                let expected = match metric {
+                    DeltaE::Ok => a.delta_e_ok(b),
+                    DeltaE::Cie76 => lab_d50(a).distance(lab_d50(b)),
+                    DeltaE::Cie2000 => delta_e_2000(lab_d50(a), lab_d50(b)),
+                };
+                assert!((matrix.get(i, j) - expected).abs() < 1e-4);
+                assert_eq!(matrix.get(i, j), matrix.get(j, i));
+                assert_eq!(matrix.row(i)[j], matrix.get(i, j));
+            }
+        }
+    }
+}
+
+#[test]
+fn minimum_distance() {
+    let mut colors = colors();
+    colors.push(Color::srgb_u8(40, 233, 112));
+
+    for metric in [DeltaE::Ok, DeltaE::Cie76, DeltaE::Cie2000] {
+        let matrix = DistanceMatrix::new(&colors, metric);
+        let closest = distance::min_distance(&colors, metric).unwrap();
+        assert_eq!(matrix.min_pair(), Some(closest));
+        // The added color nearly duplicates the fourth.
+        assert_eq!((closest.0, closest.1), (3, 20));
+        let brute = (0..colors.len())
+            .flat_map(|i| {
+                (0..colors.len())
+                    .filter(move |&j| j != i)
+                    .map(move |j| (i, j))
+            })
+            .map(|(i, j)| matrix.get(i, j))
+            .fold(f32::INFINITY, f32::min);
+        assert_eq!(closest.2, brute);
+    }
+
+    // Duplicates are at no distance.
+    let duplicates = [
+        Color::srgb_u8(1, 2, 3),
+        Color::srgb_u8(200, 0, 0),
+        Color::srgb_u8(1, 2, 3),
+    ];
+    assert_eq!(
+        distance::min_distance(&duplicates, DeltaE::Ok),
+        Some((0, 2, 0.0))
+    );
+}
+
+#[test]
+fn fewer_than_two_colors() {
+    let one = [Color::srgb_u8(255, 0, 0)];
+    assert_eq!(distance::min_distance(&one, DeltaE::default()), None);
+    let matrix = DistanceMatrix::new(&one, DeltaE::default());
+    assert_eq!(matrix.min_pair(), None);
+    assert_eq!(matrix.as_slice(), &[0.0]);
+
+    let none: [Color<SrgbU8>; 0] = [];
+    assert!(DistanceMatrix::new(&none, DeltaE::Cie2000).is_empty());
+    assert_eq!(distance::min_distance(&none, DeltaE::Cie2000), None);
+}
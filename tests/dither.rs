@@ -0,0 +1,101 @@
+//! Tests for low-bit gray encodings and dithering.
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::details::encodings::{EncodedGrayU1, EncodedGrayU2, EncodedGrayU4, EncodedGrayU8};
+use colstodian::dither::{Dither, dither};
+use colstodian::*;
+
+#[test]
+fn low_bit_encodings() {
+    assert_eq!(Color::<EncodedGrayU1>::WHITE.v, 1);
+    assert_eq!(Color::<EncodedGrayU2>::WHITE.v, 3);
+    assert_eq!(Color::<EncodedGrayU4>::WHITE.v, 15);
+
+    // Levels are evenly spaced in the sRGB encoding.
+    assert_eq!(
+        Color::encoded_gray_u4(8).convert::<EncodedGrayU8>(),
+        Color::encoded_gray_u8(136)
+    );
+    assert_eq!(
+        Color::encoded_gray_u2(1).convert::<SrgbU8>(),
+        Color::srgb_u8(85, 85, 85)
+    );
+    assert_eq!(
+        Color::srgb_u8(200, 200, 200).convert::<EncodedGrayU1>(),
+        Color::<EncodedGrayU1>::WHITE
+    );
+    assert_eq!(
+        Color::srgb_u8(100, 100, 100).convert::<EncodedGrayU1>(),
+        Color::<EncodedGrayU1>::BLACK
+    );
+}
+
+#[cfg(feature = "std")]
+fn mean_luminance<D>(pixels: &[Color<D>]) -> f32
+where
+    D: ColorEncoding,
+    details::encodings::CieXyz: details::traits::ConvertFrom<D>,
+    details::linear_spaces::CieXYZ: details::traits::LinearConvertFromRaw<D::LinearSpace>,
+{
+    pixels
+        .iter()
+        .map(|pixel| pixel.convert::<details::encodings::CieXyz>().y)
+        .sum::<f32>()
+        / pixels.len() as f32
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn dithering_keeps_luminance() {
+    use colstodian::buffer::ColorBuffer;
+
+    let (width, height) = (32, 32);
+    for luminance in [0.05, 0.18, 0.5, 0.8] {
+        let src = ColorBuffer::<LinearSrgb>::filled(
+            width,
+            height,
+            Color::linear_srgb(luminance, luminance, luminance),
+        );
+        for method in [Dither::Ordered, Dither::FloydSteinberg] {
+            let one_bit = src.dithered::<EncodedGrayU1>(method);
+            assert!(
+                (mean_luminance(one_bit.pixels()) - luminance).abs() < 0.01,
+                "{method:?} at {luminance}"
+            );
+            let two_bit = src.dithered::<EncodedGrayU2>(method);
+            assert!((mean_luminance(two_bit.pixels()) - luminance).abs() < 0.01);
+        }
+
+        // Atkinson drops a quarter of the error, pushing tones apart.
+        let atkinson = mean_luminance(src.dithered::<EncodedGrayU1>(Dither::Atkinson).pixels());
+        if luminance < 0.5 {
+            assert!(atkinson < luminance);
+        } else if luminance > 0.5 {
+            assert!(atkinson > luminance);
+        } else {
+            assert!((atkinson - luminance).abs() < 0.01);
+        }
+    }
+}
+
+#[test]
+fn exact_levels_are_not_dithered() {
+    let level = Color::encoded_gray_u2(2).convert::<LinearSrgb>();
+    let src = [level; 64];
+    let mut dst = [Color::<EncodedGrayU2>::BLACK; 64];
+    for method in [Dither::Ordered, Dither::FloydSteinberg, Dither::Atkinson] {
+        let mut scratch = vec![0.0; method.scratch_len(8)];
+        dither(&src, 8, method, &mut scratch, &mut dst);
+        assert!(dst.iter().all(|pixel| pixel.v == 2), "{method:?}");
+    }
+
+    assert_eq!(level.dithered::<EncodedGrayU2>(3, 5).v, 2);
+}
+
+#[test]
+#[should_panic(expected = "scratch buffer is too short")]
+fn short_scratch_panics() {
+    let src = [Color::linear_srgb(0.5, 0.5, 0.5); 16];
+    let mut dst = [Color::<EncodedGrayU1>::BLACK; 16];
+    dither(&src, 4, Dither::Atkinson, &mut [0.0; 8], &mut dst);
+}
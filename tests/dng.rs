@@ -0,0 +1,86 @@
+//! Tests for DNG camera transforms.
+
+use colstodian::Color;
+use colstodian::calibration::ColorMatrix;
+use colstodian::details::encodings::CieXyz;
+use colstodian::dng::DngProfile;
+use glam::{Mat3, Vec3};
+
+const D50: Vec3 = Vec3::new(0.964_22, 1.0, 0.825_21);
+const D65: Vec3 = Vec3::new(0.950_47, 1.0, 1.088_83);
+
+fn color_matrix() -> Mat3 {
+    Mat3::from_cols(
+        Vec3::new(0.9, -0.3, 0.05),
+        Vec3::new(0.2, 1.3, -0.2),
+        Vec3::new(-0.1, 0.05, 0.8),
+    )
+}
+
+#[test]
+fn color_matrix_round_trip() {
+    let profile = DngProfile::new(color_matrix());
+    let neutral = color_matrix() * D65;
+    assert!(profile.white_point(neutral).unwrap().abs_diff_eq(D65, 1e-5));
+
+    let transform = profile.camera_transform(neutral).unwrap();
+    assert!((transform.to_xyz_d50() * neutral).abs_diff_eq(D50, 1e-5));
+
+    // Under a D65 shot, camera values develop back into the scene colors.
+    let xyz = Vec3::new(0.3, 0.2, 0.6);
+    let developed: Color<CieXyz> = transform.apply(color_matrix() * xyz);
+    assert!(developed.repr.abs_diff_eq(xyz, 1e-5));
+}
+
+#[test]
+fn forward_matrix_matches_color_matrix() {
+    let neutral = color_matrix() * D65;
+    let adapt = ColorMatrix::bradford(D65, D50).matrix;
+    let forward = adapt * color_matrix().inverse() * Mat3::from_diagonal(neutral);
+    let with_forward = DngProfile::new(color_matrix())
+        .with_forward_matrix(forward)
+        .camera_transform(neutral)
+        .unwrap();
+    let without = DngProfile::new(color_matrix())
+        .camera_transform(neutral)
+        .unwrap();
+    assert!(
+        with_forward
+            .to_xyz_d50()
+            .abs_diff_eq(without.to_xyz_d50(), 1e-5)
+    );
+
+    // Calibration and analog balance are undone before the forward matrix.
+    let balanced = DngProfile::new(color_matrix())
+        .with_forward_matrix(forward)
+        .with_analog_balance(Vec3::new(2.0, 1.0, 1.5))
+        .camera_transform(neutral * Vec3::new(2.0, 1.0, 1.5))
+        .unwrap();
+    let camera = color_matrix() * Vec3::new(0.3, 0.2, 0.6);
+    assert!(
+        (balanced.to_xyz_d50() * (camera * Vec3::new(2.0, 1.0, 1.5)))
+            .abs_diff_eq(with_forward.to_xyz_d50() * camera, 1e-5)
+    );
+}
+
+#[test]
+fn dual_illuminant_interpolation() {
+    let first = DngProfile::new(Mat3::IDENTITY);
+    let second = DngProfile::new(Mat3::from_diagonal(Vec3::splat(3.0)));
+    let at = |kelvin| DngProfile::interpolate(&first, 2856.0, &second, 6504.0, kelvin).color_matrix;
+    assert_eq!(at(2000.0), first.color_matrix);
+    assert_eq!(at(10_000.0), second.color_matrix);
+    // Halfway in inverse temperature.
+    let middle = 2.0 / (1.0 / 2856.0 + 1.0 / 6504.0);
+    assert!(at(middle).abs_diff_eq(Mat3::from_diagonal(Vec3::splat(2.0)), 1e-4));
+}
+
+#[test]
+fn singular_profiles() {
+    assert!(
+        DngProfile::new(Mat3::ZERO)
+            .camera_transform(Vec3::ONE)
+            .is_none()
+    );
+    assert!(DngProfile::new(Mat3::ZERO).white_point(Vec3::ONE).is_none());
+}
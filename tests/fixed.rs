@@ -0,0 +1,112 @@
+//! Tests for the fixed-point sRGB and matrix conversions.
+
+use colstodian::calibration::ColorMatrix;
+use colstodian::details::linear_spaces::{Bt2020, CieXYZ, DisplayP3, Srgb};
+use colstodian::fixed::{self, FixedMatrix};
+use colstodian::transfer::TransferFunction;
+use colstodian::*;
+use glam::{Mat3, Vec3};
+
+#[test]
+fn decode_matches_f32() {
+    (0..=255u8).for_each(|encoded| {
+        let exact = TransferFunction::Srgb.decode_f32(encoded as f32 / 255.0) * 65535.0;
+        let fixed = fixed::srgb_decode(encoded) as f32;
+        assert!((fixed - exact).abs() <= 0.5, "{encoded}: {fixed} {exact}");
+    });
+    assert_eq!(fixed::srgb_decode(0), 0);
+    assert_eq!(fixed::srgb_decode(255), 65535);
+}
+
+#[test]
+fn encode_matches_f32() {
+    (0..=u16::MAX).for_each(|linear| {
+        let exact = TransferFunction::Srgb.encode_f32(linear as f32 / 65535.0) * 255.0;
+        let fixed = fixed::srgb_encode(linear) as f32;
+        assert!(
+            (fixed - exact).abs() <= 0.5 + 1e-3,
+            "{linear}: {fixed} {exact}"
+        );
+    });
+}
+
+#[test]
+fn round_trip() {
+    (0..=255u8).for_each(|encoded| {
+        assert_eq!(fixed::srgb_encode(fixed::srgb_decode(encoded)), encoded);
+    });
+
+    let color = Color::srgb_u8(12, 128, 250);
+    assert_eq!(
+        Color::<details::encodings::EncodedSrgbU8>::from_linear_fixed(color.to_linear_fixed()),
+        color
+    );
+}
+
+#[test]
+fn linear_to_u8_rounds() {
+    assert_eq!(fixed::linear_to_u8(0), 0);
+    assert_eq!(fixed::linear_to_u8(65535), 255);
+    assert_eq!(fixed::linear_to_u8(32768), 128);
+    (0..=255u8).for_each(|value| {
+        assert_eq!(fixed::linear_to_u8(value as u16 * 257), value);
+    });
+}
+
+#[test]
+fn matrices_match_f32() {
+    fn check(fixed: FixedMatrix, matrix: Mat3) {
+        (0..=16u16).for_each(|r| {
+            (0..=16u16).for_each(|b| {
+                let rgb = [r * 4095, 65535 - r * 2048, b * 4095];
+                let exact = (matrix * Vec3::from_array(rgb.map(f32::from)))
+                    .clamp(Vec3::ZERO, Vec3::splat(65535.0));
+                let result = Vec3::from_array(fixed.transform(rgb).map(f32::from));
+                assert!(
+                    (result - exact).abs().max_element() <= 4e-4 * 65535.0,
+                    "{rgb:?}: {result} {exact}"
+                );
+            });
+        });
+    }
+
+    check(
+        FixedMatrix::conversion::<Srgb, Bt2020>().unwrap(),
+        ColorMatrix::conversion::<Srgb, Bt2020>().matrix,
+    );
+    check(
+        FixedMatrix::conversion::<DisplayP3, Srgb>().unwrap(),
+        ColorMatrix::conversion::<DisplayP3, Srgb>().matrix,
+    );
+    check(
+        FixedMatrix::conversion::<CieXYZ, Srgb>().unwrap(),
+        ColorMatrix::conversion::<CieXYZ, Srgb>().matrix,
+    );
+    check(FixedMatrix::IDENTITY, Mat3::IDENTITY);
+    check(
+        FixedMatrix::scale(0.25).unwrap(),
+        Mat3::from_diagonal(Vec3::splat(0.25)),
+    );
+}
+
+#[test]
+fn large_coefficients() {
+    assert!(FixedMatrix::scale(8.0).is_none());
+    assert!(FixedMatrix::scale(-7.9).is_some());
+    assert!(
+        FixedMatrix::from_mat3(Mat3::from_cols(
+            Vec3::X,
+            Vec3::splat(-3.0),
+            Vec3::splat(4.0)
+        ))
+        .is_none()
+    );
+    assert_eq!(
+        FixedMatrix::from_mat3(Mat3::IDENTITY),
+        Some(FixedMatrix::IDENTITY)
+    );
+    assert_eq!(
+        FixedMatrix::IDENTITY.rows[0][0],
+        1 << FixedMatrix::FRACTION_BITS
+    );
+}
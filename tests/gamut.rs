@@ -0,0 +1,83 @@
+//! Tests for gamut size and coverage.
+
+use colstodian::custom::CustomColorSpace;
+use colstodian::details::linear_spaces::{Aces2065, Bt2020, DisplayP3, Srgb};
+use colstodian::gamut;
+
+#[test]
+fn xy_area_of_srgb() {
+    let srgb = CustomColorSpace::of::<Srgb>();
+
+    assert!((gamut::xy_area(&srgb) - 0.11205).abs() < 1e-4);
+}
+
+#[test]
+fn xy_coverage() {
+    let srgb = CustomColorSpace::of::<Srgb>();
+    let p3 = CustomColorSpace::of::<DisplayP3>();
+    let bt2020 = CustomColorSpace::of::<Bt2020>();
+
+    assert!((gamut::xy_coverage(&srgb, &srgb) - 1.0).abs() < 1e-4);
+    assert!(gamut::xy_coverage(&bt2020, &p3) > 0.999);
+    let expected = gamut::xy_area(&srgb) / gamut::xy_area(&bt2020);
+    assert!((gamut::xy_coverage(&srgb, &bt2020) - expected).abs() < 1e-4);
+
+    // Partial overlap: P3 red lies outside of sRGB but sRGB blue is shared.
+    let coverage = gamut::xy_coverage(&srgb, &p3);
+    assert!(coverage > 0.7 && coverage < 0.8, "{coverage}");
+}
+
+#[test]
+fn oklab_volume_orders_gamuts() {
+    let volumes = [
+        CustomColorSpace::of::<Srgb>(),
+        CustomColorSpace::of::<DisplayP3>(),
+        CustomColorSpace::of::<Bt2020>(),
+        CustomColorSpace::of::<Aces2065>(),
+    ]
+    .map(|space| gamut::oklab_volume(&space));
+
+    assert!((volumes[0] - 0.0542).abs() < 1e-3);
+    assert!(volumes.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn gamut_containment() {
+    use colstodian::details::linear_spaces::{AcesCg, CieXYZ};
+    use colstodian::details::traits::LinearColorSpace;
+
+    assert!(Srgb::gamut_contains::<Srgb>());
+    assert!(DisplayP3::gamut_contains::<Srgb>());
+    assert!(Bt2020::gamut_contains::<Srgb>());
+    assert!(!DisplayP3::gamut_contains::<Bt2020>());
+    // The red primary of P3 lies just outside of BT.2020.
+    assert!(!Bt2020::gamut_contains::<DisplayP3>());
+    assert!(AcesCg::gamut_contains::<Srgb>());
+    assert!(!Srgb::gamut_contains::<CieXYZ>());
+
+    let wide = CustomColorSpace::from_primaries_d65([0.70, 0.29], [0.17, 0.79], [0.13, 0.05]);
+    assert!(wide.gamut_contains(&CustomColorSpace::default()));
+    assert!(!CustomColorSpace::default().gamut_contains(&wide));
+}
+
+#[test]
+fn colors_fit_in_encodings() {
+    use colstodian::Color;
+    use colstodian::basic_encodings::SrgbU8;
+    use colstodian::custom::DynamicColor;
+    use colstodian::details::encodings::{self, Oklab};
+
+    assert!(Color::srgb_u8(255, 0, 0).fits_in::<encodings::DisplayP3>());
+    assert!(Color::display_p3(1.0, 1.0, 1.0).fits_in::<SrgbU8>());
+    assert!(!Color::display_p3(1.0, 0.0, 0.0).fits_in::<SrgbU8>());
+    assert!(!Color::linear_srgb(1.5, 1.0, 1.0).fits_in::<SrgbU8>());
+    assert!(!Color::oklab(0.7, 0.3, 0.0).fits_in::<SrgbU8>());
+    assert!(Color::oklab(0.7, 0.05, 0.0).fits_in::<encodings::Bt2020>());
+    assert!(Color::<Oklab>::WHITE.fits_in::<SrgbU8>());
+
+    let p3 = CustomColorSpace::of::<DisplayP3>();
+    let p3_green = DynamicColor::new(0.0, 1.0, 0.0, p3);
+    assert!(p3_green.fits_in(&p3));
+    assert!(p3_green.fits_in(&CustomColorSpace::of::<Bt2020>()));
+    assert!(!p3_green.fits_in(&CustomColorSpace::default()));
+}
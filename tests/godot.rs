@@ -0,0 +1,41 @@
+//! Tests for the `godot` color conversions.
+#![cfg(feature = "godot")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use godot::builtin::Color as GodotColor;
+
+#[test]
+fn godot_colors_are_encoded() {
+    let orange: GodotColor = Color::srgb_u8(255, 128, 0).into();
+    assert!((orange.r - 1.0).abs() < 1e-6);
+    assert!((orange.g - 128.0 / 255.0).abs() < 1e-6);
+    assert_eq!((orange.b, orange.a), (0.0, 1.0));
+
+    let back: Color<SrgbU8> = orange.into();
+    assert_eq!(back, Color::srgb_u8(255, 128, 0));
+
+    // Alpha is carried over.
+    let translucent = GodotColor::from_rgba(1.0, 1.0, 1.0, 0.25);
+    assert_eq!(
+        Color::<colstodian::basic_encodings::LinearSrgba>::from(translucent)
+            .repr
+            .w,
+        0.25
+    );
+}
+
+#[test]
+fn godot_linear_colors() {
+    let gray = Color::linear_srgb(0.2, 0.2, 0.2);
+    assert_eq!(
+        gray.to_godot_linear(),
+        GodotColor::from_rgba(0.2, 0.2, 0.2, 1.0)
+    );
+
+    let encoded: GodotColor = gray.into();
+    assert!(encoded.r > 0.48);
+
+    let back = Color::<LinearSrgb>::from_godot_linear(gray.to_godot_linear());
+    assert_eq!(back, gray);
+}
@@ -0,0 +1,268 @@
+//! Tests for gradients, value maps and color tracks.
+
+#![cfg(feature = "std")]
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbaU8};
+use colstodian::details::encodings::{Oklab, Oklch};
+use colstodian::gradient::{Easing, Gradient, InterpolationSpace};
+use colstodian::track::{ColorTrack, Repeat};
+use colstodian::value_map::{Normalization, ValueMap};
+use colstodian::{Color, HueInterpolation};
+
+fn gray(v: f32) -> Color<LinearSrgb> {
+    Color::linear_srgb(v, v, v)
+}
+
+fn black_to_white() -> Gradient<LinearSrgb> {
+    Gradient::evenly_spaced([gray(0.0), gray(1.0)])
+}
+
+#[test]
+fn gradient_sampling() {
+    let gradient = Gradient::new([(1.0, gray(1.0)), (0.0, gray(0.0)), (0.25, gray(0.5))]);
+
+    assert_eq!(gradient.stops()[1].0, 0.25);
+    assert_eq!(gradient.sample(-1.0), gray(0.0));
+    assert_eq!(gradient.sample(0.125), gray(0.25));
+    assert_eq!(gradient.sample(0.25), gray(0.5));
+    assert_eq!(gradient.sample(0.625), gray(0.75));
+    assert_eq!(gradient.sample(2.0), gray(1.0));
+
+    let single = Gradient::evenly_spaced([gray(0.3)]);
+    assert_eq!(single.sample(0.7), gray(0.3));
+}
+
+#[test]
+#[should_panic(expected = "at least one stop")]
+fn gradient_needs_stops() {
+    Gradient::<LinearSrgb>::new([]);
+}
+
+#[test]
+fn linear_and_log_normalization() {
+    assert_eq!(Normalization::linear(10.0, 20.0).normalize(15.0), 0.5);
+    assert!((Normalization::log(1.0, 100.0).normalize(10.0) - 0.5).abs() < 1e-6);
+
+    // Values that aren't positive are below a logarithmic range.
+    let blue = Color::linear_srgb(0.0, 0.0, 1.0);
+    let map =
+        ValueMap::new(black_to_white(), Normalization::log(1.0, 100.0)).with_under_color(blue);
+    assert_eq!(map.map(0.0), blue);
+    assert_eq!(map.map(-1.0), blue);
+    assert_eq!(map.map(1.0), gray(0.0));
+}
+
+#[test]
+fn percentile_normalization() {
+    let normalization = Normalization::percentile(&[100.0, 1.0, f32::NAN, 3.0, 2.0, 1000.0]);
+
+    assert_eq!(normalization.normalize(1.0), 0.0);
+    assert_eq!(normalization.normalize(3.0), 0.5);
+    assert_eq!(normalization.normalize(51.5), 0.625);
+    assert_eq!(normalization.normalize(1000.0), 1.0);
+    assert!(normalization.normalize(0.5) < 0.0);
+    assert!(normalization.normalize(2000.0) > 1.0);
+
+    // Constant data maps to the start of the gradient.
+    let constant = Normalization::percentile(&[4.0, 4.0]);
+    assert_eq!(constant.normalize(4.0), 0.0);
+    assert_eq!(Normalization::percentile(&[4.0]).normalize(4.0), 0.0);
+    assert!(constant.normalize(3.0) < 0.0);
+    assert!(constant.normalize(5.0) > 1.0);
+}
+
+#[test]
+fn value_map_special_colors() {
+    let red = Color::linear_srgb(1.0, 0.0, 0.0);
+    let blue = Color::linear_srgb(0.0, 0.0, 1.0);
+    let map = ValueMap::new(black_to_white(), Normalization::linear(0.0, 10.0));
+
+    assert_eq!(map.map(5.0), gray(0.5));
+    assert_eq!(map.map(-5.0), gray(0.0));
+    assert_eq!(map.map(50.0), gray(1.0));
+    assert_eq!(map.map(f32::NAN), Color::default());
+
+    let map = map
+        .with_nan_color(red)
+        .with_under_color(blue)
+        .with_over_color(red);
+    assert_eq!(map.map(-5.0), blue);
+    assert_eq!(map.map(50.0), red);
+    assert_eq!(map.map(f32::NAN), red);
+    assert_eq!(map.map(10.0), gray(1.0));
+}
+
+#[test]
+fn equality() {
+    assert_eq!(black_to_white(), black_to_white());
+    assert_ne!(
+        black_to_white(),
+        black_to_white().with_space(InterpolationSpace::Oklab)
+    );
+
+    let map = || ValueMap::new(black_to_white(), Normalization::linear(0.0, 10.0));
+    assert_eq!(map(), map().clone());
+    assert_ne!(map(), map().with_over_color(gray(0.5)));
+}
+
+#[test]
+fn polar_gradient_wraps_hue() {
+    let gradient =
+        Gradient::evenly_spaced([Color::oklch(0.6, 0.1, 300.0), Color::oklch(0.6, 0.1, 60.0)]);
+    assert_eq!(gradient.sample_hue(0.5, HueInterpolation::Shorter).h, 0.0);
+    assert_eq!(gradient.sample_hue(0.5, HueInterpolation::Longer).h, 180.0);
+    assert_eq!(gradient.sample_hue(2.0, HueInterpolation::Shorter).h, 60.0);
+}
+
+#[test]
+fn easing() {
+    assert_eq!(Easing::Linear.apply(0.3), 0.3);
+    assert_eq!(Easing::Steps(4).apply(0.3), 0.25);
+    assert_eq!(Easing::Steps(4).apply(1.0), 1.0);
+    [
+        Easing::Ease,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ]
+    .into_iter()
+    .for_each(|easing| {
+        assert!(easing.apply(0.0).abs() < 1e-5);
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-5);
+    });
+    assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-5);
+    assert!(Easing::EaseIn.apply(0.25) < 0.25);
+    assert!(Easing::EaseOut.apply(0.25) > 0.25);
+    assert!((Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.3) - 0.3).abs() < 1e-4);
+
+    let gradient = Gradient::evenly_spaced([gray(0.0), gray(1.0), gray(0.0)])
+        .with_segment_easing(1, Easing::Steps(2));
+    assert_eq!(gradient.easings(), &[Easing::Linear, Easing::Steps(2)]);
+    assert_eq!(gradient.sample(0.25), gray(0.5));
+    assert_eq!(gradient.sample(0.7), gray(1.0));
+    assert_eq!(gradient.sample(0.8), gray(0.5));
+}
+
+#[test]
+fn interpolation_spaces() {
+    let red = Color::srgb_u8(255, 0, 0);
+    let blue = Color::srgb_u8(0, 0, 255);
+    let gradient = Gradient::evenly_spaced([red, blue]);
+
+    assert_eq!(gradient.sample_mixed(0.5), Color::srgb_u8(188, 0, 188));
+    assert_eq!(
+        gradient
+            .clone()
+            .with_space(InterpolationSpace::Oklab)
+            .sample_mixed(0.5),
+        red.mix_in::<Oklab>(blue, 0.5)
+    );
+    let oklch = gradient.with_space(InterpolationSpace::Oklch(HueInterpolation::Longer));
+    assert_eq!(
+        oklch.space(),
+        InterpolationSpace::Oklch(HueInterpolation::Longer)
+    );
+    assert_eq!(
+        oklch.sample_mixed(0.5),
+        red.mix_in_polar::<Oklch>(blue, 0.5, HueInterpolation::Longer)
+    );
+
+    // Gradients that can interpolate in their encoding use the space too.
+    let linear =
+        Gradient::evenly_spaced([gray(0.0), gray(1.0)]).with_space(InterpolationSpace::Oklab);
+    assert_eq!(
+        linear.sample(0.5),
+        gray(0.0).mix_in::<Oklab>(gray(1.0), 0.5)
+    );
+}
+
+#[test]
+fn css_stops() {
+    let gradient = Gradient::<SrgbaU8>::from_css_stops("red, #00ff0080 25% 50%, blue, transparent")
+        .unwrap()
+        .with_space(InterpolationSpace::Oklch(HueInterpolation::Longer));
+
+    let positions: Vec<_> = gradient.stops().iter().map(|(p, _)| *p).collect();
+    assert_eq!(positions, [0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(
+        gradient.to_css_stops(),
+        "#ff0000 0%, #00ff0080 25%, #00ff0080 50%, #0000ff 75%, #00000000 100%"
+    );
+    assert_eq!(
+        gradient.to_css_conic_gradient(45.0),
+        format!(
+            "conic-gradient(from 45deg in oklch longer hue, {})",
+            gradient.to_css_stops()
+        )
+    );
+
+    let parsed = Gradient::<SrgbaU8>::from_css_stops(&gradient.to_css_stops()).unwrap();
+    assert_eq!(parsed.stops(), gradient.stops());
+
+    let fixed = Gradient::<SrgbaU8>::from_css_stops("red 50%, lime 20%, blue").unwrap();
+    let positions: Vec<_> = fixed.stops().iter().map(|(p, _)| *p).collect();
+    assert_eq!(positions, [0.5, 0.5, 1.0]);
+
+    assert!(Gradient::<SrgbaU8>::from_css_stops("").is_none());
+    assert!(Gradient::<SrgbaU8>::from_css_stops("red, notacolor").is_none());
+    assert!(Gradient::<SrgbaU8>::from_css_stops("red 10, blue").is_none());
+}
+
+#[test]
+fn css_stops_reject_non_finite_positions() {
+    ["NaN%", "inf%", "-inf%"].iter().for_each(|position| {
+        let stops = format!("red {position}, green, blue");
+        assert!(
+            Gradient::<SrgbaU8>::from_css_stops(&stops).is_none(),
+            "{stops}"
+        );
+    });
+}
+
+#[test]
+fn color_track_repeat() {
+    let track = ColorTrack::new([(2.0, gray(0.0)), (6.0, gray(1.0))]);
+
+    assert_eq!(track.start(), 2.0);
+    assert_eq!(track.duration(), 4.0);
+    assert_eq!(track.sample(0.0), gray(0.0));
+    assert_eq!(track.sample(3.0), gray(0.25));
+    assert_eq!(track.sample(10.0), gray(1.0));
+
+    let looped = track.clone().with_repeat(Repeat::Loop);
+    assert_eq!(looped.sample(7.0), gray(0.25));
+    assert_eq!(looped.sample(-1.0), gray(0.25));
+
+    let ping_pong = track.with_repeat(Repeat::PingPong);
+    assert_eq!(ping_pong.sample(7.0), gray(0.75));
+    assert_eq!(ping_pong.sample(11.0), gray(0.25));
+    assert_eq!(ping_pong.sample(1.0), gray(0.25));
+}
+
+#[test]
+fn color_track_space_and_easing() {
+    let red = Color::<SrgbaU8>::srgba_u8(255, 0, 0, 255);
+    let blue = Color::srgba_u8(0, 0, 255, 255);
+    let track = ColorTrack::new([(0.0, red), (1.0, blue)])
+        .with_space(InterpolationSpace::Oklab)
+        .with_easing(Easing::Steps(2));
+
+    assert_eq!(track.sample_mixed(0.4), red);
+    assert_eq!(
+        track.sample_mixed(0.6),
+        Gradient::evenly_spaced([red, blue])
+            .with_space(InterpolationSpace::Oklab)
+            .sample_mixed(0.5)
+    );
+}
+
+#[test]
+fn ansi_preview() {
+    let gradient =
+        Gradient::evenly_spaced([Color::srgb_u8(0, 0, 0), Color::srgb_u8(255, 255, 255)]);
+    let preview = gradient.to_ansi_preview(4);
+
+    assert_eq!(preview.matches("\x1b[48;2;").count(), 4);
+    assert!(preview.starts_with("\x1b[48;2;"));
+    assert!(preview.ends_with(" \x1b[0m"));
+}
@@ -0,0 +1,66 @@
+//! Tests for color harmonies.
+
+use colstodian::Color;
+use colstodian::basic_encodings::LinearSrgba;
+use colstodian::details::encodings::Oklab;
+
+fn hue(color: Color<Oklab>) -> f32 {
+    color
+        .repr
+        .z
+        .atan2(color.repr.y)
+        .to_degrees()
+        .rem_euclid(360.0)
+}
+
+fn chroma(color: Color<Oklab>) -> f32 {
+    color.repr.y.hypot(color.repr.z)
+}
+
+#[test]
+fn rotation_keeps_lightness_and_chroma() {
+    let base = Color::oklab(0.6, 0.1, -0.05);
+    let rotated = base.rotate_hue(75.0);
+
+    assert!((rotated.repr.x - base.repr.x).abs() < 1e-4);
+    assert!((chroma(rotated) - chroma(base)).abs() < 1e-4);
+    assert!(((hue(rotated) - hue(base)).rem_euclid(360.0) - 75.0).abs() < 1e-2);
+}
+
+#[test]
+fn harmonies_are_evenly_spaced() {
+    let base = Color::oklab(0.7, 0.08, 0.02);
+    let [first, second, third, fourth] = base.tetradic();
+
+    assert_eq!(first, base);
+    [second, third, fourth]
+        .iter()
+        .zip([90.0, 180.0, 270.0])
+        .for_each(|(c, angle)| {
+            assert!(((hue(*c) - hue(base)).rem_euclid(360.0) - angle).abs() < 1e-2);
+        });
+
+    let [_, complement] = base.complementary();
+    assert!((complement.repr.y + base.repr.y).abs() < 1e-4);
+    assert!((complement.repr.z + base.repr.z).abs() < 1e-4);
+}
+
+#[test]
+fn harmonies_in_other_encodings() {
+    let base = Color::srgb_u8(200, 120, 40);
+    let [same, left, right] = base.analogous(30.0);
+
+    assert_eq!(same, base);
+    assert_ne!(left, base);
+    left.rotate_hue(60.0)
+        .repr
+        .iter()
+        .zip(right.repr)
+        .for_each(|(a, b)| assert!(a.abs_diff(b) <= 1));
+
+    let base = Color::<LinearSrgba>::from_repr(glam::Vec4::new(0.2, 0.4, 0.6, 0.5));
+    base.split_complementary()
+        .iter()
+        .chain(&base.triadic())
+        .for_each(|c| assert_eq!(c.repr.w, 0.5));
+}
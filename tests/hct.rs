@@ -0,0 +1,64 @@
+//! Tests for HCT and Material tonal palettes.
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::details::encodings::Hct;
+use colstodian::hct::{CorePalette, TonalPalette};
+
+#[test]
+fn hct_of_primaries() {
+    // Reference values of material-color-utilities.
+    let cases = [
+        ([255, 0, 0], [27.408, 113.357, 53.241]),
+        ([0, 255, 0], [142.139, 108.410, 87.737]),
+        ([0, 0, 255], [282.788, 87.230, 32.302]),
+    ];
+    for (rgb, [h, c, t]) in cases {
+        let hct = Color::<SrgbU8>::from_repr(rgb).convert::<Hct>();
+        assert!((hct.h - h).abs() < 0.05, "{hct:?}");
+        assert!((hct.c - c).abs() < 0.05, "{hct:?}");
+        assert!((hct.t - t).abs() < 0.05, "{hct:?}");
+        assert_eq!(hct.convert::<SrgbU8>().repr, rgb);
+    }
+
+    let white = Color::<SrgbU8>::WHITE.convert::<Hct>();
+    assert!(white.c < 3.0 && (white.t - 100.0).abs() < 0.01);
+}
+
+#[test]
+fn hct_maps_chroma_into_gamut() {
+    for tone in [5.0, 30.0, 50.0, 70.0, 95.0] {
+        let color = Color::hct(200.0, 150.0, tone).convert::<SrgbU8>();
+        let hct = color.convert::<Hct>();
+
+        assert!((hct.t - tone).abs() < 0.5, "{hct:?}");
+        assert!((hct.h - 200.0).abs() < 2.0, "{hct:?}");
+        assert!(hct.c < 150.0);
+    }
+}
+
+#[test]
+fn tonal_palettes() {
+    let palette = TonalPalette::new(270.0, 36.0);
+    let tones = palette.tones();
+
+    assert_eq!(tones[0], Color::srgb_u8(0, 0, 0));
+    assert_eq!(tones[12], Color::srgb_u8(255, 255, 255));
+    for (color, tone) in tones.iter().zip(TonalPalette::TONES).skip(1).take(11) {
+        let hct = color.convert::<Hct>();
+        assert!((hct.t - tone).abs() < 0.5, "{hct:?}");
+        // Quantization shifts the hue of the palest tones.
+        if hct.c > 10.0 {
+            assert!((hct.h - 270.0).abs() < 2.0, "{hct:?}");
+        }
+    }
+
+    let core = CorePalette::new(Color::srgb_u8(66, 133, 244));
+    assert_eq!(core.primary.hue, core.secondary.hue);
+    assert!(core.primary.chroma >= 48.0);
+    assert_eq!(core.neutral.chroma, 4.0);
+    assert_eq!(
+        TonalPalette::of(core.primary.tone(40.0)).hue.round(),
+        core.primary.hue.round()
+    );
+}
@@ -0,0 +1,123 @@
+//! Tests for HDR10 static metadata.
+
+use colstodian::Color;
+use colstodian::basic_encodings::LinearSrgb;
+use colstodian::details::linear_spaces::DisplayP3;
+use colstodian::hdr10::{ContentLightLevel, MasteringDisplay};
+use glam::Vec3;
+
+#[test]
+fn light_levels_across_frames() {
+    let mut cll = ContentLightLevel::default();
+
+    cll.add_frame([Color::linear_srgb(0.5, 0.5, 0.5); 4], 100.0);
+    assert_eq!(
+        cll,
+        ContentLightLevel {
+            max_cll: 50,
+            max_fall: 50
+        }
+    );
+
+    cll.add_frame(
+        [
+            Color::linear_srgb(2.0, 2.0, 2.0),
+            Color::<LinearSrgb>::BLACK,
+        ],
+        100.0,
+    );
+    assert_eq!(
+        cll,
+        ContentLightLevel {
+            max_cll: 200,
+            max_fall: 100
+        }
+    );
+
+    // A dimmer frame changes nothing and an empty one is ignored.
+    cll.add_frame([Color::linear_srgb(0.1, 0.1, 0.1)], 100.0);
+    cll.add_frame(core::iter::empty::<Color<LinearSrgb>>(), 100.0);
+    assert_eq!(
+        cll,
+        ContentLightLevel {
+            max_cll: 200,
+            max_fall: 100
+        }
+    );
+}
+
+#[test]
+fn light_levels_use_bt2020_components() {
+    let mut cll = ContentLightLevel::default();
+
+    // Pure sRGB red has a red component below 1.0 in BT.2020.
+    cll.add_frame([Color::linear_srgb(1.0, 0.0, 0.0)], 1000.0);
+    assert_eq!(cll.max_cll, 627);
+}
+
+#[test]
+fn pq_light_levels() {
+    let mut cll = ContentLightLevel::default();
+
+    // PQ code values for 100, 1000 and 10000 nits.
+    cll.add_pq_frame([
+        Vec3::splat(0.508078),
+        Vec3::new(0.751827, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    ]);
+    assert_eq!(cll.max_cll, 10_000);
+    assert_eq!(cll.max_fall, 3_700);
+}
+
+#[test]
+fn st2086_of_display_p3() {
+    let st2086 = MasteringDisplay::of::<DisplayP3>(1000.0, 0.005).to_st2086();
+
+    assert_eq!(st2086.red, [34000, 16000]);
+    assert_eq!(st2086.green, [13250, 34500]);
+    assert_eq!(st2086.blue, [7500, 3000]);
+    // The exact D65 of ST 2086 tooling, not kolor's (0.31273, 0.32902).
+    assert_eq!(st2086.white_point, [15635, 16450]);
+    assert_eq!(st2086.max_luminance, 10_000_000);
+    assert_eq!(st2086.min_luminance, 50);
+}
+
+#[test]
+fn st2086_of_custom_space() {
+    use colstodian::custom::CustomColorSpace;
+
+    // Without CICP primaries, the chromaticities come from the matrices.
+    let space = CustomColorSpace::from_primaries_d65([0.7, 0.3], [0.2, 0.75], [0.14, 0.05]);
+    let st2086 = MasteringDisplay::from_custom_color_space(&space, 1000.0, 0.005).to_st2086();
+    assert_eq!(st2086.red, [35000, 15000]);
+    assert_eq!(st2086.green, [10000, 37500]);
+}
+
+#[test]
+fn hdr10_pixels() {
+    use colstodian::details::encodings::{AcesCg, Bt2020, EncodedHdr10, HDR_REFERENCE_WHITE};
+    use colstodian::details::traits::AlphaEncoding;
+
+    let black = Color::bt2020(0.0, 0.0, 0.0).convert::<EncodedHdr10>();
+    assert_eq!(black.bits, 0xc000_0000);
+
+    let peak = Color::bt2020(1.0, 1.0, 1.0) * (10_000.0 / HDR_REFERENCE_WHITE);
+    let peak = peak.convert::<EncodedHdr10>();
+    assert_eq!(peak.bits, 0xffff_ffff);
+
+    let color = Color::encoded_hdr10(592, 300, 1000, 2);
+    assert_eq!(
+        (color.r(), color.g(), color.b(), color.a()),
+        (592, 300, 1000, 2)
+    );
+    assert_eq!(EncodedHdr10::alpha(color), 2.0 / 3.0);
+
+    // Bt2020 has no alpha, so colors come back opaque.
+    let opaque = EncodedHdr10::with_alpha(color, 1.0);
+    let round_trip = color.convert::<Bt2020>().convert::<EncodedHdr10>();
+    assert_eq!(round_trip, opaque);
+
+    let aces = Color::aces_cg(0.5, 0.25, 0.125);
+    let decoded = aces.convert::<EncodedHdr10>().convert::<AcesCg>();
+    assert!((decoded.repr - aces.repr).abs().max_element() < 0.005);
+}
@@ -0,0 +1,76 @@
+//! Tests for ICtCp and hue-preserving HDR brightness adjustments.
+
+use colstodian::details::encodings::{EncodedHdr10, ICtCpPq, Srgba};
+use colstodian::*;
+use glam::{Vec3, Vec4};
+
+#[test]
+fn ictcp_round_trip() {
+    for color in [
+        Color::bt2020(0.0, 0.0, 0.0),
+        Color::bt2020(1.0, 1.0, 1.0),
+        Color::bt2020(4.0, 0.5, 0.1),
+        Color::bt2020(0.02, 0.3, 1.5),
+    ] {
+        let back = color
+            .convert::<ICtCpPq>()
+            .convert::<details::encodings::Bt2020>();
+        assert!(
+            back.repr
+                .abs_diff_eq(color.repr, 1e-4 * color.repr.max_element().max(1.0))
+        );
+    }
+
+    // Grays have no chroma, and HDR10 code values convert alike.
+    let gray = Color::encoded_hdr10(520, 520, 520, 3).convert::<ICtCpPq>();
+    assert!((gray.i - 520.0 / 1023.0).abs() < 1e-5);
+    assert!(gray.ct.abs() < 1e-5 && gray.cp.abs() < 1e-5);
+    let back = gray.convert::<EncodedHdr10>();
+    assert_eq!((back.r(), back.g(), back.b()), (520, 520, 520));
+}
+
+#[test]
+fn exposure() {
+    let gray = Color::bt2020(0.5, 0.5, 0.5);
+    assert!(
+        gray.hdr_exposure(2.0)
+            .repr
+            .abs_diff_eq(Vec3::splat(2.0), 1e-3)
+    );
+    assert!(
+        gray.hdr_exposure(-1.0)
+            .repr
+            .abs_diff_eq(Vec3::splat(0.25), 1e-4)
+    );
+    assert!(gray.hdr_exposure(0.0).repr.abs_diff_eq(gray.repr, 1e-5));
+
+    let saturated = Color::bt2020(6.0, 1.0, 0.3);
+    let before = saturated.convert::<ICtCpPq>();
+    let after = saturated.hdr_exposure(1.5).convert::<ICtCpPq>();
+    assert!(after.i > before.i);
+    assert!((after.ct - before.ct).abs() < 1e-4);
+    assert!((after.cp - before.cp).abs() < 1e-4);
+
+    // Intensity stops at the PQ peak.
+    assert!((gray.hdr_exposure(20.0).hdr_intensity() - 1.0).abs() < 1e-5);
+    assert_eq!(
+        Color::bt2020(0.0, 0.0, 0.0).hdr_exposure(3.0).repr,
+        Vec3::ZERO
+    );
+}
+
+#[test]
+fn brightness() {
+    let color = Color::srgba(0.8, 0.2, 0.1, 0.5);
+    let before = color.convert::<ICtCpPq>();
+    let brighter: Color<Srgba> = color.hdr_brightness(0.05);
+    let after = brighter.convert::<ICtCpPq>();
+
+    assert!((after.i - before.i - 0.05).abs() < 1e-4);
+    assert!((after.ct - before.ct).abs() < 1e-4);
+    assert!((after.cp - before.cp).abs() < 1e-4);
+    assert_eq!(brighter.a, 0.5);
+
+    let gray = Color::srgba(0.5, 0.5, 0.5, 1.0);
+    assert!(gray.hdr_brightness(-2.0).repr.abs_diff_eq(Vec4::W, 1e-6));
+}
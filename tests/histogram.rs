@@ -0,0 +1,95 @@
+//! Tests for color histograms.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::LinearSrgb;
+use colstodian::details::encodings::{EncodedSrgbF32, Oklab};
+use colstodian::histogram::Histogram;
+
+#[test]
+fn bins_and_out_of_range_values() {
+    let mut histogram = Histogram::new(4, 0.0..=2.0);
+    [-1.0, 0.0, 0.49, 0.5, 1.99, 2.0, 5.0, f32::NAN]
+        .into_iter()
+        .for_each(|value| histogram.add(value));
+
+    assert_eq!(histogram.counts(), &[3, 1, 0, 3]);
+    assert_eq!(histogram.total(), 7);
+    assert_eq!(histogram.bin_range(1), 0.5..1.0);
+    assert_eq!(histogram.bin_of(f32::NAN), None);
+}
+
+#[test]
+fn luminance_histogram() {
+    let colors = [
+        Color::linear_srgb(1.0, 1.0, 1.0),
+        Color::linear_srgb(0.0, 1.0, 0.0),
+        Color::linear_srgb(0.0, 0.0, 1.0),
+        Color::<LinearSrgb>::BLACK,
+    ];
+    let histogram = Histogram::luminance(colors, 10, 0.0..=1.0);
+
+    // Luminances are 1.0, ~0.715, ~0.072 and 0.0.
+    assert_eq!(histogram.counts(), &[2, 0, 0, 0, 0, 0, 0, 1, 0, 1]);
+}
+
+#[test]
+fn channel_histograms() {
+    let colors = [
+        Color::srgb_u8(255, 0, 0),
+        Color::srgb_u8(0, 255, 0),
+        Color::srgb_u8(0, 0, 255),
+    ];
+
+    let [red, green, blue] =
+        Histogram::channels::<_, EncodedSrgbF32>(colors, 2, [0.0..=1.0, 0.0..=1.0, 0.0..=1.0]);
+    [red, green, blue]
+        .iter()
+        .for_each(|histogram| assert_eq!(histogram.counts(), &[2, 1]));
+
+    // Blue is the darkest of the three in Oklab.
+    let [lightness, ..] =
+        Histogram::channels::<_, Oklab>(colors, 10, [0.0..=1.0, -0.4..=0.4, -0.4..=0.4]);
+    assert_eq!(lightness.counts(), &[0, 0, 0, 0, 1, 0, 1, 0, 1, 0]);
+}
+
+#[test]
+fn merged_histograms() {
+    let mut left = Histogram::new(4, 0.0..=1.0);
+    let mut right = left.clone();
+    [0.1, 0.6].into_iter().for_each(|value| left.add(value));
+    [0.6, 0.9].into_iter().for_each(|value| right.add(value));
+    left.merge(&right);
+    assert_eq!(left.counts(), &[1, 0, 2, 1]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_histograms() {
+    let colors: Vec<_> = (0..=255u8)
+        .map(|v| Color::srgb_u8(v, 255 - v, v / 2))
+        .collect();
+
+    assert_eq!(
+        Histogram::par_luminance(colors.clone(), 16, 0.0..=1.0),
+        Histogram::luminance(colors.clone(), 16, 0.0..=1.0)
+    );
+    let ranges = [0.0..=1.0, -0.4..=0.4, -0.4..=0.4];
+    assert_eq!(
+        Histogram::par_channels::<_, Oklab>(colors.clone(), 16, ranges.clone()),
+        Histogram::channels::<_, Oklab>(colors, 16, ranges)
+    );
+}
+
+#[test]
+#[should_panic]
+fn mismatched_merge_panics() {
+    Histogram::new(4, 0.0..=1.0).merge(&Histogram::new(8, 0.0..=1.0));
+}
+
+#[test]
+#[should_panic]
+fn empty_range_panics() {
+    Histogram::new(4, 1.0..=1.0);
+}
@@ -0,0 +1,98 @@
+//! Tests for the addressable LED encoding and limits.
+
+use colstodian::details::encodings::{EncodedLedGrbU8, EncodedSrgbU8, Srgb};
+use colstodian::led::{LedLimits, encode_leds};
+use colstodian::*;
+use glam::Vec3;
+
+#[test]
+fn grb_order_and_linear_duty() {
+    let led = Color::encoded_led_grb_u8(10, 20, 30);
+    assert_eq!(led.repr, [20, 10, 30]);
+    assert_eq!((led.g, led.r, led.b), (20, 10, 30));
+
+    // Duty cycles are linear light.
+    let led = Color::linear_srgb(0.5, 1.0, 0.0).convert::<EncodedLedGrbU8>();
+    assert_eq!(led, Color::encoded_led_grb_u8(128, 255, 0));
+    let back = led.convert::<Srgb>();
+    assert!(
+        back.repr
+            .abs_diff_eq(Vec3::new(128.0 / 255.0, 1.0, 0.0), 1e-6)
+    );
+
+    // Middle gray in sRGB is about a fifth of the light.
+    let gray = Color::srgb_u8(128, 128, 128).convert::<EncodedLedGrbU8>();
+    assert_eq!(gray, Color::encoded_led_grb_u8(55, 55, 55));
+    assert_eq!(
+        gray.convert::<EncodedSrgbU8>(),
+        Color::srgb_u8(128, 128, 128)
+    );
+}
+
+#[test]
+fn brightness_and_channel_limits() {
+    let color = Color::linear_srgb(1.0, 0.5, 0.2);
+    assert_eq!(color.to_led(&LedLimits::default()), color.convert());
+
+    let dimmed = color.to_led(&LedLimits {
+        brightness: 0.25,
+        ..LedLimits::default()
+    });
+    assert_eq!(dimmed, Color::encoded_led_grb_u8(64, 32, 13));
+
+    // Capping blue keeps the levels below it in proportion.
+    let limits = LedLimits {
+        channel_max: Vec3::new(1.0, 1.0, 0.5),
+        ..LedLimits::default()
+    };
+    assert_eq!(
+        Color::linear_srgb(1.0, 1.0, 1.0).to_led(&limits),
+        Color::encoded_led_grb_u8(255, 255, 128)
+    );
+    assert_eq!(
+        color.to_led(&limits),
+        Color::encoded_led_grb_u8(255, 128, 26)
+    );
+
+    // Out of range components are clipped before scaling.
+    let hdr = Color::linear_srgb(4.0, -1.0, 0.0).to_led(&LedLimits {
+        brightness: 0.5,
+        ..LedLimits::default()
+    });
+    assert_eq!(hdr, Color::encoded_led_grb_u8(128, 0, 0));
+}
+
+#[test]
+fn power_budget() {
+    let limits = LedLimits {
+        max_power: 0.25,
+        ..LedLimits::default()
+    };
+
+    // A frame within the budget is unchanged.
+    let dim = [Color::linear_srgb(0.2, 0.2, 0.2); 4];
+    let mut leds = [Color::<EncodedLedGrbU8>::BLACK; 4];
+    encode_leds(&dim, &limits, &mut leds);
+    assert!(leds.iter().all(|led| *led == dim[0].to_led(&limits)));
+
+    // A frame over it is dimmed alike to fit.
+    let frame = [
+        Color::linear_srgb(1.0, 1.0, 1.0),
+        Color::linear_srgb(1.0, 0.0, 0.0),
+        Color::linear_srgb(0.0, 0.0, 0.0),
+        Color::linear_srgb(0.0, 1.0, 1.0),
+    ];
+    encode_leds(&frame, &limits, &mut leds);
+    assert_eq!(leds[0], Color::encoded_led_grb_u8(128, 128, 128));
+    assert_eq!(leds[1], Color::encoded_led_grb_u8(128, 0, 0));
+    assert_eq!(leds[2], Color::<EncodedLedGrbU8>::BLACK);
+    let sum: u32 = leds.iter().flat_map(|led| led.repr).map(u32::from).sum();
+    assert!(sum as f32 <= 0.25 * 12.0 * 255.0 + 3.0);
+}
+
+#[test]
+#[should_panic(expected = "differ in size")]
+fn encode_leds_size_mismatch() {
+    let mut leds = [Color::<EncodedLedGrbU8>::BLACK; 2];
+    encode_leds(&[Color::srgb_u8(0, 0, 0)], &LedLimits::default(), &mut leds);
+}
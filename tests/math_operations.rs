@@ -79,3 +79,110 @@ fn assignment_operations() {
     assert_relative_eq!(color.g, 0.5, epsilon = 0.001);
     assert_relative_eq!(color.b, 0.7, epsilon = 0.001);
 }
+
+#[test]
+fn splat_and_primaries() {
+    use colstodian::basic_encodings::*;
+
+    let gray = Color::<LinearSrgb>::splat(0.18);
+    assert_eq!(gray.repr, Vec3::splat(0.18));
+
+    let gray = Color::<LinearSrgba>::splat(0.18);
+    assert_eq!(gray.a, 1.0);
+
+    assert_eq!(
+        Color::<LinearSrgb>::RED + Color::<LinearSrgb>::GREEN,
+        Color::<LinearSrgb>::YELLOW
+    );
+    assert_eq!(
+        Color::<LinearSrgb>::CYAN + Color::<LinearSrgb>::RED,
+        Color::<LinearSrgb>::WHITE
+    );
+    assert_eq!(
+        Color::<LinearSrgb>::MAGENTA.convert::<SrgbU8>(),
+        Color::srgb_u8(255, 0, 255)
+    );
+}
+
+#[test]
+fn sum_and_average() {
+    let lights = [
+        Color::linear_srgb(0.25, 0.0, 0.0),
+        Color::linear_srgb(0.0, 0.5, 0.0),
+        Color::linear_srgb(0.5, 0.25, 1.0),
+    ];
+
+    let total: Color<basic_encodings::LinearSrgb> = lights.into_iter().sum();
+    assert_eq!(total, Color::linear_srgb(0.75, 0.75, 1.0));
+    assert_eq!(lights.iter().sum::<Color<_>>(), total);
+    assert_eq!(
+        std::iter::empty::<Color<basic_encodings::LinearSrgb>>().sum::<Color<_>>(),
+        Color::linear_srgb(0.0, 0.0, 0.0)
+    );
+
+    assert_eq!(Color::average(lights), Some(total / 3.0));
+    assert_eq!(
+        Color::average([
+            Color::linear_srgba(1.0, 0.0, 0.0, 1.0),
+            Color::linear_srgba(0.0, 0.0, 1.0, 0.0)
+        ]),
+        Some(Color::linear_srgba(0.5, 0.0, 0.5, 0.5))
+    );
+    assert_eq!(Color::<basic_encodings::LinearSrgb>::average([]), None);
+}
+
+#[test]
+fn weighted_mix() {
+    let corners = [
+        Color::linear_srgb(1.0, 0.0, 0.0),
+        Color::linear_srgb(0.0, 1.0, 0.0),
+        Color::linear_srgb(0.0, 0.0, 1.0),
+    ];
+    let mix = |weights: [f32; 3]| {
+        Color::weighted_mix(&[
+            (corners[0], weights[0]),
+            (corners[1], weights[1]),
+            (corners[2], weights[2]),
+        ])
+    };
+
+    assert_eq!(mix([1.0, 0.0, 0.0]), Some(corners[0]));
+    assert_eq!(
+        mix([2.0, 2.0, 4.0]),
+        Some(Color::linear_srgb(0.25, 0.25, 0.5))
+    );
+    assert_eq!(mix([1.0, -1.0, 0.0]), None);
+    assert_eq!(
+        Color::<basic_encodings::LinearSrgb>::weighted_mix(&[]),
+        None
+    );
+
+    let lerp = corners[0].lerp(corners[1], 0.25);
+    let weighted = Color::weighted_mix(&[(corners[0], 0.75), (corners[1], 0.25)]).unwrap();
+    assert_relative_eq!(weighted.r, lerp.r);
+    assert_relative_eq!(weighted.g, lerp.g);
+    assert_relative_eq!(weighted.b, lerp.b);
+}
+
+#[test]
+fn component_wise_operations() {
+    let a = Color::linear_srgb(0.5, -0.25, 2.0);
+    let b = Color::linear_srgb(0.25, 0.5, 1.0);
+
+    assert_eq!(a.min(b), Color::linear_srgb(0.25, -0.25, 1.0));
+    assert_eq!(a.max(b), Color::linear_srgb(0.5, 0.5, 2.0));
+    assert_eq!(a.abs(), Color::linear_srgb(0.5, 0.25, 2.0));
+    assert_eq!(
+        a.clamp(Color::linear_srgb(0.0, 0.0, 0.0), b),
+        Color::linear_srgb(0.25, 0.0, 1.0)
+    );
+    assert_eq!(b.powf(2.0), Color::linear_srgb(0.0625, 0.25, 1.0));
+
+    let rgba = Color::linear_srgba(0.25, 4.0, 1.0, 0.25);
+    assert_eq!(rgba.powf(0.5), Color::linear_srgba(0.5, 2.0, 1.0, 0.5));
+    let wide = Color::srgb_f64(0.5, -1.0, 0.0);
+    assert_eq!(
+        wide.abs().max(Color::srgb_f64(0.75, 0.0, 0.0)),
+        Color::srgb_f64(0.75, 1.0, 0.0)
+    );
+}
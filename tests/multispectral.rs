@@ -0,0 +1,84 @@
+//! Tests for multispectral encodings with more than three bands.
+
+use colstodian::details::encodings::{CieXyz, Multispectral};
+use colstodian::details::traits::SpectralBands;
+use colstodian::*;
+use glam::Vec3;
+
+/// Six bands from violet to deep red, like those of an agricultural camera.
+struct SixBand;
+
+impl SpectralBands<6> for SixBand {
+    const NAME: &'static str = "SixBand";
+    const TO_XYZ: [Vec3; 6] = [
+        Vec3::new(0.1623, 0.0163, 0.8003),
+        Vec3::new(0.0216, 0.1573, 0.2228),
+        Vec3::new(0.1297, 0.5116, 0.0055),
+        Vec3::new(0.4683, 0.2934, 0.0004),
+        Vec3::new(0.1644, 0.0612, 0.0),
+        Vec3::new(0.0042, 0.0015, 0.0),
+    ];
+}
+
+type SixBandF32 = Multispectral<SixBand, 6>;
+
+#[test]
+fn info_and_formatting() {
+    let info = SixBandF32::info();
+    assert_eq!(info.name, "SixBand");
+    assert_eq!((info.components, info.component_type), (6, "f32"));
+    assert!(!info.has_alpha);
+
+    let color = Color::<SixBandF32>::multispectral([0.0, 0.5, 1.0, 0.25, 0.0, 0.0]);
+    assert_eq!(color.bands[2], 1.0);
+    assert_eq!(color.iter().count(), 6);
+    assert_eq!(
+        format!("{color:?}"),
+        "Color<SixBand>(Bands: 0 0.5 1 0.25 0 0)"
+    );
+}
+
+#[test]
+fn bands_convert_through_xyz() {
+    let color = Color::<SixBandF32>::multispectral([0.2, 0.4, 0.6, 0.8, 0.6, 0.4]);
+    let xyz = color.convert::<CieXyz>();
+    let expected = SixBand::TO_XYZ
+        .iter()
+        .zip(color.repr)
+        .fold(Vec3::ZERO, |sum, (&response, band)| sum + response * band);
+    assert!(xyz.repr.abs_diff_eq(expected, 1e-6));
+
+    // Back to bands, the XYZ is kept.
+    let bands = xyz.convert::<SixBandF32>();
+    assert!(bands.convert::<CieXyz>().repr.abs_diff_eq(xyz.repr, 1e-5));
+
+    let srgb = Color::srgb_u8(40, 160, 220);
+    assert_eq!(
+        srgb.convert::<SixBandF32>()
+            .convert::<basic_encodings::SrgbU8>(),
+        srgb
+    );
+}
+
+/// The bands of linear sRGB, with green split into two equal bands.
+struct SplitGreen;
+
+impl SpectralBands<4> for SplitGreen {
+    const NAME: &'static str = "SplitGreen";
+    const TO_XYZ: [Vec3; 4] = [
+        Vec3::new(0.4124, 0.2126, 0.0193),
+        Vec3::new(0.1788, 0.3576, 0.0596),
+        Vec3::new(0.1788, 0.3576, 0.0596),
+        Vec3::new(0.1805, 0.0722, 0.9505),
+    ];
+}
+
+#[test]
+fn smallest_bands_by_default() {
+    // Of all the ways to split green, the smallest one splits it equally.
+    let color = Color::srgb(0.2, 0.6, 1.0).convert::<Multispectral<SplitGreen, 4>>();
+    [0.2, 0.6, 0.6, 1.0]
+        .iter()
+        .zip(color.repr)
+        .for_each(|(expected, band)| assert!((band - expected).abs() < 1e-3, "{:?}", color.repr));
+}
@@ -0,0 +1,21 @@
+//! Tests for the `nalgebra` vector conversions.
+#![cfg(feature = "nalgebra")]
+
+use colstodian::Color;
+use colstodian::details::encodings::{AcesCg, SrgbF64, Srgba};
+use nalgebra::{Vector3, Vector4};
+
+#[test]
+fn nalgebra_vectors() {
+    let color = Color::aces_cg(0.1, 0.2, 0.3);
+    let vector: Vector3<f32> = color.into();
+    assert_eq!(vector, Vector3::new(0.1, 0.2, 0.3));
+    assert_eq!(Color::<AcesCg>::from(vector), color);
+
+    let translucent: Vector4<f32> = Color::linear_srgba(0.5, 0.25, 1.0, 0.5).into();
+    assert_eq!(translucent, Vector4::new(0.5, 0.25, 1.0, 0.5));
+    assert_eq!(Color::<Srgba>::from(translucent).repr.w, 0.5);
+
+    let precise = Color::<SrgbF64>::from(Vector3::new(0.1f64, 0.2, 0.3));
+    assert_eq!(Vector3::<f64>::from(precise), Vector3::new(0.1, 0.2, 0.3));
+}
@@ -0,0 +1,65 @@
+//! Tests for named color lookup.
+
+use colstodian::Color;
+use colstodian::named;
+
+#[test]
+fn x11_lookup_ignores_case_and_spaces() {
+    let expected = Some(Color::srgb_u8(30, 144, 255));
+
+    assert_eq!(named::x11("dodgerblue"), expected);
+    assert_eq!(named::x11("DodgerBlue"), expected);
+    assert_eq!(named::x11("dodger blue"), expected);
+    assert_eq!(named::x11("DODGER BLUE1"), expected);
+    assert_eq!(named::x11("dodgerblue4"), Some(Color::srgb_u8(16, 78, 139)));
+}
+
+#[test]
+fn x11_lookup_misses() {
+    assert_eq!(named::x11(""), None);
+    assert_eq!(named::x11("dodgerblue5"), None);
+    assert_eq!(named::x11("dodger-blue"), None);
+}
+
+#[test]
+fn x11_table_is_sorted_and_complete() {
+    let names: Vec<_> = named::x11_colors().map(|(name, _)| name).collect();
+
+    assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(names.contains(&"gray50") && names.contains(&"grey50"));
+    names
+        .iter()
+        .for_each(|name| assert!(named::x11(name).is_some(), "{name}"));
+}
+
+#[test]
+fn css_differs_from_x11() {
+    assert_eq!(named::css("Green"), Some(Color::srgb_u8(0, 128, 0)));
+    assert_eq!(named::x11("Green"), Some(Color::srgb_u8(0, 255, 0)));
+    assert_eq!(
+        named::css("rebecca purple"),
+        Some(Color::srgb_u8(102, 51, 153))
+    );
+    assert_eq!(named::x11("rebeccapurple"), None);
+    assert_eq!(named::css_colors().len(), 148);
+}
+
+#[test]
+fn nearest_named_color() {
+    // Exact matches have no distance.
+    named::css_colors().for_each(|(name, color)| {
+        let (nearest, distance) = named::nearest_css(color);
+        assert_eq!(distance, 0.0);
+        assert_eq!(named::css(nearest), Some(color), "{name}");
+    });
+
+    assert_eq!(
+        named::nearest_x11(Color::srgb_u8(30, 144, 255)).0,
+        "dodgerblue"
+    );
+    assert_eq!(named::nearest_x11(Color::srgb_u8(190, 190, 190)).0, "gray");
+
+    let (name, distance) = named::nearest_css(Color::srgb_f32(0.98, 0.5, 0.45));
+    assert_eq!(name, "salmon");
+    assert!(distance > 0.0 && distance < 0.02);
+}
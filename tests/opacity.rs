@@ -0,0 +1,64 @@
+//! Tests for opacity and alpha operations.
+
+use colstodian::details::encodings::{EncodedSrgbaPremultipliedU8, SrgbaPremultiplied};
+use colstodian::*;
+use glam::Vec4;
+
+#[test]
+fn opacity_range() {
+    assert_eq!(Opacity::new(1.5), Opacity::OPAQUE);
+    assert_eq!(Opacity::new(-0.5), Opacity::TRANSPARENT);
+    assert_eq!(Opacity::new(f32::NAN), Opacity::TRANSPARENT);
+    assert_eq!(f32::from(Opacity::new(0.3)), 0.3);
+    assert!(Opacity::OPAQUE.is_opaque() && Opacity::TRANSPARENT.is_transparent());
+    assert_eq!(Opacity::new(0.25).complement(), Opacity::new(0.75));
+}
+
+#[test]
+fn composing_opacities() {
+    let layer = Opacity::new(0.5);
+    let mut nested = layer;
+    nested *= Opacity::new(0.5);
+    assert_eq!(nested, layer * layer);
+    assert_eq!(nested.get(), 0.25);
+
+    // Stacking matches the alpha of compositing.
+    let stacked = layer.over(Opacity::new(0.5));
+    assert_eq!(stacked, Opacity::new(0.75));
+    let over = Color::linear_srgba_premultiplied(0.5, 0.0, 0.0, 0.5);
+    let under = Color::linear_srgba_premultiplied(0.0, 0.0, 0.5, 0.5);
+    assert_eq!(over.alpha_over(under).opacity(), stacked);
+    assert_eq!(Opacity::OPAQUE.over(layer), Opacity::OPAQUE);
+}
+
+#[test]
+fn straight_and_premultiplied_alpha() {
+    assert!(!basic_encodings::SrgbaU8::info().is_premultiplied);
+    assert!(SrgbaPremultiplied::info().is_premultiplied);
+    assert!(EncodedSrgbaPremultipliedU8::info().is_premultiplied);
+
+    let straight = Color::srgba_u8(200, 100, 50, 255);
+    assert_eq!(
+        straight.faded(Opacity::new(0.5)),
+        Color::srgba_u8(200, 100, 50, 128)
+    );
+    assert_eq!(
+        straight.multiplied_alpha(0.5).multiplied_alpha(4.0),
+        straight
+    );
+    assert_eq!(
+        straight.with_opacity(Opacity::TRANSPARENT).opaque(),
+        straight
+    );
+
+    let premultiplied = Color::linear_srgba_premultiplied(0.4, 0.2, 0.0, 0.8);
+    let faded = premultiplied.faded(Opacity::new(0.5));
+    assert!(faded.repr.abs_diff_eq(Vec4::new(0.2, 0.1, 0.0, 0.4), 1e-6));
+    assert!(
+        faded
+            .opaque()
+            .repr
+            .abs_diff_eq(Vec4::new(0.5, 0.25, 0.0, 1.0), 1e-6)
+    );
+    assert_eq!(faded.opacity().get(), 0.4);
+}
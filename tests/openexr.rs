@@ -0,0 +1,65 @@
+//! Tests for the OpenEXR chromaticities interop.
+
+use colstodian::custom::CustomColorSpace;
+use colstodian::details::linear_spaces::{Aces2065, AcesCg, Bt2020, Srgb};
+use colstodian::openexr::Chromaticities;
+
+fn assert_xy_eq(a: [f32; 2], b: [f32; 2]) {
+    assert!(
+        (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4,
+        "{a:?} != {b:?}"
+    );
+}
+
+#[test]
+fn linear_spaces_have_known_chromaticities() {
+    let default = Chromaticities::default();
+    let srgb = Chromaticities::of::<Srgb>();
+    assert_xy_eq(srgb.red, default.red);
+    assert_xy_eq(srgb.green, default.green);
+    assert_xy_eq(srgb.blue, default.blue);
+    assert_xy_eq(srgb.white, default.white);
+
+    let bt2020 = Chromaticities::of::<Bt2020>();
+    assert_xy_eq(bt2020.red, [0.708, 0.292]);
+    assert_xy_eq(bt2020.green, [0.170, 0.797]);
+
+    let ap0 = Chromaticities::of::<Aces2065>();
+    assert_xy_eq(ap0.red, [0.7347, 0.2653]);
+    assert_xy_eq(ap0.white, [0.32168, 0.33767]);
+}
+
+#[test]
+fn chromaticities_identify_spaces() {
+    let rounded = Chromaticities {
+        red: [0.713, 0.293],
+        green: [0.165, 0.830],
+        blue: [0.128, 0.044],
+        white: [0.32168, 0.33767],
+    };
+
+    assert!(rounded.is::<AcesCg>());
+    assert!(!rounded.is::<Aces2065>());
+    assert!(Chromaticities::default().is::<Srgb>());
+}
+
+#[test]
+fn custom_space_round_trip() {
+    let space = CustomColorSpace::from_primaries_d50([0.68, 0.31], [0.22, 0.70], [0.14, 0.07]);
+    let chromaticities = Chromaticities::from(space);
+
+    assert_xy_eq(chromaticities.green, [0.22, 0.70]);
+    let round_trip = Chromaticities::from(CustomColorSpace::from(chromaticities));
+    assert_xy_eq(round_trip.red, chromaticities.red);
+    assert_xy_eq(round_trip.white, chromaticities.white);
+}
+
+#[cfg(feature = "exr")]
+#[test]
+fn exr_attribute_round_trip() {
+    let chromaticities = Chromaticities::of::<AcesCg>();
+    let attribute = exr::meta::attribute::Chromaticities::from(chromaticities);
+
+    assert_eq!(attribute.white.0, chromaticities.white[0]);
+    assert_eq!(Chromaticities::from(attribute), chromaticities);
+}
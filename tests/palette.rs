@@ -0,0 +1,153 @@
+//! Tests for palette generation and fixed palettes.
+
+use colstodian::Color;
+use colstodian::details::encodings::{Oklab, Oklch};
+use colstodian::palette::{CategoricalOptions, Palette, RampOptions, categorical, tonal_ramp};
+
+fn min_pairwise_distance(colors: &[Color<Oklab>]) -> f32 {
+    colors
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| colors[i + 1..].iter().map(move |b| a.distance(*b)))
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[test]
+fn categorical_colors_are_distinct() {
+    let colors = categorical::<12>(&CategoricalOptions::default())
+        .unwrap()
+        .map(|c| c.convert::<Oklab>());
+
+    assert!(min_pairwise_distance(&colors) > 0.1);
+    colors
+        .iter()
+        .for_each(|c| assert!((0.45..=0.85).contains(&c.repr.x), "{c:?}"));
+}
+
+#[test]
+fn categorical_is_deterministic_and_prefix_stable() {
+    let options = CategoricalOptions::default();
+    let six = categorical::<6>(&options).unwrap();
+    let eight = categorical::<8>(&options).unwrap();
+
+    assert_eq!(Some(six), categorical::<6>(&options));
+    assert_eq!(six[..], eight[..6]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn categorical_vec_matches_arrays() {
+    use colstodian::palette::categorical_vec;
+
+    let options = CategoricalOptions::default();
+    assert_eq!(
+        categorical_vec(8, &options),
+        categorical::<8>(&options).map(Vec::from)
+    );
+    assert_eq!(categorical_vec(0, &options), Some(vec![]));
+}
+
+#[test]
+fn categorical_respects_lightness_band() {
+    let options = CategoricalOptions {
+        lightness: 0.3..=0.4,
+        cvd_weight: 1.0,
+    };
+
+    categorical::<5>(&options)
+        .unwrap()
+        .map(|c| c.convert::<Oklab>())
+        .iter()
+        .for_each(|c| assert!((0.3..=0.4).contains(&c.repr.x), "{c:?}"));
+}
+
+#[test]
+fn categorical_rejects_empty_band() {
+    let options = CategoricalOptions {
+        lightness: 1.5..=2.0,
+        ..Default::default()
+    };
+
+    assert_eq!(categorical::<2>(&options), None);
+    assert_eq!(categorical::<0>(&options), None);
+    #[cfg(feature = "std")]
+    assert_eq!(colstodian::palette::categorical_vec(2, &options), None);
+}
+
+#[test]
+fn tonal_ramp_is_even_and_keeps_hue() {
+    let base = Color::srgb_u8(37, 99, 235);
+    let base_lch = base.convert::<Oklch>();
+    let ramp = tonal_ramp::<_, 10>(base, &RampOptions::default()).map(|c| c.convert::<Oklch>());
+
+    assert!((ramp[0].l - 0.97).abs() < 0.01);
+    assert!((ramp[9].l - 0.25).abs() < 0.01);
+    ramp.windows(2).for_each(|pair| {
+        assert!((pair[0].l - pair[1].l - 0.08).abs() < 0.01, "{pair:?}");
+    });
+    ramp.iter().filter(|c| c.c > 0.03).for_each(|c| {
+        assert!((c.h - base_lch.h).abs() < 3.0, "{c:?}");
+        assert!(c.c < base_lch.c + 0.01, "{c:?}");
+    });
+}
+
+#[test]
+fn retro_palettes() {
+    assert_eq!(Palette::CGA.len(), 16);
+    assert_eq!(Palette::EGA.len(), 64);
+    assert_eq!(Palette::VGA.len(), 256);
+    assert_eq!(Palette::GAME_BOY.len(), 4);
+    assert_eq!(Palette::PICO_8.len(), 16);
+
+    // EGA index bits are `rgbRGB`, the CGA brown is EGA color 20.
+    assert_eq!(Palette::EGA.colors()[0o07], Color::srgb_u8(170, 170, 170));
+    assert_eq!(Palette::EGA.colors()[0o24], Color::srgb_u8(170, 85, 0));
+    assert_eq!(Palette::EGA.colors()[0o77], Color::srgb_u8(255, 255, 255));
+    for color in Palette::CGA.colors() {
+        assert!(Palette::EGA.colors().contains(color));
+    }
+
+    let vga = Palette::VGA.colors();
+    assert_eq!(vga[..16], *Palette::CGA.colors());
+    assert_eq!(vga[16], Color::srgb_u8(0, 0, 0));
+    assert_eq!(vga[31], Color::srgb_u8(255, 255, 255));
+    assert_eq!(vga[32], Color::srgb_u8(0, 0, 255));
+    assert_eq!(vga[40], Color::srgb_u8(255, 0, 0));
+    assert_eq!(vga[48], Color::srgb_u8(0, 255, 0));
+    assert_eq!(vga[104], Color::srgb_u8(0, 0, 113));
+    assert_eq!(vga[247], Color::srgb_u8(44, 48, 65));
+    assert_eq!(vga[255], Color::srgb_u8(0, 0, 0));
+}
+
+#[test]
+fn nearest_palette_color() {
+    // Every entry maps to itself.
+    for palette in [Palette::CGA, Palette::GAME_BOY, Palette::PICO_8] {
+        for (index, color) in palette.colors().iter().enumerate() {
+            assert_eq!(palette.nearest_index(*color), index);
+        }
+    }
+
+    assert_eq!(
+        Palette::GAME_BOY.nearest(Color::srgb_u8(0, 0, 0)),
+        Palette::GAME_BOY.colors()[0]
+    );
+    assert_eq!(
+        Palette::GAME_BOY.nearest_index(Color::srgb_u8(255, 255, 255)),
+        3
+    );
+    assert_eq!(
+        Palette::CGA.nearest(Color::linear_srgb(0.9, 0.0, 0.0)),
+        Color::srgb_u8(255, 85, 85)
+    );
+    assert_eq!(
+        Palette::VGA.nearest(Color::srgb_u8(250, 5, 3)),
+        Color::srgb_u8(255, 0, 0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "palette is not empty")]
+fn nearest_in_empty_palette() {
+    Palette::new(&[]).nearest(Color::srgb_u8(0, 0, 0));
+}
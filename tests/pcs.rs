@@ -0,0 +1,60 @@
+//! Tests for profile connection spaces.
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::calibration::ColorMatrix;
+use colstodian::custom::{CustomColorSpace, DynamicColor};
+use colstodian::details::encodings::{CieXyz, Oklab};
+use colstodian::details::linear_spaces::{Bt2020, Srgb};
+use colstodian::pcs::ConnectionSpace;
+use glam::{Mat3, Vec3};
+
+#[test]
+fn encode_and_decode() {
+    let xyz = Vec3::new(0.3, 0.4, 0.2);
+    let lms = ConnectionSpace::linear(ColorMatrix::bradford(Vec3::ONE, Vec3::ONE)).unwrap();
+    [ConnectionSpace::Xyz, ConnectionSpace::Oklab, lms]
+        .into_iter()
+        .for_each(|pcs| {
+            assert!(
+                pcs.decode(pcs.encode(xyz)).abs_diff_eq(xyz, 1e-5),
+                "{pcs:?}"
+            );
+        });
+
+    assert_eq!(ConnectionSpace::default(), ConnectionSpace::Xyz);
+    assert_eq!(ConnectionSpace::Xyz.encode(xyz), xyz);
+    let white = Color::srgb(1.0, 1.0, 1.0).convert::<Oklab>().repr;
+    assert!(
+        ConnectionSpace::Oklab
+            .encode(Color::srgb(1.0, 1.0, 1.0).convert::<CieXyz>().repr)
+            .abs_diff_eq(white, 1e-5)
+    );
+
+    assert!(ConnectionSpace::Oklab.matrices().is_none());
+    assert!(ConnectionSpace::linear(ColorMatrix::new(Mat3::ZERO)).is_none());
+}
+
+#[test]
+fn dynamic_colors_through_a_pcs() {
+    let color = DynamicColor::new(0.2, 0.9, 0.1, CustomColorSpace::of::<Bt2020>());
+    let srgb = CustomColorSpace::of::<Srgb>();
+
+    // Without a step, every PCS gives the plain conversion.
+    let plain: Color<SrgbU8> = color.to_color();
+    [ConnectionSpace::Xyz, ConnectionSpace::Oklab]
+        .into_iter()
+        .for_each(|pcs| {
+            assert_eq!(color.to_color_via::<SrgbU8>(pcs, |value| value), plain);
+            let converted = color.convert_via(&srgb, pcs, |value| value);
+            assert_eq!(converted.space, srgb);
+        });
+
+    // Halving Oklab chroma keeps lightness, halving XYZ doesn't.
+    let lightness = color.to_color::<Oklab>().repr.x;
+    let halved: Color<Oklab> =
+        color.to_color_via(ConnectionSpace::Oklab, |lab| lab * Vec3::new(1.0, 0.5, 0.5));
+    assert!((halved.repr.x - lightness).abs() < 1e-5);
+    let darker: Color<Oklab> = color.to_color_via(ConnectionSpace::Xyz, |xyz| xyz * 0.5);
+    assert!(darker.repr.x < lightness - 0.1);
+}
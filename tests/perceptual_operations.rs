@@ -57,3 +57,136 @@ fn oklab_blend_interpolation() {
     assert!(three_quarter.l >= half.l);
     assert!(end_oklab.l >= three_quarter.l);
 }
+
+#[test]
+fn lightness_chroma_hue() {
+    let color = Color::srgb_u8(40, 110, 200).convert::<Oklab>();
+    let lch = Color::srgb_u8(40, 110, 200).convert::<Oklch>();
+    assert!((color.lightness() - lch.l).abs() < 1e-6);
+    assert!((color.chroma() - lch.c).abs() < 1e-6);
+    assert!((color.hue() - lch.h).abs() < 1e-3);
+
+    let lighter = color.with_lightness(0.9);
+    assert_eq!(lighter.lightness(), 0.9);
+    assert!((lighter.hue() - color.hue()).abs() < 1e-3);
+
+    let muted = color.with_chroma(0.05);
+    assert!((muted.chroma() - 0.05).abs() < 1e-6);
+    assert!((muted.hue() - color.hue()).abs() < 1e-3);
+
+    let rotated = color.with_hue(color.hue() + 30.0);
+    assert!((rotated.chroma() - color.chroma()).abs() < 1e-6);
+    assert_eq!(
+        rotated.convert::<SrgbU8>(),
+        Color::srgb_u8(40, 110, 200).rotate_hue(30.0)
+    );
+
+    assert_eq!(Color::<Oklab>::gray(0.5).hue(), 0.0);
+}
+
+/// Generic code can be written against `PerceptualEncoding`.
+fn darken<E: colstodian::PerceptualEncoding>(color: Color<E>) -> Color<E> {
+    color.with_lightness(color.lightness() * 0.5)
+}
+
+#[test]
+fn generic_perceptual_code() {
+    assert_eq!(darken(Color::oklab(0.8, 0.1, 0.0)).lightness(), 0.4);
+    assert_eq!(darken(Color::oklab_f64(0.8, 0.1, 0.0)).lightness(), 0.4);
+}
+
+#[test]
+fn mix_in() {
+    let red = Color::srgb_u8(255, 0, 0);
+    let blue = Color::srgb_u8(0, 0, 255);
+    assert_eq!(red.mix_in::<Oklab>(blue, 0.0), red);
+    assert_eq!(red.mix_in::<Oklab>(blue, 1.0), blue);
+    assert_eq!(
+        red.mix_in::<LinearSrgb>(blue, 0.5),
+        Color::srgb_u8(188, 0, 188)
+    );
+
+    // A transparent color does not tint the mix, only its alpha is mixed.
+    let opaque = Color::srgba_u8(255, 0, 0, 255);
+    let transparent = Color::srgba_u8(0, 0, 255, 0);
+    assert_eq!(
+        opaque.mix_in::<Oklab>(transparent, 0.5),
+        Color::srgba_u8(255, 0, 0, 128)
+    );
+}
+
+#[test]
+fn hue_interpolation() {
+    use colstodian::HueInterpolation::*;
+
+    assert_eq!(Shorter.lerp(350.0, 10.0, 0.5), 0.0);
+    assert_eq!(Shorter.lerp(10.0, 350.0, 0.25), 5.0);
+    assert_eq!(Longer.lerp(350.0, 10.0, 0.5), 180.0);
+    assert_eq!(Increasing.lerp(350.0, 10.0, 0.5), 0.0);
+    assert_eq!(Increasing.lerp(10.0, 350.0, 0.5), 180.0);
+    assert_eq!(Decreasing.lerp(350.0, 10.0, 0.5), 180.0);
+    assert_eq!(Decreasing.lerp(10.0, 350.0, 0.5), 0.0);
+
+    let from = Color::oklch(0.5, 0.1, 350.0);
+    let to = Color::oklch(0.7, 0.1, 10.0);
+    let mid = from.lerp_hue(to, 0.5, Shorter);
+    assert!((mid.l - 0.6).abs() < 1e-6);
+    assert_eq!(mid.h, 0.0);
+}
+
+#[test]
+fn mix_in_polar() {
+    use colstodian::HueInterpolation;
+
+    let from = Color::oklch(0.7, 0.1, 340.0).convert::<SrgbU8>();
+    let to = Color::oklch(0.7, 0.1, 40.0).convert::<SrgbU8>();
+    let hue = |mode| {
+        from.mix_in_polar::<Oklch>(to, 0.5, mode)
+            .convert::<Oklch>()
+            .h
+    };
+    assert!((hue(HueInterpolation::Shorter) - 10.0).abs() < 1.0);
+    assert!((hue(HueInterpolation::Longer) - 190.0).abs() < 1.0);
+}
+
+#[test]
+fn visually_eq() {
+    let teal = Color::srgb_u8(0, 128, 128);
+
+    assert!(teal.delta_e_ok(teal.convert::<Oklch>()) < 1e-5);
+    assert!(teal.delta_e_ok(teal.convert::<LinearSrgb>()) < 1e-5);
+    assert!(teal.visually_eq(teal.convert::<EncodedSrgbU16>(), 0.001));
+    assert!(teal.visually_eq(Color::srgb_u8(0, 129, 128), 0.02));
+    assert!(!teal.visually_eq(Color::srgb_u8(0, 140, 128), 0.02));
+
+    let oklab = teal.convert::<Oklab>();
+    assert!(
+        (teal.delta_e_ok(Color::srgb_u8(40, 100, 200))
+            - oklab.distance(Color::srgb_u8(40, 100, 200).convert()))
+        .abs()
+            < 1e-6
+    );
+}
+
+#[test]
+fn assert_color_eq_tolerance() {
+    let purple = Color::srgb_u8(107, 70, 193);
+
+    colstodian::assert_color_eq!(purple, purple.convert::<LinearSrgb>());
+    colstodian::assert_color_eq!(purple, Color::srgb_u8(127, 70, 193), de = 5.0);
+    colstodian::assert_color_eq!(purple, Color::srgb_u8(110, 70, 190), de = 1.0);
+    assert_eq!(purple.to_hex().to_string(), "#6b46c1");
+}
+
+#[test]
+#[should_panic(expected = "ΔE")]
+fn assert_color_eq_cie_scale() {
+    let purple = Color::srgb_u8(107, 70, 193);
+    colstodian::assert_color_eq!(purple, Color::srgb_u8(117, 70, 193), de = 1.0);
+}
+
+#[test]
+#[should_panic(expected = "left: #6b46c1")]
+fn assert_color_eq_reports_hex() {
+    colstodian::assert_color_eq!(Color::srgb_u8(107, 70, 193), Color::srgb_u8(127, 70, 193));
+}
@@ -0,0 +1,59 @@
+//! Tests for color picker slices.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{SrgbU8, SrgbaU8};
+use colstodian::details::encodings::Oklab;
+use colstodian::picker::{self, Plane};
+use glam::Vec2;
+
+#[test]
+fn hsv_square() {
+    let plane = Plane::HsvSaturationValue { h: 120.0 };
+
+    let corner = |x, y| plane.color_at(x, y).convert::<SrgbU8>();
+    assert_eq!(corner(1.0, 0.0), Color::srgb_u8(0, 255, 0));
+    assert_eq!(corner(0.0, 0.0), Color::srgb_u8(255, 255, 255));
+    assert_eq!(corner(0.5, 1.0), Color::srgb_u8(0, 0, 0));
+
+    let position = plane.position(plane.color_at(0.25, 0.75));
+    assert!((position - Vec2::new(0.25, 0.75)).length() < 1e-3);
+
+    // Every pixel of the square is in the sRGB gamut.
+    let outside = Color::srgba_u8(0, 0, 0, 0);
+    let pixels = picker::render::<SrgbaU8>(plane, 8, 8, outside);
+    assert!(pixels.pixels().iter().all(|&pixel| pixel != outside));
+}
+
+#[test]
+fn oklch_slices() {
+    let planes = [
+        Plane::OklabAb {
+            l: 0.6,
+            extent: 0.3,
+        },
+        Plane::OklchChromaLightness {
+            h: 30.0,
+            max_chroma: 0.3,
+        },
+        Plane::OklchHueChroma {
+            l: 0.6,
+            max_chroma: 0.3,
+        },
+    ];
+    for plane in planes {
+        let position = plane.position(plane.color_at(0.3, 0.6));
+        assert!((position - Vec2::new(0.3, 0.6)).length() < 1e-3);
+    }
+
+    // Gray along the left edge fits, the saturated top right corner doesn't.
+    let plane = planes[1];
+    let outside = Color::srgba_u8(0, 0, 0, 0);
+    let pixels = picker::render::<SrgbaU8>(plane, 16, 16, outside);
+    assert_ne!(pixels[(0, 8)], outside);
+    assert_eq!(pixels[(15, 0)], outside);
+
+    let gray = pixels[(0, 8)].convert::<Oklab>();
+    assert!(gray.a.abs() < 0.01 && gray.b.abs() < 0.01);
+}
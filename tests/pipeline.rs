@@ -0,0 +1,174 @@
+//! Tests for conversion pipelines.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::buffer::ColorBuffer;
+use colstodian::calibration::ColorMatrix;
+use colstodian::details::encodings::{Bt2020, DisplayP3, Oklab};
+use colstodian::details::linear_spaces;
+use colstodian::details::traits::LinearConvertFromRaw;
+use colstodian::pcs::ConnectionSpace;
+use colstodian::pipeline::Pipeline;
+use glam::{Mat3, Vec3};
+
+#[test]
+fn plain_conversion_matches_convert() {
+    let pipeline = Pipeline::<SrgbU8, LinearSrgb, linear_spaces::Srgb>::new();
+    assert_eq!(pipeline.step_count(), 0);
+    let color = Color::srgb_u8(200, 40, 90);
+    assert!(
+        pipeline
+            .convert(color)
+            .repr
+            .abs_diff_eq(color.convert::<LinearSrgb>().repr, 1e-6)
+    );
+
+    let wide = Pipeline::<Bt2020, DisplayP3, linear_spaces::Srgb>::new();
+    assert_eq!(wide.step_count(), 1);
+    let color = Color::bt2020(0.2, 0.5, 0.3);
+    let mut expected = color.repr;
+    <linear_spaces::DisplayP3 as LinearConvertFromRaw<linear_spaces::Bt2020>>::linear_part_raw(
+        &mut expected,
+    );
+    assert!(wide.convert(color).repr.abs_diff_eq(expected, 1e-5));
+}
+
+#[test]
+fn matrices_fuse() {
+    let tint = ColorMatrix::new(Mat3::from_diagonal(Vec3::new(1.0, 0.9, 0.8)));
+    let lift = ColorMatrix::with_offset(Mat3::IDENTITY, Vec3::splat(0.01));
+    let fused = Pipeline::<LinearSrgb, LinearSrgb, linear_spaces::Srgb>::new()
+        .exposure(-1.0)
+        .matrix(tint)
+        .matrix(lift);
+    assert_eq!(fused.step_count(), 1);
+
+    let color = Color::linear_srgb(0.4, 0.6, 0.8);
+    let expected = lift.transform(tint.transform(color * 0.5));
+    assert!(fused.convert(color).repr.abs_diff_eq(expected.repr, 1e-6));
+
+    let graded = fused
+        .clone()
+        .map(|rgb| rgb * rgb)
+        .exposure(1.0)
+        .map(|rgb| rgb.min(Vec3::ONE));
+    assert_eq!(graded.step_count(), 4);
+    let expected = (expected.repr * expected.repr * 2.0).min(Vec3::ONE);
+    assert!(graded.convert(color).repr.abs_diff_eq(expected, 1e-6));
+
+    let buffer = ColorBuffer::filled(2, 2, color);
+    let converted = graded.convert_buffer(&buffer);
+    assert!(
+        converted
+            .pixels()
+            .iter()
+            .all(|pixel| pixel.repr.abs_diff_eq(expected, 1e-6))
+    );
+
+    let mut out = [Color::linear_srgb(0.0, 0.0, 0.0); 2];
+    graded.convert_slice(&[color; 2], &mut out);
+    assert!(
+        out.iter()
+            .all(|pixel| pixel.repr.abs_diff_eq(expected, 1e-6))
+    );
+}
+
+#[test]
+fn convert_rows() {
+    let pipeline = Pipeline::<SrgbU8, LinearSrgb, linear_spaces::Srgb>::new().exposure(-1.0);
+    let buffer = ColorBuffer::from_fn(3, 4, |x, y| Color::srgb_u8(x as u8 * 80, y as u8 * 60, 255));
+    let expected = pipeline.convert_buffer(&buffer);
+
+    let mut rows = Vec::new();
+    pipeline.convert_rows(buffer.rows(), |row| rows.push(row.to_vec()));
+    assert_eq!(rows.len(), 4);
+    rows.iter()
+        .enumerate()
+        .for_each(|(y, row)| assert_eq!(row.as_slice(), expected.row(y)));
+
+    // Rows can be owned, e.g. decoded on the fly.
+    let mut count = 0;
+    let decoded = (0..2).map(|_| vec![Color::srgb_u8(255, 255, 255); 5]);
+    pipeline.convert_rows(decoded, |row| {
+        assert!(
+            row.iter()
+                .all(|pixel| pixel.repr.abs_diff_eq(Vec3::splat(0.5), 1e-6))
+        );
+        count += row.len();
+    });
+    assert_eq!(count, 10);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_conversion() {
+    let pipeline = Pipeline::<SrgbU8, LinearSrgb, linear_spaces::Srgb>::new().exposure(-1.0);
+    let buffer = ColorBuffer::from_fn(64, 48, |x, y| Color::srgb_u8(x as u8, y as u8, 7));
+    let expected = pipeline.convert_buffer(&buffer);
+    assert_eq!(pipeline.par_convert_buffer(&buffer), expected);
+
+    let mut out = vec![Color::linear_srgb(0.0, 0.0, 0.0); buffer.pixels().len()];
+    pipeline.par_convert_slice(buffer.pixels(), &mut out);
+    assert_eq!(out, expected.pixels());
+
+    let mut rows = Vec::new();
+    pipeline.par_convert_rows(buffer.rows(), |row| rows.extend_from_slice(row));
+    assert_eq!(rows, expected.pixels());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn convert_stream() {
+    let pipeline = Pipeline::<SrgbU8, LinearSrgb, linear_spaces::Srgb>::new();
+    let buffer = ColorBuffer::from_fn(7, 5, |x, y| Color::srgb_u8(x as u8 * 30, y as u8 * 50, 9));
+    let expected = pipeline.convert_buffer(&buffer);
+
+    // Chunks smaller than, equal to and larger than the image, and chunks
+    // that divide it exactly.
+    for chunk_pixels in [0, 1, 4, 7, 35, 100] {
+        let mut output = Vec::new();
+        let pixels = pipeline
+            .convert_stream(buffer.as_bytes(), &mut output, chunk_pixels)
+            .unwrap();
+        assert_eq!(pixels, 35);
+        assert_eq!(output, expected.as_bytes());
+    }
+
+    let mut output = Vec::new();
+    assert_eq!(
+        pipeline.convert_stream(&[][..], &mut output, 16).unwrap(),
+        0
+    );
+    assert!(output.is_empty());
+
+    // A truncated stream converts the whole pixels and fails.
+    let truncated = &buffer.as_bytes()[..3 * 6 + 2];
+    let error = pipeline
+        .convert_stream(truncated, &mut output, 4)
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(output, &expected.as_bytes()[..12 * 6]);
+}
+
+#[test]
+fn steps_in_a_connection_space() {
+    let color = Color::linear_srgb(0.1, 0.8, 0.3);
+    let plain = Pipeline::<LinearSrgb, SrgbU8, linear_spaces::Bt2020>::new();
+
+    // In linear connection spaces, the conversions fuse around the step.
+    let xyz = plain.clone().map_in(ConnectionSpace::Xyz, |xyz| xyz);
+    assert_eq!(xyz.step_count(), 3);
+    assert_eq!(xyz.convert(color), plain.convert(color));
+
+    let oklab = plain
+        .clone()
+        .map_in(ConnectionSpace::Oklab, |lab| lab * Vec3::new(1.0, 0.5, 0.5));
+    assert_eq!(oklab.step_count(), 3);
+    let expected: Color<SrgbU8> = color
+        .convert::<Oklab>()
+        .with_chroma(color.convert::<Oklab>().chroma() * 0.5)
+        .convert();
+    assert_eq!(oklab.convert(color), expected);
+}
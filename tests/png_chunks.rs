@@ -0,0 +1,95 @@
+//! Tests for the PNG color chunk helpers.
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::cicp::{Cicp, primaries};
+use colstodian::details::encodings::Bt2020;
+use colstodian::png::PngColorInfo;
+
+#[test]
+fn written_chunks_read_back() {
+    let written = PngColorInfo::for_encoding::<Bt2020>();
+
+    let mut read = PngColorInfo::default();
+    assert!(read.read_chunk(*b"cICP", &written.cicp_chunk().unwrap()));
+    assert!(read.read_chunk(*b"gAMA", &written.gama_chunk().unwrap()));
+    assert!(read.read_chunk(*b"cHRM", &written.chrm_chunk().unwrap()));
+    #[cfg(feature = "std")]
+    assert!(read.read_chunk(*b"iCCP", &written.iccp_chunk().unwrap()));
+
+    assert_eq!(read, written);
+    assert!(read.is::<Bt2020>());
+    assert_eq!(written.srgb_chunk(), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn icc_profile_fallback() {
+    assert_eq!(PngColorInfo::for_encoding::<SrgbU8>().iccp_chunk(), None);
+
+    // A file with only the generated profile, and an `sRGB` chunk it overrides.
+    let mut read = PngColorInfo::default();
+    read.read_chunk(*b"sRGB", &[0]);
+    let written = PngColorInfo::for_encoding::<Bt2020>();
+    assert!(read.read_chunk(*b"iCCP", &written.iccp_chunk().unwrap()));
+    assert_eq!(read.effective_cicp(), Some(Cicp::LINEAR_BT2020));
+    assert_eq!(
+        Cicp::color_primaries_of(&read.to_custom_color_space().unwrap()),
+        Some(primaries::BT2020)
+    );
+
+    // A profile that isn't in matrix/TRC form describes no CICP triplet.
+    let lut = PngColorInfo {
+        icc_profile: Some(vec![0; 132]),
+        ..read
+    };
+    assert_eq!(lut.effective_cicp(), None);
+}
+
+#[test]
+fn srgb_fallback_chunks() {
+    let info = PngColorInfo::for_encoding::<SrgbU8>();
+
+    assert_eq!(info.gama_chunk(), Some(45455u32.to_be_bytes()));
+    assert_eq!(
+        &info.chrm_chunk().unwrap()[..8],
+        &[0, 0, 122, 38, 0, 0, 128, 132]
+    );
+
+    // A legacy file with only gAMA and cHRM.
+    let legacy = PngColorInfo {
+        cicp: None,
+        srgb_intent: None,
+        ..info
+    };
+    assert!(legacy.is::<SrgbU8>());
+}
+
+#[test]
+fn chunk_precedence() {
+    let mut info = PngColorInfo::default();
+    assert_eq!(info.effective_cicp(), None);
+
+    info.read_chunk(*b"gAMA", &100_000u32.to_be_bytes());
+    assert!(info.is::<LinearSrgb>());
+
+    info.read_chunk(*b"sRGB", &[0]);
+    assert!(info.is::<SrgbU8>());
+
+    info.read_chunk(*b"cICP", &[primaries::BT2020, 16, 0, 1]);
+    assert_eq!(info.effective_cicp(), Some(Cicp::BT2100_PQ));
+    assert_eq!(
+        Cicp::color_primaries_of(&info.to_custom_color_space().unwrap()),
+        Some(primaries::BT2020)
+    );
+}
+
+#[test]
+fn malformed_chunks_are_rejected() {
+    let mut info = PngColorInfo::default();
+
+    assert!(!info.read_chunk(*b"gAMA", &[0, 1]));
+    assert!(!info.read_chunk(*b"cHRM", &[0; 31]));
+    assert!(!info.read_chunk(*b"IDAT", &[]));
+    assert!(!info.read_chunk(*b"iCCP", b"name\0\0not zlib"));
+    assert_eq!(info, PngColorInfo::default());
+}
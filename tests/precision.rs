@@ -0,0 +1,42 @@
+//! Tests for measuring the precision of conversion chains.
+
+#![cfg(feature = "std")]
+
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::details::encodings::{EncodedSrgbF32, EncodedSrgbU16, Oklab, Srgb};
+use colstodian::distance::DeltaE;
+use colstodian::precision;
+
+#[test]
+fn lossless_chains() {
+    let identity = precision::measure::<Srgb>(5, DeltaE::Cie2000, |color| color).unwrap();
+    assert_eq!(identity.samples, 125);
+    assert_eq!((identity.max_delta_e, identity.mean_delta_e), (0.0, 0.0));
+    assert_eq!((identity.max_error, identity.effective_bits), (0.0, 24.0));
+
+    let f32 = precision::round_trip::<LinearSrgb, EncodedSrgbF32>(9, DeltaE::Ok).unwrap();
+    assert!(f32.max_delta_e < 1e-4);
+    assert!(f32.effective_bits > 16.0);
+
+    assert!(precision::round_trip::<Srgb, Oklab>(0, DeltaE::Ok).is_none());
+}
+
+#[test]
+fn quantized_intermediates() {
+    let u8 = precision::round_trip::<LinearSrgb, SrgbU8>(17, DeltaE::Cie76).unwrap();
+    let u16 = precision::round_trip::<LinearSrgb, EncodedSrgbU16>(17, DeltaE::Cie76).unwrap();
+    assert!(u8.effective_bits > 7.0 && u8.effective_bits < u16.effective_bits - 7.0);
+    assert!(u8.max_delta_e > u8.mean_delta_e && u8.mean_delta_e > 0.0);
+    assert!(u16.max_delta_e < u8.max_delta_e);
+
+    // The largest linear error of 8-bit sRGB is at most half a step at white.
+    assert!(
+        u8.max_error < 0.0045 && u8.max_error > 0.001,
+        "{}",
+        u8.max_error
+    );
+
+    // Colors already in the source encoding survive unchanged.
+    let same = precision::round_trip::<SrgbU8, LinearSrgb>(17, DeltaE::Cie2000).unwrap();
+    assert_eq!(same.max_delta_e, 0.0);
+}
@@ -0,0 +1,129 @@
+//! Tests for soft proofing with output profiles.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::details::encodings::{CieXyz, EncodedSrgbU8, EncodedSrgbaU8};
+use colstodian::icc::{IccProfile, RenderingIntent};
+use colstodian::proof::SoftProof;
+use glam::Vec3;
+
+mod common;
+
+use common::icc;
+
+const D50: Vec3 = Vec3::new(0.964_22, 1.0, 0.825_21);
+
+/// A gray press profile with the paper white `media_white`, where no ink
+/// prints `L* = 100` and full ink `L* = 20`, relative to the paper.
+fn press_profile(media_white: Option<Vec3>) -> Vec<u8> {
+    // Legacy 16-bit CIELAB encoding.
+    let legacy = 65280.0 / 65535.0;
+    let neutral = 128.0 / 255.0 * legacy;
+    let a_to_b = [legacy, neutral, neutral, 0.2 * legacy, neutral, neutral];
+    let b_to_a: Vec<f32> = (0..125)
+        .map(|index| {
+            let lightness = (index / 25) as f32 / 4.0 / legacy * 100.0;
+            ((100.0 - lightness) / 80.0).clamp(0.0, 1.0)
+        })
+        .collect();
+
+    let mut tags = vec![
+        (b"A2B0", icc::mft2(1, 3, 2, &a_to_b)),
+        (b"B2A0", icc::mft2(3, 1, 5, &b_to_a)),
+    ];
+    if let Some(white) = media_white {
+        tags.push((b"wtpt", icc::xyz(white)));
+    }
+    icc::profile(b"GRAY", b"Lab ", &tags)
+}
+
+fn proof(paper_white: bool) -> SoftProof {
+    let profile = IccProfile::parse(&press_profile(Some(0.9 * D50))).unwrap();
+    SoftProof::new(profile, RenderingIntent::RelativeColorimetric)
+        .unwrap()
+        .with_paper_white(paper_white)
+}
+
+#[test]
+fn media_white() {
+    let profile = IccProfile::parse(&press_profile(Some(0.9 * D50))).unwrap();
+    assert!((profile.media_white() - 0.9 * D50).abs().max_element() < 1e-4);
+    let profile = IccProfile::parse(&press_profile(None)).unwrap();
+    assert_eq!(profile.media_white(), D50);
+
+    // Profiles without tables can't be proofed.
+    let mut data = vec![0; 132];
+    data[16..20].copy_from_slice(b"GRAY");
+    data[20..24].copy_from_slice(b"Lab ");
+    data[36..40].copy_from_slice(b"acsp");
+    let profile = IccProfile::parse(&data).unwrap();
+    assert!(SoftProof::new(profile, RenderingIntent::Perceptual).is_none());
+}
+
+#[test]
+fn absolute_colorimetric() {
+    let profile = IccProfile::parse(&press_profile(Some(0.9 * D50))).unwrap();
+    let relative = RenderingIntent::RelativeColorimetric;
+    let absolute = RenderingIntent::AbsoluteColorimetric;
+
+    // The paper is the white of the PCS relative to itself, and darker in
+    // absolute terms.
+    let paper = profile.to_pcs(&[0.0], relative).unwrap();
+    assert!((paper.x - 100.0).abs() < 0.1);
+    let paper = profile.to_pcs(&[0.0], absolute).unwrap();
+    let lightness = 116.0 * 0.9f32.cbrt() - 16.0;
+    assert!((paper.x - lightness).abs() < 0.1 && paper.y.abs() < 0.1);
+
+    // Absolute paper white prints without ink, up to the legacy encoding
+    // reaching beyond `L* = 100`.
+    let ink = profile.from_pcs(paper, absolute).unwrap()[0];
+    assert!(ink < 0.01);
+    let ink = profile.from_pcs(paper, relative).unwrap()[0];
+    assert!(ink > 0.04);
+}
+
+#[test]
+fn proof_colors() {
+    let proof = proof(false);
+    assert_eq!(proof.intent(), RenderingIntent::RelativeColorimetric);
+    assert!(!proof.paper_white());
+
+    let white = Color::srgb_u8(255, 255, 255);
+    let gray = Color::srgb_u8(119, 119, 119);
+    let black = Color::srgb_u8(0, 0, 0);
+    assert!(proof.device_values(white)[0] < 0.01);
+    assert!((proof.device_values(gray)[0] - 0.625).abs() < 0.01);
+    assert!((proof.device_values(black)[0] - 1.0).abs() < 1e-3);
+
+    // Grays the press can print look the same, and the paper is white.
+    let proofed: Color<EncodedSrgbU8> = proof.proof(white);
+    assert!(proofed.r >= 254 && proofed.r == proofed.g && proofed.g == proofed.b);
+    let proofed: Color<EncodedSrgbU8> = proof.proof(gray);
+    assert!((proofed.r as i32 - 119).abs() <= 1);
+
+    // Black is as dark as the ink gets.
+    let (_, _, luminance) = proof.proof::<_, CieXyz>(black).to_xy_luminance();
+    assert!((luminance - 0.0299).abs() < 1e-3);
+
+    // Alpha is kept.
+    let proofed: Color<EncodedSrgbaU8> = proof.proof(Color::srgba_u8(119, 119, 119, 128));
+    assert_eq!(proofed.a, 128);
+}
+
+#[test]
+fn proof_paper_white() {
+    let proof = proof(true);
+    assert!(proof.paper_white());
+
+    // The paper is as dark as its white point, and neutral.
+    let white = Color::srgb_u8(255, 255, 255);
+    let (x, y, luminance) = proof.proof::<_, CieXyz>(white).to_xy_luminance();
+    assert!((luminance - 0.9).abs() < 0.01);
+    assert!((x - 0.3127).abs() < 1e-3 && (y - 0.329).abs() < 1e-3);
+
+    // Everything else is scaled with it.
+    let black = Color::srgb_u8(0, 0, 0);
+    let (_, _, luminance) = proof.proof::<_, CieXyz>(black).to_xy_luminance();
+    assert!((luminance - 0.9 * 0.0299).abs() < 1e-3);
+}
@@ -0,0 +1,102 @@
+use colstodian::basic_encodings::*;
+use colstodian::details::encodings::{EncodedSrgbaPremultipliedU16, SrgbaPremultipliedU16};
+use colstodian::details::quantize::Rounding;
+use colstodian::*;
+
+#[test]
+fn half_up_matches_convert() {
+    let colors = (0..=100).map(|i| {
+        let f = i as f32 / 100.0;
+        Color::linear_srgb(f, 1.0 - f, (f * 3.0) % 1.0)
+    });
+
+    colors.for_each(|color| {
+        assert_eq!(
+            color.convert_with_rounding::<SrgbU8>(Rounding::HalfUp),
+            color.convert::<SrgbU8>()
+        );
+    });
+}
+
+#[test]
+fn ties() {
+    // 0.5 * 255 = 127.5 exactly.
+    assert_eq!(Rounding::HalfUp.quantize_u8(0.5, 0), 128);
+    assert_eq!(Rounding::HalfEven.quantize_u8(0.5, 0), 128);
+    assert_eq!(Rounding::TruncateBiased.quantize_u8(0.5, 0), 128);
+
+    // 0.25 * 255 = 63.75.
+    assert_eq!(Rounding::HalfEven.quantize_u8(0.25, 0), 64);
+    assert_eq!(Rounding::TruncateBiased.quantize_u8(0.25, 0), 64);
+}
+
+#[test]
+fn clamping() {
+    [
+        Rounding::HalfUp,
+        Rounding::HalfEven,
+        Rounding::TruncateBiased,
+        Rounding::Stochastic { seed: 7 },
+    ]
+    .into_iter()
+    .for_each(|rounding| {
+        assert_eq!(rounding.quantize_u8(-1.0, 0), 0);
+        assert_eq!(rounding.quantize_u8(2.0, 0), 255);
+        assert_eq!(rounding.quantize_u8(1.0, 0), 255);
+    });
+}
+
+#[test]
+fn stochastic_is_reproducible_and_unbiased() {
+    // 100.25 / 255.
+    let value = 100.25 / 255.0;
+
+    let first = (0..64)
+        .map(|seed| Rounding::Stochastic { seed }.quantize_u8(value, 0))
+        .collect::<Vec<_>>();
+    let second = (0..64)
+        .map(|seed| Rounding::Stochastic { seed }.quantize_u8(value, 0))
+        .collect::<Vec<_>>();
+    assert_eq!(first, second);
+
+    let count = 20_000;
+    let mean = (0..count)
+        .map(|seed| Rounding::Stochastic { seed }.quantize_u8(value, 0) as f32)
+        .sum::<f32>()
+        / count as f32;
+
+    assert!((mean - 100.25).abs() < 0.02, "mean was {mean}");
+}
+
+#[test]
+fn alpha_is_quantized_with_policy() {
+    let color = Color::linear_srgba(0.0, 0.0, 0.0, 0.25);
+    let out = color.convert_with_rounding::<SrgbaU8>(Rounding::HalfEven);
+
+    assert_eq!(out.a, 64);
+}
+
+#[test]
+fn u16_rounding() {
+    assert_eq!(Rounding::HalfUp.quantize_u16(1.0, 0), u16::MAX);
+    assert_eq!(Rounding::HalfUp.quantize_u16(0.5, 0), 32768);
+    assert_eq!(Rounding::HalfEven.quantize_u16(0.5, 0), 32768);
+    assert_eq!(Rounding::TruncateBiased.quantize_u16(-1.0, 0), 0);
+}
+
+#[test]
+fn premultiplied_u16_encodings() {
+    let color = Color::linear_srgba(0.8, 0.4, 0.2, 0.5);
+
+    let linear = color.convert::<SrgbaPremultipliedU16>();
+    assert_eq!(linear.repr, [26214, 13107, 6554, 32768]);
+
+    let encoded = color.convert::<EncodedSrgbaPremultipliedU16>();
+    let back = encoded.convert::<LinearSrgba>();
+    assert!((back.r - 0.8).abs() < 1e-4);
+    assert!((back.a - 0.5).abs() < 1e-4);
+
+    // Compositing over opaque black keeps the premultiplied values.
+    let over = linear.alpha_over(Color::<SrgbaPremultipliedU16>::BLACK);
+    assert_eq!(over.repr, [26214, 13107, 6554, u16::MAX]);
+}
@@ -0,0 +1,92 @@
+//! Tests for RGBW and RGBWW fixtures.
+
+use colstodian::details::encodings::CieXyz;
+use colstodian::rgbw::{RgbwFixture, RgbwwFixture, white_led};
+use colstodian::*;
+use glam::Vec3;
+
+fn colors() -> [Color<details::encodings::Srgb>; 6] {
+    [
+        Color::linear_srgb(1.0, 1.0, 1.0),
+        Color::linear_srgb(0.9, 0.6, 0.3),
+        Color::linear_srgb(0.2, 0.5, 0.8),
+        Color::linear_srgb(0.05, 0.05, 0.04),
+        Color::linear_srgb(1.0, 0.0, 0.0),
+        Color::linear_srgb(0.0, 0.0, 0.0),
+    ]
+}
+
+#[test]
+fn white_led_chromaticity() {
+    let (cct, duv) = white_led(2700.0, 1.0).cct_duv();
+    assert!((cct - 2700.0).abs() < 5.0);
+    assert!(duv.abs() < 1e-4);
+    assert!((white_led(5000.0, 0.8).y - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn rgbw_split() {
+    // A white LED with the white point of sRGB replaces equal RGB.
+    let d65 = Color::srgb_u8(255, 255, 255).convert::<CieXyz>();
+    let fixture = RgbwFixture::new(d65);
+    let levels = fixture.split(Color::linear_srgb(0.7, 0.5, 0.5));
+    let expected = [0.2, 0.0, 0.0, 0.5];
+    assert!(
+        levels
+            .iter()
+            .zip(expected)
+            .all(|(a, b)| (a - b).abs() < 1e-4)
+    );
+
+    let fixture = RgbwFixture::new(white_led(3000.0, 1.5));
+    for color in colors() {
+        let levels = fixture.split(color);
+        assert!(levels.iter().all(|level| (0.0..=1.0).contains(level)));
+        assert!(fixture.combine(levels).repr.abs_diff_eq(color.repr, 1e-4));
+        // The white is maxed out: one of red, green and blue is used up or
+        // the white LED is at full.
+        let [r, g, b, w] = levels;
+        assert!(r.min(g).min(b) < 1e-4 || w == 1.0);
+    }
+    assert_eq!(fixture.split(Color::linear_srgb(1.0, 0.0, 0.0))[3], 0.0);
+}
+
+#[test]
+fn rgbw_clips_out_of_range() {
+    let fixture = RgbwFixture::new(white_led(6500.0, 1.0));
+    let levels = fixture.split(Color::linear_srgb(5.0, -1.0, 0.5));
+    assert!(levels.iter().all(|level| (0.0..=1.0).contains(level)));
+}
+
+#[test]
+fn rgbww_split() {
+    let fixture = RgbwwFixture::new(white_led(2700.0, 1.0), white_led(6500.0, 1.0));
+    for color in colors() {
+        let levels = fixture.split(color);
+        assert!(levels.iter().all(|level| (0.0..=1.0).contains(level)));
+        assert!(fixture.combine(levels).repr.abs_diff_eq(color.repr, 1e-4));
+    }
+
+    // A warm color is mixed mostly from the warm LED, a cool one from the
+    // cool LED.
+    let warm = white_led(2900.0, 0.8).convert::<details::encodings::Srgb>();
+    let [.., w, c] = fixture.split(warm);
+    assert!(w > 0.5 && w > 4.0 * c);
+    let cool = white_led(6000.0, 0.8).convert::<details::encodings::Srgb>();
+    let [.., w, c] = fixture.split(cool);
+    assert!(c > 0.5 && c > 4.0 * w);
+
+    // Two whites give more white light than either alone.
+    let between = white_led(4000.0, 1.2);
+    let y = |levels: [f32; 5]| {
+        fixture
+            .combine([0.0, 0.0, 0.0, levels[3], levels[4]])
+            .repr
+            .dot(Vec3::new(0.2126, 0.7152, 0.0722))
+    };
+    let both = y(fixture.split(between));
+    for single in [white_led(2700.0, 1.0), white_led(6500.0, 1.0)] {
+        let [.., w] = RgbwFixture::new(single).split(between);
+        assert!(both > w * single.y - 1e-4);
+    }
+}
@@ -0,0 +1,76 @@
+//! Tests for vectorscope, waveform and zebra data.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::buffer::ColorBuffer;
+use colstodian::chromaticity::xy_to_uv_prime;
+use colstodian::details::encodings::Bt2020;
+use colstodian::scope::{self, ZebraMask};
+use glam::Vec2;
+
+#[test]
+fn vectorscope() {
+    let colors = [
+        Color::srgb_u8(128, 128, 128),
+        Color::srgb_u8(0, 0, 255),
+        Color::srgb_u8(0, 0, 0),
+    ];
+
+    let cbcr = scope::vectorscope_cbcr(colors);
+    assert_eq!(cbcr.len(), 3);
+    assert!(cbcr[0].abs_diff_eq(Vec2::ZERO, 1e-6));
+    assert!((cbcr[1].x - 0.5).abs() < 1e-5);
+
+    // Black has no chromaticity, and gray sits at the D65 white point.
+    let uv = scope::vectorscope_uv_prime(colors);
+    assert_eq!(uv.len(), 2);
+    assert!(uv[0].abs_diff_eq(Vec2::from(xy_to_uv_prime(0.3127, 0.3290)), 1e-3));
+    assert!(uv[1].abs_diff_eq(Vec2::from(xy_to_uv_prime(0.15, 0.06)), 1e-3));
+}
+
+#[test]
+fn waveform_columns() {
+    let buffer = ColorBuffer::from_fn(3, 4, |x, y| {
+        let v = (x * 100 + y * 15) as u8;
+        Color::srgb_u8(v, v, v)
+    });
+    let waveform = scope::waveform(&buffer, 256);
+    assert_eq!(waveform.len(), 3);
+    waveform
+        .iter()
+        .for_each(|column| assert_eq!(column.total(), 4));
+    // Gray has a luma of its encoded value.
+    assert_eq!(waveform[1].bin_of(100.0 / 255.0), Some(100));
+    assert_eq!(waveform[1].counts()[100], 1);
+    assert_eq!(waveform[2].counts()[245], 1);
+}
+
+#[test]
+fn zebra_masks() {
+    let buffer = ColorBuffer::from_fn(2, 2, |x, y| match (x, y) {
+        (0, 0) => Color::bt2020(0.0, 1.0, 0.0),
+        (1, 0) => Color::bt2020(0.5, 0.5, 0.5),
+        (0, 1) => Color::bt2020(0.95, 0.95, 0.95),
+        _ => Color::bt2020(0.1, 0.1, 0.1),
+    });
+
+    let gamut = ZebraMask::out_of_gamut::<SrgbU8, _>(&buffer);
+    assert_eq!(gamut.flags(), &[true, false, false, false]);
+    assert!(
+        ZebraMask::out_of_gamut::<Bt2020, _>(&buffer)
+            .flags()
+            .iter()
+            .all(|flag| !flag)
+    );
+
+    let luminance = ZebraMask::over_luminance(&buffer, 0.9);
+    assert_eq!(luminance.get(0, 1), Some(true));
+    assert_eq!(luminance.get(2, 0), None);
+
+    let union = gamut.union(&luminance);
+    assert_eq!(union.count(), 2);
+    assert_eq!(union.fraction(), 0.5);
+    assert_eq!((union.width(), union.height()), (2, 2));
+}
@@ -0,0 +1,73 @@
+//! Tests for smart light xy and brightness.
+
+use colstodian::details::encodings::CieXyz;
+use colstodian::smart_light::{LightGamut, SmartLightColor};
+use colstodian::*;
+
+#[test]
+fn white_and_black() {
+    for gamut in [LightGamut::A, LightGamut::B, LightGamut::C] {
+        let white = Color::srgb_u8(255, 255, 255).to_smart_light(&gamut);
+        assert!((white.x - 0.3127).abs() < 1e-3 && (white.y - 0.329).abs() < 1e-3);
+        assert!((white.brightness - 1.0).abs() < 1e-4);
+        assert_eq!(white.hue_bri(), 254);
+
+        let black = Color::srgb_u8(0, 0, 0).to_smart_light(&gamut);
+        assert_eq!(black.brightness, 0.0);
+        assert_eq!(black.hue_bri(), 1);
+        assert!(gamut.contains(black.x, black.y));
+    }
+}
+
+#[test]
+fn gamut_clamping() {
+    let gamut = LightGamut::B;
+    // The primaries and points inside are kept.
+    for (x, y) in [gamut.red, gamut.green, gamut.blue, (0.4, 0.3)] {
+        assert!(gamut.contains(x, y));
+        assert_eq!(gamut.clamp(x, y), (x, y));
+    }
+
+    // Points outside move to the closest point on the edge.
+    assert!(!gamut.contains(0.2, 0.7));
+    let (x, y) = gamut.clamp(0.2, 0.7);
+    assert!(gamut.contains(x, y));
+    assert!((x, y) != (0.2, 0.7));
+    // Past a corner, the corner itself.
+    assert_eq!(gamut.clamp(0.8, 0.3), gamut.red);
+    assert_eq!(gamut.clamp(0.1, 0.0), gamut.blue);
+    // Past the middle of the red–blue edge, straight onto it.
+    let (x, y) = gamut.clamp(0.45, 0.1);
+    let (cx, cy) = gamut.clamp(x, y);
+    assert!((cx - x).abs() < 1e-6 && (cy - y).abs() < 1e-6);
+    assert!(y > 0.1);
+
+    // Saturated sRGB green is outside of gamut B, but inside of gamut C.
+    let green = Color::srgb_u8(0, 255, 0);
+    let on_b = green.to_smart_light(&LightGamut::B);
+    let on_c = green.to_smart_light(&LightGamut::C);
+    assert!((on_c.x - 0.3).abs() < 1e-3 && (on_c.y - 0.6).abs() < 1e-3);
+    assert!(on_b.x > 0.3);
+    assert_eq!(on_b.brightness, on_c.brightness);
+}
+
+#[test]
+fn attributes() {
+    let light = SmartLightColor {
+        x: 0.5,
+        y: 0.25,
+        brightness: 0.5,
+    };
+    assert_eq!(light.zigbee_xy(), (32768, 16384));
+    assert_eq!(light.hue_bri(), 128);
+
+    let color = light.to_color();
+    assert!((color.y - 0.5).abs() < 1e-6);
+    let (x, y, _) = color.to_xy_luminance();
+    assert!((x - 0.5).abs() < 1e-6 && (y - 0.25).abs() < 1e-6);
+
+    // Round trips for colors inside the gamut.
+    let orange = Color::srgb_u8(255, 140, 30);
+    let back = orange.to_smart_light(&LightGamut::C).to_color();
+    assert!(back.repr.abs_diff_eq(orange.convert::<CieXyz>().repr, 1e-5));
+}
@@ -0,0 +1,143 @@
+//! Tests for reflectance spectra under different illuminants.
+
+use colstodian::Color;
+use colstodian::basic_encodings::SrgbU8;
+use colstodian::details::encodings::CieXyz;
+use colstodian::spectral::{self, Illuminant, Spectrum};
+
+fn chromaticity(illuminant: &Illuminant) -> (f32, f32) {
+    let white = illuminant.white();
+    let (x, y, luminance) = Color::<CieXyz>::from_repr(white).to_xy_luminance();
+    assert!((luminance - 1.0).abs() < 1e-4);
+    (x, y)
+}
+
+#[test]
+fn white_points() {
+    for (illuminant, expected) in [
+        (Illuminant::d65(), (0.3127, 0.3290)),
+        (Illuminant::a(), (0.4476, 0.4074)),
+        (Illuminant::f2(), (0.3721, 0.3751)),
+        (Illuminant::f11(), (0.3805, 0.3769)),
+    ] {
+        let (x, y) = chromaticity(&illuminant);
+        assert!((x - expected.0).abs() < 1e-3 && (y - expected.1).abs() < 2e-3);
+    }
+}
+
+#[test]
+fn led() {
+    for kelvin in [2700.0, 4000.0, 6500.0] {
+        let led = Illuminant::led(kelvin);
+        let (cct, duv) = Color::<CieXyz>::from_repr(led.white()).cct_duv();
+        assert!((cct - kelvin).abs() < 1.0 && duv.abs() < 1e-5);
+
+        // A blue peak and a broad phosphor emission.
+        let spectrum = led.spectrum();
+        assert!(spectrum.at(450.0) > spectrum.at(480.0));
+        assert!(spectrum.at(580.0) > spectrum.at(480.0));
+    }
+    let (cct, _) = Color::<CieXyz>::from_repr(Illuminant::led(1000.0).white()).cct_duv();
+    assert!((cct - 2500.0).abs() < 1.0);
+}
+
+#[test]
+fn spectrum_samples() {
+    let spectrum = Spectrum::from_samples(400.0, 100.0, &[0.0, 1.0, 0.5]);
+    assert_eq!(spectrum.at(380.0), 0.0);
+    assert_eq!(spectrum.at(450.0), 0.5);
+    assert_eq!(spectrum.at(500.0), 1.0);
+    assert_eq!(spectrum.at(780.0), 0.5);
+    assert_eq!(
+        spectrum.at(452.5),
+        (spectrum.at(450.0) + spectrum.at(455.0)) / 2.0
+    );
+    assert_eq!(Spectrum::from_fn(|nm| nm).values[2], 390.0);
+}
+
+#[test]
+fn gray_in_any_light() {
+    // A flat reflectance is neutral under every illuminant.
+    let gray = Spectrum::constant(0.18);
+    for illuminant in [
+        Illuminant::d65(),
+        Illuminant::a(),
+        Illuminant::f11(),
+        Illuminant::led(3000.0),
+    ] {
+        let lab = gray.lab_under(&illuminant);
+        assert!((lab.x - 49.5).abs() < 0.1 && lab.y.abs() < 1e-3 && lab.z.abs() < 1e-3);
+        let color: Color<SrgbU8> = gray.color_under(&illuminant).convert();
+        assert_eq!(color, Color::srgb_u8(118, 118, 118));
+    }
+}
+
+#[test]
+fn estimate_reflectance() {
+    let d65 = Illuminant::d65();
+    for color in [
+        Color::srgb_u8(255, 255, 255),
+        Color::srgb_u8(0, 0, 0),
+        Color::srgb_u8(255, 0, 0),
+        Color::srgb_u8(0, 255, 0),
+        Color::srgb_u8(210, 180, 140),
+        Color::srgb_u8(128, 10, 200),
+    ] {
+        let reflectance = Spectrum::estimate_reflectance(color);
+        assert!(
+            reflectance
+                .values
+                .iter()
+                .all(|value| (0.0..=1.0).contains(value))
+        );
+        let estimated: Color<SrgbU8> = reflectance.color_under(&d65).convert();
+        assert_eq!(estimated, color);
+    }
+
+    // Smooth spectra have no sharp bands.
+    let reflectance = Spectrum::estimate_reflectance(Color::srgb_u8(118, 118, 118));
+    assert!((reflectance.at(400.0) - reflectance.at(700.0)).abs() < 1e-3);
+}
+
+#[test]
+fn metamerism() {
+    let d65 = Illuminant::d65();
+    let a = Illuminant::a();
+    let f11 = Illuminant::f11();
+
+    let bands = Spectrum::from_fn(|nm| {
+        if (440.0..=470.0).contains(&nm) || (560.0..=590.0).contains(&nm) {
+            0.8
+        } else {
+            0.2
+        }
+    });
+    let smooth = Spectrum::estimate_reflectance(bands.color_under(&d65));
+
+    // The pair matches in daylight, but not in other lights.
+    assert!(bands.delta_e_under(&smooth, &d65) < 0.1);
+    assert!(bands.delta_e_under(&smooth, &a) > 5.0);
+    assert!(spectral::metamerism_index(&bands, &smooth, &d65, &d65) < 1e-3);
+    assert!(spectral::metamerism_index(&bands, &smooth, &d65, &a) > 5.0);
+    assert!(spectral::metamerism_index(&bands, &smooth, &d65, &f11) > 2.0);
+
+    // Identical spectra match in any light, and a constant difference is
+    // corrected for.
+    assert_eq!(spectral::metamerism_index(&bands, &bands, &d65, &f11), 0.0);
+    let lighter = Spectrum::constant(0.3);
+    let darker = Spectrum::constant(0.2);
+    assert!(lighter.delta_e_under(&darker, &a) > 5.0);
+    assert!(spectral::metamerism_index(&lighter, &darker, &d65, &a) < 0.1);
+}
+
+#[test]
+fn whiteness() {
+    // A white reflecting more blue than the rest is whiter than a flat one.
+    let flat = Spectrum::constant(0.9);
+    let bluish = Spectrum::from_fn(|nm| if nm < 480.0 { 0.95 } else { 0.9 });
+    for illuminant in [Illuminant::d65(), Illuminant::a(), Illuminant::f2()] {
+        let white = flat.whiteness_under(&illuminant);
+        assert!((white.whiteness - 90.0).abs() < 1e-3 && white.tint.abs() < 1e-3);
+        assert!(bluish.whiteness_under(&illuminant).whiteness > white.whiteness);
+    }
+}
@@ -0,0 +1,160 @@
+//! Tests for color buffer statistics.
+
+#![cfg(feature = "std")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::details::encodings::{CieXyz, EncodedSrgbaU8, Oklab};
+use colstodian::stats::{self, LuminanceStats, Metering, WhiteEstimation};
+use glam::Vec3;
+
+#[test]
+fn average_in_working_encodings() {
+    let pixels = [Color::srgb_u8(255, 255, 255), Color::srgb_u8(0, 0, 0)];
+
+    assert_eq!(
+        stats::average::<_, LinearSrgb>(pixels),
+        Some(Color::srgb_u8(188, 188, 188))
+    );
+    // Oklab lightness 0.5 is a luminance of 0.125.
+    let perceptual = stats::average::<_, Oklab>(pixels).unwrap();
+    assert!((perceptual.r as i32 - 99).abs() <= 1);
+
+    assert_eq!(
+        stats::average::<SrgbU8, LinearSrgb>(core::iter::empty()),
+        None
+    );
+}
+
+#[test]
+fn average_keeps_alpha_separate() {
+    let pixels = [
+        Color::<EncodedSrgbaU8>::from_repr([255, 0, 0, 255]),
+        Color::<EncodedSrgbaU8>::from_repr([255, 0, 0, 0]),
+    ];
+    let average = stats::average::<_, LinearSrgb>(pixels).unwrap();
+    assert_eq!(average.repr, [255, 0, 0, 128]);
+}
+
+#[test]
+fn dominant_colors() {
+    let red = Color::srgb_u8(250, 10, 10);
+    let reddish = Color::srgb_u8(240, 20, 15);
+    let blue = Color::srgb_u8(10, 20, 240);
+    let pixels = [red, reddish, red, reddish, red, blue];
+
+    let dominant = stats::dominant(pixels, 2);
+    assert_eq!(dominant.len(), 2);
+
+    let (first, share) = dominant[0];
+    assert!((share - 5.0 / 6.0).abs() < 1e-6);
+    assert!(first.r >= 240 && first.b <= 15);
+
+    let (second, share) = dominant[1];
+    assert!((share - 1.0 / 6.0).abs() < 1e-6);
+    assert_eq!(second, blue);
+}
+
+#[test]
+fn dominant_colors_of_few_distinct_colors() {
+    let pixels = [Color::srgb_u8(0, 128, 0); 5];
+    assert_eq!(
+        stats::dominant(pixels, 4),
+        vec![(Color::srgb_u8(0, 128, 0), 1.0)]
+    );
+    assert!(stats::dominant(pixels, 0).is_empty());
+}
+
+#[test]
+fn luminance_stats() {
+    let pixels = [0.0, 0.25, 0.5, 1.0, 4.0].map(|v| Color::linear_srgb(v, v, v));
+    let stats = LuminanceStats::new(pixels).unwrap();
+
+    assert_eq!(stats.count(), 5);
+    assert!(stats.min().abs() < 1e-5);
+    assert!((stats.max() - 4.0).abs() < 1e-4);
+    assert!((stats.median() - 0.5).abs() < 1e-5);
+    assert!((stats.percentile(87.5) - 2.5).abs() < 1e-4);
+    assert!((stats.percentile(150.0) - stats.max()).abs() < 1e-6);
+
+    let expected = [0.0f32, 0.25, 0.5, 1.0, 4.0]
+        .map(|v| (v + LuminanceStats::LOG_DELTA).ln())
+        .iter()
+        .sum::<f32>()
+        / 5.0;
+    assert!((stats.log_average() - expected.exp()).abs() < 1e-4);
+}
+
+#[test]
+fn luminance_stats_of_nothing() {
+    assert_eq!(
+        LuminanceStats::new([Color::linear_srgb(f32::NAN, f32::NAN, f32::NAN)]),
+        None
+    );
+}
+
+#[test]
+fn white_estimation() {
+    // A scene of grays and a few colors under a warm illuminant.
+    let illuminant = Vec3::new(1.0, 0.8, 0.5);
+    let scene = [
+        Vec3::new(0.2, 0.2, 0.2),
+        Vec3::new(0.5, 0.5, 0.5),
+        Vec3::new(0.9, 0.9, 0.9),
+        Vec3::new(0.6, 0.3, 0.2),
+        Vec3::new(0.2, 0.3, 0.6),
+    ];
+    let pixels = scene.map(|reflectance| Color::<LinearSrgb>::from_repr(reflectance * illuminant));
+    let expected: Color<CieXyz> = Color::<LinearSrgb>::from_repr(illuminant).convert();
+    let expected = expected.repr / expected.repr.y;
+
+    let white_patch =
+        stats::estimate_white::<_, LinearSrgb>(pixels, WhiteEstimation::WhitePatch(100.0)).unwrap();
+    assert!(white_patch.repr.abs_diff_eq(expected, 1e-5));
+    assert_eq!(white_patch.repr.y, 1.0);
+
+    // The colored patches balance out, so gray world is close too.
+    let gray_world =
+        stats::estimate_white::<_, LinearSrgb>(pixels, WhiteEstimation::GrayWorld).unwrap();
+    assert!(gray_world.repr.abs_diff_eq(expected, 0.05));
+
+    let shades =
+        stats::estimate_white::<_, LinearSrgb>(pixels, WhiteEstimation::ShadesOfGray(1.0)).unwrap();
+    assert!(shades.repr.abs_diff_eq(gray_world.repr, 1e-5));
+
+    assert_eq!(
+        stats::estimate_white::<LinearSrgb, LinearSrgb>([], WhiteEstimation::GrayWorld),
+        None
+    );
+    assert_eq!(
+        stats::estimate_white::<_, LinearSrgb>(
+            [Color::linear_srgb(0.0, 0.0, 0.0)],
+            WhiteEstimation::GrayWorld
+        ),
+        None
+    );
+}
+
+#[test]
+fn exposure_compensation() {
+    let gray = |v| Color::linear_srgb(v, v, v);
+    let stats = LuminanceStats::new([gray(0.045), gray(0.045), gray(0.9)]).unwrap();
+
+    let median =
+        stats.exposure_compensation(Metering::Percentile(50.0), LuminanceStats::MIDDLE_GRAY);
+    assert!((median - 2.0).abs() < 1e-4);
+    assert!((0.045 * stats::exposure_scale(median) - 0.18).abs() < 1e-5);
+
+    let highlights = stats.exposure_compensation(Metering::Percentile(100.0), 0.9);
+    assert!(highlights.abs() < 1e-5);
+
+    let key = stats.exposure_compensation(Metering::LogAverage, stats.log_average());
+    assert!(key.abs() < 1e-5);
+
+    let black = LuminanceStats::new([gray(0.0)]).unwrap();
+    assert!(
+        black
+            .exposure_compensation(Metering::Percentile(50.0), 0.18)
+            .is_finite()
+    );
+}
@@ -0,0 +1,47 @@
+//! Tests for viewing surround compensation.
+
+use colstodian::basic_encodings::{LinearSrgba, SrgbU8};
+use colstodian::details::encodings::CieXyz;
+use colstodian::surround::Surround;
+use colstodian::*;
+
+#[test]
+fn gammas() {
+    assert_eq!(Surround::default(), Surround::Average);
+    assert_eq!(Surround::Average.system_gamma(), 1.0);
+    assert!(Surround::Dim.system_gamma() < Surround::Dark.system_gamma());
+    assert_eq!(Surround::Dark.gamma_from(Surround::Dark), 1.0);
+    assert!(Surround::Average.gamma_from(Surround::Dark) < 1.0);
+}
+
+#[test]
+fn luminance_and_chromaticity() {
+    let color = Color::linear_srgba(0.4, 0.2, 0.1, 0.5);
+    let dark: Color<LinearSrgba> = color.convert_for_surround(Surround::Dark);
+    assert_eq!(dark.a, 0.5);
+
+    let (before, after) = (color.convert::<CieXyz>(), dark.convert::<CieXyz>());
+    assert!((after.y - before.y.powf(1.2)).abs() < 1e-5);
+    let xy = |xyz: Color<CieXyz>| glam::Vec2::new(xyz.x, xyz.y) / (xyz.x + xyz.y + xyz.z);
+    assert!(xy(after).abs_diff_eq(xy(before), 1e-5));
+
+    // Going back to the mastering surround undoes it.
+    let back: Color<LinearSrgba> =
+        dark.convert_between_surrounds(Surround::Dark, Surround::Average);
+    assert!(back.repr.abs_diff_eq(color.repr, 1e-5));
+}
+
+#[test]
+fn black_and_white() {
+    for surround in [Surround::Average, Surround::Dim, Surround::Dark] {
+        assert_eq!(
+            Color::srgb_u8(0, 0, 0).convert_for_surround::<SrgbU8>(surround),
+            Color::srgb_u8(0, 0, 0)
+        );
+        assert_eq!(
+            Color::srgb_u8(255, 255, 255)
+                .convert_between_surrounds::<SrgbU8>(Surround::Dim, surround),
+            Color::srgb_u8(255, 255, 255)
+        );
+    }
+}
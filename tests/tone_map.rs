@@ -0,0 +1,70 @@
+//! Tests for tone mapping to target displays.
+
+use colstodian::details::encodings::{Bt2020, ICtCpPq, Srgba};
+use colstodian::tone_map::{TargetDisplay, ToneMapper};
+use colstodian::*;
+
+const TARGETS: [TargetDisplay; 3] = [
+    TargetDisplay::SDR,
+    TargetDisplay::new(300.0, 0.2, 203.0),
+    TargetDisplay::HDR_1000,
+];
+
+#[test]
+fn luminance_curve() {
+    for target in TARGETS {
+        let mapper = ToneMapper::new(target);
+        let source_peak = mapper.source_peak() * target.sdr_white_nits;
+        let mut previous = 0.0;
+        for step in 0..=100 {
+            let nits = mapper.map_luminance(step as f32 / 100.0 * source_peak);
+            assert!(nits >= previous, "{target:?} decreases at {step}");
+            assert!(nits <= target.peak_nits * 1.0001);
+            previous = nits;
+        }
+        assert!((previous - target.peak_nits).abs() < 1e-3 * target.peak_nits);
+        assert!((mapper.map_luminance(0.0) - target.black_nits).abs() < 1e-3);
+        let white = mapper.map_luminance(target.sdr_white_nits);
+        assert!(white <= target.sdr_white_nits * 1.01 && white > target.sdr_white_nits * 0.75);
+    }
+}
+
+#[test]
+fn source_peak() {
+    let display = TargetDisplay::HDR_1000;
+    // A source that fits the display is left alone.
+    let fitting = ToneMapper::with_source_peak(display, 2.0);
+    assert!((fitting.map_luminance(300.0) - 300.0).abs() < 0.1);
+    assert!(
+        ToneMapper::with_source_peak(display, 20.0).map_luminance(2000.0)
+            < ToneMapper::new(display).map_luminance(2000.0)
+    );
+    assert_eq!(TargetDisplay::SDR.headroom(), 1.0);
+}
+
+#[test]
+fn colors() {
+    for target in TARGETS {
+        let mapper = ToneMapper::new(target);
+        let color = Color::srgba(4.0, 1.5, 0.5, 0.25);
+        let mapped = color.tone_mapped(&mapper);
+        assert_eq!(mapped.a, 0.25);
+        assert!(mapped.r < color.r);
+
+        // The hue angle in ICtCp is kept.
+        let hue = |color: Color<Srgba>| {
+            let ictcp = color.convert::<ICtCpPq>();
+            ictcp.cp.atan2(ictcp.ct)
+        };
+        assert!((hue(mapped) - hue(color)).abs() < 1e-3);
+    }
+
+    let black = Color::<Bt2020>::from_repr(glam::Vec3::ZERO);
+    assert!(
+        black
+            .tone_mapped(&ToneMapper::new(TARGETS[1]))
+            .repr
+            .max_element()
+            < 0.01
+    );
+}
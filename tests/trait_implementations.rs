@@ -135,3 +135,235 @@ fn rgba_component_struct_traits() {
     assert!(rgba_set.contains(&rgba2)); // Same as rgba1.
     assert!(rgba_set.contains(&rgba3));
 }
+
+#[test]
+fn default_is_zero() {
+    use colstodian::basic_encodings::*;
+
+    assert_eq!(Color::<SrgbU8>::default(), Color::<SrgbU8>::BLACK);
+    assert_eq!(Color::<LinearSrgb>::default(), Color::<LinearSrgb>::BLACK);
+    // Encodings with alpha default to transparent black.
+    assert_eq!(Color::<SrgbaU8>::default(), Color::<SrgbaU8>::TRANSPARENT);
+    assert_eq!(
+        Color::<LinearSrgba>::default(),
+        Color::<LinearSrgba>::TRANSPARENT
+    );
+}
+
+#[test]
+fn constants_convert_consistently() {
+    use colstodian::basic_encodings::*;
+    use colstodian::details::encodings::Oklab;
+
+    assert_eq!(
+        Color::<SrgbU8>::WHITE.convert::<LinearSrgb>(),
+        Color::<LinearSrgb>::WHITE
+    );
+    assert_eq!(
+        Color::<LinearSrgba>::BLACK.convert::<SrgbaU8>(),
+        Color::<SrgbaU8>::BLACK
+    );
+    assert_eq!(
+        Color::<LinearSrgba>::TRANSPARENT.convert::<SrgbaU8>(),
+        Color::<SrgbaU8>::TRANSPARENT
+    );
+
+    let white = Color::<Oklab>::WHITE.convert::<SrgbU8>();
+    assert_eq!(white, Color::<SrgbU8>::WHITE);
+}
+
+#[test]
+fn gray_constructors() {
+    use colstodian::basic_encodings::*;
+    use colstodian::details::encodings::Oklab;
+
+    assert_eq!(Color::<SrgbU8>::gray(128), Color::srgb_u8(128, 128, 128));
+    assert_eq!(Color::<SrgbaU8>::gray(7), Color::srgba_u8(7, 7, 7, 255));
+    assert_eq!(Color::<LinearSrgb>::gray(1.0), Color::<LinearSrgb>::WHITE);
+    assert_eq!(Color::<LinearSrgba>::gray(0.0), Color::<LinearSrgba>::BLACK);
+
+    // Oklab grays are neutral in sRGB.
+    let gray = Color::<Oklab>::gray(0.6).convert::<SrgbU8>();
+    assert!(gray.r == gray.g && gray.g == gray.b);
+}
+
+#[test]
+fn component_iteration() {
+    use colstodian::basic_encodings::*;
+
+    let color = Color::srgba_u8(1, 2, 3, 4);
+    assert_eq!(color.to_array(), [1, 2, 3, 4]);
+    assert_eq!(color.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    let linear = Color::linear_srgb(0.25, 0.5, 0.125);
+    assert_eq!(linear.iter().fold(0.0, f32::max), 0.5);
+    assert!(
+        linear
+            .iter()
+            .zip(Color::linear_srgb(0.25, 0.5, 0.25))
+            .any(|(a, b)| a != b)
+    );
+
+    assert_eq!(Color::<SrgbaU8>::from_array(color.to_array()), color);
+    assert_eq!(Color::<LinearSrgb>::from_array(linear.to_array()), linear);
+}
+
+#[test]
+fn debug_and_display_formatting() {
+    let purple = Color::srgb_u8(107, 70, 193);
+    assert_eq!(purple.to_string(), "EncodedSrgbU8(#6b46c1)");
+    assert_eq!(
+        format!("{purple:?}"),
+        "Color<EncodedSrgbU8>(#6b46c1, R: 107, G: 70, B: 193)"
+    );
+    assert_eq!(
+        Color::srgba_u8(107, 70, 193, 128).to_string(),
+        "EncodedSrgbaU8(#6b46c180)"
+    );
+
+    let linear = Color::linear_srgb(0.5, 0.25, 1.0);
+    assert_eq!(linear.to_string(), "Srgb(R: 0.500, G: 0.250, B: 1.000)");
+    assert_eq!(format!("{linear:?}"), "Color<Srgb>(R: 0.5, G: 0.25, B: 1)");
+}
+
+#[cfg(all(feature = "schemars", feature = "serde"))]
+#[test]
+fn json_schema_matches_serde() {
+    use colstodian::basic_encodings::{LinearSrgb, SrgbaU8};
+
+    let schema = serde_json::to_value(schemars::schema_for!(Color<SrgbaU8>)).unwrap();
+    let json = serde_json::to_value(Color::srgba_u8(1, 2, 3, 4)).unwrap();
+    assert_eq!(json, serde_json::json!({ "repr": [1, 2, 3, 4] }));
+    assert_eq!(schema["required"], serde_json::json!(["repr"]));
+    assert_eq!(schema["properties"]["repr"]["type"], "array");
+    assert_eq!(schema["properties"]["repr"]["minItems"], 4);
+    assert_eq!(schema["properties"]["repr"]["items"]["maximum"], 255);
+
+    let schema = serde_json::to_value(schemars::schema_for!(Color<LinearSrgb>)).unwrap();
+    let json = serde_json::to_value(Color::linear_srgb(0.5, 0.25, 1.0)).unwrap();
+    assert_eq!(json, serde_json::json!({ "repr": [0.5, 0.25, 1.0] }));
+    assert_eq!(schema["title"], "Color_Srgb");
+    assert_eq!(schema["properties"]["repr"]["maxItems"], 3);
+    assert_eq!(schema["properties"]["repr"]["items"]["type"], "number");
+}
+
+#[test]
+fn encoding_info() {
+    use colstodian::custom::{RgbPrimaries, WhitePoint};
+    use colstodian::details::encodings::*;
+    use colstodian::transfer::TransferFunction;
+
+    fn working<E: WorkingEncoding>() -> bool {
+        E::info().is_working
+    }
+    fn perceptual<E: PerceptualEncoding>() -> bool {
+        E::info().is_perceptual
+    }
+
+    let info = EncodedSrgbU8::info();
+    assert_eq!(info.name, "EncodedSrgbU8");
+    assert_eq!(info.primaries, RgbPrimaries::Bt709);
+    assert_eq!(info.white_point, WhitePoint::D65);
+    assert_eq!(info.transfer, Some(TransferFunction::Srgb));
+    assert_eq!((info.components, info.component_type), (3, "u8"));
+    assert!(!info.has_alpha && !info.is_working && !info.is_perceptual);
+
+    let info = SrgbaF64::info();
+    assert_eq!(info.transfer, Some(TransferFunction::Linear));
+    assert_eq!((info.components, info.component_type), (4, "f64"));
+    assert!(info.has_alpha);
+
+    assert_eq!(Oklch::info().transfer, None);
+    assert_eq!(
+        EncodedProPhotoRgbU8::info().transfer,
+        Some(TransferFunction::ProPhotoRgb)
+    );
+    assert_eq!(
+        EncodedSrgbU10::info().transfer,
+        Some(TransferFunction::Srgb)
+    );
+
+    assert!(
+        [
+            working::<Srgb>(),
+            working::<Srgba>(),
+            working::<Oklab>(),
+            working::<SrgbF64>(),
+            working::<SrgbaF64>(),
+            working::<CieXyzF64>(),
+            working::<OklabF64>(),
+            working::<AdobeRgb>(),
+            working::<ProPhotoRgb>(),
+            working::<DisplayP3>(),
+            working::<AcesCg>(),
+            working::<Aces2065>(),
+            working::<Bt2020>(),
+        ]
+        .into_iter()
+        .all(|is_working| is_working)
+    );
+    assert!(perceptual::<Oklab>() && perceptual::<OklabF64>());
+}
+
+#[test]
+fn alpha_encoding() {
+    use colstodian::details::encodings::{EncodedBgraU8, EncodedSrgbaU16, SrgbaPremultiplied};
+
+    fn opaque<E: AlphaEncoding>(color: Color<E>) -> Color<E> {
+        color.with_alpha(1.0)
+    }
+
+    let mut color = Color::srgba_u8(10, 20, 30, 40);
+    assert_eq!(color.alpha(), 40.0 / 255.0);
+    color.set_alpha(0.5);
+    assert_eq!(color, Color::srgba_u8(10, 20, 30, 128));
+    assert_eq!(opaque(color), Color::srgba_u8(10, 20, 30, 255));
+
+    let bgra = Color::<EncodedBgraU8>::from_repr([1, 2, 3, 4]);
+    assert_eq!(opaque(bgra).repr, [1, 2, 3, 255]);
+    let wide = Color::<EncodedSrgbaU16>::from_repr([1, 2, 3, 4]);
+    assert_eq!(wide.with_alpha(0.0).repr, [1, 2, 3, 0]);
+    assert_eq!(Color::linear_srgba(0.1, 0.2, 0.3, 0.4).alpha(), 0.4);
+
+    let premultiplied = Color::<SrgbaPremultiplied>::srgba_premultiplied(0.5, 0.25, 0.0, 0.5);
+    assert_eq!(premultiplied.alpha(), 0.5);
+    assert_eq!(
+        opaque(premultiplied),
+        Color::srgba_premultiplied(1.0, 0.5, 0.0, 1.0)
+    );
+    assert_eq!(
+        premultiplied.with_alpha(0.0).with_alpha(1.0),
+        Color::srgba_premultiplied(0.0, 0.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn slice_casting() {
+    use colstodian::basic_encodings::*;
+
+    let mut reprs = [[10, 20, 30], [40, 50, 60]];
+    let colors = Color::<SrgbU8>::slice_from_reprs_mut(&mut reprs);
+    colors[0] = Color::srgb_u8(1, 2, 3);
+    assert_eq!(Color::slice_as_reprs(colors), &[[1, 2, 3], [40, 50, 60]]);
+    Color::slice_as_reprs_mut(colors)[1] = [4, 5, 6];
+    assert_eq!(reprs, [[1, 2, 3], [4, 5, 6]]);
+
+    let linear = [Color::linear_srgb(0.5, 0.25, 1.0)];
+    assert_eq!(Color::slice_as_reprs(&linear)[0].y, 0.25);
+
+    #[cfg(feature = "bytemuck")]
+    {
+        let mut linear = linear;
+        let bytes = Color::slice_as_bytes_mut(&mut linear);
+        assert_eq!(bytes.len(), 12);
+        bytes[..4].copy_from_slice(&2.0f32.to_ne_bytes());
+        assert_eq!(linear[0].r, 2.0);
+
+        let bytes = [0u8; 7];
+        assert_eq!(
+            Color::<SrgbU8>::slice_from_bytes(&bytes[..6]).map(<[_]>::len),
+            Some(2)
+        );
+        assert_eq!(Color::<SrgbU8>::slice_from_bytes(&bytes), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Tests for the bare transfer functions.
+
+use colstodian::Color;
+use colstodian::transfer::{self, TransferFunction, TransferParameters};
+use glam::Vec3;
+
+#[test]
+fn curves_round_trip() {
+    let values = Vec3::new(0.001, 0.18, 0.9);
+    [
+        TransferFunction::Linear,
+        TransferFunction::Srgb,
+        TransferFunction::AdobeRgb,
+        TransferFunction::ProPhotoRgb,
+    ]
+    .into_iter()
+    .for_each(|curve| {
+        let round_trip = curve.decode(curve.encode(values));
+        assert!(round_trip.abs_diff_eq(values, 1e-5), "{curve:?}");
+    });
+
+    let nits = Vec3::new(0.1, 203.0, 4000.0);
+    let round_trip = TransferFunction::Pq.decode(TransferFunction::Pq.encode(nits));
+    assert!(((round_trip - nits) / nits).abs().max_element() < 1e-3);
+}
+
+#[test]
+fn curves_match_encodings() {
+    let color = Color::srgb_u8(20, 128, 240);
+    let encoded = Vec3::new(20.0, 128.0, 240.0) / 255.0;
+    assert!(
+        transfer::srgb_eotf(encoded).abs_diff_eq(
+            color
+                .convert::<colstodian::basic_encodings::LinearSrgb>()
+                .repr,
+            1e-6
+        )
+    );
+    assert_eq!(
+        TransferFunction::Srgb.decode_f32(0.5),
+        transfer::srgb_eotf(Vec3::splat(0.5)).x
+    );
+}
+
+#[test]
+fn pq_reference_levels() {
+    let encoded = transfer::pq_inverse_eotf(Vec3::new(100.0, 1000.0, 10_000.0));
+    assert!((encoded.x - 0.5081).abs() < 1e-3);
+    assert!((encoded.y - 0.7518).abs() < 1e-3);
+    assert!((encoded.z - 1.0).abs() < 1e-6);
+    assert_eq!(transfer::pq_eotf(Vec3::ONE).x, 10_000.0);
+}
+
+#[test]
+fn parametric_curves() {
+    [
+        TransferFunction::Linear,
+        TransferFunction::Srgb,
+        TransferFunction::AdobeRgb,
+        TransferFunction::ProPhotoRgb,
+    ]
+    .into_iter()
+    .for_each(|transfer| {
+        let parameters = transfer.parameters().unwrap();
+        (0..=100).map(|i| i as f32 / 100.0).for_each(|x| {
+            let decoded = parameters.decode(Vec3::splat(x)).x;
+            assert!(
+                (decoded - transfer.decode_f32(x)).abs() < 1e-5,
+                "{transfer:?} at {x}"
+            );
+            let encoded = parameters.encode(Vec3::splat(decoded)).x;
+            assert!((encoded - x).abs() < 1e-4, "{transfer:?} inverse at {x}");
+        });
+    });
+    assert_eq!(TransferFunction::Pq.parameters(), None);
+    assert_eq!(core::mem::size_of::<TransferParameters>(), 32);
+}
+
+#[test]
+fn cineon_curve() {
+    let code = |linear: f32| TransferFunction::Cineon.encode_f32(linear) * 1023.0;
+
+    assert!((code(1.0) - 685.0).abs() < 1e-3);
+    assert!((code(0.0) - 95.0).abs() < 1e-3);
+    assert!((code(0.18) - 467.8).abs() < 0.1);
+
+    let values = Vec3::new(0.0, 0.18, 8.0);
+    let round_trip = transfer::cineon_eotf(transfer::cineon_oetf(values));
+    assert!(round_trip.abs_diff_eq(values, 1e-4));
+    assert_eq!(TransferFunction::Cineon.parameters(), None);
+}
+
+#[test]
+fn camera_log_curves() {
+    let gray = Vec3::splat(0.18);
+    assert!((transfer::v_log_oetf(gray).x - 0.4233).abs() < 1e-4);
+    assert!((transfer::canon_log2_oetf(gray).x - 0.3983).abs() < 1e-4);
+    assert!((transfer::canon_log3_oetf(gray).x - 0.3434).abs() < 1e-4);
+
+    let values = Vec3::new(-0.01, 0.005, 0.18);
+    let high = Vec3::new(0.0, 2.0, 16.0);
+    [
+        (
+            transfer::v_log_oetf as fn(Vec3) -> Vec3,
+            transfer::v_log_eotf as fn(Vec3) -> Vec3,
+        ),
+        (transfer::canon_log2_oetf, transfer::canon_log2_eotf),
+        (transfer::canon_log3_oetf, transfer::canon_log3_eotf),
+    ]
+    .into_iter()
+    .for_each(|(encode, decode)| {
+        assert!(decode(encode(values)).abs_diff_eq(values, 1e-5));
+        assert!(decode(encode(high)).abs_diff_eq(high, 1e-3));
+    });
+}
+
+#[test]
+fn camera_log_encodings() {
+    use colstodian::details::encodings::{AcesCg, CanonLog2, CanonLog3, CinemaGamut, VGamut, VLog};
+
+    let color = Color::aces_cg(0.4, 0.18, 0.05);
+    let v_log = color.convert::<VLog>();
+    let canon_log2 = color.convert::<CanonLog2>();
+    let canon_log3 = color.convert::<CanonLog3>();
+
+    assert!(v_log.convert::<AcesCg>().repr.abs_diff_eq(color.repr, 1e-4));
+    assert!(
+        canon_log2
+            .convert::<AcesCg>()
+            .repr
+            .abs_diff_eq(color.repr, 1e-4)
+    );
+    assert!(
+        canon_log3
+            .convert::<AcesCg>()
+            .repr
+            .abs_diff_eq(color.repr, 1e-4)
+    );
+
+    // Grays are grays in every gamut.
+    let gray = Color::aces_cg(0.18, 0.18, 0.18);
+    assert!(
+        gray.convert::<VGamut>()
+            .repr
+            .abs_diff_eq(Vec3::splat(0.18), 1e-4)
+    );
+    assert!(
+        gray.convert::<CinemaGamut>()
+            .repr
+            .abs_diff_eq(Vec3::splat(0.18), 1e-4)
+    );
+
+    // Between cameras, without a detour through a working space.
+    let matched = v_log.convert::<CanonLog3>();
+    assert!(matched.repr.abs_diff_eq(canon_log3.repr, 1e-4));
+}
@@ -0,0 +1,82 @@
+//! Tests for strided views of external memory.
+#![cfg(feature = "bytemuck")]
+
+use colstodian::Color;
+use colstodian::basic_encodings::{LinearSrgb, SrgbU8};
+use colstodian::buffer::ColorBuffer;
+use colstodian::details::encodings::EncodedSrgbaU8;
+use colstodian::view::{PixelLayout, StridedView, StridedViewMut};
+
+#[test]
+fn layout_bounds_are_checked() {
+    let data = [0u8; 23];
+    let layout = PixelLayout::packed::<SrgbU8>(2, 2)
+        .with_pixel_stride(4)
+        .with_row_stride(12);
+
+    // The last pixel ends at byte 12 + 4 + 3.
+    assert!(StridedView::<SrgbU8>::new(&data[..19], layout).is_some());
+    assert!(StridedView::<SrgbU8>::new(&data[..18], layout).is_none());
+    // Reordered components can reach into the padding.
+    let padded = layout.with_channel_order([3, 2, 1, 0]);
+    assert!(StridedView::<SrgbU8>::new(&data[..19], padded).is_none());
+    assert!(StridedView::<SrgbU8>::new(&data[..20], padded).is_some());
+
+    assert!(StridedView::<SrgbU8>::new(&[], PixelLayout::packed::<SrgbU8>(0, 5)).is_some());
+}
+
+#[test]
+fn float_views_read_unaligned_memory() {
+    let mut bytes = vec![0u8];
+    [1.0f32, 0.5, 0.25, 0.0, 0.75, 1.0]
+        .iter()
+        .for_each(|v| bytes.extend(v.to_ne_bytes()));
+
+    let view = StridedView::<LinearSrgb>::new(&bytes[1..], PixelLayout::packed::<LinearSrgb>(2, 1))
+        .unwrap();
+    assert_eq!(
+        view.row(0).collect::<Vec<_>>(),
+        [
+            Color::linear_srgb(1.0, 0.5, 0.25),
+            Color::linear_srgb(0.0, 0.75, 1.0)
+        ]
+    );
+}
+
+#[test]
+fn writing_keeps_padding_and_order() {
+    let mut data = [0xAAu8; 8];
+    let layout = PixelLayout::packed::<EncodedSrgbaU8>(2, 1).with_channel_order([2, 1, 0, 3]);
+    let mut view = StridedViewMut::<EncodedSrgbaU8>::new(&mut data, layout).unwrap();
+
+    view.set(1, 0, Color::from_repr([1, 2, 3, 4]));
+    view.map_in_place(|mut color| {
+        color.repr[3] = 255;
+        color
+    });
+    assert_eq!(view.get(1, 0), Some(Color::from_repr([1, 2, 3, 255])));
+    assert_eq!(data, [0xAA, 0xAA, 0xAA, 255, 3, 2, 1, 255]);
+
+    let mut padded = [0u8; 6];
+    let layout = PixelLayout::packed::<SrgbU8>(1, 1).with_row_stride(6);
+    StridedViewMut::<SrgbU8>::new(&mut padded, layout)
+        .unwrap()
+        .set(0, 0, Color::srgb_u8(9, 8, 7));
+    assert_eq!(padded, [9, 8, 7, 0, 0, 0]);
+}
+
+#[test]
+fn conversion_between_views() {
+    let src_bytes = [255u8, 0, 0, 0, 0, 255];
+    let src = StridedView::<SrgbU8>::new(&src_bytes, PixelLayout::packed::<SrgbU8>(2, 1)).unwrap();
+
+    let mut dst_bytes = [0u8; 24];
+    let mut dst =
+        StridedViewMut::<LinearSrgb>::new(&mut dst_bytes, PixelLayout::packed::<LinearSrgb>(2, 1))
+            .unwrap();
+    dst.convert_from(&src);
+
+    assert_eq!(dst.get(1, 0), Some(Color::linear_srgb(0.0, 0.0, 1.0)));
+    let buffer = ColorBuffer::from_view(&dst.as_view());
+    assert_eq!(buffer[(0, 0)], Color::linear_srgb(1.0, 0.0, 0.0));
+}